@@ -1 +1,2 @@
+pub mod tic_runner;
 pub mod window;
@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+/// Game logic runs at the rate of the original engine
+pub const TICRATE: u32 = 35;
+/// Most tics run for one frame, after a stall the game slows down instead of catching up
+const MAX_TICS_PER_FRAME: u32 = 10;
+
+/// Turn elapsed time into fixed game tics, decoupled from the frame rate
+pub struct TicRunner {
+    last_time: Instant,
+    /// Time not turned into tics yet
+    accumulator: Duration,
+}
+
+impl TicRunner {
+    pub fn new() -> Self {
+        TicRunner {
+            last_time: Instant::now(),
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    pub fn tic_duration() -> Duration {
+        Duration::from_secs(1) / TICRATE
+    }
+
+    /// Number of tics to run for the time elapsed since the last call
+    pub fn advance(&mut self) -> u32 {
+        let now = Instant::now();
+        let elapsed = now - self.last_time;
+        self.last_time = now;
+        self.advance_by(elapsed)
+    }
+
+    /// Number of tics to run once the given time has passed
+    fn advance_by(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+        let tic = Self::tic_duration();
+        let mut tics = 0;
+        while self.accumulator >= tic {
+            self.accumulator -= tic;
+            tics += 1;
+        }
+        tics.min(MAX_TICS_PER_FRAME)
+    }
+
+    /// How far the frame is between the last tic and the next one, from 0 to 1
+    pub fn fraction(&self) -> f32 {
+        self.accumulator.as_secs_f32() / Self::tic_duration().as_secs_f32()
+    }
+}
+
+impl Default for TicRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_left_over_adds_up_to_tics() {
+        let mut runner = TicRunner::new();
+        let tic = TicRunner::tic_duration();
+
+        assert_eq!(runner.advance_by(tic / 2), 0);
+        assert!((runner.fraction() - 0.5).abs() < 1e-3);
+        assert_eq!(runner.advance_by(tic / 2), 1);
+        assert_eq!(runner.advance_by(tic * 3 + tic / 4), 3);
+        assert!((runner.fraction() - 0.25).abs() < 1e-3);
+        assert_eq!(runner.advance_by(tic * 3 / 4), 1);
+    }
+
+    #[test]
+    fn stall_runs_at_most_max_tics() {
+        let mut runner = TicRunner::new();
+        let tic = TicRunner::tic_duration();
+
+        assert_eq!(runner.advance_by(tic * 25), MAX_TICS_PER_FRAME);
+        // The skipped tics are dropped, not run on the next frames
+        assert_eq!(runner.advance_by(Duration::ZERO), 0);
+        assert_eq!(runner.advance_by(tic), 1);
+    }
+}
@@ -1,9 +1,17 @@
 use kabal_core::frame_timer::FrameTimer;
+
+use crate::tic_runner::TicRunner;
 use winit::{
-    event::{DeviceEvent, ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+    event::{
+        DeviceEvent, ElementState, Event, KeyboardInput, ModifiersState, MouseButton,
+        MouseScrollDelta, VirtualKeyCode, WindowEvent,
+    },
     event_loop::{EventLoop, EventLoopBuilder},
 };
 
+/// Pixels of a touchpad scroll counted as one wheel step
+const PIXELS_PER_WHEEL_STEP: f64 = 20.0;
+
 pub fn init_window(
     event_loop: &EventLoop<()>,
     title: &str,
@@ -18,7 +26,10 @@ pub fn init_window(
 }
 
 pub trait KabalApp {
-    fn run_frame(&mut self, delta_time: f32);
+    /// Advance the game logic by one tic, called TICRATE times per second
+    fn run_tic(&mut self);
+    /// Draw a frame, `fraction` tells how far it is between the last tic and the next one
+    fn run_frame(&mut self, delta_time: f32, fraction: f32, fps: f32);
     fn recreate_swapchain(&mut self);
     fn cleanup_swapchain(&self);
     fn wait_devide_idle(&mut self);
@@ -27,6 +38,14 @@ pub trait KabalApp {
     fn window_ref(&self) -> &winit::window::Window;
     fn on_keyboard_event(&mut self, key_code: VirtualKeyCode, state: ElementState);
     fn on_mouse_move(&mut self, x: f64, y: f64);
+    fn on_mouse_button(&mut self, button: MouseButton, state: ElementState);
+    /// Wheel moves in steps, positive when scrolling up or right
+    fn on_mouse_wheel(&mut self, x: f32, y: f32);
+    /// Text typed with the keyboard layout applied, for text fields
+    fn on_received_character(&mut self, c: char);
+    fn on_modifiers_changed(&mut self, modifiers: ModifiersState);
+    /// Checked once per frame, the loop exits when it returns true
+    fn exit_requested(&self) -> bool;
 }
 
 pub struct ProgramProc {
@@ -41,6 +60,7 @@ impl ProgramProc {
 
     pub fn main_loop<A: 'static + KabalApp>(self, mut app: A) {
         let mut frame_timer = FrameTimer::new();
+        let mut tic_runner = TicRunner::new();
 
         self.event_loop
             .run(move |event, _, control_flow| match event {
@@ -55,15 +75,29 @@ impl ProgramProc {
                             state,
                             ..
                         } = input;
-                        match (virtual_keycode, state) {
-                            (Some(VirtualKeyCode::Escape), ElementState::Pressed) => {
-                                app.wait_devide_idle();
-                                control_flow.set_exit();
-                            }
-                            (Some(key_code), state) => app.on_keyboard_event(key_code, state),
-                            _ => (),
+                        if let Some(key_code) = virtual_keycode {
+                            app.on_keyboard_event(key_code, state);
                         }
                     }
+                    WindowEvent::MouseInput { button, state, .. } => {
+                        app.on_mouse_button(button, state);
+                    }
+                    WindowEvent::MouseWheel { delta, .. } => {
+                        let (x, y) = match delta {
+                            MouseScrollDelta::LineDelta(x, y) => (x, y),
+                            MouseScrollDelta::PixelDelta(position) => (
+                                (position.x / PIXELS_PER_WHEEL_STEP) as f32,
+                                (position.y / PIXELS_PER_WHEEL_STEP) as f32,
+                            ),
+                        };
+                        app.on_mouse_wheel(x, y);
+                    }
+                    WindowEvent::ReceivedCharacter(c) => {
+                        app.on_received_character(c);
+                    }
+                    WindowEvent::ModifiersChanged(modifiers) => {
+                        app.on_modifiers_changed(modifiers);
+                    }
                     WindowEvent::Resized(_new_size) => {
                         app.wait_devide_idle();
                         app.resize_framebuffer();
@@ -80,13 +114,19 @@ impl ProgramProc {
                     app.on_mouse_move(delta.0, delta.1);
                 }
                 Event::MainEventsCleared => {
+                    if app.exit_requested() {
+                        app.wait_devide_idle();
+                        control_flow.set_exit();
+                        return;
+                    }
                     app.window_ref().request_redraw();
                 }
                 Event::RedrawRequested(_window_id) => {
+                    for _ in 0..tic_runner.advance() {
+                        app.run_tic();
+                    }
                     let delta_time = frame_timer.delta_time();
-                    app.run_frame(delta_time);
-
-                    println!("FPS: {}", frame_timer.fps());
+                    app.run_frame(delta_time, tic_runner.fraction(), frame_timer.fps());
 
                     frame_timer.tick();
                 }
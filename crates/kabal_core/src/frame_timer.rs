@@ -34,7 +34,7 @@ impl FrameTimer {
 
     /// Return current delta time in seconds
     pub fn delta_time(&self) -> f32 {
-        self.delta_frame / 1_000_000.0_f32 // µs to s
+        self.delta_frame
     }
 
     /// Return the current framerate
@@ -0,0 +1,15 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Write top-down RGBA pixels to a binary PPM file, alpha is dropped
+pub fn write_ppm(path: &Path, width: usize, height: usize, rgba: &[u8]) -> io::Result<()> {
+    assert!(rgba.len() == width * height * 4);
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    write!(writer, "P6\n{} {}\n255\n", width, height)?;
+    for pixel in rgba.chunks_exact(4) {
+        writer.write_all(&pixel[0..3])?;
+    }
+    writer.flush()
+}
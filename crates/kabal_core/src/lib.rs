@@ -1,2 +1,4 @@
 pub mod frame_timer;
+pub mod image;
 pub mod math;
+pub mod random;
@@ -1,8 +1,8 @@
 use std::iter::Sum;
 
-pub fn mean<'a, T: 'a>(numbers: &'a [T]) -> f32
+pub fn mean<'a, T>(numbers: &'a [T]) -> f32
 where
-    T: Sum<&'a T>,
+    T: 'a + Sum<&'a T>,
     f32: From<T>,
 {
     let sum: f32 = numbers.iter().sum::<T>().into();
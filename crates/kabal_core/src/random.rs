@@ -0,0 +1,139 @@
+//! Random numbers read from a fixed table, like vanilla m_random.c
+
+/// The vanilla rndtable, every game goes through it in the same order
+#[rustfmt::skip]
+const RNDTABLE: [u8; 256] = [
+    0,   8, 109, 220, 222, 241, 149, 107,  75, 248, 254, 140,  16,  66,
+    74,  21, 211,  47,  80, 242, 154,  27, 205, 128, 161,  89,  77,  36,
+    95, 110,  85,  48, 212, 140, 211, 249,  22,  79, 200,  50,  28, 188,
+    52, 140, 202, 120,  68, 145,  62,  70, 184, 190,  91, 197, 152, 224,
+    149, 104,  25, 178, 252, 182, 202, 182, 141, 197,   4,  81, 181, 242,
+    145,  42,  39, 227, 156, 198, 225, 193, 219,  93, 122, 175, 249,   0,
+    175, 143,  70, 239,  46, 246, 163,  53, 163, 109, 168, 135,   2, 235,
+    25,  92,  20, 145, 138,  77,  69, 166,  78, 176, 173, 212, 166, 113,
+    94, 161,  41,  50, 239,  49, 111, 164,  70,  60,   2,  37, 171,  75,
+    136, 156,  11,  56,  42, 146, 138, 229,  73, 146,  77,  61,  98, 196,
+    135, 106,  63, 197, 195,  86,  96, 203, 113, 101, 170, 247, 181, 113,
+    80, 250, 108,   7, 255, 237, 129, 226,  79, 107, 112, 166, 103, 241,
+    24, 223, 239, 120, 198,  58,  60,  82, 128,   3, 184,  66, 143, 224,
+    145, 224,  81, 206, 163,  45,  63,  90, 168, 114,  59,  33, 159,  95,
+    28, 139, 123,  98, 125, 196,  15,  70, 194, 253,  54,  14, 109, 226,
+    71,  17, 161,  93, 186,  87, 244, 138,  20,  52, 123, 251,  26,  36,
+    17,  46,  52, 231, 232,  76,  31, 221,  84,  37, 216, 165, 212, 106,
+    197, 242,  98,  43,  39, 175, 254, 145, 190,  84, 118, 222, 187, 136,
+    120, 163, 236, 249,
+];
+
+/// Two positions in the random table, like vanilla prndindex and rndindex
+///
+/// The gameplay numbers are drawn at the same points as vanilla, the cosmetic ones can be drawn
+/// any time without changing the game. Positions are f32 and not vanilla 16.16 fixed point, so
+/// only the order of the numbers matches: once rounding changes a move or a hit the game leaves
+/// the vanilla one and vanilla demos do not play back. Runs of this engine still repeat exactly.
+pub struct Random {
+    gameplay: u8,
+    cosmetic: u8,
+}
+
+impl Random {
+    /// Both sequences at the beginning of the table, like after vanilla M_ClearRandom
+    pub fn new() -> Self {
+        Random {
+            gameplay: 0,
+            cosmetic: 0,
+        }
+    }
+
+    /// Positions of the gameplay and cosmetic sequences in the table
+    pub fn indices(&self) -> (u8, u8) {
+        (self.gameplay, self.cosmetic)
+    }
+
+    /// Both sequences back to the beginning of the table, like vanilla M_ClearRandom
+    ///
+    /// Done on each level start and when a game is loaded, saves do not keep the positions
+    pub fn clear(&mut self) {
+        *self = Random::new();
+    }
+
+    /// Next gameplay number from 0 to 255, like vanilla P_Random
+    pub fn gameplay(&mut self) -> i32 {
+        self.gameplay = self.gameplay.wrapping_add(1);
+        RNDTABLE[self.gameplay as usize] as i32
+    }
+
+    /// Difference of two gameplay numbers, from -255 to 255 and more likely close to 0
+    pub fn sub_random(&mut self) -> i32 {
+        let first = self.gameplay();
+        first - self.gameplay()
+    }
+
+    /// Next number from 0 to 255 for what does not change the game, like vanilla M_Random
+    pub fn cosmetic(&mut self) -> i32 {
+        self.cosmetic = self.cosmetic.wrapping_add(1);
+        RNDTABLE[self.cosmetic as usize] as i32
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Random::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_numbers_follow_the_table() {
+        let mut random = Random::new();
+        let gameplay: Vec<i32> = (0..4).map(|_| random.gameplay()).collect();
+        assert_eq!(gameplay, [8, 109, 220, 222]);
+        // The cosmetic sequence has its own position
+        assert_eq!(random.cosmetic(), 8);
+        assert_eq!(random.indices(), (4, 1));
+    }
+
+    #[test]
+    fn indices_wrap_around_the_table() {
+        let mut random = Random::new();
+        for _ in 0..255 {
+            random.gameplay();
+            random.cosmetic();
+        }
+        assert_eq!(random.indices(), (255, 255));
+        assert_eq!(random.gameplay(), 0);
+        assert_eq!(random.cosmetic(), 0);
+        assert_eq!(random.indices(), (0, 0));
+        assert_eq!(random.gameplay(), 8);
+
+        random.clear();
+        for _ in 0..254 {
+            random.gameplay();
+        }
+        // The last number of the table minus the first one
+        assert_eq!(random.sub_random(), 249);
+        assert_eq!(random.indices(), (0, 0));
+    }
+
+    #[test]
+    fn sub_random_draws_two_gameplay_numbers() {
+        let mut random = Random::new();
+        assert_eq!(random.sub_random(), 8 - 109);
+        assert_eq!(random.sub_random(), 220 - 222);
+        assert_eq!(random.indices(), (4, 0));
+    }
+
+    #[test]
+    fn clear_starts_both_sequences_over() {
+        let mut random = Random::new();
+        random.gameplay();
+        random.cosmetic();
+        random.cosmetic();
+        random.clear();
+        assert_eq!(random.indices(), (0, 0));
+        assert_eq!(random.gameplay(), 8);
+        assert_eq!(random.cosmetic(), 8);
+    }
+}
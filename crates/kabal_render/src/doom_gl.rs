@@ -1,4 +1,3 @@
-use cgmath::Vector3;
 use glutin::prelude::GlDisplay;
 use std::{ffi::CString, sync::Once};
 
@@ -10,14 +9,6 @@ pub mod gl {
 static mut DOOMGL: Option<DoomGl> = None;
 static DOOMGL_INIT: Once = Once::new();
 
-#[repr(C, packed)]
-#[derive(Debug, Copy, Clone)]
-pub struct GVertex {
-    pub pos: Vector3<f32>,
-    pub uv: Vector3<f32>,
-    pub light: f32,
-}
-
 //https://www.khronos.org/opengl/wiki/Debug_Output
 extern "system" fn gl_debug_message_callback(
     _source: gl::types::GLenum,
@@ -88,7 +79,11 @@ impl DoomGl {
     }
 
     pub fn get() -> DoomGl {
-        unsafe { DOOMGL.clone().expect("DoomGl not initialized.") }
+        unsafe {
+            (*std::ptr::addr_of!(DOOMGL))
+                .clone()
+                .expect("DoomGl not initialized.")
+        }
     }
 
     pub fn gl() -> gl::Gl {
@@ -110,7 +105,7 @@ impl DoomGl {
         texture_id: u32,
         width: i32,
         height: i32,
-        textures: &Vec<Vec<u8>>,
+        textures: &[Vec<u8>],
     ) {
         unsafe {
             self.gl.BindTexture(gl::TEXTURE_2D_ARRAY, texture_id);
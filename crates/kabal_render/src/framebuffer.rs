@@ -0,0 +1,126 @@
+use crate::doom_gl::{gl, DoomGl};
+
+/// Offscreen render target with a RGBA8 color and a depth attachment
+pub struct Framebuffer {
+    pub width: i32,
+    pub height: i32,
+    fbo: u32,
+    color: u32,
+    depth: u32,
+}
+
+impl Framebuffer {
+    pub fn new(width: i32, height: i32) -> Self {
+        let gl = DoomGl::gl();
+        let mut fbo = 0;
+        let mut color = 0;
+        let mut depth = 0;
+
+        unsafe {
+            gl.GenFramebuffers(1, &mut fbo);
+            gl.BindFramebuffer(gl::FRAMEBUFFER, fbo);
+            assert!(gl.GetError() == 0);
+
+            gl.GenRenderbuffers(1, &mut color);
+            gl.BindRenderbuffer(gl::RENDERBUFFER, color);
+            gl.RenderbufferStorage(gl::RENDERBUFFER, gl::RGBA8, width, height);
+            gl.FramebufferRenderbuffer(
+                gl::FRAMEBUFFER,
+                gl::COLOR_ATTACHMENT0,
+                gl::RENDERBUFFER,
+                color,
+            );
+            assert!(gl.GetError() == 0);
+
+            gl.GenRenderbuffers(1, &mut depth);
+            gl.BindRenderbuffer(gl::RENDERBUFFER, depth);
+            gl.RenderbufferStorage(gl::RENDERBUFFER, gl::DEPTH_COMPONENT24, width, height);
+            gl.FramebufferRenderbuffer(
+                gl::FRAMEBUFFER,
+                gl::DEPTH_ATTACHMENT,
+                gl::RENDERBUFFER,
+                depth,
+            );
+            assert!(gl.GetError() == 0);
+
+            let status = gl.CheckFramebufferStatus(gl::FRAMEBUFFER);
+            assert!(
+                status == gl::FRAMEBUFFER_COMPLETE,
+                "Incomplete framebuffer: {:#x}",
+                status
+            );
+
+            gl.BindRenderbuffer(gl::RENDERBUFFER, 0);
+            gl.BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+
+        Framebuffer {
+            width,
+            height,
+            fbo,
+            color,
+            depth,
+        }
+    }
+
+    /// Redirect rendering to this framebuffer
+    pub fn bind(&self) {
+        let gl = DoomGl::gl();
+        unsafe {
+            gl.BindFramebuffer(gl::FRAMEBUFFER, self.fbo);
+            gl.Viewport(0, 0, self.width, self.height);
+            assert!(gl.GetError() == 0);
+        }
+    }
+
+    /// Go back to the default framebuffer
+    pub fn unbind(&self) {
+        unsafe { DoomGl::gl().BindFramebuffer(gl::FRAMEBUFFER, 0) };
+    }
+
+    /// Read back the color attachment as top-down RGBA rows
+    pub fn read_pixels(&self) -> Vec<u8> {
+        read_framebuffer(self.fbo, self.width, self.height)
+    }
+}
+
+/// Read the color buffer of a framebuffer, 0 being the window, as top-down RGBA rows
+pub fn read_framebuffer(fbo: u32, width: i32, height: i32) -> Vec<u8> {
+    let gl = DoomGl::gl();
+    let row_size = width as usize * 4;
+    let mut pixels = vec![0u8; row_size * height as usize];
+
+    unsafe {
+        gl.BindFramebuffer(gl::READ_FRAMEBUFFER, fbo);
+        gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
+        gl.ReadPixels(
+            0,
+            0,
+            width,
+            height,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            pixels.as_mut_ptr() as *mut _,
+        );
+        assert!(gl.GetError() == 0);
+        gl.BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+    }
+
+    // GL origin is bottom-left, images are stored top-down
+    let mut flipped = Vec::with_capacity(pixels.len());
+    for row in pixels.chunks_exact(row_size).rev() {
+        flipped.extend_from_slice(row);
+    }
+    flipped
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        unsafe {
+            let gl = DoomGl::gl();
+            gl.DeleteRenderbuffers(1, &self.color);
+            gl.DeleteRenderbuffers(1, &self.depth);
+            gl.DeleteFramebuffers(1, &self.fbo);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::rc::Rc;
+
+use cgmath::Matrix4;
+
+use crate::doom_gl::{gl, DoomGl};
+use crate::framebuffer::read_framebuffer;
+use crate::material::{Material, MaterialParam, MaterialValue, Stride};
+use crate::renderer::{DrawBatch, GVertex, MeshId, Renderer, TextureId};
+
+const MESH_FRAG_STR: &str = include_str!("shaders/mesh.frag");
+const MESH_VERT_STR: &str = include_str!("shaders/mesh.vert");
+
+struct GlMesh {
+    vao: u32,
+    vb: u32,
+    ib: u32,
+    index_count: i32,
+}
+
+impl Drop for GlMesh {
+    fn drop(&mut self) {
+        unsafe {
+            let gl = DoomGl::gl();
+            gl.DeleteBuffers(1, &self.ib);
+            gl.DeleteBuffers(1, &self.vb);
+            gl.DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
+
+/// OpenGL 4.5 backend, DoomGl must be initialized first
+pub struct GlRenderer {
+    material: Material,
+    view_att: Rc<MaterialParam>,
+    persp_att: Rc<MaterialParam>,
+    pos_att: Rc<MaterialParam>,
+    uv_att: Rc<MaterialParam>,
+    light_att: Rc<MaterialParam>,
+    /// Deleted meshes leave a free slot
+    meshes: Vec<Option<GlMesh>>,
+    /// Deleted texture arrays leave a free slot
+    textures: Vec<Option<u32>>,
+}
+
+impl GlRenderer {
+    pub fn new() -> Self {
+        let mut material = Material::new(MESH_VERT_STR, MESH_FRAG_STR);
+
+        let view_att = MaterialParam::from_uniform("view\0", &mut material);
+        let persp_att = MaterialParam::from_uniform("proj\0", &mut material);
+        let img_att = MaterialParam::from_uniform("image\0", &mut material);
+        let pos_att = MaterialParam::from_attrib("position\0", &mut material);
+        let uv_att = MaterialParam::from_attrib("uv\0", &mut material);
+        let light_att = MaterialParam::from_attrib("light\0", &mut material);
+
+        // Texture arrays are always bound on unit 0
+        img_att.set_value(MaterialValue::Int(0));
+
+        GlRenderer {
+            material,
+            view_att,
+            persp_att,
+            pos_att,
+            uv_att,
+            light_att,
+            meshes: Vec::new(),
+            textures: Vec::new(),
+        }
+    }
+}
+
+impl Default for GlRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer for GlRenderer {
+    fn begin_frame(&mut self, clear_color: [f32; 4]) {
+        let gl = DoomGl::gl();
+        unsafe {
+            gl.ClearColor(
+                clear_color[0],
+                clear_color[1],
+                clear_color[2],
+                clear_color[3],
+            );
+            gl.Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+        }
+    }
+
+    fn upload_texture_array(&mut self, width: i32, height: i32, layers: &[Vec<u8>]) -> TextureId {
+        let gl = DoomGl::get();
+        let id = gl.gen_texture_id();
+        gl.fill_texture_2d_array(id, width, height, layers);
+
+        match self.textures.iter().position(Option::is_none) {
+            Some(slot) => {
+                self.textures[slot] = Some(id);
+                TextureId(slot)
+            }
+            None => {
+                self.textures.push(Some(id));
+                TextureId(self.textures.len() - 1)
+            }
+        }
+    }
+
+    fn delete_texture(&mut self, texture: TextureId) {
+        if let Some(id) = self.textures[texture.0].take() {
+            unsafe { DoomGl::gl().DeleteTextures(1, &id) };
+        }
+    }
+
+    fn upload_mesh(&mut self, vertices: &[GVertex], indices: &[u32]) -> MeshId {
+        let mut vao = 0;
+        let mut vb = 0;
+        let mut ib = 0;
+
+        unsafe {
+            let gl = DoomGl::gl();
+            // generate and bind the vao
+            gl.GenVertexArrays(1, &mut vao);
+            gl.BindVertexArray(vao);
+
+            gl.GenBuffers(1, &mut vb);
+            gl.BindBuffer(gl::ARRAY_BUFFER, vb);
+            gl.BufferData(
+                gl::ARRAY_BUFFER,
+                std::mem::size_of_val(vertices) as gl::types::GLsizeiptr,
+                vertices.as_ptr() as *const _,
+                gl::DYNAMIC_DRAW,
+            );
+
+            gl.GenBuffers(1, &mut ib);
+            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ib);
+            gl.BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                std::mem::size_of_val(indices) as gl::types::GLsizeiptr,
+                indices.as_ptr() as *const _,
+                gl::DYNAMIC_DRAW,
+            );
+            assert!(gl.GetError() == 0);
+        }
+
+        // Always bind stride after the buffer is bound
+        let stride = std::mem::size_of::<GVertex>();
+        self.pos_att.set_value(MaterialValue::FloatStride(Stride {
+            count: 3,
+            stride,
+            offset: 0,
+        }));
+        self.uv_att.set_value(MaterialValue::FloatStride(Stride {
+            count: 3,
+            stride,
+            offset: 3 * std::mem::size_of::<f32>(),
+        }));
+        self.light_att.set_value(MaterialValue::FloatStride(Stride {
+            count: 1,
+            stride,
+            offset: 6 * std::mem::size_of::<f32>(),
+        }));
+        self.pos_att.bind();
+        self.uv_att.bind();
+        self.light_att.bind();
+
+        unsafe { DoomGl::gl().BindVertexArray(0) };
+
+        let mesh = GlMesh {
+            vao,
+            vb,
+            ib,
+            index_count: indices.len() as i32,
+        };
+        match self.meshes.iter().position(Option::is_none) {
+            Some(slot) => {
+                self.meshes[slot] = Some(mesh);
+                MeshId(slot)
+            }
+            None => {
+                self.meshes.push(Some(mesh));
+                MeshId(self.meshes.len() - 1)
+            }
+        }
+    }
+
+    fn update_mesh(&mut self, mesh: MeshId, vertices: &[GVertex], indices: &[u32]) {
+        let mesh = self.meshes[mesh.0].as_mut().expect("deleted mesh");
+        unsafe {
+            let gl = DoomGl::gl();
+            // The index buffer binding is part of the vao state
+            gl.BindVertexArray(mesh.vao);
+
+            gl.BindBuffer(gl::ARRAY_BUFFER, mesh.vb);
+            gl.BufferData(
+                gl::ARRAY_BUFFER,
+                std::mem::size_of_val(vertices) as gl::types::GLsizeiptr,
+                vertices.as_ptr() as *const _,
+                gl::DYNAMIC_DRAW,
+            );
+
+            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, mesh.ib);
+            gl.BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                std::mem::size_of_val(indices) as gl::types::GLsizeiptr,
+                indices.as_ptr() as *const _,
+                gl::DYNAMIC_DRAW,
+            );
+
+            gl.BindVertexArray(0);
+            assert!(gl.GetError() == 0);
+        }
+        mesh.index_count = indices.len() as i32;
+    }
+
+    fn delete_mesh(&mut self, mesh: MeshId) {
+        // Dropping the mesh frees its buffers
+        self.meshes[mesh.0] = None;
+    }
+
+    fn set_view_projection(&mut self, view: &Matrix4<f32>, projection: &Matrix4<f32>) {
+        self.view_att.set_value(MaterialValue::Matrix(*view));
+        self.persp_att.set_value(MaterialValue::Matrix(*projection));
+    }
+
+    fn draw_batch(&mut self, batch: &DrawBatch) {
+        let mesh = self.meshes[batch.mesh.0].as_ref().expect("deleted mesh");
+        let gl = DoomGl::gl();
+        unsafe {
+            if batch.cull_face {
+                gl.Enable(gl::CULL_FACE);
+            } else {
+                gl.Disable(gl::CULL_FACE);
+            }
+            if batch.depth_test {
+                gl.Enable(gl::DEPTH_TEST);
+            } else {
+                gl.Disable(gl::DEPTH_TEST);
+            }
+            gl.BindVertexArray(mesh.vao);
+        }
+
+        // Always bind material after the vao is bound
+        self.material.bind();
+
+        unsafe {
+            gl.ActiveTexture(gl::TEXTURE0);
+            gl.BindTexture(
+                gl::TEXTURE_2D_ARRAY,
+                self.textures[batch.texture.0].unwrap_or(0),
+            );
+            gl.DrawElements(
+                gl::TRIANGLES,
+                mesh.index_count,
+                gl::UNSIGNED_INT,
+                std::ptr::null(),
+            );
+            gl.BindVertexArray(0);
+            assert!(gl.GetError() == 0);
+        }
+    }
+
+    fn set_wireframe(&mut self, enabled: bool) {
+        let mode = if enabled { gl::LINE } else { gl::FILL };
+        unsafe { DoomGl::gl().PolygonMode(gl::FRONT_AND_BACK, mode) };
+    }
+
+    fn read_pixels(&mut self, width: u32, height: u32) -> Vec<u8> {
+        let gl = DoomGl::gl();
+        let mut fbo = 0;
+        unsafe { gl.GetIntegerv(gl::DRAW_FRAMEBUFFER_BINDING, &mut fbo) };
+        read_framebuffer(fbo as u32, width as i32, height as i32)
+    }
+}
+
+impl Drop for GlRenderer {
+    fn drop(&mut self) {
+        let textures: Vec<u32> = self.textures.iter().flatten().copied().collect();
+        unsafe {
+            DoomGl::gl().DeleteTextures(textures.len() as i32, textures.as_ptr());
+        }
+    }
+}
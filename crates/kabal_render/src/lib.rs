@@ -1 +1,6 @@
 pub mod doom_gl;
+pub mod framebuffer;
+pub mod gl_renderer;
+mod material;
+pub mod renderer;
+pub mod screen2d;
@@ -1,5 +1,5 @@
+use crate::doom_gl::{gl, DoomGl};
 use cgmath::{BaseNum, Matrix4};
-use kabal_render::doom_gl::{gl, DoomGl};
 
 use std::cell::Cell;
 use std::rc::Rc;
@@ -0,0 +1,61 @@
+use cgmath::{Matrix4, Vector3};
+
+/// Vertex layout shared by every mesh, uv.z is the layer in the texture array
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct GVertex {
+    pub pos: Vector3<f32>,
+    pub uv: Vector3<f32>,
+    pub light: f32,
+}
+
+/// Handle on a mesh owned by a renderer
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MeshId(pub usize);
+
+/// Handle on a texture array owned by a renderer
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TextureId(pub usize);
+
+/// One draw call: a whole mesh with a single texture array
+#[derive(Debug, Copy, Clone)]
+pub struct DrawBatch {
+    pub mesh: MeshId,
+    pub texture: TextureId,
+    /// Skip back faces, walls are one sided while flats are seen from both sides
+    pub cull_face: bool,
+    /// Screen overlays are drawn in submission order on top of everything
+    pub depth_test: bool,
+}
+
+/// Everything the game needs from a render backend
+pub trait Renderer {
+    /// Clear the target before drawing a new frame
+    fn begin_frame(&mut self, clear_color: [f32; 4]);
+
+    /// Upload same sized RGBA images as the layers of a texture array
+    fn upload_texture_array(&mut self, width: i32, height: i32, layers: &[Vec<u8>]) -> TextureId;
+
+    /// Free a texture array, its id can be handed out again
+    fn delete_texture(&mut self, texture: TextureId);
+
+    /// Upload an indexed triangle list
+    fn upload_mesh(&mut self, vertices: &[GVertex], indices: &[u32]) -> MeshId;
+
+    /// Replace the content of a mesh, for geometry rebuilt every frame
+    fn update_mesh(&mut self, mesh: MeshId, vertices: &[GVertex], indices: &[u32]);
+
+    /// Free a mesh, its id can be handed out again
+    fn delete_mesh(&mut self, mesh: MeshId);
+
+    /// Camera used by the next draw batches
+    fn set_view_projection(&mut self, view: &Matrix4<f32>, projection: &Matrix4<f32>);
+
+    fn draw_batch(&mut self, batch: &DrawBatch);
+
+    /// Draw only the edges of the next batches
+    fn set_wireframe(&mut self, enabled: bool);
+
+    /// Read back what has been drawn so far as top-down RGBA rows
+    fn read_pixels(&mut self, width: u32, height: u32) -> Vec<u8>;
+}
@@ -0,0 +1,162 @@
+use cgmath::{Matrix4, SquareMatrix, Vector3};
+
+use crate::renderer::{DrawBatch, GVertex, MeshId, Renderer, TextureId};
+
+/// Width of the original screen, every 2D coordinate is given in this space
+pub const SCREEN_WIDTH: f32 = 320.0;
+/// Height of the original screen
+pub const SCREEN_HEIGHT: f32 = 200.0;
+
+/// A patch image stored in a layer of a texture array
+#[derive(Debug, Copy, Clone)]
+pub struct PatchImage {
+    pub texture: TextureId,
+    pub layer: u32,
+    pub width: i32,
+    pub height: i32,
+    pub left_offset: i32,
+    pub top_offset: i32,
+}
+
+/// Consecutive patches sharing a texture array
+struct Run {
+    texture: TextureId,
+    vertices: Vec<GVertex>,
+    indices: Vec<u32>,
+}
+
+/// Draw patches in the 320x200 screen space, scaled to the window and drawn in call order
+pub struct Screen2D {
+    runs: Vec<Run>,
+    /// Meshes reused from frame to frame, one per run
+    meshes: Vec<MeshId>,
+}
+
+impl Screen2D {
+    pub fn new() -> Self {
+        Screen2D {
+            runs: Vec::new(),
+            meshes: Vec::new(),
+        }
+    }
+
+    /// Draw a patch like vanilla V_DrawPatch, the offsets are subtracted from the position
+    pub fn draw_patch(&mut self, x: i32, y: i32, patch: &PatchImage) {
+        self.draw_patch_scaled(x as f32, y as f32, 1.0, patch);
+    }
+
+    /// Draw a patch scaled around its offset point
+    pub fn draw_patch_scaled(&mut self, x: f32, y: f32, scale: f32, patch: &PatchImage) {
+        self.draw_patch_light(x, y, scale, 1.0, patch);
+    }
+
+    /// Stretch a patch over a rectangle, offsets are ignored
+    pub fn draw_stretched(&mut self, x: f32, y: f32, width: f32, height: f32, patch: &PatchImage) {
+        let stretched = PatchImage {
+            width: 1,
+            height: 1,
+            left_offset: 0,
+            top_offset: 0,
+            ..*patch
+        };
+        self.push_quad(x, y, x + width, y + height, 1.0, &stretched);
+    }
+
+    /// Draw a scaled patch with a light level applied to its colours
+    pub fn draw_patch_light(&mut self, x: f32, y: f32, scale: f32, light: f32, patch: &PatchImage) {
+        let left = x - patch.left_offset as f32 * scale;
+        let top = y - patch.top_offset as f32 * scale;
+        let right = left + patch.width as f32 * scale;
+        let bottom = top + patch.height as f32 * scale;
+        self.push_quad(left, top, right, bottom, light, patch);
+    }
+
+    fn push_quad(
+        &mut self,
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+        light: f32,
+        patch: &PatchImage,
+    ) {
+        if self.runs.last().map(|r| r.texture) != Some(patch.texture) {
+            self.runs.push(Run {
+                texture: patch.texture,
+                vertices: Vec::new(),
+                indices: Vec::new(),
+            });
+        }
+        let run = self.runs.last_mut().unwrap();
+
+        // Images are stored bottom-up
+        let layer = patch.layer as f32;
+        let vertex = |x: f32, y: f32, u: f32, v: f32| GVertex {
+            pos: Vector3::new(x, y, 0.0),
+            uv: Vector3::new(u, v, layer),
+            light,
+        };
+        let startidx = run.vertices.len() as u32;
+        run.vertices.extend([
+            vertex(left, bottom, 0.0, 0.0),
+            vertex(right, bottom, 1.0, 0.0),
+            vertex(left, top, 0.0, 1.0),
+            vertex(right, top, 1.0, 1.0),
+        ]);
+        run.indices.extend([
+            startidx,
+            startidx + 1,
+            startidx + 2,
+            startidx + 2,
+            startidx + 1,
+            startidx + 3,
+        ]);
+    }
+
+    /// Screen space covered by a window, the 320x200 area is kept centered with its aspect ratio
+    pub fn visible_area(width: u32, height: u32) -> (f32, f32, f32, f32) {
+        let aspect = width.max(1) as f32 / height.max(1) as f32;
+        let screen_aspect = SCREEN_WIDTH / SCREEN_HEIGHT;
+        if aspect > screen_aspect {
+            let margin = (SCREEN_HEIGHT * aspect - SCREEN_WIDTH) / 2.0;
+            (-margin, SCREEN_WIDTH + margin, 0.0, SCREEN_HEIGHT)
+        } else {
+            let margin = (SCREEN_WIDTH / aspect - SCREEN_HEIGHT) / 2.0;
+            (0.0, SCREEN_WIDTH, -margin, SCREEN_HEIGHT + margin)
+        }
+    }
+
+    /// Draw every queued patch over the current frame
+    pub fn flush(&mut self, renderer: &mut dyn Renderer, width: u32, height: u32) {
+        let (left, right, top, bottom) = Self::visible_area(width, height);
+        let projection = cgmath::ortho(left, right, bottom, top, -1.0, 1.0);
+        renderer.set_view_projection(&Matrix4::identity(), &projection);
+
+        for (i, run) in self.runs.drain(..).enumerate() {
+            let mesh = match self.meshes.get(i) {
+                Some(mesh) => {
+                    renderer.update_mesh(*mesh, &run.vertices, &run.indices);
+                    *mesh
+                }
+                None => {
+                    let mesh = renderer.upload_mesh(&run.vertices, &run.indices);
+                    self.meshes.push(mesh);
+                    mesh
+                }
+            };
+
+            renderer.draw_batch(&DrawBatch {
+                mesh,
+                texture: run.texture,
+                cull_face: false,
+                depth_test: false,
+            });
+        }
+    }
+}
+
+impl Default for Screen2D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
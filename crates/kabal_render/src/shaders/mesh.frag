@@ -0,0 +1,18 @@
+#version 450
+
+in TVertexData {
+	float light;
+	vec3 uv;
+} inData;
+
+out vec4 fragColor;
+
+uniform sampler2DArray image;
+
+void main() {
+	vec4 color = texture(image, inData.uv);
+	if (color.w < 0.01) {
+		discard;
+	}
+	fragColor = vec4(inData.light * vec3( color.x, color.y, color.z ), color.w);
+}
@@ -0,0 +1,558 @@
+use cgmath::{ortho, Matrix4, SquareMatrix, Vector2, Vector3, Zero};
+use kabal_render::renderer::{DrawBatch, GVertex, MeshId, Renderer, TextureId};
+use winit::event::VirtualKeyCode;
+
+use crate::camera::Camera;
+use crate::input::{bindings::Action, InputListener};
+use crate::render::view_clip::ViewClip;
+use crate::wad::file::WadFile;
+use crate::wad::map::{LinedefFlags, WadMap, NF_SUBSECTOR};
+use crate::wad::playpal::PlayPal;
+
+// Palette indices used by vanilla
+const REDS: u8 = 176;
+const BROWNS: u8 = 64;
+const YELLOWS: u8 = 231;
+const GRAYS: u8 = 96;
+const GREENS: u8 = 112;
+const WHITE: u8 = 209;
+
+const WALL_COLOR: u8 = REDS;
+const TELEPORTER_COLOR: u8 = REDS + 8;
+const FLOOR_CHANGE_COLOR: u8 = BROWNS;
+const CEILING_CHANGE_COLOR: u8 = YELLOWS;
+const TWO_SIDED_COLOR: u8 = GRAYS;
+const COMPUTER_MAP_COLOR: u8 = GRAYS + 3;
+const GRID_COLOR: u8 = GRAYS + 8;
+const THING_COLOR: u8 = GREENS;
+const PLAYER_COLOR: u8 = WHITE;
+const MARK_COLOR: u8 = WHITE;
+
+/// Linedef special of the walk-over teleporter
+const TELEPORTER_SPECIAL: i16 = 39;
+
+/// Size of a blockmap cell, the grid follows the blockmap
+const GRID_SIZE: f32 = 128.0;
+const MAX_MARKS: usize = 10;
+/// Vanilla pans 4 pixels of a 320x200 screen per tic
+const PAN_SPEED: f32 = 4.0 / 200.0;
+/// Vanilla zooms by 2% per tic
+const ZOOM_SPEED: f32 = 1.02;
+/// Map units visible vertically when zoomed in the most
+const MIN_SCALE: f32 = 128.0;
+/// Height of the 320x200 screen the automap is designed for
+const BASE_HEIGHT: f32 = 200.0;
+/// Vanilla player radius, used to size the arrow and things
+const PLAYER_RADIUS: f32 = 16.0;
+
+/// A line from a start point to an end point, in shape units
+type ShapeLine = ((f32, f32), (f32, f32));
+
+/// Later layers are drawn above the first ones
+#[derive(Copy, Clone)]
+enum Layer {
+    Grid,
+    Walls,
+    Things,
+    Player,
+}
+
+/// What is shown on top of the seen lines, cycled by the IDDT cheat
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Reveal {
+    Seen,
+    AllLines,
+    AllLinesAndThings,
+}
+
+/// Thin quads built in map space, all with a palette color
+struct LineBatch {
+    vertices: Vec<GVertex>,
+    indices: Vec<u32>,
+    thickness: f32,
+}
+
+impl LineBatch {
+    fn line(&mut self, start: Vector2<f32>, end: Vector2<f32>, color: u8, layer: Layer) {
+        let direction = end - start;
+        if direction.is_zero() {
+            return;
+        }
+        let length = (direction.x * direction.x + direction.y * direction.y).sqrt();
+        let normal = Vector2::new(-direction.y, direction.x) * (self.thickness / 2.0 / length);
+
+        // Depth only orders the layers, near is the most visible
+        let z = layer as u32 as f32 - 8.0;
+        let vertex = |p: Vector2<f32>| GVertex {
+            pos: Vector3::new(p.x, p.y, z),
+            uv: Vector3::new(0.5, 0.5, color as f32),
+            light: 1.0,
+        };
+
+        let startidx = self.vertices.len() as u32;
+        self.vertices.extend([
+            vertex(start - normal),
+            vertex(end - normal),
+            vertex(start + normal),
+            vertex(end + normal),
+        ]);
+        self.indices.extend([
+            startidx,
+            startidx + 1,
+            startidx + 2,
+            startidx + 2,
+            startidx + 1,
+            startidx + 3,
+        ]);
+    }
+
+    /// Draw a line shape rotated by angle (radians) and scaled around a position
+    fn shape(
+        &mut self,
+        lines: &[ShapeLine],
+        position: Vector2<f32>,
+        angle: f32,
+        scale: f32,
+        color: u8,
+        layer: Layer,
+    ) {
+        let (sin, cos) = angle.sin_cos();
+        let transform = |(x, y): (f32, f32)| {
+            position + Vector2::new(x * cos - y * sin, x * sin + y * cos) * scale
+        };
+        for (start, end) in lines {
+            self.line(transform(*start), transform(*end), color, layer);
+        }
+    }
+}
+
+/// Vanilla player arrow, pointing to +x
+const PLAYER_ARROW: [ShapeLine; 7] = {
+    const R: f32 = 8.0 / 7.0;
+    [
+        ((-R + R / 8.0, 0.0), (R, 0.0)),
+        ((R, 0.0), (R - R / 2.0, R / 4.0)),
+        ((R, 0.0), (R - R / 2.0, -R / 4.0)),
+        ((-R + R / 8.0, 0.0), (-R - R / 8.0, R / 4.0)),
+        ((-R + R / 8.0, 0.0), (-R - R / 8.0, -R / 4.0)),
+        ((-R + 3.0 * R / 8.0, 0.0), (-R + R / 8.0, R / 4.0)),
+        ((-R + 3.0 * R / 8.0, 0.0), (-R + R / 8.0, -R / 4.0)),
+    ]
+};
+
+/// Vanilla thing triangle, pointing to +x
+const THING_TRIANGLE: [ShapeLine; 3] = [
+    ((-0.5, -0.7), (1.0, 0.0)),
+    ((1.0, 0.0), (-0.5, 0.7)),
+    ((-0.5, 0.7), (-0.5, -0.7)),
+];
+
+pub struct Automap {
+    pub active: bool,
+    /// Set by the computer area map powerup, unseen lines are shown in gray
+    pub computer_map: bool,
+    pub reveal: Reveal,
+    follow: bool,
+    grid: bool,
+    /// Map units visible from the bottom to the top of the screen
+    scale: f32,
+    /// Scale to go back to when leaving the whole map view
+    saved_scale: Option<f32>,
+    center: Vector2<f32>,
+    pan: Vector2<f32>,
+    zoom: f32,
+    marks: Vec<Vector2<f32>>,
+    clip: ViewClip,
+    view: Vector2<f32>,
+    /// Last typed letters, to catch the cheat code
+    typed: Vec<VirtualKeyCode>,
+    palette: TextureId,
+    mesh: Option<MeshId>,
+}
+
+impl Automap {
+    pub fn new(file: &WadFile, renderer: &mut dyn Renderer) -> Self {
+        // One layer of a single texel per palette entry
+        let playpal = PlayPal::new(file);
+        let layers = playpal.palettes[0]
+            .colors
+            .iter()
+            .map(|c| vec![c.r, c.g, c.b, 255])
+            .collect::<Vec<Vec<u8>>>();
+
+        Automap {
+            active: false,
+            computer_map: false,
+            reveal: Reveal::Seen,
+            follow: true,
+            grid: false,
+            scale: 1024.0,
+            saved_scale: None,
+            center: Vector2::zero(),
+            pan: Vector2::zero(),
+            zoom: 0.0,
+            marks: Vec::new(),
+            clip: ViewClip::new(320),
+            view: Vector2::zero(),
+            typed: Vec::new(),
+            palette: renderer.upload_texture_array(1, 1, &layers),
+            mesh: None,
+        }
+    }
+
+    /// Forget the marks, for a new level
+    pub fn clear(&mut self) {
+        self.marks.clear();
+    }
+
+    /// Follow the camera
+    pub fn update(&mut self, camera: &Camera) {
+        if self.follow {
+            let (x, y, _) = camera.map_position();
+            self.center = Vector2::new(x, y);
+        }
+    }
+
+    /// Run one tic of panning and zooming
+    pub fn tick(&mut self, map: &WadMap) {
+        if !self.follow {
+            self.center += self.pan * (PAN_SPEED * self.scale);
+        }
+
+        let max_scale = Self::max_scale(map);
+        if self.saved_scale.is_some() {
+            self.scale = max_scale;
+        } else {
+            self.scale = (self.scale * ZOOM_SPEED.powf(-self.zoom)).clamp(MIN_SCALE, max_scale);
+        }
+    }
+
+    /// Scale showing the whole map
+    fn max_scale(map: &WadMap) -> f32 {
+        let (min, max) = map.vertexes.iter().fold(
+            (
+                Vector2::new(f32::MAX, f32::MAX),
+                Vector2::new(f32::MIN, f32::MIN),
+            ),
+            |(min, max), v| {
+                let (x, y) = (v.x as f32, v.y as f32);
+                (
+                    Vector2::new(min.x.min(x), min.y.min(y)),
+                    Vector2::new(max.x.max(x), max.y.max(y)),
+                )
+            },
+        );
+        (max.y - min.y).max(max.x - min.x) * 1.1
+    }
+
+    /// Walk the BSP front to back like the renderer and map every line with a visible part,
+    /// like vanilla R_StoreWallRange sets ML_MAPPED
+    ///
+    /// The flag is kept in the map lines, so saves keep what was seen
+    pub fn mark_seen_lines(&mut self, map: &mut WadMap, camera: &Camera) {
+        let (x, y, _) = camera.map_position();
+        self.view = Vector2::new(x, y);
+        self.clip.reset(x, y, camera.map_angle().0.to_radians());
+
+        if map.nodes.is_empty() {
+            self.mark_subsector(map, 0);
+        } else {
+            self.mark_node(map, map.nodes.len() - 1);
+        }
+    }
+
+    fn mark_node(&mut self, map: &mut WadMap, index: usize) {
+        if self.clip.is_full() {
+            return;
+        }
+
+        let node = map.nodes[index];
+        let side = node.point_on_side(self.view.x, self.view.y);
+        let bbox = node.bbox;
+
+        self.mark_child(map, node.children[side]);
+        if self.clip.check_bbox(&bbox[side ^ 1]) {
+            self.mark_child(map, node.children[side ^ 1]);
+        }
+    }
+
+    fn mark_child(&mut self, map: &mut WadMap, child: u16) {
+        if child & NF_SUBSECTOR != 0 {
+            self.mark_subsector(map, (child & !NF_SUBSECTOR) as usize);
+        } else {
+            self.mark_node(map, child as usize);
+        }
+    }
+
+    fn mark_subsector(&mut self, map: &mut WadMap, index: usize) {
+        let subsector = map.subsectors[index];
+        let first = subsector.first_seg as usize;
+        for index in first..first + subsector.seg_count as usize {
+            let seg = map.segs[index];
+            let v1 = map.vertexes[seg.start_vertex as usize];
+            let v2 = map.vertexes[seg.end_vertex as usize];
+            let Some((x1, x2, _)) =
+                self.clip
+                    .clip_span(v1.x as f32, v1.y as f32, v2.x as f32, v2.y as f32)
+            else {
+                continue;
+            };
+            if !self.clip.is_visible(x1, x2) {
+                continue;
+            }
+
+            let linedef = &mut map.linedefs[seg.linedef as usize];
+            linedef.flags = { linedef.flags } | LinedefFlags::MAPPED;
+            let linedef = *linedef;
+
+            let (front_side, back_side) = if seg.side == 0 {
+                (linedef.front_sidedef, linedef.back_sidedef)
+            } else {
+                (linedef.back_sidedef, linedef.front_sidedef)
+            };
+            let solid = if back_side == -1 {
+                true
+            } else {
+                let front = map.sectors[map.sidedefs[front_side as usize].sector as usize];
+                let back = map.sectors[map.sidedefs[back_side as usize].sector as usize];
+                back.ceiling <= front.floor || back.floor >= front.ceiling
+            };
+            if solid {
+                self.clip.mark_solid(x1, x2);
+            }
+        }
+    }
+
+    /// Color of a line, None when it must not be drawn
+    fn line_color(&self, map: &WadMap, index: usize) -> Option<u8> {
+        let line = map.linedefs[index];
+        let cheating = self.reveal != Reveal::Seen;
+        let never = (line.flags & LinedefFlags::AUTO_MAP_NEVER) != LinedefFlags::NONE;
+
+        let mapped = (line.flags & LinedefFlags::MAPPED) != LinedefFlags::NONE;
+        if !cheating && !mapped {
+            return if self.computer_map && !never {
+                Some(COMPUTER_MAP_COLOR)
+            } else {
+                None
+            };
+        }
+        if never && !cheating {
+            return None;
+        }
+
+        if line.back_sidedef == -1 {
+            return Some(WALL_COLOR);
+        }
+
+        let front = map.sectors[map.sidedefs[line.front_sidedef as usize].sector as usize];
+        let back = map.sectors[map.sidedefs[line.back_sidedef as usize].sector as usize];
+        if line.special_type == TELEPORTER_SPECIAL {
+            Some(TELEPORTER_COLOR)
+        } else if (line.flags & LinedefFlags::SECRET) != LinedefFlags::NONE {
+            // Secret doors look like plain walls
+            Some(WALL_COLOR)
+        } else if back.floor != front.floor {
+            Some(FLOOR_CHANGE_COLOR)
+        } else if back.ceiling != front.ceiling {
+            Some(CEILING_CHANGE_COLOR)
+        } else if cheating {
+            Some(TWO_SIDED_COLOR)
+        } else {
+            None
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        map: &WadMap,
+        camera: &Camera,
+        renderer: &mut dyn Renderer,
+        width: u32,
+        height: u32,
+    ) {
+        renderer.begin_frame([0.0, 0.0, 0.0, 1.0]);
+
+        let half_height = self.scale / 2.0;
+        let half_width = half_height * width as f32 / height as f32;
+        let (min, max) = (
+            self.center - Vector2::new(half_width, half_height),
+            self.center + Vector2::new(half_width, half_height),
+        );
+        renderer.set_view_projection(
+            &Matrix4::identity(),
+            &ortho(min.x, max.x, min.y, max.y, 0.0, 16.0),
+        );
+
+        // Lines are one pixel wide on the original 320x200 screen
+        let mut batch = LineBatch {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            thickness: self.scale / BASE_HEIGHT,
+        };
+
+        if self.grid {
+            let origin = Vector2::new(
+                *map.blockmap.first().unwrap_or(&0) as f32,
+                *map.blockmap.get(1).unwrap_or(&0) as f32,
+            );
+            let mut x = origin.x + ((min.x - origin.x) / GRID_SIZE).ceil() * GRID_SIZE;
+            while x < max.x {
+                let (start, end) = (Vector2::new(x, min.y), Vector2::new(x, max.y));
+                batch.line(start, end, GRID_COLOR, Layer::Grid);
+                x += GRID_SIZE;
+            }
+            let mut y = origin.y + ((min.y - origin.y) / GRID_SIZE).ceil() * GRID_SIZE;
+            while y < max.y {
+                let (start, end) = (Vector2::new(min.x, y), Vector2::new(max.x, y));
+                batch.line(start, end, GRID_COLOR, Layer::Grid);
+                y += GRID_SIZE;
+            }
+        }
+
+        for (index, line) in map.linedefs.iter().enumerate() {
+            if let Some(color) = self.line_color(map, index) {
+                let start = map.vertexes[line.start_vertex as usize];
+                let end = map.vertexes[line.end_vertex as usize];
+                batch.line(
+                    Vector2::new(start.x as f32, start.y as f32),
+                    Vector2::new(end.x as f32, end.y as f32),
+                    color,
+                    Layer::Walls,
+                );
+            }
+        }
+
+        if self.reveal == Reveal::AllLinesAndThings {
+            for thing in &map.things {
+                let angle = (thing.angle as f32).to_radians();
+                let position = Vector2::new(thing.x as f32, thing.y as f32);
+                batch.shape(
+                    &THING_TRIANGLE,
+                    position,
+                    angle,
+                    PLAYER_RADIUS,
+                    THING_COLOR,
+                    Layer::Things,
+                );
+            }
+        }
+
+        for mark in &self.marks {
+            let size = 4.0 * self.scale / BASE_HEIGHT;
+            let cross = [((-1.0, -1.0), (1.0, 1.0)), ((-1.0, 1.0), (1.0, -1.0))];
+            batch.shape(&cross, *mark, 0.0, size, MARK_COLOR, Layer::Player);
+        }
+
+        let (x, y, _) = camera.map_position();
+        batch.shape(
+            &PLAYER_ARROW,
+            Vector2::new(x, y),
+            camera.map_angle().0.to_radians(),
+            PLAYER_RADIUS,
+            PLAYER_COLOR,
+            Layer::Player,
+        );
+
+        let mesh = match self.mesh {
+            Some(mesh) => {
+                renderer.update_mesh(mesh, &batch.vertices, &batch.indices);
+                mesh
+            }
+            None => renderer.upload_mesh(&batch.vertices, &batch.indices),
+        };
+        self.mesh = Some(mesh);
+
+        renderer.draw_batch(&DrawBatch {
+            mesh,
+            texture: self.palette,
+            cull_face: false,
+            depth_test: true,
+        });
+    }
+
+    /// Watch for IDDT typed while the automap is open
+    fn check_cheat(&mut self, key: VirtualKeyCode) {
+        const IDDT: [VirtualKeyCode; 4] = [
+            VirtualKeyCode::I,
+            VirtualKeyCode::D,
+            VirtualKeyCode::D,
+            VirtualKeyCode::T,
+        ];
+
+        self.typed.push(key);
+        if self.typed.len() > IDDT.len() {
+            self.typed.remove(0);
+        }
+        if self.typed == IDDT {
+            self.typed.clear();
+            self.reveal = match self.reveal {
+                Reveal::Seen => Reveal::AllLines,
+                Reveal::AllLines => Reveal::AllLinesAndThings,
+                Reveal::AllLinesAndThings => Reveal::Seen,
+            };
+        }
+    }
+}
+
+impl InputListener for Automap {
+    fn on_action(&mut self, action: Action, pressed: bool) -> bool {
+        if action == Action::Automap {
+            if pressed {
+                self.active = !self.active;
+            }
+            return true;
+        }
+        if !self.active {
+            return false;
+        }
+
+        let value = if pressed { 1.0 } else { 0.0 };
+        match action {
+            // Panning only takes the keys from the player when the map doesn't follow the player
+            Action::MapWest => self.pan.x = -value,
+            Action::MapEast => self.pan.x = value,
+            Action::MapNorth => self.pan.y = value,
+            Action::MapSouth => self.pan.y = -value,
+            Action::MapZoomIn => self.zoom = value,
+            Action::MapZoomOut => self.zoom = -value,
+            _ => (),
+        }
+        if matches!(
+            action,
+            Action::MapWest | Action::MapEast | Action::MapNorth | Action::MapSouth
+        ) {
+            return !self.follow;
+        }
+
+        if !pressed {
+            return false;
+        }
+        match action {
+            Action::MapFollow => self.follow = !self.follow,
+            Action::MapGrid => self.grid = !self.grid,
+            Action::MapMark if self.marks.len() < MAX_MARKS => self.marks.push(self.center),
+            Action::MapClearMarks => self.marks.clear(),
+            Action::MapMaxZoom => {
+                self.saved_scale = match self.saved_scale {
+                    Some(scale) => {
+                        self.scale = scale;
+                        None
+                    }
+                    None => Some(self.scale),
+                }
+            }
+            Action::MapZoomIn | Action::MapZoomOut => (),
+            _ => return false,
+        }
+        true
+    }
+
+    fn on_key_press(&mut self, key: VirtualKeyCode) {
+        if self.active {
+            self.check_cheat(key);
+        }
+    }
+
+    fn on_mouse_move(&mut self, _delta: (f64, f64)) {}
+}
@@ -1,77 +1,275 @@
-use crate::input::InputListener;
+use crate::{
+    game::{
+        player::Player,
+        ticcmd::{Buttons, TicCmd, FORWARD_MOVE, SIDE_MOVE, WEAPON_SHIFT},
+        weapons::next_weapon_slot,
+    },
+    input::{bindings::Action, InputListener},
+};
 use cgmath::{
-    Deg, InnerSpace, Matrix4, Point3, Quaternion, Rotation, Rotation3, Vector2, Vector3, Zero,
+    Angle, Deg, InnerSpace, Matrix4, One, Point3, Quaternion, Rad, Rotation3, Vector2, Vector3,
+    Zero,
 };
-use std::time::Instant;
-use winit::event::VirtualKeyCode;
+use kabal_app::tic_runner::TICRATE;
+
+/// Keyboard turn in degrees per tic, like vanilla angleturn
+const TURN_SPEED: f32 = 640.0 * 360.0 / 65536.0;
+
+/// Actions picking a weapon, in slot order
+const WEAPON_ACTIONS: [Action; 7] = [
+    Action::Weapon1,
+    Action::Weapon2,
+    Action::Weapon3,
+    Action::Weapon4,
+    Action::Weapon5,
+    Action::Weapon6,
+    Action::Weapon7,
+];
 
+#[derive(Clone)]
 pub struct Camera {
     pub persp: Matrix4<f32>,
     pub origin: Point3<f32>,
     pub direction: Quaternion<f32>,
     movement: Vector3<f32>,
-    last_update: Instant,
+    /// -1 to turn right, 1 to turn left
+    turn: f32,
+    /// Forward and right walking directions, from -1 to 1
+    walk: (i32, i32),
+    /// Movement actions currently held
+    held: Vec<Action>,
+    /// Actions pressed since the last tic command, a press and release in between still counts
+    pressed: Vec<Action>,
+    /// Origin and yaw at the previous tic, rendering goes from them to the current ones
+    prev_origin: Point3<f32>,
+    prev_yaw: Deg<f32>,
     yaw: Deg<f32>,
     pitch: Deg<f32>,
     last_delta: Vector2<f32>,
+    /// Fly speed in map units per second
+    pub move_speed: f32,
+    /// Degrees turned per mouse count, horizontally and vertically
+    pub sensitivity: Vector2<f32>,
+    /// Average the last two mouse moves
+    pub smooth_mouse: bool,
+    /// Horizontal field of view on a 4:3 screen, like the original renderer
+    fov: Deg<f32>,
+    aspect: f32,
 }
 
 impl Camera {
     pub fn new() -> Self {
         let origin = Point3::new(-1280.0f32, 48.0f32, -3295.0f32);
-        let direction = Rotation::look_at(Vector3::unit_x(), Vector3::unit_y());
 
-        let persp = cgmath::perspective(Deg(45.0), 16.0 / 9.0, 10.0, 10000.0);
-
-        Camera {
-            persp,
+        let mut camera = Camera {
+            persp: Matrix4::one(),
             origin,
-            direction,
+            direction: Quaternion::one(),
             movement: Vector3::zero(),
-            last_update: Instant::now(),
+            turn: 0.0,
+            walk: (0, 0),
+            held: Vec::new(),
+            pressed: Vec::new(),
+            prev_origin: origin,
+            prev_yaw: Deg::zero(),
             yaw: Deg::zero(),
             pitch: Deg::zero(),
             last_delta: Vector2::zero(),
+            move_speed: 512.0,
+            sensitivity: Vector2::new(0.5, 0.35),
+            smooth_mouse: true,
+            fov: Deg(90.0),
+            aspect: 16.0 / 9.0,
+        };
+        camera.update_projection();
+        camera.update_direction();
+        camera
+    }
+
+    /// Place the camera at a map position (doom coordinates) looking toward `angle`
+    pub fn from_map_position(x: f32, y: f32, z: f32, angle: Deg<f32>, pitch: Deg<f32>) -> Self {
+        let mut camera = Camera::new();
+        camera.origin = Point3::new(-x, z, y);
+        camera.yaw = angle - Deg(90.0);
+        camera.pitch = pitch;
+        camera.prev_origin = camera.origin;
+        camera.prev_yaw = camera.yaw;
+        camera.update_direction();
+        camera
+    }
+
+    /// The camera in between the previous tic and the current one, to render smoothly
+    pub fn interpolated(&self, fraction: f32) -> Camera {
+        let mut camera = self.clone();
+        camera.origin = self.prev_origin + (self.origin - self.prev_origin) * fraction;
+        camera.yaw = self.prev_yaw + (self.yaw - self.prev_yaw) * fraction;
+        camera.update_direction();
+        camera
+    }
+
+    /// Position in map coordinates: x, y and eye height
+    pub fn map_position(&self) -> (f32, f32, f32) {
+        (-self.origin.x, self.origin.z, self.origin.y)
+    }
+
+    /// Facing angle in the map plane, 0 is east and it grows counter-clockwise
+    pub fn map_angle(&self) -> Deg<f32> {
+        self.yaw + Deg(90.0)
+    }
+
+    pub fn pitch(&self) -> Deg<f32> {
+        self.pitch
+    }
+
+    pub fn set_aspect_ratio(&mut self, aspect: f32) {
+        self.aspect = aspect;
+        self.update_projection();
+    }
+
+    pub fn set_fov(&mut self, fov: Deg<f32>) {
+        if fov != self.fov {
+            self.fov = fov;
+            self.update_projection();
+        }
+    }
+
+    fn update_projection(&mut self) {
+        // The vertical field of view is the one of a 4:3 screen with this horizontal one
+        let vertical = Rad(2.0 * (Rad::from(self.fov / 2.0).0.tan() * 3.0 / 4.0).atan());
+        self.persp = cgmath::perspective(vertical, self.aspect, 10.0, 10000.0);
+    }
+
+    fn update_direction(&mut self) {
+        let quat_yaw: Quaternion<f32> = Rotation3::from_angle_y(self.yaw);
+        let quat_pitch: Quaternion<f32> = Rotation3::from_angle_x(self.pitch);
+
+        self.direction = quat_yaw * quat_pitch;
+    }
+
+    /// Start a tic: keep the previous view for interpolation and turn with the keyboard
+    pub fn tick(&mut self) {
+        self.prev_origin = self.origin;
+        self.prev_yaw = self.yaw;
+
+        if self.turn != 0.0 {
+            self.yaw += Deg(self.turn * TURN_SPEED * self.speed());
+            self.update_direction();
         }
     }
-    pub fn update(&mut self) {
-        let now = Instant::now();
-        let delta = now - self.last_update;
-        self.last_update = now;
 
+    /// Fly through everything for one tic, in noclip mode
+    pub fn fly(&mut self) {
         let move_dir = self.direction * self.movement;
-        let move_speed_factor = 2.0;
-        self.origin += move_dir * 256.0 * delta.as_secs_f32() * move_speed_factor;
+        self.origin += move_dir * self.move_speed * self.speed() / TICRATE as f32;
     }
-}
 
-impl InputListener for Camera {
-    fn on_input_change(&mut self, key: VirtualKeyCode, pressed: bool) {
-        match key {
-            VirtualKeyCode::Z => self.movement.z = if pressed { 1.0 } else { 0.0 },
-            VirtualKeyCode::S => self.movement.z = if pressed { -1.0 } else { 0.0 },
-            VirtualKeyCode::Q => self.movement.x = if pressed { 1.0 } else { 0.0 },
-            VirtualKeyCode::D => self.movement.x = if pressed { -1.0 } else { 0.0 },
-            VirtualKeyCode::Space => self.movement.y = if pressed { 1.0 } else { 0.0 },
-            VirtualKeyCode::C => self.movement.y = if pressed { -1.0 } else { 0.0 },
-
-            _ => (),
+    /// Move the eyes to a map position, keeping the view direction
+    pub fn set_map_position(&mut self, x: f32, y: f32, z: f32) {
+        self.origin = Point3::new(-x, z, y);
+    }
+
+    /// Jump to a map position and angle, without moving smoothly from the previous tic
+    pub fn teleport(&mut self, x: f32, y: f32, z: f32, angle: Deg<f32>) {
+        self.set_map_position(x, y, z);
+        self.yaw = angle - Deg(90.0);
+        self.prev_origin = self.origin;
+        self.prev_yaw = self.yaw;
+        self.update_direction();
+    }
+
+    /// Face a map angle the game turned the player to, turning smoothly from the previous tic
+    pub fn turn_to(&mut self, angle: Deg<f32>) {
+        self.yaw += (angle - self.map_angle()).normalize_signed();
+        self.update_direction();
+    }
+
+    fn speed(&self) -> f32 {
+        if self.held.contains(&Action::Run) {
+            2.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Walking and buttons asked by the held actions, and by the ones pressed since the last
+    /// command like a wheel step
+    ///
+    /// The next and previous weapon actions pick a weapon the player owns
+    pub fn ticcmd(&mut self, player: &Player) -> TicCmd {
+        let pressed = std::mem::take(&mut self.pressed);
+        let active = |action| self.held.contains(&action) || pressed.contains(&action);
+        let run = self.held.contains(&Action::Run) as usize;
+        let mut buttons = Buttons::NONE;
+        if active(Action::Fire) {
+            buttons |= Buttons::ATTACK;
+        }
+        if active(Action::Use) {
+            buttons |= Buttons::USE;
         }
+        let step = active(Action::NextWeapon) as i32 - active(Action::PrevWeapon) as i32;
+        let slot = WEAPON_ACTIONS
+            .iter()
+            .position(|action| active(*action))
+            .or_else(|| next_weapon_slot(player, step).filter(|_| step != 0));
+        if let Some(slot) = slot {
+            buttons |= Buttons::CHANGE | Buttons::from_bits_truncate((slot as u8) << WEAPON_SHIFT);
+        }
+        TicCmd {
+            forward_move: self.walk.0 * FORWARD_MOVE[run],
+            side_move: self.walk.1 * SIDE_MOVE[run],
+            buttons,
+        }
+    }
+
+    /// Movement and turn directions from the held actions
+    fn update_movement(&mut self) {
+        let held = |action| self.held.contains(&action) as i32 as f32;
+        let mut side = held(Action::StrafeLeft) - held(Action::StrafeRight);
+        let mut turn = held(Action::TurnLeft) - held(Action::TurnRight);
+        if self.held.contains(&Action::Strafe) {
+            side = (side + turn).clamp(-1.0, 1.0);
+            turn = 0.0;
+        }
+        let up = held(Action::Jump) - held(Action::Crouch);
+        let forward = held(Action::MoveForward) - held(Action::MoveBackward);
+
+        self.turn = turn;
+        self.walk = (forward as i32, -side as i32);
+        self.movement = Vector3::new(side, up, forward);
         if !self.movement.is_zero() {
             self.movement = self.movement.normalize();
         }
     }
+}
 
-    fn on_mouse_move(&mut self, delta: (f64, f64)) {
-        let x_sensitivity = 0.5;
-        let y_sensitivity = 0.35;
+impl InputListener for Camera {
+    fn on_action(&mut self, action: Action, pressed: bool) -> bool {
+        if pressed {
+            self.held.push(action);
+            if !self.pressed.contains(&action) {
+                self.pressed.push(action);
+            }
+        } else {
+            self.held.retain(|held| *held != action);
+        }
+        self.update_movement();
+        false
+    }
 
+    fn on_mouse_move(&mut self, delta: (f64, f64)) {
         let delta = Vector2::new(delta.0 as f32, delta.1 as f32);
-        let smoothed = (self.last_delta + delta) / 2.0;
+        let smoothed = if self.smooth_mouse {
+            (self.last_delta + delta) / 2.0
+        } else {
+            delta
+        };
         self.last_delta = delta;
 
-        self.yaw -= Deg(smoothed.x * x_sensitivity);
-        self.pitch += Deg(smoothed.y * y_sensitivity);
+        // The mouse turns right away, not at the next tic
+        let turn = Deg(smoothed.x * self.sensitivity.x);
+        self.yaw -= turn;
+        self.prev_yaw -= turn;
+        self.pitch += Deg(smoothed.y * self.sensitivity.y);
 
         if self.pitch > Deg(88.0) {
             self.pitch = Deg(88.0);
@@ -79,9 +277,71 @@ impl InputListener for Camera {
             self.pitch = Deg(-88.0);
         }
 
-        let quat_yaw: Quaternion<f32> = Rotation3::from_angle_y(self.yaw);
-        let quat_pitch: Quaternion<f32> = Rotation3::from_angle_x(self.pitch);
+        self.update_direction();
+    }
+}
 
-        self.direction = quat_yaw * quat_pitch;
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::{
+        game::{
+            player::{PlayerStatus, Weapon},
+            skill::Skill,
+            world::World,
+        },
+        input::{bindings::Button, Input},
+        wad::test_wad::room_map,
+    };
+
+    #[test]
+    fn wheel_steps_last_until_the_next_command() {
+        let camera = Rc::new(RefCell::new(Camera::new()));
+        let mut input = Input::new();
+        input.bindings.bind(Action::Use, vec![Button::WheelUp]);
+        input.bindings.bind(Action::NextWeapon, Vec::new());
+        input.listeners.push(camera.clone());
+
+        input.register_wheel(1.0);
+        let player = Player::default();
+        let cmd = camera.borrow_mut().ticcmd(&player);
+        assert_eq!(cmd.buttons, Buttons::USE);
+        let cmd = camera.borrow_mut().ticcmd(&player);
+        assert_eq!(cmd.buttons, Buttons::NONE);
+    }
+
+    #[test]
+    fn wheel_changes_to_the_next_owned_weapon() {
+        let mut map = room_map();
+        let mut world = World::new();
+        world.spawn_map_things(&map, Skill::Medium);
+        let mut status = PlayerStatus::default();
+        status.weapons[Weapon::Shotgun as usize] = true;
+        world.player = Player::spawn(&mut map, &mut world, status);
+
+        let camera = Rc::new(RefCell::new(Camera::new()));
+        let mut input = Input::new();
+        input.listeners.push(camera.clone());
+        // Scroll, then wait for the weapon in hand to change
+        let mut scroll = |delta| {
+            input.register_wheel(delta);
+            let cmd = camera.borrow_mut().ticcmd(&world.player);
+            world.with_player(|world, player| player.think(&mut map, world, &cmd, Deg(90.0)));
+            let pending = world.player.pending_weapon;
+            for _ in 0..TICRATE {
+                let cmd = TicCmd::default();
+                world.with_player(|world, player| player.think(&mut map, world, &cmd, Deg(90.0)));
+            }
+            assert_eq!(Some(world.player.status.ready_weapon), pending);
+            pending
+        };
+
+        assert_eq!(scroll(1.0), Some(Weapon::Shotgun));
+        assert_eq!(scroll(-1.0), Some(Weapon::Pistol));
+        // The chainsaw is not owned, the wheel goes past it to the fist
+        assert_eq!(scroll(-1.0), Some(Weapon::Fist));
+        assert_eq!(scroll(-1.0), Some(Weapon::Shotgun));
     }
 }
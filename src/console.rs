@@ -0,0 +1,195 @@
+use kabal_render::{
+    renderer::Renderer,
+    screen2d::{PatchImage, Screen2D},
+};
+use winit::event::{ModifiersState, VirtualKeyCode};
+
+use crate::{
+    render::font::{Font, TextColor, TextStyle},
+    sys::cvars::Cvars,
+};
+
+/// Commands run by the game, with their help line
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("map", "map <name>: start a level, like map E1M3"),
+    ("noclip", "noclip: toggle walking through walls"),
+    ("screenshot", "screenshot: save the screen to DOOMnn.ppm"),
+    (
+        "hash",
+        "hash: show the level state hash, to compare two games",
+    ),
+    (
+        "bind",
+        "bind <action> <buttons>: bind keys, mouse buttons or the wheel",
+    ),
+    ("unbind", "unbind <action>: remove the buttons of an action"),
+    ("cvarlist", "cvarlist: show every variable"),
+    ("help", "help: show the commands"),
+    ("clear", "clear: empty the console"),
+    ("quit", "quit: leave the game"),
+];
+
+/// Bottom of the console in screen space, it covers the upper half of the screen
+const CONSOLE_HEIGHT: f32 = 100.0;
+const MAX_LINES: usize = 256;
+const MARGIN: f32 = 4.0;
+
+/// Drop-down console toggled with the tilde key
+pub struct Console {
+    pub active: bool,
+    lines: Vec<String>,
+    input: String,
+    history: Vec<String>,
+    /// Entry of the history being shown, None while typing a new line
+    history_pos: Option<usize>,
+    /// Lines scrolled back from the last one
+    scroll: usize,
+    background: PatchImage,
+}
+
+impl Console {
+    pub fn new(renderer: &mut dyn Renderer) -> Self {
+        let texture = renderer.upload_texture_array(1, 1, &[vec![16, 16, 24, 255]]);
+
+        Console {
+            active: false,
+            lines: Vec::new(),
+            input: String::new(),
+            history: Vec::new(),
+            history_pos: None,
+            scroll: 0,
+            background: PatchImage {
+                texture,
+                layer: 0,
+                width: 1,
+                height: 1,
+                left_offset: 0,
+                top_offset: 0,
+            },
+        }
+    }
+
+    /// Add lines at the bottom of the console
+    pub fn print(&mut self, text: &str) {
+        self.lines.extend(text.lines().map(str::to_string));
+        if self.lines.len() > MAX_LINES {
+            self.lines.drain(..self.lines.len() - MAX_LINES);
+        }
+        self.scroll = 0;
+    }
+
+    /// Scroll the output back, or forward when negative
+    pub fn scroll(&mut self, lines: i32) {
+        let max = self.lines.len().saturating_sub(1) as i32;
+        self.scroll = (self.scroll as i32 + lines).clamp(0, max) as usize;
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.scroll = 0;
+    }
+
+    /// Type a character on the command line
+    pub fn on_char(&mut self, c: char) {
+        // The tilde key opens and closes the console
+        if self.active && (c == ' ' || c.is_ascii_graphic()) && c != '`' && c != '~' {
+            self.input.push(c);
+        }
+    }
+
+    /// Handle a key press, returns the line to run when Return is pressed
+    pub fn responder(
+        &mut self,
+        key: VirtualKeyCode,
+        modifiers: ModifiersState,
+        cvars: &Cvars,
+    ) -> Option<String> {
+        match key {
+            VirtualKeyCode::L if modifiers.ctrl() => self.clear(),
+            VirtualKeyCode::U if modifiers.ctrl() => self.input.clear(),
+            VirtualKeyCode::Grave | VirtualKeyCode::Escape => self.active = false,
+            VirtualKeyCode::Return => {
+                let line = std::mem::take(&mut self.input);
+                self.history_pos = None;
+                self.print(&format!("]{}", line));
+                if !line.trim().is_empty() {
+                    self.history.push(line.clone());
+                    return Some(line);
+                }
+            }
+            VirtualKeyCode::Back => {
+                self.input.pop();
+            }
+            VirtualKeyCode::Up if !self.history.is_empty() => {
+                let pos = self
+                    .history_pos
+                    .map_or(self.history.len() - 1, |pos| pos.saturating_sub(1));
+                self.history_pos = Some(pos);
+                self.input = self.history[pos].clone();
+            }
+            VirtualKeyCode::Down => {
+                if let Some(pos) = self.history_pos {
+                    if pos + 1 < self.history.len() {
+                        self.history_pos = Some(pos + 1);
+                        self.input = self.history[pos + 1].clone();
+                    } else {
+                        self.history_pos = None;
+                        self.input.clear();
+                    }
+                }
+            }
+            VirtualKeyCode::PageUp => self.scroll(4),
+            VirtualKeyCode::PageDown => self.scroll(-4),
+            VirtualKeyCode::Tab => self.complete(cvars),
+            _ => (),
+        }
+        None
+    }
+
+    /// Complete the command or variable name being typed
+    fn complete(&mut self, cvars: &Cvars) {
+        if self.input.is_empty() || self.input.contains(' ') {
+            return;
+        }
+        let names = COMMANDS
+            .iter()
+            .map(|(name, _)| *name)
+            .chain(cvars.iter().map(|cvar| cvar.name))
+            .filter(|name| name.starts_with(self.input.as_str()))
+            .collect::<Vec<_>>();
+        match names.as_slice() {
+            [] => (),
+            [name] => self.input = format!("{} ", name),
+            _ => {
+                let list = names.join(" ");
+                self.print(&list);
+            }
+        }
+    }
+
+    pub fn draw(&self, screen: &mut Screen2D, font: &Font) {
+        if !self.active {
+            return;
+        }
+
+        // Wide enough for any window aspect ratio
+        screen.draw_stretched(-320.0, 0.0, 960.0, CONSOLE_HEIGHT, &self.background);
+
+        let line_height = font.line_height() as f32;
+        let mut y = CONSOLE_HEIGHT - line_height - MARGIN;
+        let prompt = TextStyle {
+            color: TextColor::Gold,
+            ..Default::default()
+        };
+        font.draw(screen, MARGIN, y, &format!("]{}_", self.input), &prompt);
+
+        let text = TextStyle::default();
+        for line in self.lines.iter().rev().skip(self.scroll) {
+            y -= line_height;
+            if y < 0.0 {
+                break;
+            }
+            font.draw(screen, MARGIN, y, line, &text);
+        }
+    }
+}
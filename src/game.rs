@@ -0,0 +1,25 @@
+pub mod attacks;
+pub mod ceilings;
+pub mod doors;
+pub mod enemy;
+pub mod floors;
+pub mod info;
+pub mod interaction;
+pub mod lights;
+pub mod missiles;
+pub mod mobj;
+pub mod movement;
+pub mod pickups;
+pub mod plats;
+pub mod player;
+pub mod savegame;
+pub mod sight;
+pub mod skill;
+pub mod specials;
+pub mod states;
+pub mod switches;
+pub mod teleport;
+pub mod ticcmd;
+pub mod triggers;
+pub mod weapons;
+pub mod world;
@@ -0,0 +1,338 @@
+//! Aiming and instant hits, like P_AimLineAttack and P_LineAttack of vanilla p_map.c
+
+use cgmath::Vector2;
+
+use super::{
+    info::{MobjFlags, MobjType},
+    interaction::damage_mobj,
+    mobj::Mobj,
+    movement::{intercept, line_opening, line_points, line_sectors},
+    player::Player,
+    states::StateNum,
+    triggers::shoot_special_line,
+    world::{Thinker, ThinkerId, World},
+};
+use crate::wad::map::{Sector, WadMap, BLOCK_SIZE};
+
+/// Reach of punches and the chainsaw, like vanilla MELEERANGE
+pub const MELEERANGE: f32 = 64.0;
+/// Reach of bullets, like vanilla MISSILERANGE
+pub const MISSILERANGE: f32 = 32.0 * 64.0;
+/// Reach of the autoaim of player bullets and missiles
+pub const MISSILE_AIM_RANGE: f32 = 16.0 * 64.0;
+/// Angle tried on each side when nothing is straight ahead, a vanilla angle of 1 << 26
+const AUTOAIM_ANGLE: f32 = 360.0 / 64.0;
+/// Highest and lowest slopes that can be aimed at, the edges of the original view
+const AIM_SLOPE: f32 = 100.0 / 160.0;
+
+/// Something crossed by a trace
+#[derive(Copy, Clone)]
+enum Crossed {
+    Line(usize),
+    Thing(ThinkerId),
+}
+
+/// Start and length of a trace going from a mobj toward an angle in degrees
+fn trace(shooter: &Mobj, angle: f32, distance: f32) -> (Vector2<f32>, Vector2<f32>) {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    (
+        Vector2::new(shooter.x, shooter.y),
+        Vector2::new(cos, sin) * distance,
+    )
+}
+
+/// Height shots leave a mobj from
+fn shoot_z(shooter: &Mobj) -> f32 {
+    shooter.z + shooter.height / 2.0 + 8.0
+}
+
+/// Mobjs linked in the blockmap cells a trace goes through, in the order they run
+///
+/// Like the block walk of vanilla P_PathTraverse, a thing linked to a cell the trace misses is not
+/// found even when its box reaches the trace
+fn things_along(
+    map: &WadMap,
+    world: &World,
+    start: Vector2<f32>,
+    end: Vector2<f32>,
+) -> Vec<ThinkerId> {
+    let [origin_x, origin_y, ..] = map.blockmap[..] else {
+        return world.mobjs().map(|(id, _)| id).collect();
+    };
+    let origin = Vector2::new(origin_x as f32, origin_y as f32);
+    let from = (start - origin) / BLOCK_SIZE;
+    let to = (end - origin) / BLOCK_SIZE;
+    let mut cell = (from.x.floor() as i32, from.y.floor() as i32);
+    let last = (to.x.floor() as i32, to.y.floor() as i32);
+
+    // Fraction of the trace to the next column and row boundaries, and between two of them
+    let step = (
+        (to.x - from.x).signum() as i32,
+        (to.y - from.y).signum() as i32,
+    );
+    let boundary = |from: f32, to: f32, cell: i32| {
+        let next = if to > from { cell + 1 } else { cell } as f32;
+        if to == from {
+            f32::INFINITY
+        } else {
+            (next - from) / (to - from)
+        }
+    };
+    let mut next = (
+        boundary(from.x, to.x, cell.0),
+        boundary(from.y, to.y, cell.1),
+    );
+    let delta = (1.0 / (to.x - from.x).abs(), 1.0 / (to.y - from.y).abs());
+
+    let mut ids = Vec::new();
+    loop {
+        // The center of the cell only touches that cell
+        let center = origin + Vector2::new(cell.0 as f32 + 0.5, cell.1 as f32 + 0.5) * BLOCK_SIZE;
+        ids.extend(world.mobjs_in_box(center.x, center.y, center.x, center.y));
+        if cell == last || (next.0 > 1.0 && next.1 > 1.0) {
+            break;
+        }
+        if next.0 < next.1 {
+            cell.0 += step.0;
+            next.0 += delta.0;
+        } else {
+            cell.1 += step.1;
+            next.1 += delta.1;
+        }
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+/// Lines and shootable things crossed by a trace, closest first, like vanilla P_PathTraverse
+///
+/// The lines and things come from the blockmap, a thing is crossed on the diagonal of its box
+/// facing the trace
+fn path_traverse(
+    map: &WadMap,
+    world: &World,
+    start: Vector2<f32>,
+    delta: Vector2<f32>,
+) -> Vec<(f32, Crossed)> {
+    let end = start + delta;
+    let mut crossed: Vec<(f32, Crossed)> = map
+        .lines_in_box(
+            start.x.min(end.x),
+            start.y.min(end.y),
+            start.x.max(end.x),
+            start.y.max(end.y),
+        )
+        .into_iter()
+        .filter_map(|index| {
+            let (v1, v2) = line_points(map, &map.linedefs[index]);
+            intercept(start, delta, v1, v2).map(|frac| (frac, Crossed::Line(index)))
+        })
+        .collect();
+
+    let positive = (delta.x >= 0.0) == (delta.y >= 0.0);
+    for id in things_along(map, world, start, end) {
+        let Some(mo) = world.mobj(id) else {
+            continue;
+        };
+        if (mo.flags & MobjFlags::SHOOTABLE) == MobjFlags::NONE {
+            continue;
+        }
+        let r = mo.radius;
+        let (d1, d2) = if positive {
+            (
+                Vector2::new(mo.x - r, mo.y + r),
+                Vector2::new(mo.x + r, mo.y - r),
+            )
+        } else {
+            (
+                Vector2::new(mo.x - r, mo.y - r),
+                Vector2::new(mo.x + r, mo.y + r),
+            )
+        };
+        if let Some(frac) = intercept(start, delta, d1, d2) {
+            crossed.push((frac, Crossed::Thing(id)));
+        }
+    }
+
+    // Lines come first when something is at the same distance
+    crossed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    crossed
+}
+
+/// Slope toward the first shootable mobj in sight and that mobj, like vanilla P_AimLineAttack
+///
+/// The slope is 0 when nothing is in sight
+pub fn aim_line_attack(
+    map: &WadMap,
+    world: &World,
+    shooter: &Mobj,
+    angle: f32,
+    distance: f32,
+) -> (f32, Option<ThinkerId>) {
+    let (start, delta) = trace(shooter, angle, distance);
+    let shoot_z = shoot_z(shooter);
+    let mut top_slope = AIM_SLOPE;
+    let mut bottom_slope = -AIM_SLOPE;
+
+    for (frac, crossed) in path_traverse(map, world, start, delta) {
+        let dist = distance * frac;
+        match crossed {
+            Crossed::Line(index) => {
+                let line = &map.linedefs[index];
+                let (front, Some(back)) = line_sectors(map, line) else {
+                    break;
+                };
+                let (open_top, open_bottom) = line_opening(map, line);
+                if open_bottom >= open_top {
+                    break;
+                }
+                // What is seen through the opening gets smaller
+                if front.floor != back.floor {
+                    bottom_slope = bottom_slope.max((open_bottom - shoot_z) / dist);
+                }
+                if front.ceiling != back.ceiling {
+                    top_slope = top_slope.min((open_top - shoot_z) / dist);
+                }
+                if top_slope <= bottom_slope {
+                    break;
+                }
+            }
+            Crossed::Thing(id) => {
+                let Some(thing) = world.mobj(id) else {
+                    continue;
+                };
+                let thing_top = (thing.z + thing.height - shoot_z) / dist;
+                let thing_bottom = (thing.z - shoot_z) / dist;
+                // Over or under it
+                if thing_top < bottom_slope || thing_bottom > top_slope {
+                    continue;
+                }
+                let slope = (thing_top.min(top_slope) + thing_bottom.max(bottom_slope)) / 2.0;
+                return (slope, Some(id));
+            }
+        }
+    }
+    (0.0, None)
+}
+
+/// Angle and slope toward a shootable mobj straight ahead of a mobj or a bit to a side, None
+/// when nothing is in sight
+///
+/// The aiming of vanilla P_BulletSlope and P_SpawnPlayerMissile
+pub fn auto_aim(map: &WadMap, world: &World, mo: &Mobj, distance: f32) -> Option<(f32, f32)> {
+    [mo.angle, mo.angle + AUTOAIM_ANGLE, mo.angle - AUTOAIM_ANGLE]
+        .into_iter()
+        .find_map(|angle| {
+            let (slope, target) = aim_line_attack(map, world, mo, angle, distance);
+            target.map(|_| (angle, slope))
+        })
+}
+
+/// Fire a bullet or a blow hitting the first wall or shootable mobj in the way, like vanilla
+/// P_LineAttack
+///
+/// Walls get a puff, mobjs bleed unless they have no blood, and the special lines shot are
+/// triggered on the way
+#[allow(clippy::too_many_arguments)]
+pub fn line_attack(
+    map: &mut WadMap,
+    world: &mut World,
+    shooter: &mut Mobj,
+    mut player: Option<&mut Player>,
+    angle: f32,
+    distance: f32,
+    slope: f32,
+    damage: i32,
+) {
+    let (start, delta) = trace(shooter, angle, distance);
+    let shoot_z = shoot_z(shooter);
+
+    for (frac, crossed) in path_traverse(map, world, start, delta) {
+        let dist = distance * frac;
+        match crossed {
+            Crossed::Line(index) => {
+                if map.linedefs[index].special_type != 0 {
+                    shoot_special_line(map, world, shooter, player.as_deref_mut(), index);
+                }
+                let line = &map.linedefs[index];
+                let (front, back) = line_sectors(map, line);
+
+                // Through the opening of a two-sided line
+                if let Some(back) = back {
+                    let (open_top, open_bottom) = line_opening(map, line);
+                    let low = front.floor != back.floor && (open_bottom - shoot_z) / dist > slope;
+                    let high = front.ceiling != back.ceiling && (open_top - shoot_z) / dist < slope;
+                    if !low && !high {
+                        continue;
+                    }
+                }
+
+                // The puff is a bit in front of the wall, none in the sky
+                let frac = frac - 4.0 / distance;
+                let z = shoot_z + slope * frac * distance;
+                if front.sky_ceiling()
+                    && (z > front.ceiling || back.is_some_and(Sector::sky_ceiling))
+                {
+                    return;
+                }
+                let hit = start + delta * frac;
+                spawn_puff(map, world, hit.x, hit.y, z, distance);
+                return;
+            }
+            Crossed::Thing(id) => {
+                let Some(thing) = world.mobj(id) else {
+                    continue;
+                };
+                let thing_top = (thing.z + thing.height - shoot_z) / dist;
+                let thing_bottom = (thing.z - shoot_z) / dist;
+                if thing_top < slope || thing_bottom > slope {
+                    continue;
+                }
+
+                let frac = frac - 10.0 / distance;
+                let z = shoot_z + slope * frac * distance;
+                let hit = start + delta * frac;
+                if (thing.flags & MobjFlags::NOBLOOD) != MobjFlags::NONE {
+                    spawn_puff(map, world, hit.x, hit.y, z, distance);
+                } else {
+                    spawn_blood(map, world, hit.x, hit.y, z, damage);
+                }
+
+                if damage > 0 {
+                    world.with_mobj(id, |world, target| {
+                        damage_mobj(map, world, target, Some(shooter), Some(shooter), damage)
+                    });
+                }
+                return;
+            }
+        }
+    }
+}
+
+/// Smoke where a shot hit, like vanilla P_SpawnPuff
+pub fn spawn_puff(map: &WadMap, world: &mut World, x: f32, y: f32, z: f32, distance: f32) {
+    let z = z + world.random.sub_random() as f32 / 64.0;
+    let mut puff = Mobj::spawn_at(map, world, MobjType::Puff, x, y, z);
+    puff.momz = 1.0;
+    puff.tics = (puff.tics - (world.random.gameplay() & 3)).max(1);
+    // Punches do not spark on walls
+    if distance == MELEERANGE {
+        puff.set_state(StateNum::Puff3);
+    }
+    world.add(Thinker::Mobj(puff));
+}
+
+/// Blood where a shot hit, less of it for small damage, like vanilla P_SpawnBlood
+fn spawn_blood(map: &WadMap, world: &mut World, x: f32, y: f32, z: f32, damage: i32) {
+    let z = z + world.random.sub_random() as f32 / 64.0;
+    let mut blood = Mobj::spawn_at(map, world, MobjType::Blood, x, y, z);
+    blood.momz = 2.0;
+    blood.tics = (blood.tics - (world.random.gameplay() & 3)).max(1);
+    if (9..=12).contains(&damage) {
+        blood.set_state(StateNum::Blood2);
+    } else if damage < 9 {
+        blood.set_state(StateNum::Blood3);
+    }
+    world.add(Thinker::Mobj(blood));
+}
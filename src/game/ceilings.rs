@@ -0,0 +1,189 @@
+//! Moving ceilings and crushers, like vanilla p_ceilng.c
+
+use super::{
+    specials::{highest_ceiling_surrounding, move_plane, tagged_sectors, MoveResult, Plane},
+    world::{Thinker, World},
+};
+use crate::wad::map::{LineDef, WadMap};
+
+/// Speed of a ceiling in map units per tic, like vanilla CEILSPEED
+const CEILSPEED: f32 = 1.0;
+
+/// What a ceiling does, like vanilla ceiling_e
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CeilingKind {
+    LowerToFloor,
+    RaiseToHighest,
+    /// Down to 8 above the floor
+    LowerAndCrush,
+    /// A crusher going up and down until stopped
+    CrushAndRaise,
+    FastCrushAndRaise,
+    SilentCrushAndRaise,
+}
+
+/// A ceiling moving between two heights, like vanilla ceiling_t
+pub struct Ceiling {
+    pub kind: CeilingKind,
+    pub sector: usize,
+    pub bottom: f32,
+    pub top: f32,
+    pub speed: f32,
+    pub crush: bool,
+    /// 1 up, 0 stopped by a line, -1 down
+    pub direction: i32,
+    pub tag: i16,
+    /// Direction before being stopped
+    pub old_direction: i32,
+}
+
+impl Ceiling {
+    /// Move for one tic, like vanilla T_MoveCeiling
+    ///
+    /// Returns false once the ceiling is done
+    pub fn think(&mut self, map: &mut WadMap, world: &mut World) -> bool {
+        match self.direction {
+            1 => {
+                let result = move_plane(
+                    map,
+                    world,
+                    self.sector,
+                    self.speed,
+                    self.top,
+                    false,
+                    Plane::Ceiling,
+                    1,
+                );
+                if result == MoveResult::PastDest {
+                    match self.kind {
+                        CeilingKind::RaiseToHighest => return false,
+                        CeilingKind::CrushAndRaise
+                        | CeilingKind::FastCrushAndRaise
+                        | CeilingKind::SilentCrushAndRaise => self.direction = -1,
+                        _ => (),
+                    }
+                }
+            }
+            -1 => {
+                let result = move_plane(
+                    map,
+                    world,
+                    self.sector,
+                    self.speed,
+                    self.bottom,
+                    self.crush,
+                    Plane::Ceiling,
+                    -1,
+                );
+                match (result, self.kind) {
+                    (
+                        MoveResult::PastDest,
+                        CeilingKind::CrushAndRaise | CeilingKind::SilentCrushAndRaise,
+                    ) => {
+                        self.speed = CEILSPEED;
+                        self.direction = 1;
+                    }
+                    (MoveResult::PastDest, CeilingKind::FastCrushAndRaise) => self.direction = 1,
+                    (
+                        MoveResult::PastDest,
+                        CeilingKind::LowerAndCrush | CeilingKind::LowerToFloor,
+                    ) => return false,
+                    // Slow crushers slow down even more on what they crush
+                    (
+                        MoveResult::Crushed,
+                        CeilingKind::CrushAndRaise
+                        | CeilingKind::SilentCrushAndRaise
+                        | CeilingKind::LowerAndCrush,
+                    ) => self.speed = CEILSPEED / 8.0,
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+        true
+    }
+}
+
+/// Start the ceilings of the sectors tagged like a line, like vanilla EV_DoCeiling
+///
+/// Crushers stopped with the same tag start again. Returns true when a ceiling started
+pub fn do_ceiling(map: &WadMap, world: &mut World, line: &LineDef, kind: CeilingKind) -> bool {
+    let tag = line.sector_tag;
+    if matches!(
+        kind,
+        CeilingKind::CrushAndRaise
+            | CeilingKind::FastCrushAndRaise
+            | CeilingKind::SilentCrushAndRaise
+    ) {
+        activate_in_stasis(world, tag);
+    }
+
+    let mut started = false;
+    for sector in tagged_sectors(map, tag) {
+        if world.sector_mover(sector).is_some() {
+            continue;
+        }
+        started = true;
+
+        let (floor, ceiling) = (map.sectors[sector].floor, map.sectors[sector].ceiling);
+        let mut mover = Ceiling {
+            kind,
+            sector,
+            bottom: floor,
+            top: ceiling,
+            speed: CEILSPEED,
+            crush: false,
+            direction: -1,
+            tag,
+            old_direction: -1,
+        };
+        match kind {
+            CeilingKind::FastCrushAndRaise => {
+                mover.crush = true;
+                mover.bottom = floor + 8.0;
+                mover.speed = CEILSPEED * 2.0;
+            }
+            CeilingKind::CrushAndRaise | CeilingKind::SilentCrushAndRaise => {
+                mover.crush = true;
+                mover.bottom = floor + 8.0;
+            }
+            CeilingKind::LowerAndCrush => mover.bottom = floor + 8.0,
+            CeilingKind::LowerToFloor => (),
+            CeilingKind::RaiseToHighest => {
+                mover.top = highest_ceiling_surrounding(map, sector);
+                mover.direction = 1;
+            }
+        }
+        world.add_mover(sector, Thinker::Ceiling(mover));
+    }
+    started
+}
+
+/// Start again the crushers with a tag stopped by a line, like vanilla P_ActivateInStasisCeiling
+fn activate_in_stasis(world: &mut World, tag: i16) {
+    for (_, thinker) in world.iter_mut() {
+        if let Thinker::Ceiling(ceiling) = thinker {
+            if ceiling.tag == tag && ceiling.direction == 0 {
+                ceiling.direction = ceiling.old_direction;
+            }
+        }
+    }
+}
+
+/// Stop the moving ceilings with the tag of a line, like vanilla EV_CeilingCrushStop
+///
+/// Returns true when a ceiling stopped
+pub fn crush_stop(world: &mut World, line: &LineDef) -> bool {
+    let tag = line.sector_tag;
+    let mut stopped = false;
+    for (_, thinker) in world.iter_mut() {
+        if let Thinker::Ceiling(ceiling) = thinker {
+            if ceiling.tag == tag && ceiling.direction != 0 {
+                ceiling.old_direction = ceiling.direction;
+                ceiling.direction = 0;
+                stopped = true;
+            }
+        }
+    }
+    stopped
+}
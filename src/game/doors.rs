@@ -0,0 +1,229 @@
+//! Doors opening and closing, like vanilla p_doors.c
+
+use kabal_app::tic_runner::TICRATE;
+
+use super::{
+    specials::{
+        back_sector, lowest_ceiling_surrounding, move_plane, tagged_sectors, MoveResult, Plane,
+    },
+    world::{Thinker, World},
+};
+use crate::wad::map::{LineDef, SectorType, WadMap};
+
+/// Speed of a door in map units per tic, like vanilla VDOORSPEED
+const VDOORSPEED: f32 = 2.0;
+/// Tics an open door waits before closing, like vanilla VDOORWAIT
+const VDOORWAIT: i32 = 150;
+
+/// What a door does, like vanilla vldoor_e
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DoorKind {
+    /// Open, wait, then close
+    Normal,
+    Close30ThenOpen,
+    Close,
+    Open,
+    RaiseIn5Mins,
+    BlazeRaise,
+    BlazeOpen,
+    BlazeClose,
+}
+
+/// A door moving its sector ceiling, like vanilla vldoor_t
+pub struct Door {
+    pub kind: DoorKind,
+    pub sector: usize,
+    /// Ceiling height when open
+    pub top_height: f32,
+    pub speed: f32,
+    /// 1 up, 0 waiting, -1 down, 2 waiting to raise for the first time
+    pub direction: i32,
+    /// Tics to wait when open
+    pub top_wait: i32,
+    /// Tics left before moving again
+    pub top_countdown: i32,
+}
+
+impl Door {
+    fn new(kind: DoorKind, sector: usize, direction: i32, speed: f32, top_height: f32) -> Self {
+        Door {
+            kind,
+            sector,
+            top_height,
+            speed,
+            direction,
+            top_wait: VDOORWAIT,
+            top_countdown: 0,
+        }
+    }
+
+    /// Move for one tic, like vanilla T_VerticalDoor
+    ///
+    /// Returns false once the door is done
+    pub fn think(&mut self, map: &mut WadMap, world: &mut World) -> bool {
+        match self.direction {
+            0 => {
+                self.top_countdown -= 1;
+                if self.top_countdown == 0 {
+                    match self.kind {
+                        DoorKind::BlazeRaise | DoorKind::Normal => self.direction = -1,
+                        DoorKind::Close30ThenOpen => self.direction = 1,
+                        _ => (),
+                    }
+                }
+            }
+            2 => {
+                self.top_countdown -= 1;
+                if self.top_countdown == 0 && self.kind == DoorKind::RaiseIn5Mins {
+                    self.direction = 1;
+                    self.kind = DoorKind::Normal;
+                }
+            }
+            -1 => {
+                let floor = map.sectors[self.sector].floor;
+                let result = move_plane(
+                    map,
+                    world,
+                    self.sector,
+                    self.speed,
+                    floor,
+                    false,
+                    Plane::Ceiling,
+                    -1,
+                );
+                match (result, self.kind) {
+                    (
+                        MoveResult::PastDest,
+                        DoorKind::BlazeRaise
+                        | DoorKind::BlazeClose
+                        | DoorKind::Normal
+                        | DoorKind::Close,
+                    ) => return false,
+                    (MoveResult::PastDest, DoorKind::Close30ThenOpen) => {
+                        self.direction = 0;
+                        self.top_countdown = TICRATE as i32 * 30;
+                    }
+                    // Something is in the way, go back up
+                    (MoveResult::Crushed, DoorKind::BlazeClose | DoorKind::Close) => (),
+                    (MoveResult::Crushed, _) => self.direction = 1,
+                    _ => (),
+                }
+            }
+            1 => {
+                let result = move_plane(
+                    map,
+                    world,
+                    self.sector,
+                    self.speed,
+                    self.top_height,
+                    false,
+                    Plane::Ceiling,
+                    1,
+                );
+                if result == MoveResult::PastDest {
+                    match self.kind {
+                        DoorKind::BlazeRaise | DoorKind::Normal => {
+                            self.direction = 0;
+                            self.top_countdown = self.top_wait;
+                        }
+                        DoorKind::Close30ThenOpen | DoorKind::BlazeOpen | DoorKind::Open => {
+                            return false
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+        true
+    }
+}
+
+/// Start the doors of the sectors tagged like a line, like vanilla EV_DoDoor
+///
+/// Returns true when a door started
+pub fn do_door(map: &WadMap, world: &mut World, line: &LineDef, kind: DoorKind) -> bool {
+    let mut started = false;
+    for sector in tagged_sectors(map, line.sector_tag) {
+        if world.sector_mover(sector).is_some() {
+            continue;
+        }
+        started = true;
+
+        let open_height = lowest_ceiling_surrounding(map, sector) - 4.0;
+        let door = match kind {
+            DoorKind::BlazeClose => Door::new(kind, sector, -1, VDOORSPEED * 4.0, open_height),
+            DoorKind::Close => Door::new(kind, sector, -1, VDOORSPEED, open_height),
+            DoorKind::Close30ThenOpen => {
+                let ceiling = map.sectors[sector].ceiling;
+                Door::new(kind, sector, -1, VDOORSPEED, ceiling)
+            }
+            DoorKind::BlazeRaise | DoorKind::BlazeOpen => {
+                Door::new(kind, sector, 1, VDOORSPEED * 4.0, open_height)
+            }
+            DoorKind::Normal | DoorKind::Open | DoorKind::RaiseIn5Mins => {
+                Door::new(kind, sector, 1, VDOORSPEED, open_height)
+            }
+        };
+        world.add_mover(sector, Thinker::Door(door));
+    }
+    started
+}
+
+/// Open the door on the back of a line pushed by a mobj, like vanilla EV_VerticalDoor
+///
+/// Pushing a door that can close again sends it back up, or down when it is the player.
+/// Returns true when a door started
+pub fn vertical_door(
+    map: &WadMap,
+    world: &mut World,
+    line: &LineDef,
+    kind: DoorKind,
+    by_player: bool,
+) -> bool {
+    let Some(sector) = back_sector(map, line) else {
+        return false;
+    };
+
+    if let Some(id) = world.sector_mover(sector) {
+        if let Some(Thinker::Door(door)) = world.get_mut(id) {
+            if matches!(kind, DoorKind::Normal | DoorKind::BlazeRaise) {
+                if door.direction == -1 {
+                    door.direction = 1;
+                } else if by_player {
+                    door.direction = -1;
+                }
+            }
+        }
+        return false;
+    }
+
+    let speed = match kind {
+        DoorKind::BlazeRaise | DoorKind::BlazeOpen => VDOORSPEED * 4.0,
+        _ => VDOORSPEED,
+    };
+    let open_height = lowest_ceiling_surrounding(map, sector) - 4.0;
+    let door = Door::new(kind, sector, 1, speed, open_height);
+    world.add_mover(sector, Thinker::Door(door));
+    true
+}
+
+/// Close a door 30 seconds after the level started, like vanilla P_SpawnDoorCloseIn30
+pub fn spawn_door_close_in_30(map: &mut WadMap, world: &mut World, sector: usize) {
+    let ceiling = map.sectors[sector].ceiling;
+    map.sectors[sector].stype = SectorType::Normal;
+
+    let mut door = Door::new(DoorKind::Normal, sector, 0, VDOORSPEED, ceiling);
+    door.top_countdown = TICRATE as i32 * 30;
+    world.add_mover(sector, Thinker::Door(door));
+}
+
+/// Open a door 5 minutes after the level started, like vanilla P_SpawnDoorRaiseIn5Mins
+pub fn spawn_door_raise_in_5_mins(map: &mut WadMap, world: &mut World, sector: usize) {
+    let open_height = lowest_ceiling_surrounding(map, sector) - 4.0;
+    map.sectors[sector].stype = SectorType::Normal;
+
+    let mut door = Door::new(DoorKind::RaiseIn5Mins, sector, 2, VDOORSPEED, open_height);
+    door.top_countdown = TICRATE as i32 * 5 * 60;
+    world.add_mover(sector, Thinker::Door(door));
+}
@@ -0,0 +1,1276 @@
+//! Monster behaviour, like vanilla p_enemy.c
+
+use std::collections::HashMap;
+
+use super::{
+    attacks::{aim_line_attack, line_attack, MELEERANGE, MISSILERANGE},
+    doors::{do_door, DoorKind},
+    floors::{do_floor, FloorKind},
+    info::{MobjFlags, MobjType},
+    interaction::damage_mobj,
+    missiles::{angle_between, radius_attack, spawn_missile},
+    mobj::{set_mobj_state, Mobj},
+    movement::{
+        aprox_distance, blocking_thing, check_move, check_position, line_opening, set_position,
+        things_around, touched_special_lines, try_move, MAXMOVE,
+    },
+    sight::check_sight,
+    skill::Skill,
+    states::StateNum,
+    teleport::teleport_move,
+    triggers::{cross_special_lines, use_special_line},
+    world::{Thinker, ThinkerId, World},
+};
+use crate::wad::map::{LineDef, LinedefFlags, WadMap};
+
+/// Height a floating monster rises or sinks each tic, like vanilla FLOATSPEED
+const FLOATSPEED: f32 = 4.0;
+/// Speed of a charging lost soul, like vanilla SKULLSPEED
+const SKULLSPEED: f32 = 20.0;
+/// Degrees between the fireballs of a mancubus, like vanilla FATSPREAD
+const FATSPREAD: f32 = 90.0 / 8.0;
+/// Degrees of one step of the random spread of bullets, a vanilla angle shifted by 20 bits
+const BULLET_SPREAD: f32 = 360.0 / 4096.0;
+/// Degrees of one step of the random aim at a fuzzy target, a vanilla angle shifted by 21 bits
+const FUZZY_SPREAD: f32 = 360.0 / 2048.0;
+/// Distance of the fire of an arch-vile in front of its victim
+const FIRE_DISTANCE: f32 = 24.0;
+/// Lost souls in the level above which a pain elemental spits no more
+const MAX_SKULLS: usize = 20;
+/// Tag of the sectors moved when the bosses die
+const BOSS_TAG: i16 = 666;
+
+/// Directions monsters walk in, counterclockwise from east, like vanilla dirtype_t
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoveDir {
+    East,
+    NorthEast,
+    North,
+    NorthWest,
+    West,
+    SouthWest,
+    South,
+    SouthEast,
+    None,
+}
+
+const DIRECTIONS: [MoveDir; 8] = [
+    MoveDir::East,
+    MoveDir::NorthEast,
+    MoveDir::North,
+    MoveDir::NorthWest,
+    MoveDir::West,
+    MoveDir::SouthWest,
+    MoveDir::South,
+    MoveDir::SouthEast,
+];
+
+impl MoveDir {
+    /// The other way, like vanilla opposite
+    fn opposite(self) -> MoveDir {
+        match self {
+            MoveDir::None => MoveDir::None,
+            dir => DIRECTIONS[(dir as usize + 4) % 8],
+        }
+    }
+
+    /// Facing angle in degrees
+    fn angle(self) -> Option<f32> {
+        (self != MoveDir::None).then_some(self as usize as f32 * 45.0)
+    }
+
+    /// Move for a speed of 1, like vanilla xspeed and yspeed
+    fn step(self) -> Option<(f32, f32)> {
+        const DIAGONAL: f32 = 47000.0 / 65536.0;
+        let step = match self {
+            MoveDir::East => (1.0, 0.0),
+            MoveDir::NorthEast => (DIAGONAL, DIAGONAL),
+            MoveDir::North => (0.0, 1.0),
+            MoveDir::NorthWest => (-DIAGONAL, DIAGONAL),
+            MoveDir::West => (-1.0, 0.0),
+            MoveDir::SouthWest => (-DIAGONAL, -DIAGONAL),
+            MoveDir::South => (0.0, -1.0),
+            MoveDir::SouthEast => (DIAGONAL, -DIAGONAL),
+            MoveDir::None => return None,
+        };
+        Some(step)
+    }
+}
+
+/// Targets of the cubes spat by the boss brain, like vanilla braintargets
+#[derive(Default)]
+pub struct Brain {
+    pub targets: Vec<ThinkerId>,
+    pub next: usize,
+    /// Every other cube is skipped on the easy skills
+    pub easy: bool,
+}
+
+/// Wake up the monsters that can hear a mobj, like vanilla P_NoiseAlert
+///
+/// The noise goes through the open two-sided lines, and through one line blocking sound at most
+pub fn noise_alert(map: &WadMap, world: &mut World, emitter: &Mobj) {
+    let mut traversed = HashMap::new();
+    let sector = map.sector_at(emitter.x, emitter.y);
+    recursive_sound(map, world, &mut traversed, sector, 0, emitter.id);
+}
+
+/// Flood the noise from a sector, like vanilla P_RecursiveSound
+fn recursive_sound(
+    map: &WadMap,
+    world: &mut World,
+    traversed: &mut HashMap<usize, u32>,
+    sector: usize,
+    sound_blocks: u32,
+    target: ThinkerId,
+) {
+    if traversed
+        .get(&sector)
+        .is_some_and(|&blocks| blocks <= sound_blocks + 1)
+    {
+        return;
+    }
+    traversed.insert(sector, sound_blocks + 1);
+    world.sound_targets.insert(sector, target);
+
+    for &index in &map.sector_lines[sector] {
+        let line = &map.linedefs[index];
+        if line.back_sidedef == -1 {
+            continue;
+        }
+        // Closed door
+        let (open_top, open_bottom) = line_opening(map, line);
+        if open_top <= open_bottom {
+            continue;
+        }
+        let front = map.sidedefs[line.front_sidedef as usize].sector as usize;
+        let back = map.sidedefs[line.back_sidedef as usize].sector as usize;
+        let other = if front == sector { back } else { front };
+        if (line.flags & LinedefFlags::BLOCK_SOUND) == LinedefFlags::NONE {
+            recursive_sound(map, world, traversed, other, sound_blocks, target);
+        } else if sound_blocks == 0 {
+            recursive_sound(map, world, traversed, other, 1, target);
+        }
+    }
+}
+
+/// Target the player if it is alive and in sight, like vanilla P_LookForPlayers in a single
+/// player game
+///
+/// Unless looking all around, a player behind the monster is only noticed when very close
+fn look_for_player(map: &WadMap, world: &World, actor: &mut Mobj, all_around: bool) -> bool {
+    let id = world.player.mobj;
+    let Some(player) = world.mobj(id) else {
+        return false;
+    };
+    if player.health <= 0 || !check_sight(map, actor, player) {
+        return false;
+    }
+    if !all_around {
+        let angle =
+            (angle_between(actor.x, actor.y, player.x, player.y) - actor.angle).rem_euclid(360.0);
+        let dist = aprox_distance(player.x - actor.x, player.y - actor.y);
+        if angle > 90.0 && angle < 270.0 && dist > MELEERANGE {
+            return false;
+        }
+    }
+    actor.target = id;
+    true
+}
+
+/// Stand still until the player is seen or heard, like vanilla A_Look
+///
+/// A monster set to ambush only wakes up to a noise when it sees who made it
+pub fn look(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    // Any shot wakes it up
+    actor.threshold = 0;
+
+    let sector = map.sector_at(actor.x, actor.y);
+    let heard = world.sound_targets.get(&sector).and_then(|&id| {
+        let target = world.mobj(id)?;
+        ((target.flags & MobjFlags::SHOOTABLE) != MobjFlags::NONE).then_some((id, target))
+    });
+    let mut wakes_up = false;
+    if let Some((id, target)) = heard {
+        actor.target = id;
+        wakes_up =
+            (actor.flags & MobjFlags::AMBUSH) == MobjFlags::NONE || check_sight(map, actor, target);
+    }
+    if wakes_up || look_for_player(map, world, actor, false) {
+        // Nothing plays it yet, but the pick is drawn like vanilla
+        let _sound = see_sound(world, actor);
+        set_mobj_state(map, world, actor, actor.info.see_state);
+    }
+}
+
+/// Sound of a monster waking up, former humans and imps pick one of a few
+fn see_sound(world: &mut World, actor: &Mobj) -> Option<&'static str> {
+    Some(match actor.info.see_sound? {
+        "posit1" | "posit2" | "posit3" => {
+            ["posit1", "posit2", "posit3"][(world.random.gameplay() % 3) as usize]
+        }
+        "bgsit1" | "bgsit2" => ["bgsit1", "bgsit2"][(world.random.gameplay() % 2) as usize],
+        sound => sound,
+    })
+}
+
+/// Cry of a dying monster, like vanilla A_Scream
+///
+/// Former humans and imps pick one of a few
+pub fn scream(world: &mut World, actor: &Mobj) -> Option<&'static str> {
+    Some(match actor.info.death_sound? {
+        "podth1" | "podth2" | "podth3" => {
+            ["podth1", "podth2", "podth3"][(world.random.gameplay() % 3) as usize]
+        }
+        "bgdth1" | "bgdth2" => ["bgdth1", "bgdth2"][(world.random.gameplay() % 2) as usize],
+        sound => sound,
+    })
+}
+
+/// Walk after the target and attack it when possible, like vanilla A_Chase
+pub fn chase(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if actor.reaction_time > 0 {
+        actor.reaction_time -= 1;
+    }
+
+    // The grudge against an attacker wears off
+    if actor.threshold > 0 {
+        if world
+            .mobj(actor.target)
+            .is_none_or(|target| target.health <= 0)
+        {
+            actor.threshold = 0;
+        } else {
+            actor.threshold -= 1;
+        }
+    }
+
+    // Turn toward the walking direction, 45 degrees at a time
+    if let Some(dir_angle) = actor.move_dir.angle() {
+        let angle = (actor.angle / 45.0).floor() * 45.0;
+        let delta = (angle - dir_angle + 180.0).rem_euclid(360.0) - 180.0;
+        actor.angle = if delta > 0.0 {
+            angle - 45.0
+        } else if delta < 0.0 {
+            angle + 45.0
+        } else {
+            angle
+        }
+        .rem_euclid(360.0);
+    }
+
+    let shootable = world
+        .mobj(actor.target)
+        .is_some_and(|target| (target.flags & MobjFlags::SHOOTABLE) != MobjFlags::NONE);
+    if !shootable {
+        // The player is in the middle of its own tic
+        if world.running(actor.target) {
+            return;
+        }
+        if !look_for_player(map, world, actor, true) {
+            set_mobj_state(map, world, actor, actor.info.spawn_state);
+        }
+        return;
+    }
+
+    // Not twice in a row
+    if (actor.flags & MobjFlags::JUSTATTACKED) != MobjFlags::NONE {
+        actor.flags.remove(MobjFlags::JUSTATTACKED);
+        if world.skill != Skill::Nightmare {
+            new_chase_dir(map, world, actor);
+        }
+        return;
+    }
+
+    if actor.info.melee_state != StateNum::Null && check_melee_range(map, world, actor) {
+        set_mobj_state(map, world, actor, actor.info.melee_state);
+        return;
+    }
+    // Nightmare monsters do not walk a while between shots
+    if actor.info.missile_state != StateNum::Null
+        && (world.skill == Skill::Nightmare || actor.move_count == 0)
+        && check_missile_range(map, world, actor)
+    {
+        set_mobj_state(map, world, actor, actor.info.missile_state);
+        actor.flags |= MobjFlags::JUSTATTACKED;
+        return;
+    }
+
+    actor.move_count -= 1;
+    if actor.move_count < 0 || !move_actor(map, world, actor) {
+        new_chase_dir(map, world, actor);
+    }
+
+    // Now and then, nothing plays it yet but the chance is drawn like vanilla
+    let _sound = actor
+        .info
+        .active_sound
+        .filter(|_| world.random.gameplay() < 3);
+}
+
+/// Turn toward the target, not quite for a fuzzy one, like vanilla A_FaceTarget
+pub fn face_target(world: &mut World, actor: &mut Mobj) {
+    let Some(target) = world.mobj(actor.target) else {
+        return;
+    };
+    let fuzzy = (target.flags & MobjFlags::SHADOW) != MobjFlags::NONE;
+    let (x, y) = (target.x, target.y);
+    face(world, actor, x, y, fuzzy);
+}
+
+fn face(world: &mut World, actor: &mut Mobj, x: f32, y: f32, fuzzy: bool) {
+    actor.flags.remove(MobjFlags::AMBUSH);
+    let mut angle = angle_between(actor.x, actor.y, x, y);
+    if fuzzy {
+        angle += world.random.sub_random() as f32 * FUZZY_SPREAD;
+    }
+    actor.angle = angle.rem_euclid(360.0);
+}
+
+/// True when the target is close enough to be hit by hand, like vanilla P_CheckMeleeRange
+fn check_melee_range(map: &WadMap, world: &World, actor: &Mobj) -> bool {
+    let Some(target) = world.mobj(actor.target) else {
+        return false;
+    };
+    let dist = aprox_distance(target.x - actor.x, target.y - actor.y);
+    dist < MELEERANGE - 20.0 + target.info.radius && check_sight(map, actor, target)
+}
+
+/// Decide to shoot at the target, more likely when it is close, like vanilla
+/// P_CheckMissileRange
+fn check_missile_range(map: &WadMap, world: &mut World, actor: &mut Mobj) -> bool {
+    let Some(target) = world.mobj(actor.target) else {
+        return false;
+    };
+    if !check_sight(map, actor, target) {
+        return false;
+    }
+    let dist = aprox_distance(target.x - actor.x, target.y - actor.y);
+
+    // Fight back at once
+    if (actor.flags & MobjFlags::JUSTHIT) != MobjFlags::NONE {
+        actor.flags.remove(MobjFlags::JUSTHIT);
+        return true;
+    }
+    if actor.reaction_time > 0 {
+        return false;
+    }
+
+    let mut dist = dist - 64.0;
+    // Shoot more without a melee attack
+    if actor.info.melee_state == StateNum::Null {
+        dist -= 128.0;
+    }
+    let mut dist = dist.floor() as i32;
+    match actor.kind {
+        MobjType::Vile if dist > 14 * 64 => return false,
+        // Too close, better punch
+        MobjType::Undead if dist < 196 => return false,
+        MobjType::Undead | MobjType::Cyborg | MobjType::Spider | MobjType::Skull => dist >>= 1,
+        _ => (),
+    }
+    dist = dist.min(200);
+    if actor.kind == MobjType::Cyborg {
+        dist = dist.min(160);
+    }
+    world.random.gameplay() >= dist
+}
+
+/// Take a step in the walking direction, like vanilla P_Move
+///
+/// A blocked monster opens the doors in the way, a floating one rises or sinks to get through
+fn move_actor(map: &mut WadMap, world: &mut World, actor: &mut Mobj) -> bool {
+    let Some((step_x, step_y)) = actor.move_dir.step() else {
+        return false;
+    };
+    let x = actor.x + actor.info.speed * step_x;
+    let y = actor.y + actor.info.speed * step_y;
+    let from = (actor.x, actor.y);
+    let floats = (actor.flags & MobjFlags::FLOAT) != MobjFlags::NONE;
+
+    match check_move(map, world, actor, x, y) {
+        Ok(()) => {
+            actor.flags.remove(MobjFlags::INFLOAT);
+            if !floats {
+                actor.z = actor.floor_z;
+            }
+            cross_special_lines(map, world, actor, None, from);
+            true
+        }
+        Err(Some(heights)) if floats => {
+            if actor.z < heights.floor_z {
+                actor.z += FLOATSPEED;
+            } else {
+                actor.z -= FLOATSPEED;
+            }
+            actor.flags |= MobjFlags::INFLOAT;
+            true
+        }
+        Err(_) => {
+            // Lines are only reached once no thing or wall is in the way, like vanilla spechit
+            if blocking_thing(world, actor, x, y).is_some()
+                || check_position(map, actor, x, y).is_none()
+            {
+                return false;
+            }
+            let lines = touched_special_lines(map, actor, x, y);
+            if lines.is_empty() {
+                return false;
+            }
+            actor.move_dir = MoveDir::None;
+            let mut opened = false;
+            for line in lines.into_iter().rev() {
+                opened |= use_special_line(map, world, actor, None, line, 0);
+            }
+            opened
+        }
+    }
+}
+
+/// Step in the walking direction and keep it for a few more, like vanilla P_TryWalk
+fn try_walk(map: &mut WadMap, world: &mut World, actor: &mut Mobj) -> bool {
+    if !move_actor(map, world, actor) {
+        return false;
+    }
+    actor.move_count = world.random.gameplay() & 15;
+    true
+}
+
+/// Pick a direction toward the target, or any other one that is free, like vanilla
+/// P_NewChaseDir
+///
+/// Monsters do not turn around unless nothing else is free
+fn new_chase_dir(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    let Some(target) = world.mobj(actor.target) else {
+        return;
+    };
+    let (dx, dy) = (target.x - actor.x, target.y - actor.y);
+    let old = actor.move_dir;
+    let turnaround = old.opposite();
+
+    let mut dirs = [
+        if dx > 10.0 {
+            MoveDir::East
+        } else if dx < -10.0 {
+            MoveDir::West
+        } else {
+            MoveDir::None
+        },
+        if dy < -10.0 {
+            MoveDir::South
+        } else if dy > 10.0 {
+            MoveDir::North
+        } else {
+            MoveDir::None
+        },
+    ];
+
+    // Straight to the target
+    if dirs[0] != MoveDir::None && dirs[1] != MoveDir::None {
+        actor.move_dir = match (dy < 0.0, dx > 0.0) {
+            (false, false) => MoveDir::NorthWest,
+            (false, true) => MoveDir::NorthEast,
+            (true, false) => MoveDir::SouthWest,
+            (true, true) => MoveDir::SouthEast,
+        };
+        if actor.move_dir != turnaround && try_walk(map, world, actor) {
+            return;
+        }
+    }
+
+    // Along one axis, the longest one first most of the time
+    if world.random.gameplay() > 200 || dy.abs() > dx.abs() {
+        dirs.swap(0, 1);
+    }
+    for dir in dirs {
+        if dir != MoveDir::None && dir != turnaround {
+            actor.move_dir = dir;
+            if try_walk(map, world, actor) {
+                return;
+            }
+        }
+    }
+
+    // No direct path, keep going or search all around
+    if old != MoveDir::None {
+        actor.move_dir = old;
+        if try_walk(map, world, actor) {
+            return;
+        }
+    }
+    let mut search = DIRECTIONS;
+    if (world.random.gameplay() & 1) == 0 {
+        search.reverse();
+    }
+    for dir in search {
+        if dir != turnaround {
+            actor.move_dir = dir;
+            if try_walk(map, world, actor) {
+                return;
+            }
+        }
+    }
+    if turnaround != MoveDir::None {
+        actor.move_dir = turnaround;
+        if try_walk(map, world, actor) {
+            return;
+        }
+    }
+    actor.move_dir = MoveDir::None;
+}
+
+/// Float up or down toward the middle of the target when getting close, like the floating part
+/// of vanilla P_ZMovement
+pub fn float_to_target(world: &World, mo: &mut Mobj) {
+    if (mo.flags & MobjFlags::FLOAT) == MobjFlags::NONE
+        || (mo.flags & (MobjFlags::SKULLFLY | MobjFlags::INFLOAT)) != MobjFlags::NONE
+    {
+        return;
+    }
+    let Some(target) = world.mobj(mo.target) else {
+        return;
+    };
+    let dist = aprox_distance(target.x - mo.x, target.y - mo.y);
+    let delta = target.z + mo.height / 2.0 - mo.z;
+    if delta < 0.0 && dist < -delta * 3.0 {
+        mo.z -= FLOATSPEED;
+    } else if delta > 0.0 && dist < delta * 3.0 {
+        mo.z += FLOATSPEED;
+    }
+}
+
+/// Hit the target by hand
+fn melee(map: &mut WadMap, world: &mut World, actor: &Mobj, damage: i32) {
+    world.with_mobj(actor.target, |world, target| {
+        damage_mobj(map, world, target, Some(actor), Some(actor), damage)
+    });
+}
+
+/// Fire a bullet at the target, with some spread
+fn shoot_bullet(map: &mut WadMap, world: &mut World, actor: &mut Mobj, angle: f32, slope: f32) {
+    let angle = angle + world.random.sub_random() as f32 * BULLET_SPREAD;
+    let damage = (world.random.gameplay() % 5 + 1) * 3;
+    line_attack(map, world, actor, None, angle, MISSILERANGE, slope, damage);
+}
+
+/// One bullet of a zombieman or a chaingunner, like vanilla A_PosAttack and A_CPosAttack
+pub fn pos_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    let angle = actor.angle;
+    let (slope, _) = aim_line_attack(map, world, actor, angle, MISSILERANGE);
+    shoot_bullet(map, world, actor, angle, slope);
+}
+
+/// Three pellets of a shotgun guy, like vanilla A_SPosAttack
+pub fn spos_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    let angle = actor.angle;
+    let (slope, _) = aim_line_attack(map, world, actor, angle, MISSILERANGE);
+    for _ in 0..3 {
+        shoot_bullet(map, world, actor, angle, slope);
+    }
+}
+
+/// Keep shooting while the target is alive and in sight, or sometimes anyway, like vanilla
+/// A_CPosRefire and A_SpidRefire
+///
+/// `chance` out of 256 to keep going without looking
+pub fn refire(map: &mut WadMap, world: &mut World, actor: &mut Mobj, chance: i32) {
+    face_target(world, actor);
+    if world.random.gameplay() < chance {
+        return;
+    }
+    let in_sight = world
+        .mobj(actor.target)
+        .is_some_and(|target| target.health > 0 && check_sight(map, actor, target));
+    if !in_sight {
+        set_mobj_state(map, world, actor, actor.info.see_state);
+    }
+}
+
+/// Plasma of an arachnotron, like vanilla A_BspiAttack
+pub fn bspi_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    spawn_missile(map, world, actor, actor.target, MobjType::ArachPlaz);
+}
+
+/// Claw of an imp, or its fireball from afar, like vanilla A_TroopAttack
+pub fn troop_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    if check_melee_range(map, world, actor) {
+        let damage = (world.random.gameplay() % 8 + 1) * 3;
+        melee(map, world, actor, damage);
+        return;
+    }
+    spawn_missile(map, world, actor, actor.target, MobjType::TroopShot);
+}
+
+/// Bite of a demon or a spectre, like vanilla A_SargAttack
+pub fn sarg_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    if check_melee_range(map, world, actor) {
+        let damage = (world.random.gameplay() % 10 + 1) * 4;
+        melee(map, world, actor, damage);
+    }
+}
+
+/// Bite of a cacodemon, or its ball from afar, like vanilla A_HeadAttack
+pub fn head_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    if check_melee_range(map, world, actor) {
+        let damage = (world.random.gameplay() % 6 + 1) * 10;
+        melee(map, world, actor, damage);
+        return;
+    }
+    spawn_missile(map, world, actor, actor.target, MobjType::HeadShot);
+}
+
+/// Claw of a baron or a hell knight, or its ball from afar, like vanilla A_BruisAttack
+pub fn bruis_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    if check_melee_range(map, world, actor) {
+        let damage = (world.random.gameplay() % 8 + 1) * 10;
+        melee(map, world, actor, damage);
+        return;
+    }
+    spawn_missile(map, world, actor, actor.target, MobjType::BruiserShot);
+}
+
+/// Rocket of a cyberdemon, like vanilla A_CyberAttack
+pub fn cyber_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    spawn_missile(map, world, actor, actor.target, MobjType::Rocket);
+}
+
+/// Homing missile of a revenant, like vanilla A_SkelMissile
+pub fn skel_missile(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    // Fired from the shoulders
+    actor.z += 16.0;
+    let missile = spawn_missile(map, world, actor, actor.target, MobjType::Tracer);
+    actor.z -= 16.0;
+
+    if let Some(mo) = missile.and_then(|id| world.mobj_mut(id)) {
+        let (x, y) = (mo.x + mo.momx, mo.y + mo.momy);
+        set_position(map, mo, x, y);
+        mo.tracer = actor.target;
+    }
+}
+
+/// Punch of a revenant, like vanilla A_SkelFist
+pub fn skel_fist(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    if check_melee_range(map, world, actor) {
+        let damage = (world.random.gameplay() % 10 + 1) * 6;
+        melee(map, world, actor, damage);
+    }
+}
+
+/// Raise a corpse in the way, or chase like the others, like vanilla A_VileChase
+pub fn vile_chase(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if let Some((step_x, step_y)) = actor.move_dir.step() {
+        let x = actor.x + actor.info.speed * step_x;
+        let y = actor.y + actor.info.speed * step_y;
+        let corpse = world.mobjs().find_map(|(id, corpse)| {
+            if (corpse.flags & MobjFlags::CORPSE) == MobjFlags::NONE
+                || corpse.tics != -1
+                || corpse.info.raise_state == StateNum::Null
+            {
+                return None;
+            }
+            let reach = corpse.info.radius + actor.info.radius;
+            if (corpse.x - x).abs() > reach || (corpse.y - y).abs() > reach {
+                return None;
+            }
+            // Room for it standing up
+            let mut standing = corpse.clone();
+            standing.height *= 4.0;
+            let fits = check_position(map, &standing, corpse.x, corpse.y).is_some()
+                && blocking_thing(world, &standing, corpse.x, corpse.y).is_none();
+            let fuzzy = (corpse.flags & MobjFlags::SHADOW) != MobjFlags::NONE;
+            fits.then_some((id, corpse.x, corpse.y, fuzzy))
+        });
+
+        if let Some((id, corpse_x, corpse_y, fuzzy)) = corpse {
+            face(world, actor, corpse_x, corpse_y, fuzzy);
+            set_mobj_state(map, world, actor, StateNum::VileHeal1);
+            world.with_mobj(id, |world, corpse| {
+                corpse.momx = 0.0;
+                corpse.momy = 0.0;
+                set_mobj_state(map, world, corpse, corpse.info.raise_state);
+                corpse.height *= 4.0;
+                corpse.flags = corpse.info.flags;
+                corpse.health = corpse.info.spawn_health;
+                corpse.target = ThinkerId::NONE;
+            });
+            return;
+        }
+    }
+    chase(map, world, actor);
+}
+
+/// Light a fire on the target, like vanilla A_VileTarget
+pub fn vile_target(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    let Some(target) = world.mobj(actor.target).cloned() else {
+        return;
+    };
+    face_target(world, actor);
+
+    let mut fire = Mobj::spawn_at(map, world, MobjType::Fire, target.x, target.y, target.z);
+    fire.target = actor.id;
+    fire.tracer = actor.target;
+    place_fire(map, &mut fire, actor, &target);
+    actor.tracer = world.add(Thinker::Mobj(fire));
+}
+
+/// Blast the target and throw it up in the air, like vanilla A_VileAttack
+///
+/// The fire explodes in front of the target
+pub fn vile_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    let Some(target) = world
+        .mobj(actor.target)
+        .filter(|target| check_sight(map, actor, target))
+    else {
+        return;
+    };
+    let (target_x, target_y) = (target.x, target.y);
+
+    world.with_mobj(actor.target, |world, target| {
+        damage_mobj(map, world, target, Some(actor), Some(actor), 20);
+        target.momz = 1000.0 / target.info.mass as f32;
+    });
+
+    let (sin, cos) = actor.angle.to_radians().sin_cos();
+    let Some(fire) = world.mobj_mut(actor.tracer) else {
+        return;
+    };
+    fire.x = target_x - FIRE_DISTANCE * cos;
+    fire.y = target_y - FIRE_DISTANCE * sin;
+    let fire = fire.clone();
+    radius_attack(map, world, &fire, Some(actor), 70);
+}
+
+/// Keep the fire of an arch-vile on its victim, like vanilla A_Fire
+pub fn fire(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    let (Some(vile), Some(dest)) = (world.mobj(actor.target), world.mobj(actor.tracer)) else {
+        return;
+    };
+    place_fire(map, actor, vile, dest);
+}
+
+/// Put a fire in front of its victim, unless the arch-vile lost sight of it
+fn place_fire(map: &WadMap, fire: &mut Mobj, vile: &Mobj, dest: &Mobj) {
+    if !check_sight(map, vile, dest) {
+        return;
+    }
+    let (sin, cos) = dest.angle.to_radians().sin_cos();
+    set_position(
+        map,
+        fire,
+        dest.x + FIRE_DISTANCE * cos,
+        dest.y + FIRE_DISTANCE * sin,
+    );
+    fire.z = dest.z;
+}
+
+/// Fireballs of a mancubus at the target, each turned by some degrees
+fn fat_shots(map: &mut WadMap, world: &mut World, actor: &Mobj, turns: [f32; 2]) {
+    for turn in turns {
+        let missile = spawn_missile(map, world, actor, actor.target, MobjType::FatShot);
+        if let Some(mo) = missile.and_then(|id| world.mobj_mut(id)) {
+            mo.angle = (mo.angle + turn).rem_euclid(360.0);
+            let (sin, cos) = mo.angle.to_radians().sin_cos();
+            mo.momx = mo.info.speed * cos;
+            mo.momy = mo.info.speed * sin;
+        }
+    }
+}
+
+/// Fireballs of a mancubus, the second one to the left, like vanilla A_FatAttack1
+pub fn fat_attack1(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    face_target(world, actor);
+    actor.angle = (actor.angle + FATSPREAD).rem_euclid(360.0);
+    fat_shots(map, world, actor, [0.0, FATSPREAD]);
+}
+
+/// Fireballs of a mancubus, the second one to the right, like vanilla A_FatAttack2
+pub fn fat_attack2(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    face_target(world, actor);
+    actor.angle = (actor.angle - FATSPREAD).rem_euclid(360.0);
+    fat_shots(map, world, actor, [0.0, -FATSPREAD * 2.0]);
+}
+
+/// Fireballs of a mancubus on both sides of the target, like vanilla A_FatAttack3
+pub fn fat_attack3(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    face_target(world, actor);
+    fat_shots(map, world, actor, [-FATSPREAD / 2.0, FATSPREAD / 2.0]);
+}
+
+/// Charge at the target, like vanilla A_SkullAttack
+pub fn skull_attack(world: &mut World, actor: &mut Mobj) {
+    let Some(dest) = world.mobj(actor.target) else {
+        return;
+    };
+    let (x, y, z) = (dest.x, dest.y, dest.z + dest.height / 2.0);
+    actor.flags |= MobjFlags::SKULLFLY;
+    face_target(world, actor);
+
+    let (sin, cos) = actor.angle.to_radians().sin_cos();
+    actor.momx = SKULLSPEED * cos;
+    actor.momy = SKULLSPEED * sin;
+    let tics = (aprox_distance(x - actor.x, y - actor.y) / SKULLSPEED)
+        .floor()
+        .max(1.0);
+    actor.momz = (z - actor.z) / tics;
+}
+
+/// Thing a charging lost soul moved to a position runs into, like vanilla PIT_CheckThing for
+/// a lost soul
+fn slammed_thing(world: &World, actor: &Mobj, x: f32, y: f32) -> Option<ThinkerId> {
+    things_around(world, actor, x, y).find_map(|(id, thing)| {
+        let touchable = MobjFlags::SOLID | MobjFlags::SPECIAL | MobjFlags::SHOOTABLE;
+        if (thing.flags & touchable) == MobjFlags::NONE || id == actor.id {
+            return None;
+        }
+        let block = thing.radius + actor.radius;
+        ((thing.x - x).abs() < block && (thing.y - y).abs() < block).then_some(id)
+    })
+}
+
+/// End the charge of a lost soul
+fn stop_skull(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    actor.flags.remove(MobjFlags::SKULLFLY);
+    actor.momx = 0.0;
+    actor.momy = 0.0;
+    actor.momz = 0.0;
+    set_mobj_state(map, world, actor, actor.info.spawn_state);
+}
+
+/// Charge of a lost soul, it bites what it runs into and stops there, like the MF_SKULLFLY
+/// parts of vanilla P_XYMovement and PIT_CheckThing
+pub fn skull_movement(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    // Stopped by a wall on the last tic
+    if actor.momx == 0.0 && actor.momy == 0.0 {
+        stop_skull(map, world, actor);
+        return;
+    }
+
+    actor.momx = actor.momx.clamp(-MAXMOVE, MAXMOVE);
+    actor.momy = actor.momy.clamp(-MAXMOVE, MAXMOVE);
+    let steps = if actor.momx.abs() > MAXMOVE / 2.0 || actor.momy.abs() > MAXMOVE / 2.0 {
+        2
+    } else {
+        1
+    };
+    for _ in 0..steps {
+        let x = actor.x + actor.momx / steps as f32;
+        let y = actor.y + actor.momy / steps as f32;
+        if let Some(id) = slammed_thing(world, actor, x, y) {
+            let damage = (world.random.gameplay() % 8 + 1) * actor.info.damage;
+            world.with_mobj(id, |world, thing| {
+                damage_mobj(map, world, thing, Some(actor), Some(actor), damage)
+            });
+            stop_skull(map, world, actor);
+            return;
+        }
+        if !try_move(map, world, actor, x, y) {
+            actor.momx = 0.0;
+            actor.momy = 0.0;
+            return;
+        }
+    }
+}
+
+/// Spit a lost soul toward an angle, like vanilla A_PainShootSkull
+///
+/// A lost soul spat into a wall dies at once
+fn pain_shoot_skull(map: &mut WadMap, world: &mut World, actor: &Mobj, angle: f32) {
+    let skulls = world
+        .mobjs()
+        .filter(|(_, mo)| mo.kind == MobjType::Skull)
+        .count();
+    if skulls > MAX_SKULLS {
+        return;
+    }
+
+    let prestep = 4.0 + 3.0 * (actor.info.radius + MobjType::Skull.info().radius) / 2.0;
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let (x, y) = (actor.x + prestep * cos, actor.y + prestep * sin);
+    let mut skull = Mobj::spawn_at(map, world, MobjType::Skull, x, y, actor.z + 8.0);
+    if try_move(map, world, &mut skull, x, y) {
+        skull.target = actor.target;
+        skull_attack(world, &mut skull);
+    } else {
+        damage_mobj(map, world, &mut skull, Some(actor), Some(actor), 10000);
+    }
+    world.add(Thinker::Mobj(skull));
+}
+
+/// Spit a lost soul at the target, like vanilla A_PainAttack
+pub fn pain_attack(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    if world.mobj(actor.target).is_none() {
+        return;
+    }
+    face_target(world, actor);
+    pain_shoot_skull(map, world, actor, actor.angle);
+}
+
+/// Spit three lost souls when dying, like vanilla A_PainDie
+pub fn pain_die(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    fall(actor);
+    for turn in [90.0, 180.0, 270.0] {
+        pain_shoot_skull(map, world, actor, actor.angle + turn);
+    }
+}
+
+/// The corpse can be walked over, like vanilla A_Fall
+pub fn fall(actor: &mut Mobj) {
+    actor.flags.remove(MobjFlags::SOLID);
+}
+
+/// True when another mobj of the same kind is still alive
+fn other_alive(world: &World, actor: &Mobj) -> bool {
+    world
+        .mobjs()
+        .any(|(id, mo)| id != actor.id && mo.kind == actor.kind && mo.health > 0)
+}
+
+/// Line standing for the death of a boss, tagged like the sectors it moves
+fn boss_line(tag: i16) -> LineDef {
+    LineDef {
+        start_vertex: 0,
+        end_vertex: 0,
+        flags: LinedefFlags::NONE,
+        special_type: 0,
+        sector_tag: tag,
+        front_sidedef: 0,
+        back_sidedef: -1,
+    }
+}
+
+/// What the death of the last boss of a kind does on a map
+enum Victory {
+    Floor(i16, FloorKind),
+    Door(i16, DoorKind),
+    /// The level is over
+    Exit,
+}
+
+/// Victory of the maps ending with bosses
+fn boss_victory(map_name: &str, kind: MobjType) -> Option<Victory> {
+    let lower = Victory::Floor(BOSS_TAG, FloorKind::LowerFloorToLowest);
+    if let Some(number) = map_name.strip_prefix("MAP") {
+        return match (number, kind) {
+            ("07", MobjType::Fatso) => Some(lower),
+            ("07", MobjType::Baby) => Some(Victory::Floor(667, FloorKind::RaiseToTexture)),
+            _ => None,
+        };
+    }
+    match map_name.as_bytes() {
+        [b'E', b'1', b'M', b'8'] if kind == MobjType::Bruiser => Some(lower),
+        [b'E', b'2', b'M', b'8'] if kind == MobjType::Cyborg => Some(Victory::Exit),
+        [b'E', b'3', b'M', b'8'] if kind == MobjType::Spider => Some(Victory::Exit),
+        [b'E', b'4', b'M', b'6'] if kind == MobjType::Cyborg => {
+            Some(Victory::Door(BOSS_TAG, DoorKind::BlazeOpen))
+        }
+        [b'E', b'4', b'M', b'8'] if kind == MobjType::Spider => Some(lower),
+        _ => None,
+    }
+}
+
+/// Open the way out once the last boss of a kind died, on the maps ending with one, like
+/// vanilla A_BossDeath
+pub fn boss_death(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    let Some(victory) = boss_victory(&map.name, actor.kind) else {
+        return;
+    };
+    // Nobody wins when the player is dead
+    let player_dead = world
+        .mobjs()
+        .any(|(_, mo)| mo.kind == MobjType::Player && mo.health <= 0);
+    if player_dead || other_alive(world, actor) {
+        return;
+    }
+    match victory {
+        Victory::Floor(tag, kind) => {
+            do_floor(map, world, &boss_line(tag), kind);
+        }
+        Victory::Door(tag, kind) => {
+            do_door(map, world, &boss_line(tag), kind);
+        }
+        Victory::Exit => world.exit_level = true,
+    }
+}
+
+/// Open the door tagged 666 once every commander Keen died, like vanilla A_KeenDie
+pub fn keen_die(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    fall(actor);
+    if other_alive(world, actor) {
+        return;
+    }
+    do_door(map, world, &boss_line(BOSS_TAG), DoorKind::Open);
+}
+
+/// The level is over once the boss brain died, like vanilla A_BrainDie
+pub fn brain_die(world: &mut World) {
+    world.exit_level = true;
+}
+
+/// Find where the boss brain sends its cubes, like vanilla A_BrainAwake
+pub fn brain_awake(world: &mut World) {
+    world.brain.targets = world
+        .mobjs()
+        .filter(|(_, mo)| mo.kind == MobjType::BossTarget)
+        .map(|(id, _)| id)
+        .collect();
+    world.brain.next = 0;
+}
+
+/// Spit a cube at the next target, like vanilla A_BrainSpit
+pub fn brain_spit(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    world.brain.easy = !world.brain.easy;
+    if matches!(world.skill, Skill::Baby | Skill::Easy) && !world.brain.easy {
+        return;
+    }
+    let Some(&target) = world.brain.targets.get(world.brain.next) else {
+        return;
+    };
+    world.brain.next = (world.brain.next + 1) % world.brain.targets.len();
+
+    let Some(cube) = spawn_missile(map, world, actor, target, MobjType::SpawnShot) else {
+        return;
+    };
+    let Some(target_y) = world.mobj(target).map(|target| target.y) else {
+        return;
+    };
+    if let Some(cube) = world.mobj_mut(cube) {
+        cube.target = target;
+        // Flight counted in steps of its animation
+        let flight = ((target_y - actor.y) / cube.momy).trunc();
+        cube.reaction_time = (flight / cube.state.info().tics as f32) as i32;
+    }
+}
+
+/// Turn a cube into a monster once it reached its target, like vanilla A_SpawnFly
+pub fn spawn_fly(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    actor.reaction_time -= 1;
+    if actor.reaction_time != 0 {
+        return;
+    }
+    let Some(target) = world.mobj(actor.target) else {
+        return;
+    };
+    let (x, y, z) = (target.x, target.y, target.z);
+
+    let fog = Mobj::spawn_at(map, world, MobjType::SpawnFire, x, y, z);
+    world.add(Thinker::Mobj(fog));
+
+    // Weaker monsters are more likely
+    let kind = match world.random.gameplay() {
+        0..50 => MobjType::Troop,
+        50..90 => MobjType::Sergeant,
+        90..120 => MobjType::Shadows,
+        120..130 => MobjType::Pain,
+        130..160 => MobjType::Head,
+        160..162 => MobjType::Vile,
+        162..172 => MobjType::Undead,
+        172..192 => MobjType::Baby,
+        192..222 => MobjType::Fatso,
+        222..246 => MobjType::Knight,
+        _ => MobjType::Bruiser,
+    };
+    let mut monster = Mobj::spawn_at(map, world, kind, x, y, z);
+    if look_for_player(map, world, &mut monster, true) {
+        let see_state = monster.info.see_state;
+        set_mobj_state(map, world, &mut monster, see_state);
+    }
+    teleport_move(map, world, &mut monster, x, y);
+    world.add(Thinker::Mobj(monster));
+    world.remove(actor.id);
+}
+
+/// A rocket blowing up at once near the boss brain
+fn brain_explosion(map: &mut WadMap, world: &mut World, x: f32, y: f32) {
+    let z = 128.0 + world.random.gameplay() as f32 * 2.0;
+    let mut rocket = Mobj::spawn_at(map, world, MobjType::Rocket, x, y, z);
+    rocket.momz = world.random.gameplay() as f32 / 128.0;
+    set_mobj_state(map, world, &mut rocket, StateNum::BrainExplode1);
+    rocket.tics = (rocket.tics - (world.random.gameplay() & 7)).max(1);
+    world.add(Thinker::Mobj(rocket));
+}
+
+/// Explosions all along the wall of the dying boss brain, like vanilla A_BrainScream
+pub fn brain_scream(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    let mut x = actor.x - 196.0;
+    while x < actor.x + 320.0 {
+        brain_explosion(map, world, x, actor.y - 320.0);
+        x += 8.0;
+    }
+}
+
+/// One more explosion near the dying boss brain, like vanilla A_BrainExplode
+pub fn brain_explode(map: &mut WadMap, world: &mut World, actor: &mut Mobj) {
+    let x = actor.x + world.random.sub_random() as f32 / 32.0;
+    brain_explosion(map, world, x, actor.y);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::{Player, PlayerStatus};
+    use crate::wad::test_wad::{room_map, steps_map};
+
+    /// Sectors hearing a noise made in the first step
+    fn hearing(map: &WadMap) -> Vec<usize> {
+        let mut world = World::new();
+        let emitter = Mobj::spawn(map, &mut world, MobjType::Player, 64.0, 64.0);
+        noise_alert(map, &mut world, &emitter);
+        let mut sectors: Vec<usize> = world.sound_targets.keys().copied().collect();
+        sectors.sort_unstable();
+        sectors
+    }
+
+    #[test]
+    fn noise_goes_through_open_lines() {
+        let mut map = steps_map();
+        assert_eq!(hearing(&map), [0, 1, 2]);
+        // The line to the third step is a closed door
+        map.sectors[2].ceiling = map.sectors[2].floor;
+        assert_eq!(hearing(&map), [0, 1]);
+    }
+
+    #[test]
+    fn noise_goes_through_one_line_blocking_sound() {
+        let mut map = steps_map();
+        // Lines between the steps
+        map.linedefs[3].flags = LinedefFlags::TWO_SIDED | LinedefFlags::BLOCK_SOUND;
+        assert_eq!(hearing(&map), [0, 1, 2]);
+        map.linedefs[6].flags = LinedefFlags::TWO_SIDED | LinedefFlags::BLOCK_SOUND;
+        assert_eq!(hearing(&map), [0, 1]);
+    }
+
+    /// The room with its player, and the imp in the north looking south at the player
+    fn room_with_player() -> (WadMap, World, Mobj) {
+        let mut map = room_map();
+        let mut world = World::new();
+        world.spawn_map_things(&map, Skill::Medium);
+        world.player = Player::spawn(&mut map, &mut world, PlayerStatus::default());
+        let imp = world
+            .mobjs()
+            .find(|(_, mo)| mo.kind == MobjType::Troop)
+            .map(|(_, mo)| mo.clone())
+            .unwrap();
+        (map, world, imp)
+    }
+
+    #[test]
+    fn monsters_target_the_player_in_front_of_them() {
+        let (map, world, mut imp) = room_with_player();
+        assert!(look_for_player(&map, &world, &mut imp, false));
+        assert_eq!(imp.target, world.player.mobj);
+
+        // Looking away, the player far behind is only seen looking all around
+        imp.target = ThinkerId::NONE;
+        imp.angle = 90.0;
+        assert!(!look_for_player(&map, &world, &mut imp, false));
+        assert_eq!(imp.target, ThinkerId::NONE);
+        assert!(look_for_player(&map, &world, &mut imp, true));
+    }
+
+    #[test]
+    fn monsters_do_not_target_a_dead_player() {
+        let (map, mut world, mut imp) = room_with_player();
+        world.mobj_mut(world.player.mobj).unwrap().health = 0;
+        assert!(!look_for_player(&map, &world, &mut imp, true));
+    }
+
+    #[test]
+    fn monsters_wake_up_to_the_noise_of_their_sector() {
+        let (mut map, mut world, mut imp) = room_with_player();
+        imp.angle = 90.0;
+        let player = world.mobj(world.player.mobj).cloned().unwrap();
+        noise_alert(&map, &mut world, &player);
+        look(&mut map, &mut world, &mut imp);
+        assert_eq!(imp.target, world.player.mobj);
+        assert_eq!(imp.state, imp.info.see_state);
+    }
+
+    /// Kill two bosses of a kind one after the other on a map, after each death the level is
+    /// over or a floor moves
+    fn kill_bosses(map_name: &str, kind: MobjType) -> Vec<(bool, bool)> {
+        let mut map = steps_map();
+        map.name = map_name.to_string();
+        map.sectors[1].tag = BOSS_TAG;
+        let mut world = World::new();
+        let bosses: Vec<ThinkerId> = [64.0, 200.0]
+            .into_iter()
+            .map(|x| {
+                let boss = Mobj::spawn(&map, &mut world, kind, x, 64.0);
+                world.add(Thinker::Mobj(boss))
+            })
+            .collect();
+
+        let mut after = Vec::new();
+        for boss in bosses {
+            world.with_mobj(boss, |world, boss| {
+                boss.health = 0;
+                boss_death(&mut map, world, boss);
+            });
+            let moving = world
+                .iter()
+                .any(|(_, thinker)| matches!(thinker, Thinker::Floor(_)));
+            after.push((world.exit_level, moving));
+        }
+        after
+    }
+
+    #[test]
+    fn last_boss_opens_the_way_out() {
+        // The barons of E1M8 lower the floor tagged 666
+        assert_eq!(
+            kill_bosses("E1M8", MobjType::Bruiser),
+            [(false, false), (false, true)]
+        );
+        // The cyberdemon of E2M8 ends the level
+        assert_eq!(
+            kill_bosses("E2M8", MobjType::Cyborg),
+            [(false, false), (true, false)]
+        );
+        // Not on other maps or for other monsters
+        assert_eq!(kill_bosses("E1M1", MobjType::Bruiser), [(false, false); 2]);
+        assert_eq!(kill_bosses("E2M8", MobjType::Bruiser), [(false, false); 2]);
+    }
+}
@@ -0,0 +1,303 @@
+//! Moving floors, stairs and donuts, like vanilla p_floor.c
+
+use super::{
+    specials::{
+        back_sector, front_sector, highest_floor_surrounding, lowest_ceiling_surrounding,
+        lowest_floor_surrounding, move_plane, next_highest_floor, next_sector, tagged_sectors,
+        MoveResult, Plane,
+    },
+    world::{Thinker, World},
+};
+use crate::wad::map::{LineDef, LinedefFlags, SectorType, WadMap};
+
+/// Speed of a floor in map units per tic, like vanilla FLOORSPEED
+const FLOORSPEED: f32 = 1.0;
+
+/// What a floor does, like vanilla floor_e
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FloorKind {
+    /// Down to the highest floor around
+    LowerFloor,
+    LowerFloorToLowest,
+    /// Fast down to 8 above the highest floor around
+    TurboLower,
+    /// Up to the lowest ceiling around
+    RaiseFloor,
+    RaiseFloorToNearest,
+    /// Up by the height of the shortest lower texture around
+    RaiseToTexture,
+    /// Down to the lowest floor around, taking its texture and type
+    LowerAndChange,
+    RaiseFloor24,
+    RaiseFloor24AndChange,
+    /// Up to 8 under the lowest ceiling around, crushing what is in the way
+    RaiseFloorCrush,
+    RaiseFloorTurbo,
+    /// The outside of a donut
+    DonutRaise,
+    RaiseFloor512,
+    /// A step of a staircase
+    BuildStairs,
+}
+
+/// Steps of a staircase, like vanilla stair_e
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StairKind {
+    /// Slow 8 units steps
+    Build8,
+    /// Fast 16 units steps
+    Turbo16,
+}
+
+/// A floor moving to a height, like vanilla floormove_t
+pub struct FloorMove {
+    pub kind: FloorKind,
+    pub crush: bool,
+    pub sector: usize,
+    /// 1 up, -1 down
+    pub direction: i32,
+    /// Sector type and floor texture taken at the end by the changing kinds
+    pub new_special: SectorType,
+    pub texture: [u8; 8],
+    pub dest: f32,
+    pub speed: f32,
+}
+
+impl FloorMove {
+    fn new(map: &WadMap, kind: FloorKind, sector: usize, direction: i32, speed: f32) -> Self {
+        let sector_info = &map.sectors[sector];
+        FloorMove {
+            kind,
+            crush: false,
+            sector,
+            direction,
+            new_special: sector_info.stype,
+            texture: sector_info.floor_tex,
+            dest: sector_info.floor,
+            speed,
+        }
+    }
+
+    /// Move for one tic, like vanilla T_MoveFloor
+    ///
+    /// Returns false once the floor is there
+    pub fn think(&mut self, map: &mut WadMap, world: &mut World) -> bool {
+        let result = move_plane(
+            map,
+            world,
+            self.sector,
+            self.speed,
+            self.dest,
+            self.crush,
+            Plane::Floor,
+            self.direction,
+        );
+        if result != MoveResult::PastDest {
+            return true;
+        }
+
+        let changes = match self.direction {
+            1 => self.kind == FloorKind::DonutRaise,
+            _ => self.kind == FloorKind::LowerAndChange,
+        };
+        if changes {
+            let sector = &mut map.sectors[self.sector];
+            sector.stype = self.new_special;
+            sector.floor_tex = self.texture;
+        }
+        false
+    }
+}
+
+/// Height of the shortest lower texture on the two-sided lines of a sector
+fn shortest_lower_texture(map: &WadMap, sector: usize) -> Option<f32> {
+    let mut shortest: Option<f32> = None;
+    for &index in &map.sector_lines[sector] {
+        let line = &map.linedefs[index];
+        if (line.flags & LinedefFlags::TWO_SIDED) == LinedefFlags::NONE {
+            continue;
+        }
+        for side in [line.front_sidedef, line.back_sidedef] {
+            let mut name = map.sidedefs[side as usize].lower_tex;
+            name.make_ascii_uppercase();
+            if let Some(&height) = map.texture_heights.get(&name) {
+                shortest = Some(shortest.map_or(height, |shortest| shortest.min(height)));
+            }
+        }
+    }
+    shortest
+}
+
+/// Start the floors of the sectors tagged like a line, like vanilla EV_DoFloor
+///
+/// Returns true when a floor started
+pub fn do_floor(map: &mut WadMap, world: &mut World, line: &LineDef, kind: FloorKind) -> bool {
+    let mut started = false;
+    for sector in tagged_sectors(map, line.sector_tag) {
+        if world.sector_mover(sector).is_some() {
+            continue;
+        }
+        started = true;
+
+        let (floor, ceiling) = (map.sectors[sector].floor, map.sectors[sector].ceiling);
+        let mut mover = FloorMove::new(map, kind, sector, 1, FLOORSPEED);
+        match kind {
+            FloorKind::LowerFloor => {
+                mover.direction = -1;
+                mover.dest = highest_floor_surrounding(map, sector);
+            }
+            FloorKind::LowerFloorToLowest => {
+                mover.direction = -1;
+                mover.dest = lowest_floor_surrounding(map, sector);
+            }
+            FloorKind::TurboLower => {
+                mover.direction = -1;
+                mover.speed = FLOORSPEED * 4.0;
+                mover.dest = highest_floor_surrounding(map, sector);
+                if mover.dest != floor {
+                    mover.dest += 8.0;
+                }
+            }
+            FloorKind::RaiseFloor | FloorKind::RaiseFloorCrush => {
+                mover.crush = kind == FloorKind::RaiseFloorCrush;
+                mover.dest = lowest_ceiling_surrounding(map, sector).min(ceiling);
+                if mover.crush {
+                    mover.dest -= 8.0;
+                }
+            }
+            FloorKind::RaiseFloorTurbo => {
+                mover.speed = FLOORSPEED * 4.0;
+                mover.dest = next_highest_floor(map, sector, floor);
+            }
+            FloorKind::RaiseFloorToNearest => {
+                mover.dest = next_highest_floor(map, sector, floor);
+            }
+            FloorKind::RaiseFloor24 => mover.dest = floor + 24.0,
+            FloorKind::RaiseFloor512 => mover.dest = floor + 512.0,
+            FloorKind::RaiseFloor24AndChange => {
+                mover.dest = floor + 24.0;
+                let front = map.sectors[front_sector(map, line)];
+                map.sectors[sector].floor_tex = front.floor_tex;
+                map.sectors[sector].stype = front.stype;
+            }
+            FloorKind::RaiseToTexture => {
+                mover.dest = floor + shortest_lower_texture(map, sector).unwrap_or(0.0);
+            }
+            FloorKind::LowerAndChange => {
+                mover.direction = -1;
+                mover.dest = lowest_floor_surrounding(map, sector);
+                // Take the looks of the sector around at that height
+                let lowest = map.sector_lines[sector]
+                    .iter()
+                    .filter_map(|&index| next_sector(map, &map.linedefs[index], sector))
+                    .find(|&other| map.sectors[other].floor == mover.dest);
+                if let Some(other) = lowest {
+                    mover.texture = map.sectors[other].floor_tex;
+                    mover.new_special = map.sectors[other].stype;
+                }
+            }
+            FloorKind::DonutRaise | FloorKind::BuildStairs => (),
+        }
+        world.add_mover(sector, Thinker::Floor(mover));
+    }
+    started
+}
+
+/// Raise the sectors tagged like a line and the following ones with the same floor
+/// as a staircase, like vanilla EV_BuildStairs
+///
+/// Returns true when a staircase started
+pub fn build_stairs(map: &WadMap, world: &mut World, line: &LineDef, kind: StairKind) -> bool {
+    let (speed, step) = match kind {
+        StairKind::Build8 => (FLOORSPEED / 4.0, 8.0),
+        StairKind::Turbo16 => (FLOORSPEED * 4.0, 16.0),
+    };
+
+    let mut started = false;
+    for first in tagged_sectors(map, line.sector_tag) {
+        if world.sector_mover(first).is_some() {
+            continue;
+        }
+        started = true;
+
+        let texture = map.sectors[first].floor_tex;
+        let mut sector = first;
+        let mut height = map.sectors[first].floor + step;
+        loop {
+            let mut mover = FloorMove::new(map, FloorKind::BuildStairs, sector, 1, speed);
+            mover.dest = height;
+            world.add_mover(sector, Thinker::Floor(mover));
+
+            // The next step is behind a line facing out of this one, with the same floor
+            let mut next = None;
+            for &index in &map.sector_lines[sector] {
+                let line = &map.linedefs[index];
+                if (line.flags & LinedefFlags::TWO_SIDED) == LinedefFlags::NONE
+                    || front_sector(map, line) != sector
+                {
+                    continue;
+                }
+                let Some(back) = back_sector(map, line) else {
+                    continue;
+                };
+                if map.sectors[back].floor_tex != texture {
+                    continue;
+                }
+                height += step;
+                if world.sector_mover(back).is_some() {
+                    continue;
+                }
+                next = Some(back);
+                break;
+            }
+            match next {
+                Some(back) => sector = back,
+                None => break,
+            }
+        }
+    }
+    started
+}
+
+/// Lower the sectors tagged like a line and raise the ring around them to the floor
+/// outside, like vanilla EV_DoDonut
+///
+/// Returns true when a donut started
+pub fn do_donut(map: &WadMap, world: &mut World, line: &LineDef) -> bool {
+    let mut started = false;
+    for hole in tagged_sectors(map, line.sector_tag) {
+        if world.sector_mover(hole).is_some() {
+            continue;
+        }
+        let Some(ring) = map.sector_lines[hole]
+            .first()
+            .and_then(|&index| next_sector(map, &map.linedefs[index], hole))
+        else {
+            continue;
+        };
+        started = true;
+
+        let outside = map.sector_lines[ring].iter().find_map(|&index| {
+            let line = &map.linedefs[index];
+            if (line.flags & LinedefFlags::TWO_SIDED) == LinedefFlags::NONE {
+                return None;
+            }
+            back_sector(map, line).filter(|&back| back != hole)
+        });
+        let Some(outside) = outside else {
+            continue;
+        };
+        let (dest, texture) = (map.sectors[outside].floor, map.sectors[outside].floor_tex);
+
+        let mut raise = FloorMove::new(map, FloorKind::DonutRaise, ring, 1, FLOORSPEED / 2.0);
+        raise.texture = texture;
+        raise.new_special = SectorType::Normal;
+        raise.dest = dest;
+        world.add_mover(ring, Thinker::Floor(raise));
+
+        let mut lower = FloorMove::new(map, FloorKind::LowerFloor, hole, -1, FLOORSPEED / 2.0);
+        lower.dest = dest;
+        world.add_mover(hole, Thinker::Floor(lower));
+    }
+    started
+}
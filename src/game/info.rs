@@ -0,0 +1,544 @@
+//! Vanilla thing types from info.c
+
+use bitflags::bitflags;
+
+use super::states::StateNum;
+
+bitflags! {
+    /// Behaviour of a mobj, like vanilla MF_ flags
+    pub struct MobjFlags: u32 {
+        const NONE = 0;
+        /// Can be picked up
+        const SPECIAL = 0x1;
+        /// Blocks other mobjs
+        const SOLID = 0x2;
+        /// Can be hit and damaged
+        const SHOOTABLE = 0x4;
+        /// Not linked in a sector, invisible
+        const NOSECTOR = 0x8;
+        /// Not linked in the blockmap, nothing collides with it
+        const NOBLOCKMAP = 0x10;
+        /// Does not wake up on sounds
+        const AMBUSH = 0x20;
+        /// Was hit and will fight back
+        const JUSTHIT = 0x40;
+        /// Just attacked and will move before the next attack
+        const JUSTATTACKED = 0x80;
+        /// Spawned hanging from the ceiling
+        const SPAWNCEILING = 0x100;
+        /// Does not fall
+        const NOGRAVITY = 0x200;
+        /// Can walk off ledges
+        const DROPOFF = 0x400;
+        /// Picks up items, only players
+        const PICKUP = 0x800;
+        /// Goes through walls
+        const NOCLIP = 0x1000;
+        /// Slides along walls
+        const SLIDE = 0x2000;
+        /// Can move up and down freely
+        const FLOAT = 0x4000;
+        /// Crosses lines without triggering them
+        const TELEPORT = 0x8000;
+        /// Explodes when hitting something
+        const MISSILE = 0x10000;
+        /// Dropped by a dead monster, not spawned with the map
+        const DROPPED = 0x20000;
+        /// Drawn fuzzy, like the spectre
+        const SHADOW = 0x40000;
+        /// Puffs instead of blood when hit
+        const NOBLOOD = 0x80000;
+        /// Dead body sliding down ledges
+        const CORPSE = 0x100000;
+        /// Moving toward its target height
+        const INFLOAT = 0x200000;
+        /// Counted in the kill percentage
+        const COUNTKILL = 0x400000;
+        /// Counted in the item percentage
+        const COUNTITEM = 0x800000;
+        /// Lost soul charging
+        const SKULLFLY = 0x1000000;
+        /// Not spawned in deathmatch
+        const NOTDMATCH = 0x2000000;
+        /// Player color translation bits
+        const TRANSLATION = 0xc000000;
+    }
+}
+
+/// Properties of a thing type, like vanilla mobjinfo_t
+#[allow(dead_code)]
+pub struct MobjInfo {
+    /// Number in the map THINGS lump, -1 for things only spawned by the game
+    pub doomednum: i16,
+    pub spawn_state: StateNum,
+    pub spawn_health: i32,
+    pub see_state: StateNum,
+    /// Tics before attacking after waking up
+    pub reaction_time: i32,
+    pub pain_state: StateNum,
+    /// Out of 256
+    pub pain_chance: i32,
+    pub melee_state: StateNum,
+    pub missile_state: StateNum,
+    pub death_state: StateNum,
+    /// Death when gibbed
+    pub xdeath_state: StateNum,
+    /// Map units per step for monsters, per tic for missiles
+    pub speed: f32,
+    pub radius: f32,
+    pub height: f32,
+    pub mass: i32,
+    /// Damage of a missile, multiplied by a random 1 to 8
+    pub damage: i32,
+    pub flags: MobjFlags,
+    /// State when resurrected by an arch-vile
+    pub raise_state: StateNum,
+    /// Sound lumps without the DS prefix, like the vanilla sfx names
+    pub see_sound: Option<&'static str>,
+    pub death_sound: Option<&'static str>,
+    /// Played now and then while chasing
+    pub active_sound: Option<&'static str>,
+}
+
+/// Values shared by most types, the table only lists what differs
+const DEFAULT: MobjInfo = MobjInfo {
+    doomednum: -1,
+    spawn_state: StateNum::Null,
+    spawn_health: 1000,
+    see_state: StateNum::Null,
+    reaction_time: 8,
+    pain_state: StateNum::Null,
+    pain_chance: 0,
+    melee_state: StateNum::Null,
+    missile_state: StateNum::Null,
+    death_state: StateNum::Null,
+    xdeath_state: StateNum::Null,
+    speed: 0.0,
+    radius: 20.0,
+    height: 16.0,
+    mass: 100,
+    damage: 0,
+    flags: MobjFlags::NONE,
+    raise_state: StateNum::Null,
+    see_sound: None,
+    death_sound: None,
+    active_sound: None,
+};
+
+/// Union of flags usable in constants
+const fn flags(list: &[MobjFlags]) -> MobjFlags {
+    let mut bits = 0;
+    let mut i = 0;
+    while i < list.len() {
+        bits |= list[i].bits();
+        i += 1;
+    }
+    MobjFlags::from_bits_truncate(bits)
+}
+
+/// Flags of most monsters
+const MONSTER: MobjFlags = flags(&[MobjFlags::SOLID, MobjFlags::SHOOTABLE, MobjFlags::COUNTKILL]);
+/// Flags of things hanging from the ceiling
+const HANGING: MobjFlags = flags(&[
+    MobjFlags::SOLID,
+    MobjFlags::SPAWNCEILING,
+    MobjFlags::NOGRAVITY,
+]);
+
+/// A thing placed with the map, shown with a single animation
+const fn decoration(
+    doomednum: i16,
+    state: StateNum,
+    radius: f32,
+    height: f32,
+    flags: MobjFlags,
+) -> MobjInfo {
+    MobjInfo {
+        doomednum,
+        spawn_state: state,
+        radius,
+        height,
+        flags,
+        ..DEFAULT
+    }
+}
+
+/// An item picked up when touched
+const fn pickup(doomednum: i16, state: StateNum, flags: MobjFlags) -> MobjInfo {
+    decoration(
+        doomednum,
+        state,
+        20.0,
+        16.0,
+        MobjFlags::SPECIAL.union(flags),
+    )
+}
+
+/// A projectile flying at `speed` map units per tic
+const fn missile(
+    spawn_state: StateNum,
+    death_state: StateNum,
+    speed: f32,
+    radius: f32,
+    damage: i32,
+) -> MobjInfo {
+    MobjInfo {
+        spawn_state,
+        death_state,
+        speed,
+        radius,
+        height: 8.0,
+        damage,
+        flags: flags(&[
+            MobjFlags::NOBLOCKMAP,
+            MobjFlags::MISSILE,
+            MobjFlags::DROPOFF,
+            MobjFlags::NOGRAVITY,
+        ]),
+        ..DEFAULT
+    }
+}
+
+/// A short lived effect
+const fn effect(spawn_state: StateNum, flags: MobjFlags) -> MobjInfo {
+    MobjInfo {
+        spawn_state,
+        flags,
+        ..DEFAULT
+    }
+}
+
+macro_rules! mobj_types {
+    ($($name:ident = $info:expr),* $(,)?) => {
+        /// Every thing type in vanilla order
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        pub enum MobjType {
+            $($name),*
+        }
+
+        const MOBJ_INFO: &[MobjInfo] = {
+            use MobjFlags as F;
+            use StateNum as S;
+            &[$($info),*]
+        };
+
+        const MOBJ_TYPES: &[MobjType] = &[$(MobjType::$name),*];
+    };
+}
+
+impl MobjType {
+    pub fn info(&self) -> &'static MobjInfo {
+        &MOBJ_INFO[*self as usize]
+    }
+
+    /// Type at a position of the vanilla table, None past its end
+    pub fn from_index(index: usize) -> Option<MobjType> {
+        MOBJ_TYPES.get(index).copied()
+    }
+
+    /// Type spawned for a doomednum of the THINGS lump
+    pub fn from_doomednum(doomednum: i16) -> Option<MobjType> {
+        if doomednum < 0 {
+            return None;
+        }
+        let index = MOBJ_INFO
+            .iter()
+            .position(|info| info.doomednum == doomednum)?;
+        Some(MOBJ_TYPES[index])
+    }
+}
+
+#[rustfmt::skip]
+mobj_types! {
+    Player = MobjInfo {
+        spawn_state: S::Play, spawn_health: 100, see_state: S::PlayRun1, reaction_time: 0,
+        pain_state: S::PlayPain, pain_chance: 255, missile_state: S::PlayAtk1,
+        death_state: S::PlayDie1, xdeath_state: S::PlayXdie1, radius: 16.0, height: 56.0,
+        flags: flags(&[F::SOLID, F::SHOOTABLE, F::DROPOFF, F::PICKUP, F::NOTDMATCH]),
+        ..DEFAULT
+    },
+    Possessed = MobjInfo {
+        doomednum: 3004, spawn_state: S::PossStnd, spawn_health: 20, see_state: S::PossRun1,
+        pain_state: S::PossPain, pain_chance: 200, missile_state: S::PossAtk1,
+        death_state: S::PossDie1, xdeath_state: S::PossXdie1, speed: 8.0, radius: 20.0,
+        height: 56.0, flags: MONSTER, raise_state: S::PossRaise1,
+        see_sound: Some("posit1"), death_sound: Some("podth1"), active_sound: Some("posact"),
+        ..DEFAULT
+    },
+    ShotGuy = MobjInfo {
+        doomednum: 9, spawn_state: S::SposStnd, spawn_health: 30, see_state: S::SposRun1,
+        pain_state: S::SposPain, pain_chance: 170, missile_state: S::SposAtk1,
+        death_state: S::SposDie1, xdeath_state: S::SposXdie1, speed: 8.0, radius: 20.0,
+        height: 56.0, flags: MONSTER, raise_state: S::SposRaise1,
+        see_sound: Some("posit2"), death_sound: Some("podth2"), active_sound: Some("posact"),
+        ..DEFAULT
+    },
+    Vile = MobjInfo {
+        doomednum: 64, spawn_state: S::VileStnd, spawn_health: 700, see_state: S::VileRun1,
+        pain_state: S::VilePain, pain_chance: 10, missile_state: S::VileAtk1,
+        death_state: S::VileDie1, speed: 15.0, radius: 20.0, height: 56.0, mass: 500,
+        flags: MONSTER,
+        see_sound: Some("vilsit"), death_sound: Some("vildth"), active_sound: Some("vilact"),
+        ..DEFAULT
+    },
+    Fire = effect(S::Fire1, flags(&[F::NOBLOCKMAP, F::NOGRAVITY])),
+    Undead = MobjInfo {
+        doomednum: 66, spawn_state: S::SkelStnd, spawn_health: 300, see_state: S::SkelRun1,
+        pain_state: S::SkelPain, pain_chance: 100, melee_state: S::SkelFist1,
+        missile_state: S::SkelMiss1, death_state: S::SkelDie1, speed: 10.0, radius: 20.0,
+        height: 56.0, mass: 500, flags: MONSTER, raise_state: S::SkelRaise1,
+        see_sound: Some("skesit"), death_sound: Some("skedth"), active_sound: Some("skeact"),
+        ..DEFAULT
+    },
+    Tracer = missile(S::Tracer, S::TraceExp1, 10.0, 11.0, 10),
+    Smoke = effect(S::Smoke1, flags(&[F::NOBLOCKMAP, F::NOGRAVITY])),
+    Fatso = MobjInfo {
+        doomednum: 67, spawn_state: S::FattStnd, spawn_health: 600, see_state: S::FattRun1,
+        pain_state: S::FattPain, pain_chance: 80, missile_state: S::FattAtk1,
+        death_state: S::FattDie1, speed: 8.0, radius: 48.0, height: 64.0, mass: 1000,
+        flags: MONSTER, raise_state: S::FattRaise1,
+        see_sound: Some("mansit"), death_sound: Some("mandth"), active_sound: Some("posact"),
+        ..DEFAULT
+    },
+    FatShot = missile(S::FatShot1, S::FatShotX1, 20.0, 6.0, 8),
+    ChainGuy = MobjInfo {
+        doomednum: 65, spawn_state: S::CposStnd, spawn_health: 70, see_state: S::CposRun1,
+        pain_state: S::CposPain, pain_chance: 170, missile_state: S::CposAtk1,
+        death_state: S::CposDie1, xdeath_state: S::CposXdie1, speed: 8.0, radius: 20.0,
+        height: 56.0, flags: MONSTER, raise_state: S::CposRaise1,
+        see_sound: Some("posit2"), death_sound: Some("podth2"), active_sound: Some("posact"),
+        ..DEFAULT
+    },
+    Troop = MobjInfo {
+        doomednum: 3001, spawn_state: S::TrooStnd, spawn_health: 60, see_state: S::TrooRun1,
+        pain_state: S::TrooPain, pain_chance: 200, melee_state: S::TrooAtk1,
+        missile_state: S::TrooAtk1, death_state: S::TrooDie1, xdeath_state: S::TrooXdie1,
+        speed: 8.0, radius: 20.0, height: 56.0, flags: MONSTER, raise_state: S::TrooRaise1,
+        see_sound: Some("bgsit1"), death_sound: Some("bgdth1"), active_sound: Some("bgact"),
+        ..DEFAULT
+    },
+    Sergeant = MobjInfo {
+        doomednum: 3002, spawn_state: S::SargStnd, spawn_health: 150, see_state: S::SargRun1,
+        pain_state: S::SargPain, pain_chance: 180, melee_state: S::SargAtk1,
+        death_state: S::SargDie1, speed: 10.0, radius: 30.0, height: 56.0, mass: 400,
+        flags: MONSTER, raise_state: S::SargRaise1,
+        see_sound: Some("sgtsit"), death_sound: Some("sgtdth"), active_sound: Some("dmact"),
+        ..DEFAULT
+    },
+    Shadows = MobjInfo {
+        doomednum: 58, spawn_state: S::SargStnd, spawn_health: 150, see_state: S::SargRun1,
+        pain_state: S::SargPain, pain_chance: 180, melee_state: S::SargAtk1,
+        death_state: S::SargDie1, speed: 10.0, radius: 30.0, height: 56.0, mass: 400,
+        flags: MONSTER.union(F::SHADOW), raise_state: S::SargRaise1,
+        see_sound: Some("sgtsit"), death_sound: Some("sgtdth"), active_sound: Some("dmact"),
+        ..DEFAULT
+    },
+    Head = MobjInfo {
+        doomednum: 3005, spawn_state: S::HeadStnd, spawn_health: 400, see_state: S::HeadRun1,
+        pain_state: S::HeadPain, pain_chance: 128, missile_state: S::HeadAtk1,
+        death_state: S::HeadDie1, speed: 8.0, radius: 31.0, height: 56.0, mass: 400,
+        flags: MONSTER.union(F::FLOAT).union(F::NOGRAVITY), raise_state: S::HeadRaise1,
+        see_sound: Some("cacsit"), death_sound: Some("cacdth"), active_sound: Some("dmact"),
+        ..DEFAULT
+    },
+    Bruiser = MobjInfo {
+        doomednum: 3003, spawn_state: S::BossStnd, spawn_health: 1000, see_state: S::BossRun1,
+        pain_state: S::BossPain, pain_chance: 50, melee_state: S::BossAtk1,
+        missile_state: S::BossAtk1, death_state: S::BossDie1, speed: 8.0, radius: 24.0,
+        height: 64.0, mass: 1000, flags: MONSTER, raise_state: S::BossRaise1,
+        see_sound: Some("brssit"), death_sound: Some("brsdth"), active_sound: Some("dmact"),
+        ..DEFAULT
+    },
+    BruiserShot = missile(S::BrBall1, S::BrBallX1, 15.0, 6.0, 8),
+    Knight = MobjInfo {
+        doomednum: 69, spawn_state: S::Bos2Stnd, spawn_health: 500, see_state: S::Bos2Run1,
+        pain_state: S::Bos2Pain, pain_chance: 50, melee_state: S::Bos2Atk1,
+        missile_state: S::Bos2Atk1, death_state: S::Bos2Die1, speed: 8.0, radius: 24.0,
+        height: 64.0, mass: 1000, flags: MONSTER, raise_state: S::Bos2Raise1,
+        see_sound: Some("kntsit"), death_sound: Some("kntdth"), active_sound: Some("dmact"),
+        ..DEFAULT
+    },
+    Skull = MobjInfo {
+        doomednum: 3006, spawn_state: S::SkullStnd, spawn_health: 100, see_state: S::SkullRun1,
+        pain_state: S::SkullPain, pain_chance: 256, missile_state: S::SkullAtk1,
+        death_state: S::SkullDie1, speed: 8.0, radius: 16.0, height: 56.0, mass: 50,
+        damage: 3, flags: flags(&[F::SOLID, F::SHOOTABLE, F::FLOAT, F::NOGRAVITY]),
+        death_sound: Some("firxpl"), active_sound: Some("dmact"), ..DEFAULT
+    },
+    Spider = MobjInfo {
+        doomednum: 7, spawn_state: S::SpidStnd, spawn_health: 3000, see_state: S::SpidRun1,
+        pain_state: S::SpidPain, pain_chance: 40, missile_state: S::SpidAtk1,
+        death_state: S::SpidDie1, speed: 12.0, radius: 128.0, height: 100.0, mass: 1000,
+        flags: MONSTER,
+        see_sound: Some("spisit"), death_sound: Some("spidth"), active_sound: Some("dmact"),
+        ..DEFAULT
+    },
+    Baby = MobjInfo {
+        doomednum: 68, spawn_state: S::BspiStnd, spawn_health: 500, see_state: S::BspiSight,
+        pain_state: S::BspiPain, pain_chance: 128, missile_state: S::BspiAtk1,
+        death_state: S::BspiDie1, speed: 12.0, radius: 64.0, height: 64.0, mass: 600,
+        flags: MONSTER, raise_state: S::BspiRaise1,
+        see_sound: Some("bspsit"), death_sound: Some("bspdth"), active_sound: Some("bspact"),
+        ..DEFAULT
+    },
+    Cyborg = MobjInfo {
+        doomednum: 16, spawn_state: S::CyberStnd, spawn_health: 4000, see_state: S::CyberRun1,
+        pain_state: S::CyberPain, pain_chance: 20, missile_state: S::CyberAtk1,
+        death_state: S::CyberDie1, speed: 16.0, radius: 40.0, height: 110.0, mass: 1000,
+        flags: MONSTER,
+        see_sound: Some("cybsit"), death_sound: Some("cybdth"), active_sound: Some("dmact"),
+        ..DEFAULT
+    },
+    Pain = MobjInfo {
+        doomednum: 71, spawn_state: S::PainStnd, spawn_health: 400, see_state: S::PainRun1,
+        pain_state: S::PainPain, pain_chance: 128, missile_state: S::PainAtk1,
+        death_state: S::PainDie1, speed: 8.0, radius: 31.0, height: 56.0, mass: 400,
+        flags: MONSTER.union(F::FLOAT).union(F::NOGRAVITY), raise_state: S::PainRaise1,
+        see_sound: Some("pesit"), death_sound: Some("pedth"), active_sound: Some("dmact"), ..DEFAULT
+    },
+    WolfSs = MobjInfo {
+        doomednum: 84, spawn_state: S::SswvStnd, spawn_health: 50, see_state: S::SswvRun1,
+        pain_state: S::SswvPain, pain_chance: 170, missile_state: S::SswvAtk1,
+        death_state: S::SswvDie1, xdeath_state: S::SswvXdie1, speed: 8.0, radius: 20.0,
+        height: 56.0, flags: MONSTER, raise_state: S::SswvRaise1,
+        see_sound: Some("sssit"), death_sound: Some("ssdth"), active_sound: Some("posact"),
+        ..DEFAULT
+    },
+    Keen = MobjInfo {
+        doomednum: 72, spawn_state: S::KeenStnd, spawn_health: 100, pain_state: S::KeenPain,
+        pain_chance: 256, death_state: S::CommKeen, radius: 16.0, height: 72.0,
+        mass: 10000000, flags: MONSTER.union(F::SPAWNCEILING).union(F::NOGRAVITY),
+        death_sound: Some("keendt"), ..DEFAULT
+    },
+    BossBrain = MobjInfo {
+        doomednum: 88, spawn_state: S::Brain, spawn_health: 250, pain_state: S::BrainPain,
+        pain_chance: 255, death_state: S::BrainDie1, radius: 16.0, height: 16.0,
+        mass: 10000000, flags: flags(&[F::SOLID, F::SHOOTABLE]), ..DEFAULT
+    },
+    BossSpit = MobjInfo {
+        doomednum: 89, spawn_state: S::BrainEye, see_state: S::BrainEyeSee, height: 32.0,
+        flags: flags(&[F::NOBLOCKMAP, F::NOSECTOR]), ..DEFAULT
+    },
+    BossTarget = MobjInfo {
+        doomednum: 87, height: 32.0, flags: flags(&[F::NOBLOCKMAP, F::NOSECTOR]), ..DEFAULT
+    },
+    SpawnShot = MobjInfo {
+        height: 32.0,
+        flags: flags(&[F::NOBLOCKMAP, F::MISSILE, F::DROPOFF, F::NOGRAVITY, F::NOCLIP]),
+        ..missile(S::Spawn1, S::Null, 10.0, 6.0, 3)
+    },
+    SpawnFire = effect(S::SpawnFire1, flags(&[F::NOBLOCKMAP, F::NOGRAVITY])),
+    Barrel = MobjInfo {
+        doomednum: 2035, spawn_state: S::Bar1, spawn_health: 20, death_state: S::Bexp,
+        radius: 10.0, height: 42.0, flags: flags(&[F::SOLID, F::SHOOTABLE, F::NOBLOOD]),
+        ..DEFAULT
+    },
+    TroopShot = missile(S::Tball1, S::TballX1, 10.0, 6.0, 3),
+    HeadShot = missile(S::Rball1, S::RballX1, 10.0, 6.0, 5),
+    Rocket = missile(S::Rocket, S::Explode1, 20.0, 11.0, 20),
+    Plasma = missile(S::PlasBall, S::PlasExp, 25.0, 13.0, 5),
+    Bfg = missile(S::BfgShot, S::BfgLand, 25.0, 13.0, 100),
+    ArachPlaz = missile(S::ArachPlaz, S::ArachPlex, 25.0, 13.0, 5),
+    Puff = effect(S::Puff1, flags(&[F::NOBLOCKMAP, F::NOGRAVITY])),
+    Blood = effect(S::Blood1, F::NOBLOCKMAP),
+    TeleportFog = effect(S::Tfog, flags(&[F::NOBLOCKMAP, F::NOGRAVITY])),
+    ItemFog = effect(S::Ifog, flags(&[F::NOBLOCKMAP, F::NOGRAVITY])),
+    TeleportMan = MobjInfo {
+        doomednum: 14, flags: flags(&[F::NOBLOCKMAP, F::NOSECTOR]), ..DEFAULT
+    },
+    ExtraBfg = effect(S::BfgExp, flags(&[F::NOBLOCKMAP, F::NOGRAVITY])),
+    GreenArmor = pickup(2018, S::Arm1, F::NONE),
+    BlueArmor = pickup(2019, S::Arm2, F::NONE),
+    HealthBonus = pickup(2014, S::Bon1, F::COUNTITEM),
+    ArmorBonus = pickup(2015, S::Bon2, F::COUNTITEM),
+    BlueCard = pickup(5, S::Bkey, F::NOTDMATCH),
+    RedCard = pickup(13, S::Rkey, F::NOTDMATCH),
+    YellowCard = pickup(6, S::Ykey, F::NOTDMATCH),
+    YellowSkull = pickup(39, S::Yskull, F::NOTDMATCH),
+    RedSkull = pickup(38, S::Rskull, F::NOTDMATCH),
+    BlueSkull = pickup(40, S::Bskull, F::NOTDMATCH),
+    Stimpack = pickup(2011, S::Stim, F::NONE),
+    Medikit = pickup(2012, S::Medi, F::NONE),
+    SoulSphere = pickup(2013, S::Soul, F::COUNTITEM),
+    Invulnerability = pickup(2022, S::Pinv, F::COUNTITEM),
+    Berserk = pickup(2023, S::Pstr, F::COUNTITEM),
+    Invisibility = pickup(2024, S::Pins, F::COUNTITEM),
+    RadSuit = pickup(2025, S::Suit, F::NONE),
+    ComputerMap = pickup(2026, S::Pmap, F::COUNTITEM),
+    LightAmp = pickup(2045, S::Pvis, F::COUNTITEM),
+    MegaSphere = pickup(83, S::Mega, F::COUNTITEM),
+    Clip = pickup(2007, S::Clip, F::NONE),
+    AmmoBox = pickup(2048, S::Ammo, F::NONE),
+    RocketAmmo = pickup(2010, S::Rock, F::NONE),
+    RocketBox = pickup(2046, S::Brok, F::NONE),
+    Cell = pickup(2047, S::Cell, F::NONE),
+    CellPack = pickup(17, S::Celp, F::NONE),
+    Shells = pickup(2008, S::Shel, F::NONE),
+    ShellBox = pickup(2049, S::Sbox, F::NONE),
+    Backpack = pickup(8, S::Bpak, F::NONE),
+    BfgGun = pickup(2006, S::Bfug, F::NONE),
+    Chaingun = pickup(2002, S::Mgun, F::NONE),
+    Chainsaw = pickup(2005, S::Csaw, F::NONE),
+    Launcher = pickup(2003, S::Laun, F::NONE),
+    PlasmaGun = pickup(2004, S::Plas, F::NONE),
+    Shotgun = pickup(2001, S::Shot, F::NONE),
+    SuperShotgun = pickup(82, S::Shot2, F::NONE),
+    TechLamp = decoration(85, S::TechLamp, 16.0, 16.0, F::SOLID),
+    Tech2Lamp = decoration(86, S::Tech2Lamp, 16.0, 16.0, F::SOLID),
+    Column = decoration(2028, S::Colu, 16.0, 16.0, F::SOLID),
+    TallGreenColumn = decoration(30, S::TallGrnCol, 16.0, 16.0, F::SOLID),
+    ShortGreenColumn = decoration(31, S::ShrtGrnCol, 16.0, 16.0, F::SOLID),
+    TallRedColumn = decoration(32, S::TallRedCol, 16.0, 16.0, F::SOLID),
+    ShortRedColumn = decoration(33, S::ShrtRedCol, 16.0, 16.0, F::SOLID),
+    SkullColumn = decoration(37, S::SkullCol, 16.0, 16.0, F::SOLID),
+    HeartColumn = decoration(36, S::HeartCol, 16.0, 16.0, F::SOLID),
+    EvilEye = decoration(41, S::EvilEye, 16.0, 16.0, F::SOLID),
+    FloatingSkull = decoration(42, S::FloatSkull, 16.0, 16.0, F::SOLID),
+    TorchTree = decoration(43, S::TorchTree, 16.0, 16.0, F::SOLID),
+    BlueTorch = decoration(44, S::BlueTorch, 16.0, 16.0, F::SOLID),
+    GreenTorch = decoration(45, S::GreenTorch, 16.0, 16.0, F::SOLID),
+    RedTorch = decoration(46, S::RedTorch, 16.0, 16.0, F::SOLID),
+    ShortBlueTorch = decoration(55, S::BTorchShrt, 16.0, 16.0, F::SOLID),
+    ShortGreenTorch = decoration(56, S::GTorchShrt, 16.0, 16.0, F::SOLID),
+    ShortRedTorch = decoration(57, S::RTorchShrt, 16.0, 16.0, F::SOLID),
+    Stalagtite = decoration(47, S::Stalagtite, 16.0, 16.0, F::SOLID),
+    TechPillar = decoration(48, S::TechPillar, 16.0, 16.0, F::SOLID),
+    Candle = decoration(34, S::Candlestik, 20.0, 16.0, F::NONE),
+    Candelabra = decoration(35, S::Candelabra, 16.0, 16.0, F::SOLID),
+    HangingTwitching = decoration(49, S::BloodyTwitch, 16.0, 68.0, HANGING),
+    HangingArmsOut = decoration(50, S::Meat2, 16.0, 84.0, HANGING),
+    HangingOneLegged = decoration(51, S::Meat3, 16.0, 84.0, HANGING),
+    HangingUpsideDown = decoration(52, S::Meat4, 16.0, 68.0, HANGING),
+    HangingLeg = decoration(53, S::Meat5, 16.0, 52.0, HANGING),
+    HangingArmsOutNonSolid = decoration(59, S::Meat2, 20.0, 84.0, flags(&[F::SPAWNCEILING, F::NOGRAVITY])),
+    HangingUpsideDownNonSolid = decoration(60, S::Meat4, 20.0, 68.0, flags(&[F::SPAWNCEILING, F::NOGRAVITY])),
+    HangingOneLeggedNonSolid = decoration(61, S::Meat3, 20.0, 52.0, flags(&[F::SPAWNCEILING, F::NOGRAVITY])),
+    HangingLegNonSolid = decoration(62, S::Meat5, 20.0, 52.0, flags(&[F::SPAWNCEILING, F::NOGRAVITY])),
+    HangingTwitchingNonSolid = decoration(63, S::BloodyTwitch, 20.0, 68.0, flags(&[F::SPAWNCEILING, F::NOGRAVITY])),
+    DeadCacodemon = decoration(22, S::HeadDie6, 20.0, 16.0, F::NONE),
+    DeadPlayer = decoration(15, S::PlayDie7, 20.0, 16.0, F::NONE),
+    DeadFormerHuman = decoration(18, S::PossDie5, 20.0, 16.0, F::NONE),
+    DeadDemon = decoration(21, S::SargDie6, 20.0, 16.0, F::NONE),
+    DeadLostSoul = decoration(23, S::SkullDie6, 20.0, 16.0, F::NONE),
+    DeadImp = decoration(20, S::TrooDie5, 20.0, 16.0, F::NONE),
+    DeadSergeant = decoration(19, S::SposDie5, 20.0, 16.0, F::NONE),
+    GibbedPlayer = decoration(10, S::PlayXdie9, 20.0, 16.0, F::NONE),
+    GibbedPlayer2 = decoration(12, S::PlayXdie9, 20.0, 16.0, F::NONE),
+    HeadsOnStick = decoration(28, S::HeadsOnStick, 16.0, 16.0, F::SOLID),
+    Gibs = decoration(24, S::Gibs, 20.0, 16.0, F::NONE),
+    HeadOnAStick = decoration(27, S::HeadOnAStick, 16.0, 16.0, F::SOLID),
+    HeadCandles = decoration(29, S::HeadCandles, 16.0, 16.0, F::SOLID),
+    DeadStick = decoration(25, S::DeadStick, 16.0, 16.0, F::SOLID),
+    LiveStick = decoration(26, S::LiveStick, 16.0, 16.0, F::SOLID),
+    BigTree = decoration(54, S::BigTree, 32.0, 16.0, F::SOLID),
+    BurningBarrel = decoration(70, S::Bbar1, 16.0, 16.0, F::SOLID),
+    HangNoGuts = decoration(73, S::HangNoGuts, 16.0, 88.0, HANGING),
+    HangBNoBrain = decoration(74, S::HangBNoBrain, 16.0, 88.0, HANGING),
+    HangTLookDown = decoration(75, S::HangTLookDn, 16.0, 64.0, HANGING),
+    HangTSkull = decoration(76, S::HangTSkull, 16.0, 64.0, HANGING),
+    HangTLookUp = decoration(77, S::HangTLookUp, 16.0, 64.0, HANGING),
+    HangTNoBrain = decoration(78, S::HangTNoBrain, 16.0, 64.0, HANGING),
+    ColonGibs = decoration(79, S::ColonGibs, 20.0, 16.0, F::NOBLOCKMAP),
+    SmallPool = decoration(80, S::SmallPool, 20.0, 16.0, F::NOBLOCKMAP),
+    BrainStem = decoration(81, S::BrainStem, 20.0, 16.0, F::NOBLOCKMAP),
+}
@@ -0,0 +1,199 @@
+//! Damage and deaths, like vanilla p_inter.c
+
+use super::{
+    info::{MobjFlags, MobjType},
+    mobj::{set_mobj_state, Mobj},
+    player::Weapon,
+    skill::Skill,
+    states::StateNum,
+    world::{Thinker, World},
+};
+use crate::wad::map::WadMap;
+
+/// Tics a monster keeps its target before turning on whoever hurts it, like vanilla
+/// BASETHRESHOLD
+const BASETHRESHOLD: i32 = 100;
+
+/// Hurt a shootable mobj, killing it when its health runs out, like vanilla P_DamageMobj
+///
+/// The inflictor pushes the target away from it, light mobjs fly further. The source is who
+/// to blame, monsters turn on it
+pub fn damage_mobj(
+    map: &mut WadMap,
+    world: &mut World,
+    target: &mut Mobj,
+    inflictor: Option<&Mobj>,
+    source: Option<&Mobj>,
+    damage: i32,
+) {
+    if (target.flags & MobjFlags::SHOOTABLE) == MobjFlags::NONE || target.health <= 0 {
+        return;
+    }
+    if (target.flags & MobjFlags::SKULLFLY) != MobjFlags::NONE {
+        target.momx = 0.0;
+        target.momy = 0.0;
+        target.momz = 0.0;
+    }
+
+    let player = target.kind == MobjType::Player;
+    let mut damage = damage;
+    if player && world.skill == Skill::Baby {
+        damage /= 2;
+    }
+
+    // The chainsaw pulls its target instead of pushing it away, and so does anything the
+    // player hurts while holding it, like vanilla
+    let sawing = source.is_some_and(|source| source.id == world.player.mobj)
+        && world.player.status.ready_weapon == Weapon::Chainsaw;
+    if let Some(inflictor) =
+        inflictor.filter(|_| (target.flags & MobjFlags::NOCLIP) == MobjFlags::NONE && !sawing)
+    {
+        let mut angle = (target.y - inflictor.y).atan2(target.x - inflictor.x);
+        let mut thrust = damage as f32 * 100.0 / 8.0 / target.info.mass as f32;
+        // A killing blow from below sometimes throws the target forward
+        if damage < 40
+            && damage > target.health
+            && target.z - inflictor.z > 64.0
+            && (world.random.gameplay() & 1) != 0
+        {
+            angle += std::f32::consts::PI;
+            thrust *= 4.0;
+        }
+        target.momx += thrust * angle.cos();
+        target.momy += thrust * angle.sin();
+    }
+
+    if player && target.id == world.player.mobj {
+        world.with_player(|world, player| player.take_damage(map, world, target, source, damage));
+        return;
+    }
+    hurt(map, world, target, source, damage);
+}
+
+/// Take health from a mobj and make it react, the second half of vanilla P_DamageMobj
+pub fn hurt(
+    map: &mut WadMap,
+    world: &mut World,
+    target: &mut Mobj,
+    source: Option<&Mobj>,
+    damage: i32,
+) {
+    target.health -= damage;
+    if target.health <= 0 {
+        kill_mobj(map, world, target);
+        return;
+    }
+
+    if world.random.gameplay() < target.info.pain_chance
+        && (target.flags & MobjFlags::SKULLFLY) == MobjFlags::NONE
+    {
+        target.flags |= MobjFlags::JUSTHIT;
+        set_mobj_state(map, world, target, target.info.pain_state);
+    }
+    // Fight back right away
+    target.reaction_time = 0;
+
+    // Turn on the attacker, unless busy with another target. Nobody holds a grudge against
+    // an arch-vile
+    let Some(source) = source.filter(|source| source.id != target.id) else {
+        return;
+    };
+    if (target.threshold == 0 || target.kind == MobjType::Vile) && source.kind != MobjType::Vile {
+        target.target = source.id;
+        target.threshold = BASETHRESHOLD;
+        if target.state == target.info.spawn_state && target.info.see_state != StateNum::Null {
+            set_mobj_state(map, world, target, target.info.see_state);
+        }
+    }
+}
+
+/// Fall dead, gibbed when the health went low enough, like vanilla P_KillMobj
+///
+/// Former humans drop their weapon
+pub fn kill_mobj(map: &mut WadMap, world: &mut World, target: &mut Mobj) {
+    target
+        .flags
+        .remove(MobjFlags::SHOOTABLE | MobjFlags::FLOAT | MobjFlags::SKULLFLY);
+    if target.kind != MobjType::Skull {
+        target.flags.remove(MobjFlags::NOGRAVITY);
+    }
+    target.flags |= MobjFlags::CORPSE | MobjFlags::DROPOFF;
+    target.height /= 4.0;
+    if target.kind == MobjType::Player {
+        target.flags.remove(MobjFlags::SOLID);
+    }
+
+    let gibbed = target.health < -target.info.spawn_health;
+    let state = if gibbed && target.info.xdeath_state != StateNum::Null {
+        target.info.xdeath_state
+    } else {
+        target.info.death_state
+    };
+    set_mobj_state(map, world, target, state);
+    target.tics = (target.tics - (world.random.gameplay() & 3)).max(1);
+
+    let item = match target.kind {
+        MobjType::WolfSs | MobjType::Possessed => MobjType::Clip,
+        MobjType::ShotGuy => MobjType::Shotgun,
+        MobjType::ChainGuy => MobjType::Chaingun,
+        _ => return,
+    };
+    let mut mo = Mobj::spawn(map, world, item, target.x, target.y);
+    mo.flags |= MobjFlags::DROPPED;
+    world.add(Thinker::Mobj(mo));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::{Player, PlayerStatus};
+    use crate::wad::test_wad::room_map;
+
+    /// Momentum the imp of the room gets from a hit of the player holding a weapon, or of
+    /// another monster when `by_player` is false
+    fn push_on_imp(weapon: Weapon, by_player: bool, in_player_think: bool) -> (f32, f32) {
+        let mut map = room_map();
+        let mut world = World::new();
+        world.spawn_map_things(&map, Skill::Medium);
+        world.player = Player::spawn(&mut map, &mut world, PlayerStatus::default());
+        world.player.status.ready_weapon = weapon;
+        let imp = world
+            .mobjs()
+            .find(|(_, mo)| mo.kind == MobjType::Troop)
+            .map(|(id, _)| id)
+            .unwrap();
+        let source = if by_player {
+            world.mobj(world.player.mobj).cloned().unwrap()
+        } else {
+            Mobj::spawn(&map, &mut world, MobjType::Sergeant, 256.0, 64.0)
+        };
+
+        let mut hit = |world: &mut World| {
+            world.with_mobj(imp, |world, target| {
+                damage_mobj(&mut map, world, target, Some(&source), Some(&source), 10)
+            });
+        };
+        if in_player_think {
+            world.with_player(|world, _| hit(world));
+        } else {
+            hit(&mut world);
+        }
+        let imp = world.mobj(imp).unwrap();
+        (imp.momx, imp.momy)
+    }
+
+    #[test]
+    fn hits_push_their_target_away() {
+        let (momx, momy) = push_on_imp(Weapon::Pistol, true, true);
+        assert!(momx > 0.0 && momy > 0.0);
+    }
+
+    #[test]
+    fn nothing_the_player_does_with_the_chainsaw_pushes() {
+        // Its own hits, and its missiles exploding after it took the chainsaw
+        assert_eq!(push_on_imp(Weapon::Chainsaw, true, true), (0.0, 0.0));
+        assert_eq!(push_on_imp(Weapon::Chainsaw, true, false), (0.0, 0.0));
+        // Monsters still push
+        assert_ne!(push_on_imp(Weapon::Chainsaw, false, false), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,254 @@
+//! Sector lights flickering, flashing, strobing and glowing, like vanilla p_lights.c
+
+use super::{
+    specials::{min_surrounding_light, neighbours, tagged_sectors},
+    world::{Thinker, World},
+};
+use crate::wad::map::{LineDef, SectorType, WadMap};
+
+/// Tics a strobe stays bright, like vanilla STROBEBRIGHT
+const STROBEBRIGHT: i32 = 5;
+/// Tics a fast strobe stays dark, like vanilla FASTDARK
+pub const FASTDARK: i32 = 15;
+/// Tics a slow strobe stays dark, like vanilla SLOWDARK
+pub const SLOWDARK: i32 = 35;
+/// Light change per tic of a glowing sector, like vanilla GLOWSPEED
+const GLOWSPEED: i16 = 8;
+
+/// A sector light flickering like a fire, like vanilla fireflicker_t
+pub struct FireFlicker {
+    pub sector: usize,
+    /// Tics left before the next change
+    pub count: i32,
+    pub max_light: i16,
+    pub min_light: i16,
+}
+
+impl FireFlicker {
+    /// Flicker for one tic, like vanilla T_FireFlicker
+    pub fn think(&mut self, map: &mut WadMap, world: &mut World) -> bool {
+        self.count -= 1;
+        if self.count != 0 {
+            return true;
+        }
+
+        let amount = ((world.random.gameplay() & 3) * 16) as i16;
+        let sector = &mut map.sectors[self.sector];
+        sector.lighting = if sector.lighting - amount < self.min_light {
+            self.min_light
+        } else {
+            self.max_light - amount
+        };
+        self.count = 4;
+        true
+    }
+}
+
+/// A sector light going off at random like a broken lamp, like vanilla lightflash_t
+pub struct LightFlash {
+    pub sector: usize,
+    /// Tics left before the next change
+    pub count: i32,
+    pub max_light: i16,
+    pub min_light: i16,
+    /// Mask of the random tics spent bright
+    pub max_time: i32,
+    /// Mask of the random tics spent dark
+    pub min_time: i32,
+}
+
+impl LightFlash {
+    /// Flash for one tic, like vanilla T_LightFlash
+    pub fn think(&mut self, map: &mut WadMap, world: &mut World) -> bool {
+        self.count -= 1;
+        if self.count != 0 {
+            return true;
+        }
+
+        let sector = &mut map.sectors[self.sector];
+        if sector.lighting == self.max_light {
+            sector.lighting = self.min_light;
+            self.count = (world.random.gameplay() & self.min_time) + 1;
+        } else {
+            sector.lighting = self.max_light;
+            self.count = (world.random.gameplay() & self.max_time) + 1;
+        }
+        true
+    }
+}
+
+/// A sector light blinking at a steady pace, like vanilla strobe_t
+pub struct Strobe {
+    pub sector: usize,
+    /// Tics left before the next change
+    pub count: i32,
+    pub min_light: i16,
+    pub max_light: i16,
+    pub dark_time: i32,
+    pub bright_time: i32,
+}
+
+impl Strobe {
+    /// Blink for one tic, like vanilla T_StrobeFlash
+    pub fn think(&mut self, map: &mut WadMap, _world: &mut World) -> bool {
+        self.count -= 1;
+        if self.count != 0 {
+            return true;
+        }
+
+        let sector = &mut map.sectors[self.sector];
+        if sector.lighting == self.min_light {
+            sector.lighting = self.max_light;
+            self.count = self.bright_time;
+        } else if sector.lighting == self.max_light {
+            sector.lighting = self.min_light;
+            self.count = self.dark_time;
+        }
+        true
+    }
+}
+
+/// A sector light fading in and out, like vanilla glow_t
+pub struct Glow {
+    pub sector: usize,
+    pub min_light: i16,
+    pub max_light: i16,
+    /// 1 brighter, -1 darker
+    pub direction: i32,
+}
+
+impl Glow {
+    /// Fade for one tic, like vanilla T_Glow
+    pub fn think(&mut self, map: &mut WadMap, _world: &mut World) -> bool {
+        let sector = &mut map.sectors[self.sector];
+        match self.direction {
+            -1 => {
+                sector.lighting -= GLOWSPEED;
+                if sector.lighting <= self.min_light {
+                    sector.lighting += GLOWSPEED;
+                    self.direction = 1;
+                }
+            }
+            1 => {
+                sector.lighting += GLOWSPEED;
+                if sector.lighting >= self.max_light {
+                    sector.lighting -= GLOWSPEED;
+                    self.direction = -1;
+                }
+            }
+            _ => (),
+        }
+        true
+    }
+}
+
+/// Make a sector flicker like a fire, like vanilla P_SpawnFireFlicker
+pub fn spawn_fire_flicker(map: &mut WadMap, world: &mut World, sector: usize) {
+    let light = map.sectors[sector].lighting;
+    map.sectors[sector].stype = SectorType::Normal;
+
+    world.add(Thinker::FireFlicker(FireFlicker {
+        sector,
+        count: 4,
+        max_light: light,
+        min_light: min_surrounding_light(map, sector, light) + 16,
+    }));
+}
+
+/// Make a sector light go off at random, like vanilla P_SpawnLightFlash
+pub fn spawn_light_flash(map: &mut WadMap, world: &mut World, sector: usize) {
+    let light = map.sectors[sector].lighting;
+    map.sectors[sector].stype = SectorType::Normal;
+
+    let max_time = 64;
+    let count = (world.random.gameplay() & max_time) + 1;
+    world.add(Thinker::LightFlash(LightFlash {
+        sector,
+        count,
+        max_light: light,
+        min_light: min_surrounding_light(map, sector, light),
+        max_time,
+        min_time: 7,
+    }));
+}
+
+/// Make a sector blink, like vanilla P_SpawnStrobeFlash
+///
+/// Strobes in sync all start together, the others start at random
+pub fn spawn_strobe_flash(
+    map: &mut WadMap,
+    world: &mut World,
+    sector: usize,
+    dark_time: i32,
+    in_sync: bool,
+) {
+    let light = map.sectors[sector].lighting;
+    let mut min_light = min_surrounding_light(map, sector, light);
+    if min_light == light {
+        min_light = 0;
+    }
+    map.sectors[sector].stype = SectorType::Normal;
+
+    let count = if in_sync {
+        1
+    } else {
+        (world.random.gameplay() & 7) + 1
+    };
+    world.add(Thinker::Strobe(Strobe {
+        sector,
+        count,
+        min_light,
+        max_light: light,
+        dark_time,
+        bright_time: STROBEBRIGHT,
+    }));
+}
+
+/// Make a sector light fade in and out, like vanilla P_SpawnGlowingLight
+pub fn spawn_glowing_light(map: &mut WadMap, world: &mut World, sector: usize) {
+    let light = map.sectors[sector].lighting;
+    map.sectors[sector].stype = SectorType::Normal;
+
+    world.add(Thinker::Glow(Glow {
+        sector,
+        min_light: min_surrounding_light(map, sector, light),
+        max_light: light,
+        direction: -1,
+    }));
+}
+
+/// Make the tagged sectors of a line blink slowly, like vanilla EV_StartLightStrobing
+///
+/// Sectors already moving are left alone
+pub fn start_light_strobing(map: &mut WadMap, world: &mut World, line: &LineDef) {
+    for sector in tagged_sectors(map, line.sector_tag) {
+        if world.sector_mover(sector).is_some() {
+            continue;
+        }
+        spawn_strobe_flash(map, world, sector, SLOWDARK, false);
+    }
+}
+
+/// Set the tagged sectors of a line to the darkest light around, like vanilla
+/// EV_TurnTagLightsOff
+pub fn turn_tag_lights_off(map: &mut WadMap, line: &LineDef) {
+    for sector in tagged_sectors(map, line.sector_tag) {
+        map.sectors[sector].lighting =
+            min_surrounding_light(map, sector, map.sectors[sector].lighting);
+    }
+}
+
+/// Set the light of the tagged sectors of a line, like vanilla EV_LightTurnOn
+///
+/// A light of 0 takes the brightest light around the first sector and keeps it for the others,
+/// like vanilla
+pub fn light_turn_on(map: &mut WadMap, line: &LineDef, mut bright: i16) {
+    for sector in tagged_sectors(map, line.sector_tag) {
+        if bright == 0 {
+            bright = neighbours(map, sector)
+                .map(|other| map.sectors[other].lighting)
+                .fold(bright, i16::max);
+        }
+        map.sectors[sector].lighting = bright;
+    }
+}
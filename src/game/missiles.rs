@@ -0,0 +1,400 @@
+//! Projectiles and explosions, like the missile parts of vanilla p_mobj.c and p_map.c
+
+use super::{
+    attacks::{aim_line_attack, auto_aim, spawn_puff, MISSILERANGE, MISSILE_AIM_RANGE},
+    info::{MobjFlags, MobjType},
+    interaction::damage_mobj,
+    mobj::{set_mobj_state, Mobj},
+    movement::{
+        aprox_distance, ceiling_line, line_sectors, set_position, things_around, try_move, MAXMOVE,
+        MAXRADIUS,
+    },
+    sight::check_sight,
+    triggers::cross_special_lines,
+    world::{Thinker, ThinkerId, World},
+};
+use crate::wad::map::WadMap;
+
+/// Height above the feet missiles are fired from
+const MISSILE_Z: f32 = 32.0;
+/// Damage at the center of a rocket or barrel explosion, it also is the reach
+const EXPLOSION_DAMAGE: i32 = 128;
+/// Degrees a homing missile turns each time it adjusts, like vanilla TRACEANGLE
+const TRACEANGLE: f32 = 360.0 * 0xc000000 as f32 / 4294967296.0;
+/// Degrees of one step of the random aim at a fuzzy target, a vanilla angle shifted by 20 bits
+const SHADOW_SPREAD: f32 = 360.0 / 4096.0;
+/// Rays of the BFG spray, spread over 90 degrees
+const BFG_RAYS: usize = 40;
+
+/// What stopped a missile
+enum Blocked {
+    /// A wall, a floor, a ceiling or a thing, the missile explodes
+    Hit,
+    /// The sky, the missile vanishes
+    Sky,
+}
+
+/// Angle from a point to another, in degrees
+pub fn angle_between(x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    (y2 - y1).atan2(x2 - x1).to_degrees()
+}
+
+/// Send a missile of a shooter toward an angle, rising `momz` each tic
+fn launch(missile: &mut Mobj, source: ThinkerId, angle: f32, momz: f32) {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    missile.target = source;
+    missile.angle = angle;
+    missile.momx = missile.info.speed * cos;
+    missile.momy = missile.info.speed * sin;
+    missile.momz = momz;
+}
+
+/// Add a fired missile to the world a bit ahead of its shooter, like vanilla P_CheckMissileSpawn
+///
+/// It explodes right away when fired into a wall or a thing. None when its explosion is already
+/// over
+fn check_missile_spawn(
+    map: &mut WadMap,
+    world: &mut World,
+    source: &Mobj,
+    mut missile: Mobj,
+) -> Option<ThinkerId> {
+    missile.tics = (missile.tics - (world.random.gameplay() & 3)).max(1);
+    // Ahead so an explosion right away has a direction
+    missile.x += missile.momx / 2.0;
+    missile.y += missile.momy / 2.0;
+    missile.z += missile.momz / 2.0;
+    let (x, y) = (missile.x, missile.y);
+    if try_missile_move(map, world, &mut missile, Some(source), x, y).is_err()
+        && !explode_missile(map, world, &mut missile)
+    {
+        return None;
+    }
+    Some(world.add(Thinker::Mobj(missile)))
+}
+
+/// Fire a missile from a monster at a mobj, like vanilla P_SpawnMissile
+///
+/// None when the target is gone
+pub fn spawn_missile(
+    map: &mut WadMap,
+    world: &mut World,
+    source: &Mobj,
+    target: ThinkerId,
+    kind: MobjType,
+) -> Option<ThinkerId> {
+    let dest = world.mobj(target)?;
+    let (dest_x, dest_y, dest_z) = (dest.x, dest.y, dest.z);
+    let fuzzy = (dest.flags & MobjFlags::SHADOW) != MobjFlags::NONE;
+
+    let mut missile = Mobj::spawn_at(map, world, kind, source.x, source.y, source.z + MISSILE_Z);
+    let mut angle = angle_between(source.x, source.y, dest_x, dest_y);
+    if fuzzy {
+        angle += world.random.sub_random() as f32 * SHADOW_SPREAD;
+    }
+    // Tics of flight to the target, enough to climb or dive to it
+    let tics = (aprox_distance(dest_x - source.x, dest_y - source.y) / missile.info.speed)
+        .floor()
+        .max(1.0);
+    launch(&mut missile, source.id, angle, (dest_z - source.z) / tics);
+    check_missile_spawn(map, world, source, missile)
+}
+
+/// Fire a missile from a player, aimed up or down at what is ahead, like vanilla
+/// P_SpawnPlayerMissile
+pub fn spawn_player_missile(map: &mut WadMap, world: &mut World, source: &Mobj, kind: MobjType) {
+    let (angle, slope) =
+        auto_aim(map, world, source, MISSILE_AIM_RANGE).unwrap_or((source.angle, 0.0));
+    let mut missile = Mobj::spawn_at(map, world, kind, source.x, source.y, source.z + MISSILE_Z);
+    let momz = missile.info.speed * slope;
+    launch(&mut missile, source.id, angle, momz);
+    check_missile_spawn(map, world, source, missile);
+}
+
+/// Thing a missile moved to a position runs into and if it is hurt, like vanilla
+/// PIT_CheckThing for a missile
+///
+/// Missiles fly over and under things, and through their shooter
+fn thing_hit(
+    world: &World,
+    mo: &Mobj,
+    source: Option<&Mobj>,
+    x: f32,
+    y: f32,
+) -> Option<(ThinkerId, bool)> {
+    let source = source.map(|source| source.kind);
+    things_around(world, mo, x, y).find_map(|(id, thing)| {
+        let touchable = MobjFlags::SOLID | MobjFlags::SPECIAL | MobjFlags::SHOOTABLE;
+        if (thing.flags & touchable) == MobjFlags::NONE || id == mo.id {
+            return None;
+        }
+        let block = thing.radius + mo.radius;
+        if (thing.x - x).abs() >= block || (thing.y - y).abs() >= block {
+            return None;
+        }
+        if mo.z > thing.z + thing.height || mo.z + mo.height < thing.z {
+            return None;
+        }
+
+        // Monsters do not hurt their own kind, barons and knights are the same
+        let same_kind = source.is_some_and(|source| {
+            source == thing.kind
+                || matches!(
+                    (source, thing.kind),
+                    (MobjType::Knight, MobjType::Bruiser) | (MobjType::Bruiser, MobjType::Knight)
+                )
+        });
+        if id == mo.target {
+            return None;
+        }
+        // Players still hurt other players
+        if same_kind && thing.kind != MobjType::Player {
+            return Some((id, false));
+        }
+
+        if (thing.flags & MobjFlags::SHOOTABLE) == MobjFlags::NONE {
+            return ((thing.flags & MobjFlags::SOLID) != MobjFlags::NONE).then_some((id, false));
+        }
+        Some((id, true))
+    })
+}
+
+/// Move a missile if nothing is in the way, damaging the thing it runs into, like vanilla
+/// P_TryMove for a missile
+fn try_missile_move(
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    source: Option<&Mobj>,
+    x: f32,
+    y: f32,
+) -> Result<(), Blocked> {
+    // The cubes of the boss brain fly through everything
+    if (mo.flags & MobjFlags::NOCLIP) != MobjFlags::NONE {
+        set_position(map, mo, x, y);
+        return Ok(());
+    }
+    if let Some((id, hurt)) = thing_hit(world, mo, source, x, y) {
+        if hurt {
+            let damage = (world.random.gameplay() % 8 + 1) * mo.info.damage;
+            world.with_mobj(id, |world, thing| {
+                damage_mobj(map, world, thing, Some(mo), source, damage)
+            });
+        }
+        return Err(Blocked::Hit);
+    }
+    if try_move(map, world, mo, x, y) {
+        return Ok(());
+    }
+
+    // Hack so missiles do not explode against the sky above a wall
+    let sky = ceiling_line(map, mo, x, y).is_some_and(|line| {
+        let (_, back) = line_sectors(map, &map.linedefs[line]);
+        back.is_some_and(|back| back.sky_ceiling())
+    });
+    Err(if sky { Blocked::Sky } else { Blocked::Hit })
+}
+
+/// Horizontal flight of a tic, fast missiles move in halves so thin walls are not crossed
+fn fly(map: &mut WadMap, world: &mut World, mo: &mut Mobj) -> Result<(), Blocked> {
+    mo.momx = mo.momx.clamp(-MAXMOVE, MAXMOVE);
+    mo.momy = mo.momy.clamp(-MAXMOVE, MAXMOVE);
+    let steps = if mo.momx.abs() > MAXMOVE / 2.0 || mo.momy.abs() > MAXMOVE / 2.0 {
+        2
+    } else {
+        1
+    };
+    let source = world.mobj(mo.target).cloned();
+    for _ in 0..steps {
+        let from = (mo.x, mo.y);
+        let x = mo.x + mo.momx / steps as f32;
+        let y = mo.y + mo.momy / steps as f32;
+        try_missile_move(map, world, mo, source.as_ref(), x, y)?;
+        cross_special_lines(map, world, mo, None, from);
+    }
+    Ok(())
+}
+
+/// Vertical flight of a tic, floors and ceilings stop it
+fn fly_z(map: &WadMap, mo: &mut Mobj) -> Result<(), Blocked> {
+    mo.z += mo.momz;
+    if (mo.flags & MobjFlags::NOCLIP) != MobjFlags::NONE {
+        return Ok(());
+    }
+    if mo.z <= mo.floor_z {
+        mo.z = mo.floor_z;
+        return Err(Blocked::Hit);
+    }
+    if mo.z + mo.height > mo.ceiling_z {
+        mo.z = mo.ceiling_z - mo.height;
+        let sector = &map.sectors[map.sector_at(mo.x, mo.y)];
+        return Err(if sector.sky_ceiling() {
+            Blocked::Sky
+        } else {
+            Blocked::Hit
+        });
+    }
+    Ok(())
+}
+
+/// Fly a missile for a tic, like the missile parts of vanilla P_XYMovement and P_ZMovement
+///
+/// It explodes on what it hits. Returns false when it went into the sky, or its explosion is
+/// already over, and it must be removed
+pub fn missile_movement(map: &mut WadMap, world: &mut World, mo: &mut Mobj) -> bool {
+    let mut flight = Ok(());
+    if mo.momx != 0.0 || mo.momy != 0.0 {
+        flight = fly(map, world, mo);
+    }
+    if flight.is_ok() && (mo.z != mo.floor_z || mo.momz != 0.0) {
+        flight = fly_z(map, mo);
+    }
+    match flight {
+        Ok(()) => true,
+        Err(Blocked::Hit) => explode_missile(map, world, mo),
+        Err(Blocked::Sky) => false,
+    }
+}
+
+/// Stop a missile and show its explosion, like vanilla P_ExplodeMissile
+///
+/// Returns false when the missile must be removed
+fn explode_missile(map: &mut WadMap, world: &mut World, mo: &mut Mobj) -> bool {
+    mo.momx = 0.0;
+    mo.momy = 0.0;
+    mo.momz = 0.0;
+    if !set_mobj_state(map, world, mo, mo.info.death_state) {
+        return false;
+    }
+    mo.tics = (mo.tics - (world.random.gameplay() & 3)).max(1);
+    mo.flags.remove(MobjFlags::MISSILE);
+    true
+}
+
+/// Hurt the shootable mobjs around an explosion that can see it, less the further they are,
+/// like vanilla P_RadiusAttack
+///
+/// The spider mastermind and the cyberdemon do not feel it
+pub fn radius_attack(
+    map: &mut WadMap,
+    world: &mut World,
+    spot: &Mobj,
+    source: Option<&Mobj>,
+    damage: i32,
+) {
+    // Things are linked by their center, a box reaching the blast can be in a cell around
+    let reach = damage as f32 + MAXRADIUS;
+    let hurt: Vec<(ThinkerId, i32)> = world
+        .mobjs_in_box(
+            spot.x - reach,
+            spot.y - reach,
+            spot.x + reach,
+            spot.y + reach,
+        )
+        .into_iter()
+        .filter_map(|id| {
+            let thing = world.mobj(id)?;
+            if (thing.flags & MobjFlags::SHOOTABLE) == MobjFlags::NONE
+                || matches!(thing.kind, MobjType::Cyborg | MobjType::Spider)
+            {
+                return None;
+            }
+            let dist = (thing.x - spot.x).abs().max((thing.y - spot.y).abs()) - thing.radius;
+            let dist = (dist.floor() as i32).max(0);
+            (dist < damage && check_sight(map, thing, spot)).then_some((id, damage - dist))
+        })
+        .collect();
+
+    for (id, damage) in hurt {
+        world.with_mobj(id, |world, thing| {
+            damage_mobj(map, world, thing, Some(spot), source, damage)
+        });
+    }
+}
+
+/// Blast of a rocket or a barrel, like vanilla A_Explode
+pub fn explode(map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    let source = world.mobj(mo.target).cloned();
+    radius_attack(map, world, mo, source.as_ref(), EXPLOSION_DAMAGE);
+}
+
+/// Leave smoke behind and turn toward the target, like vanilla A_Tracer for the revenant
+/// missiles
+pub fn tracer(map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    if !world.tic.is_multiple_of(4) {
+        return;
+    }
+
+    spawn_puff(map, world, mo.x, mo.y, mo.z, MISSILERANGE);
+    let mut smoke = Mobj::spawn_at(
+        map,
+        world,
+        MobjType::Smoke,
+        mo.x - mo.momx,
+        mo.y - mo.momy,
+        mo.z,
+    );
+    smoke.momz = 1.0;
+    smoke.tics = (smoke.tics - (world.random.gameplay() & 3)).max(1);
+    world.add(Thinker::Mobj(smoke));
+
+    let Some(dest) = world.mobj(mo.tracer).filter(|dest| dest.health > 0) else {
+        return;
+    };
+
+    // Turn by steps, snapping to the target when close enough
+    let exact = angle_between(mo.x, mo.y, dest.x, dest.y);
+    let delta = (exact - mo.angle + 180.0).rem_euclid(360.0) - 180.0;
+    mo.angle = if delta.abs() <= TRACEANGLE {
+        exact
+    } else {
+        mo.angle + TRACEANGLE.copysign(delta)
+    }
+    .rem_euclid(360.0);
+    let (sin, cos) = mo.angle.to_radians().sin_cos();
+    mo.momx = mo.info.speed * cos;
+    mo.momy = mo.info.speed * sin;
+
+    // Climb or dive toward the middle of the target
+    let tics = (aprox_distance(dest.x - mo.x, dest.y - mo.y) / mo.info.speed)
+        .floor()
+        .max(1.0);
+    let slope = (dest.z + 40.0 - mo.z) / tics;
+    if slope < mo.momz {
+        mo.momz -= 1.0 / 8.0;
+    } else {
+        mo.momz += 1.0 / 8.0;
+    }
+}
+
+/// Rays from the shooter of a BFG ball hurting what they find, like vanilla A_BFGSpray
+pub fn bfg_spray(map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    let angle = mo.angle;
+    world.with_mobj(mo.target, |world, source| {
+        for ray in 0..BFG_RAYS {
+            let ray_angle = angle - 45.0 + 90.0 / BFG_RAYS as f32 * ray as f32;
+            let (_, Some(target)) =
+                aim_line_attack(map, world, source, ray_angle, MISSILE_AIM_RANGE)
+            else {
+                continue;
+            };
+            let Some(thing) = world.mobj(target) else {
+                continue;
+            };
+            let flash = Mobj::spawn_at(
+                map,
+                world,
+                MobjType::ExtraBfg,
+                thing.x,
+                thing.y,
+                thing.z + thing.height / 4.0,
+            );
+            world.add(Thinker::Mobj(flash));
+
+            let damage = (0..15).map(|_| (world.random.gameplay() & 7) + 1).sum();
+            world.with_mobj(target, |world, thing| {
+                damage_mobj(map, world, thing, Some(source), Some(source), damage)
+            });
+        }
+    });
+}
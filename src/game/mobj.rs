@@ -0,0 +1,281 @@
+use super::{
+    enemy::{self, MoveDir},
+    info::{MobjFlags, MobjInfo, MobjType},
+    missiles::{bfg_spray, explode, tracer},
+    movement::sector_heights,
+    skill::Skill,
+    states::{ActionFn, SpriteNum, State, StateNum},
+    world::{ThinkerId, World},
+};
+use crate::wad::{
+    map::WadMap,
+    things::{Thing, ThingFlags},
+};
+
+/// A moving object in the level, like vanilla mobj_t
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct Mobj {
+    /// Set once added to the world
+    pub id: ThinkerId,
+    pub kind: MobjType,
+    pub info: &'static MobjInfo,
+    pub x: f32,
+    pub y: f32,
+    /// Height of the feet
+    pub z: f32,
+    /// Facing angle in degrees, 0 is east
+    pub angle: f32,
+    /// Momentum in map units per tic
+    pub momx: f32,
+    pub momy: f32,
+    pub momz: f32,
+    pub radius: f32,
+    pub height: f32,
+    /// Highest floor and lowest ceiling touched by the bounding box
+    pub floor_z: f32,
+    pub ceiling_z: f32,
+    pub flags: MobjFlags,
+    pub health: i32,
+    /// Tics before a monster can attack, or a teleported player move
+    pub reaction_time: i32,
+    pub state: StateNum,
+    /// Tics left before the next state, -1 stays forever
+    pub tics: i32,
+    pub sprite: SpriteNum,
+    /// Frame of the current state, with the FULLBRIGHT bit
+    pub frame: u32,
+    /// Shooter of a missile, or mobj a monster is after, like vanilla target
+    pub target: ThinkerId,
+    /// Chased by a homing missile, or fire of an arch-vile, like vanilla tracer
+    pub tracer: ThinkerId,
+    /// Tics a monster sticks to its target before turning on whoever hurts it
+    pub threshold: i32,
+    /// Direction a monster walks in
+    pub move_dir: MoveDir,
+    /// Steps left before a monster picks a new direction
+    pub move_count: i32,
+}
+
+impl Mobj {
+    /// A mobj of a type in its spawn state, like vanilla P_SpawnMobj without the level
+    fn new(kind: MobjType, x: f32, y: f32, z: f32) -> Self {
+        let info = kind.info();
+        let state = info.spawn_state.info();
+        Mobj {
+            id: ThinkerId::NONE,
+            kind,
+            info,
+            x,
+            y,
+            z,
+            angle: 0.0,
+            momx: 0.0,
+            momy: 0.0,
+            momz: 0.0,
+            radius: info.radius,
+            height: info.height,
+            floor_z: z,
+            ceiling_z: z + info.height,
+            flags: info.flags,
+            health: info.spawn_health,
+            reaction_time: info.reaction_time,
+            state: info.spawn_state,
+            tics: state.tics,
+            sprite: state.sprite,
+            frame: state.frame,
+            target: ThinkerId::NONE,
+            tracer: ThinkerId::NONE,
+            threshold: 0,
+            move_dir: MoveDir::None,
+            move_count: 0,
+        }
+    }
+
+    /// Spawn a mobj at a height in the level, like vanilla P_SpawnMobj
+    pub fn spawn_at(
+        map: &WadMap,
+        world: &mut World,
+        kind: MobjType,
+        x: f32,
+        y: f32,
+        z: f32,
+    ) -> Self {
+        let mut mo = Mobj::new(kind, x, y, z);
+        // Nightmare monsters attack as soon as they see the player
+        if world.skill == Skill::Nightmare {
+            mo.reaction_time = 0;
+        }
+        // Vanilla picks the player a monster looks at first, one player here but the number is
+        // still drawn to keep the sequence
+        world.random.gameplay();
+        // Heights of the sector under the center, not of the lines around
+        (mo.floor_z, mo.ceiling_z) = sector_heights(map, x, y);
+        mo
+    }
+
+    /// Spawn a mobj on the floor, or under the ceiling for hanging things
+    pub fn spawn(map: &WadMap, world: &mut World, kind: MobjType, x: f32, y: f32) -> Self {
+        let mut mo = Mobj::spawn_at(map, world, kind, x, y, 0.0);
+        mo.z = if (mo.flags & MobjFlags::SPAWNCEILING) != MobjFlags::NONE {
+            mo.ceiling_z - mo.height
+        } else {
+            mo.floor_z
+        };
+        mo
+    }
+
+    /// Spawn a thing of the map, None when it is not in the game at this skill
+    ///
+    /// Like vanilla P_SpawnMapThing for a single player game, player starts are not spawned
+    pub fn from_map_thing(map: &WadMap, world: &mut World, thing: &Thing) -> Option<Self> {
+        let doomednum = thing.doomednum;
+        // Player starts 1 to 4 and deathmatch starts
+        if (1..=4).contains(&doomednum) || doomednum == 11 {
+            return None;
+        }
+        if (thing.flags & ThingFlags::NOT_SINGLE_PLAYER) != ThingFlags::NONE {
+            return None;
+        }
+        let skill_bit = match world.skill {
+            Skill::Baby | Skill::Easy => ThingFlags::SKILL_EASY,
+            Skill::Medium => ThingFlags::SKILL_MEDIUM,
+            Skill::Hard | Skill::Nightmare => ThingFlags::SKILL_HARD,
+        };
+        if (thing.flags & skill_bit) == ThingFlags::NONE {
+            return None;
+        }
+
+        let kind = MobjType::from_doomednum(doomednum)?;
+        let mut mo = Mobj::spawn(map, world, kind, thing.x as f32, thing.y as f32);
+        // Animations out of step
+        if mo.tics > 0 {
+            mo.tics = 1 + world.random.gameplay() % mo.tics;
+        }
+        mo.angle = thing.direction();
+        if (thing.flags & ThingFlags::AMBUSH) != ThingFlags::NONE {
+            mo.flags |= MobjFlags::AMBUSH;
+        }
+        Some(mo)
+    }
+
+    /// Enter a state and the following zero tic ones, without running their actions
+    ///
+    /// Returns false when the mobj reached the null state and must be removed
+    pub fn set_state(&mut self, state: StateNum) -> bool {
+        let mut state = state;
+        loop {
+            if state == StateNum::Null {
+                self.state = StateNum::Null;
+                return false;
+            }
+            let info = self.enter_state(state);
+            if info.tics != 0 {
+                return true;
+            }
+            state = info.next;
+        }
+    }
+
+    fn enter_state(&mut self, state: StateNum) -> &'static State {
+        let info = state.info();
+        self.state = state;
+        self.tics = info.tics;
+        self.sprite = info.sprite;
+        self.frame = info.frame;
+        info
+    }
+}
+
+/// Enter a state and the following zero tic ones, running their actions, like vanilla
+/// P_SetMobjState
+///
+/// Returns false when the mobj reached the null state and must be removed
+pub fn set_mobj_state(map: &mut WadMap, world: &mut World, mo: &mut Mobj, state: StateNum) -> bool {
+    let mut state = state;
+    loop {
+        if state == StateNum::Null {
+            mo.state = StateNum::Null;
+            return false;
+        }
+        let info = mo.enter_state(state);
+        // The action can go to another state
+        if let Some(action) = info.action {
+            mobj_action(map, world, mo, action);
+            if mo.state == StateNum::Null {
+                return false;
+            }
+        }
+        if mo.tics != 0 {
+            return true;
+        }
+        state = info.next;
+    }
+}
+
+/// Count down the current state and go to the next one when it is over
+///
+/// Returns false when the mobj must be removed
+pub fn tick_state(map: &mut WadMap, world: &mut World, mo: &mut Mobj) -> bool {
+    if mo.tics == -1 {
+        return true;
+    }
+    mo.tics -= 1;
+    if mo.tics != 0 {
+        return true;
+    }
+    set_mobj_state(map, world, mo, mo.state.info().next)
+}
+
+/// Run the code of a mobj state, named after the vanilla A_ functions
+fn mobj_action(map: &mut WadMap, world: &mut World, mo: &mut Mobj, action: ActionFn) {
+    match action {
+        ActionFn::Explode => explode(map, world, mo),
+        ActionFn::Tracer => tracer(map, world, mo),
+        ActionFn::BfgSpray => bfg_spray(map, world, mo),
+        ActionFn::Look => enemy::look(map, world, mo),
+        ActionFn::Chase | ActionFn::Hoof | ActionFn::Metal | ActionFn::BabyMetal => {
+            enemy::chase(map, world, mo)
+        }
+        ActionFn::FaceTarget | ActionFn::SkelWhoosh | ActionFn::FatRaise => {
+            enemy::face_target(world, mo)
+        }
+        ActionFn::PosAttack | ActionFn::CPosAttack => enemy::pos_attack(map, world, mo),
+        ActionFn::SPosAttack => enemy::spos_attack(map, world, mo),
+        ActionFn::CPosRefire => enemy::refire(map, world, mo, 40),
+        ActionFn::SpidRefire => enemy::refire(map, world, mo, 10),
+        ActionFn::BspiAttack => enemy::bspi_attack(map, world, mo),
+        ActionFn::TroopAttack => enemy::troop_attack(map, world, mo),
+        ActionFn::SargAttack => enemy::sarg_attack(map, world, mo),
+        ActionFn::HeadAttack => enemy::head_attack(map, world, mo),
+        ActionFn::BruisAttack => enemy::bruis_attack(map, world, mo),
+        ActionFn::CyberAttack => enemy::cyber_attack(map, world, mo),
+        ActionFn::SkelMissile => enemy::skel_missile(map, world, mo),
+        ActionFn::SkelFist => enemy::skel_fist(map, world, mo),
+        ActionFn::VileChase => enemy::vile_chase(map, world, mo),
+        ActionFn::VileTarget => enemy::vile_target(map, world, mo),
+        ActionFn::VileAttack => enemy::vile_attack(map, world, mo),
+        ActionFn::StartFire | ActionFn::Fire | ActionFn::FireCrackle => enemy::fire(map, world, mo),
+        ActionFn::FatAttack1 => enemy::fat_attack1(map, world, mo),
+        ActionFn::FatAttack2 => enemy::fat_attack2(map, world, mo),
+        ActionFn::FatAttack3 => enemy::fat_attack3(map, world, mo),
+        ActionFn::SkullAttack => enemy::skull_attack(world, mo),
+        ActionFn::PainAttack => enemy::pain_attack(map, world, mo),
+        ActionFn::PainDie => enemy::pain_die(map, world, mo),
+        ActionFn::Fall => enemy::fall(mo),
+        // Nothing plays sounds yet, but some are picked at random
+        ActionFn::Scream => {
+            enemy::scream(world, mo);
+        }
+        ActionFn::BossDeath => enemy::boss_death(map, world, mo),
+        ActionFn::KeenDie => enemy::keen_die(map, world, mo),
+        ActionFn::BrainAwake => enemy::brain_awake(world),
+        ActionFn::BrainSpit => enemy::brain_spit(map, world, mo),
+        ActionFn::SpawnFly | ActionFn::SpawnSound => enemy::spawn_fly(map, world, mo),
+        ActionFn::BrainScream => enemy::brain_scream(map, world, mo),
+        ActionFn::BrainExplode => enemy::brain_explode(map, world, mo),
+        ActionFn::BrainDie => enemy::brain_die(world),
+        // Only sounds, or actions of the weapon states
+        _ => (),
+    }
+}
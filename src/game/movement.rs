@@ -0,0 +1,614 @@
+use cgmath::{InnerSpace, Vector2};
+
+use super::{
+    info::{MobjFlags, MobjType},
+    mobj::Mobj,
+    world::{ThinkerId, World},
+};
+use crate::wad::map::{LineDef, LinedefFlags, Sector, WadMap};
+
+/// Fastest move in one tic, like vanilla MAXMOVE
+pub const MAXMOVE: f32 = 30.0;
+/// Below this speed a mobj on the floor stops, like vanilla STOPSPEED
+const STOPSPEED: f32 = 1.0 / 16.0;
+/// Momentum kept each tic on the floor, like vanilla FRICTION
+const FRICTION: f32 = 0.90625;
+/// Speed gained falling each tic
+pub const GRAVITY: f32 = 1.0;
+/// Highest step a mobj can climb, or ledge a monster can step off
+const MAX_STEP: f32 = 24.0;
+/// Distance kept from a wall when sliding along it
+const SLIDE_FUDGE: f32 = 1.0 / 32.0;
+/// Reach of the use key, like vanilla USERANGE
+const USERANGE: f32 = 64.0;
+/// Largest radius of a thing, the cells around a position are searched this far, like vanilla
+/// MAXRADIUS
+pub const MAXRADIUS: f32 = 32.0;
+
+/// Distance along the axes with a rough correction, like vanilla P_AproxDistance
+///
+/// Monsters decide from it, the exact distance would change their choices
+pub fn aprox_distance(dx: f32, dy: f32) -> f32 {
+    let (dx, dy) = (dx.abs(), dy.abs());
+    dx + dy - dx.min(dy) / 2.0
+}
+
+pub fn line_points(map: &WadMap, line: &LineDef) -> (Vector2<f32>, Vector2<f32>) {
+    let v1 = map.vertexes[line.start_vertex as usize];
+    let v2 = map.vertexes[line.end_vertex as usize];
+    (
+        Vector2::new(v1.x as f32, v1.y as f32),
+        Vector2::new(v2.x as f32, v2.y as f32),
+    )
+}
+
+/// 0 on the front (right) side of a line, 1 on the back, like vanilla P_PointOnLineSide
+pub fn point_on_line_side(point: Vector2<f32>, v1: Vector2<f32>, v2: Vector2<f32>) -> usize {
+    let line = v2 - v1;
+    let delta = point - v1;
+    if delta.y * line.x < line.y * delta.x {
+        0
+    } else {
+        1
+    }
+}
+
+/// Gap left between the two sectors of a two-sided line: top and bottom heights
+pub fn line_opening(map: &WadMap, line: &LineDef) -> (f32, f32) {
+    let front = &map.sectors[map.sidedefs[line.front_sidedef as usize].sector as usize];
+    let back = &map.sectors[map.sidedefs[line.back_sidedef as usize].sector as usize];
+    (front.ceiling.min(back.ceiling), front.floor.max(back.floor))
+}
+
+/// Sectors in front and at the back of a line, the back one is None for a wall
+pub fn line_sectors<'a>(map: &'a WadMap, line: &LineDef) -> (&'a Sector, Option<&'a Sector>) {
+    let sector = |side: i16| &map.sectors[map.sidedefs[side as usize].sector as usize];
+    let back = (line.back_sidedef != -1).then(|| sector(line.back_sidedef));
+    (sector(line.front_sidedef), back)
+}
+
+/// Floor and ceiling of the sector under a point
+pub fn sector_heights(map: &WadMap, x: f32, y: f32) -> (f32, f32) {
+    let sector = &map.sectors[map.sector_at(x, y)];
+    (sector.floor, sector.ceiling)
+}
+
+/// Lines touched by the bounding box of a mobj moved to a position
+fn touched_lines(map: &WadMap, mo: &Mobj, x: f32, y: f32) -> Vec<usize> {
+    let (left, right) = (x - mo.radius, x + mo.radius);
+    let (bottom, top) = (y - mo.radius, y + mo.radius);
+
+    let mut lines = map.lines_in_box(left, bottom, right, top);
+    lines.retain(|&index| {
+        let (v1, v2) = line_points(map, &map.linedefs[index]);
+        if right <= v1.x.min(v2.x)
+            || left >= v1.x.max(v2.x)
+            || top <= v1.y.min(v2.y)
+            || bottom >= v1.y.max(v2.y)
+        {
+            return false;
+        }
+        // The box touches the line when its corners are on both sides
+        let corners = [(left, top), (right, top), (left, bottom), (right, bottom)]
+            .map(|(cx, cy)| point_on_line_side(Vector2::new(cx, cy), v1, v2));
+        corners.iter().any(|&side| side != corners[0])
+    });
+    lines
+}
+
+/// Heights met by a mobj moved to a position, like the tmfloorz, tmceilingz and tmdropoffz of
+/// vanilla P_CheckPosition
+#[derive(Debug, Copy, Clone)]
+pub struct Heights {
+    /// Highest floor touched
+    pub floor_z: f32,
+    /// Lowest ceiling touched
+    pub ceiling_z: f32,
+    /// Lowest floor touched, the bottom of a ledge
+    pub dropoff_z: f32,
+}
+
+/// Heights touched by a mobj moved to a position, None when a wall blocks it
+///
+/// Vanilla P_CheckPosition, the things are checked apart. Only the sector under a mobj going
+/// through walls counts
+pub fn check_position(map: &WadMap, mo: &Mobj, x: f32, y: f32) -> Option<Heights> {
+    let (floor_z, ceiling_z) = sector_heights(map, x, y);
+    let mut heights = Heights {
+        floor_z,
+        ceiling_z,
+        dropoff_z: floor_z,
+    };
+    if (mo.flags & MobjFlags::NOCLIP) != MobjFlags::NONE {
+        return Some(heights);
+    }
+    for index in touched_lines(map, mo, x, y) {
+        let line = &map.linedefs[index];
+        if line.back_sidedef == -1 {
+            return None;
+        }
+        // Missiles fly over blocking lines, players only stop at the lines blocking everything
+        if (mo.flags & MobjFlags::MISSILE) == MobjFlags::NONE {
+            let mut blocking = LinedefFlags::BLOCK_ALL;
+            if mo.kind != MobjType::Player {
+                blocking |= LinedefFlags::BLOCK_MONSTERS;
+            }
+            if (line.flags & blocking) != LinedefFlags::NONE {
+                return None;
+            }
+        }
+        let (open_top, open_bottom) = line_opening(map, line);
+        let (front, back) = line_sectors(map, line);
+        let low_floor = back.map_or(front.floor, |back| front.floor.min(back.floor));
+        heights.ceiling_z = heights.ceiling_z.min(open_top);
+        heights.floor_z = heights.floor_z.max(open_bottom);
+        heights.dropoff_z = heights.dropoff_z.min(low_floor);
+    }
+    Some(heights)
+}
+
+/// Special two-sided lines touched by a mobj moved to a position, like the vanilla spechit lines
+/// a blocked monster tries to open
+pub fn touched_special_lines(map: &WadMap, mo: &Mobj, x: f32, y: f32) -> Vec<usize> {
+    let mut lines = touched_lines(map, mo, x, y);
+    lines.retain(|&index| {
+        let line = &map.linedefs[index];
+        line.special_type != 0 && line.back_sidedef != -1
+    });
+    lines
+}
+
+/// Solid thing a mobj moved to a position bumps into, like vanilla PIT_CheckThing
+///
+/// Things are as tall as the level, only the ones linked in the blockmap cells around are
+/// checked. Missiles hit things with their own check
+pub fn blocking_thing(world: &World, mo: &Mobj, x: f32, y: f32) -> Option<ThinkerId> {
+    if (mo.flags & (MobjFlags::MISSILE | MobjFlags::NOCLIP)) != MobjFlags::NONE {
+        return None;
+    }
+    things_around(world, mo, x, y).find_map(|(id, thing)| {
+        if (thing.flags & MobjFlags::SOLID) == MobjFlags::NONE || id == mo.id {
+            return None;
+        }
+        let block = thing.radius + mo.radius;
+        ((thing.x - x).abs() < block && (thing.y - y).abs() < block).then_some(id)
+    })
+}
+
+/// Mobjs a mobj moved to a position can touch, from the blockmap cells its box reaches with
+/// the largest thing radius, like the block loop of vanilla P_CheckPosition
+pub fn things_around<'a>(
+    world: &'a World,
+    mo: &Mobj,
+    x: f32,
+    y: f32,
+) -> impl Iterator<Item = (ThinkerId, &'a Mobj)> {
+    let reach = mo.radius + MAXRADIUS;
+    world
+        .mobjs_in_box(x - reach, y - reach, x + reach, y + reach)
+        .into_iter()
+        .filter_map(|id| world.mobj(id).map(|thing| (id, thing)))
+}
+
+/// Two-sided line bringing the ceiling the lowest for a mobj moved to a position, like vanilla
+/// ceilingline
+pub fn ceiling_line(map: &WadMap, mo: &Mobj, x: f32, y: f32) -> Option<usize> {
+    let (_, mut ceiling_z) = sector_heights(map, x, y);
+    let mut lowest = None;
+    for index in touched_lines(map, mo, x, y) {
+        let line = &map.linedefs[index];
+        if line.back_sidedef == -1 {
+            continue;
+        }
+        let (open_top, _) = line_opening(map, line);
+        if open_top < ceiling_z {
+            ceiling_z = open_top;
+            lowest = Some(index);
+        }
+    }
+    lowest
+}
+
+/// Move a mobj if it fits at the new position, like vanilla P_TryMove
+pub fn try_move(map: &WadMap, world: &World, mo: &mut Mobj, x: f32, y: f32) -> bool {
+    check_move(map, world, mo, x, y).is_ok()
+}
+
+/// Move a mobj if it fits at the new position, like vanilla P_TryMove
+///
+/// When it does not, the heights there are given if the opening is tall enough for the mobj,
+/// a floating monster can rise or sink to it, like vanilla floatok
+pub fn check_move(
+    map: &WadMap,
+    world: &World,
+    mo: &mut Mobj,
+    x: f32,
+    y: f32,
+) -> Result<(), Option<Heights>> {
+    if blocking_thing(world, mo, x, y).is_some() {
+        return Err(None);
+    }
+    let heights = check_position(map, mo, x, y).ok_or(None)?;
+    // Nothing clips a noclip mobj
+    if (mo.flags & MobjFlags::NOCLIP) == MobjFlags::NONE {
+        if heights.ceiling_z - heights.floor_z < mo.height {
+            return Err(None);
+        }
+        // Room for its head, and a step low enough to climb
+        if heights.ceiling_z - mo.z < mo.height || heights.floor_z - mo.z > MAX_STEP {
+            return Err(Some(heights));
+        }
+        // Monsters do not step off high ledges
+        let may_drop = (mo.flags & (MobjFlags::DROPOFF | MobjFlags::FLOAT)) != MobjFlags::NONE;
+        if !may_drop && heights.floor_z - heights.dropoff_z > MAX_STEP {
+            return Err(Some(heights));
+        }
+    }
+
+    mo.x = x;
+    mo.y = y;
+    mo.floor_z = heights.floor_z;
+    mo.ceiling_z = heights.ceiling_z;
+    Ok(())
+}
+
+/// Put a mobj somewhere without checking anything, like a teleport
+pub fn set_position(map: &WadMap, mo: &mut Mobj, x: f32, y: f32) {
+    let (floor_z, ceiling_z) = check_position(map, mo, x, y)
+        .map_or(sector_heights(map, x, y), |heights| {
+            (heights.floor_z, heights.ceiling_z)
+        });
+    mo.x = x;
+    mo.y = y;
+    mo.floor_z = floor_z;
+    mo.ceiling_z = ceiling_z;
+}
+
+/// Keep a mobj between the floor and the ceiling after a sector moved, like vanilla P_ThingHeightClip
+///
+/// A mobj on the floor follows it, returns false when the mobj does not fit anymore
+pub fn height_clip(map: &WadMap, mo: &mut Mobj) -> bool {
+    let on_floor = mo.z == mo.floor_z;
+    let (floor_z, ceiling_z) = check_position(map, mo, mo.x, mo.y)
+        .map_or(sector_heights(map, mo.x, mo.y), |heights| {
+            (heights.floor_z, heights.ceiling_z)
+        });
+    mo.floor_z = floor_z;
+    mo.ceiling_z = ceiling_z;
+
+    if on_floor {
+        mo.z = floor_z;
+    } else if mo.z + mo.height > ceiling_z {
+        mo.z = ceiling_z - mo.height;
+    }
+    ceiling_z - floor_z >= mo.height
+}
+
+/// A line stopping a mobj sliding into it from a point, like vanilla PTR_SlideTraverse
+fn blocks_slide(map: &WadMap, mo: &Mobj, line: &LineDef) -> bool {
+    if line.back_sidedef == -1 {
+        // Only the front side of a wall is hit
+        let (v1, v2) = line_points(map, line);
+        return point_on_line_side(Vector2::new(mo.x, mo.y), v1, v2) == 0;
+    }
+    if (line.flags & LinedefFlags::BLOCK_ALL) != LinedefFlags::NONE {
+        return true;
+    }
+    let (open_top, open_bottom) = line_opening(map, line);
+    open_top - open_bottom < mo.height
+        || open_top - mo.z < mo.height
+        || open_bottom - mo.z > MAX_STEP
+}
+
+/// Fraction of a trace where it crosses a line, if it does
+pub fn intercept(
+    start: Vector2<f32>,
+    delta: Vector2<f32>,
+    v1: Vector2<f32>,
+    v2: Vector2<f32>,
+) -> Option<f32> {
+    let line = v2 - v1;
+    let denom = delta.x * line.y - delta.y * line.x;
+    if denom == 0.0 {
+        return None;
+    }
+    let to_line = v1 - start;
+    let frac = (to_line.x * line.y - to_line.y * line.x) / denom;
+    let along = (to_line.x * delta.y - to_line.y * delta.x) / denom;
+    ((0.0..=1.0).contains(&frac) && (0.0..=1.0).contains(&along)).then_some(frac)
+}
+
+/// Closest line blocking the move, traced from the three leading corners of the box
+fn find_slide_line(map: &WadMap, mo: &Mobj) -> Option<(f32, usize)> {
+    let momentum = Vector2::new(mo.momx, mo.momy);
+    let lead = Vector2::new(mo.momx.signum(), mo.momy.signum()) * mo.radius;
+    let origin = Vector2::new(mo.x, mo.y);
+    let corners = [
+        origin + lead,
+        origin + Vector2::new(-lead.x, lead.y),
+        origin + Vector2::new(lead.x, -lead.y),
+    ];
+
+    let mut best: Option<(f32, usize)> = None;
+    for corner in corners {
+        let end = corner + momentum;
+        let lines = map.lines_in_box(
+            corner.x.min(end.x),
+            corner.y.min(end.y),
+            corner.x.max(end.x),
+            corner.y.max(end.y),
+        );
+        for index in lines {
+            let line = &map.linedefs[index];
+            let (v1, v2) = line_points(map, line);
+            let Some(frac) = intercept(corner, momentum, v1, v2) else {
+                continue;
+            };
+            if best.is_none_or(|(best, _)| frac < best) && blocks_slide(map, mo, line) {
+                best = Some((frac, index));
+            }
+        }
+    }
+    best
+}
+
+/// Move along the wall that was hit instead of stopping, like vanilla P_SlideMove
+pub fn slide_move(map: &WadMap, world: &World, mo: &mut Mobj) {
+    for _ in 0..3 {
+        let Some((frac, index)) = find_slide_line(map, mo) else {
+            break;
+        };
+
+        // Go up to the wall
+        let to_wall = frac - SLIDE_FUDGE;
+        if to_wall > 0.0
+            && !try_move(
+                map,
+                world,
+                mo,
+                mo.x + mo.momx * to_wall,
+                mo.y + mo.momy * to_wall,
+            )
+        {
+            break;
+        }
+
+        // Then along it with what is left of the move
+        let left = 1.0 - (frac + SLIDE_FUDGE);
+        if left <= 0.0 {
+            return;
+        }
+        let (v1, v2) = line_points(map, &map.linedefs[index]);
+        let direction = (v2 - v1).normalize();
+        let momentum = Vector2::new(mo.momx, mo.momy);
+        let slide = direction * momentum.dot(direction);
+        mo.momx = slide.x;
+        mo.momy = slide.y;
+        if try_move(map, world, mo, mo.x + slide.x * left, mo.y + slide.y * left) {
+            return;
+        }
+    }
+
+    // Move along one axis at a time when nothing better was found
+    if !try_move(map, world, mo, mo.x, mo.y + mo.momy) {
+        try_move(map, world, mo, mo.x + mo.momx, mo.y);
+    }
+}
+
+/// Apply the horizontal momentum and the floor friction, like vanilla P_XYMovement
+///
+/// Players slide along what they hit, other mobjs stop. `pushing` keeps a slow mobj moving,
+/// like a player holding a move key
+pub fn xy_movement(map: &WadMap, world: &World, mo: &mut Mobj, pushing: bool) {
+    mo.momx = mo.momx.clamp(-MAXMOVE, MAXMOVE);
+    mo.momy = mo.momy.clamp(-MAXMOVE, MAXMOVE);
+
+    // Big moves are done in halves so thin walls are not crossed
+    let (mut xmove, mut ymove) = (mo.momx, mo.momy);
+    while xmove != 0.0 || ymove != 0.0 {
+        let (x, y) = if xmove.abs() > MAXMOVE / 2.0 || ymove.abs() > MAXMOVE / 2.0 {
+            xmove /= 2.0;
+            ymove /= 2.0;
+            (mo.x + xmove, mo.y + ymove)
+        } else {
+            let target = (mo.x + xmove, mo.y + ymove);
+            xmove = 0.0;
+            ymove = 0.0;
+            target
+        };
+
+        if !try_move(map, world, mo, x, y) {
+            if mo.kind == MobjType::Player {
+                slide_move(map, world, mo);
+            } else {
+                mo.momx = 0.0;
+                mo.momy = 0.0;
+            }
+            break;
+        }
+    }
+
+    // No friction in the air
+    if mo.z > mo.floor_z {
+        return;
+    }
+    if mo.momx.abs() < STOPSPEED && mo.momy.abs() < STOPSPEED && !pushing {
+        mo.momx = 0.0;
+        mo.momy = 0.0;
+    } else {
+        mo.momx *= FRICTION;
+        mo.momy *= FRICTION;
+    }
+}
+
+/// Apply the vertical momentum and gravity, like vanilla P_ZMovement
+///
+/// Returns the falling speed when the mobj lands, 0 otherwise
+pub fn z_movement(mo: &mut Mobj) -> f32 {
+    let mut landing = 0.0;
+    mo.z += mo.momz;
+
+    if mo.z <= mo.floor_z {
+        // A charging lost soul bounces off the floor
+        if (mo.flags & MobjFlags::SKULLFLY) != MobjFlags::NONE {
+            mo.momz = -mo.momz;
+        }
+        if mo.momz < 0.0 {
+            landing = -mo.momz;
+            mo.momz = 0.0;
+        }
+        mo.z = mo.floor_z;
+    } else if (mo.flags & MobjFlags::NOGRAVITY) == MobjFlags::NONE {
+        if mo.momz == 0.0 {
+            mo.momz = -GRAVITY * 2.0;
+        } else {
+            mo.momz -= GRAVITY;
+        }
+    }
+
+    if mo.z + mo.height > mo.ceiling_z {
+        if mo.momz > 0.0 {
+            mo.momz = 0.0;
+        }
+        mo.z = mo.ceiling_z - mo.height;
+    }
+    landing
+}
+
+/// Special lines crossed by the center of a mobj going from one point to another, with the
+/// side it came from, like the vanilla spechit lines checked at the end of P_TryMove
+pub fn crossed_special_lines(
+    map: &WadMap,
+    from: (f32, f32),
+    to: (f32, f32),
+) -> Vec<(usize, usize)> {
+    let start = Vector2::new(from.0, from.1);
+    let delta = Vector2::new(to.0, to.1) - start;
+    let lines = map.lines_in_box(
+        from.0.min(to.0),
+        from.1.min(to.1),
+        from.0.max(to.0),
+        from.1.max(to.1),
+    );
+
+    let mut crossed = Vec::new();
+    for index in lines {
+        let line = &map.linedefs[index];
+        if line.special_type == 0 {
+            continue;
+        }
+        let (v1, v2) = line_points(map, line);
+        if intercept(start, delta, v1, v2).is_none() {
+            continue;
+        }
+        let side = point_on_line_side(start, v1, v2);
+        if side != point_on_line_side(start + delta, v1, v2) {
+            crossed.push((index, side));
+        }
+    }
+    crossed
+}
+
+/// Special line a mobj can use in front of it and the side it is on, like vanilla P_UseLines
+///
+/// The closest special line within reach is used, walls and closed openings stop the search
+pub fn line_to_use(map: &WadMap, mo: &Mobj) -> Option<(usize, usize)> {
+    let start = Vector2::new(mo.x, mo.y);
+    let angle = mo.angle.to_radians();
+    let delta = Vector2::new(angle.cos(), angle.sin()) * USERANGE;
+    let end = start + delta;
+
+    let mut hits: Vec<(f32, usize)> = map
+        .lines_in_box(
+            start.x.min(end.x),
+            start.y.min(end.y),
+            start.x.max(end.x),
+            start.y.max(end.y),
+        )
+        .into_iter()
+        .filter_map(|index| {
+            let (v1, v2) = line_points(map, &map.linedefs[index]);
+            intercept(start, delta, v1, v2).map(|frac| (frac, index))
+        })
+        .collect();
+    hits.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    for (_, index) in hits {
+        let line = &map.linedefs[index];
+        if line.special_type != 0 {
+            let (v1, v2) = line_points(map, line);
+            return Some((index, point_on_line_side(start, v1, v2)));
+        }
+        if line.back_sidedef == -1 {
+            return None;
+        }
+        let (open_top, open_bottom) = line_opening(map, line);
+        if open_top <= open_bottom {
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wad::test_wad::steps_map;
+
+    /// A player standing on the floor of the steps at a position
+    fn player_at(map: &WadMap, x: f32, y: f32) -> Mobj {
+        Mobj::spawn(map, &mut World::new(), MobjType::Player, x, y)
+    }
+
+    #[test]
+    fn walls_block() {
+        let map = steps_map();
+        let mut mo = player_at(&map, 64.0, 64.0);
+        assert!(!try_move(&map, &World::new(), &mut mo, 10.0, 64.0));
+        assert!(!try_move(&map, &World::new(), &mut mo, 64.0, 120.0));
+        assert_eq!((mo.x, mo.y), (64.0, 64.0));
+        assert!(try_move(&map, &World::new(), &mut mo, 16.0, 112.0));
+    }
+
+    #[test]
+    fn steps_up_to_24_units_are_climbed() {
+        let mut map = steps_map();
+        let mut mo = player_at(&map, 100.0, 64.0);
+        assert!(try_move(&map, &World::new(), &mut mo, 120.0, 64.0));
+        assert_eq!(mo.floor_z, 24.0);
+
+        map.sectors[1].floor = 25.0;
+        let mut mo = player_at(&map, 100.0, 64.0);
+        let heights = check_move(&map, &World::new(), &mut mo, 120.0, 64.0).unwrap_err();
+        assert_eq!(heights.map(|heights| heights.floor_z), Some(25.0));
+        assert_eq!(mo.x, 100.0);
+    }
+
+    #[test]
+    fn ceilings_too_low_block() {
+        let mut map = steps_map();
+        let mut mo = player_at(&map, 200.0, 64.0);
+        assert_eq!(mo.z, 24.0);
+        // 48 units between the floor and the ceiling for a player 56 units tall
+        assert!(matches!(
+            check_move(&map, &World::new(), &mut mo, 250.0, 64.0),
+            Err(None)
+        ));
+        assert_eq!(mo.x, 200.0);
+
+        map.sectors[2].ceiling = 80.0;
+        assert!(try_move(&map, &World::new(), &mut mo, 250.0, 64.0));
+        assert_eq!(mo.ceiling_z, 80.0);
+    }
+
+    #[test]
+    fn noclip_goes_through_everything() {
+        let map = steps_map();
+        let mut mo = player_at(&map, 64.0, 64.0);
+        mo.flags |= MobjFlags::NOCLIP;
+        assert!(try_move(&map, &World::new(), &mut mo, 10.0, 64.0));
+        // Only the sector under it counts
+        assert!(try_move(&map, &World::new(), &mut mo, 300.0, 64.0));
+        assert_eq!((mo.floor_z, mo.ceiling_z), (24.0, 72.0));
+    }
+}
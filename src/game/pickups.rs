@@ -0,0 +1,346 @@
+//! Items picked up by the player, like P_TouchSpecialThing of vanilla p_inter.c
+
+use super::{
+    info::{MobjFlags, MobjType},
+    mobj::Mobj,
+    player::{AmmoType, Key, Player, Power, Weapon, NUM_AMMO},
+    skill::Skill,
+    world::{ThinkerId, World},
+};
+
+/// Ammo in a clip of each ammo type, boxes hold five clips, like vanilla clipammo
+const CLIP_AMMO: [i32; NUM_AMMO] = [10, 4, 20, 1];
+/// Health without bonuses, like vanilla MAXHEALTH
+const MAXHEALTH: i32 = 100;
+/// Health and armor the bonuses and spheres go up to
+const MAX_BONUS: i32 = 200;
+/// Tics the timed powerups last, like vanilla INVULNTICS, INVISTICS, IRONTICS and INFRATICS
+const INVULNTICS: i32 = 30 * 35;
+const INVISTICS: i32 = 60 * 35;
+const IRONTICS: i32 = 60 * 35;
+const INFRATICS: i32 = 120 * 35;
+
+/// Pick up the items a player moved onto, like the PIT_CheckThing part of vanilla P_TryMove
+pub fn touch_special_things(player: &mut Player, world: &mut World, mo: &mut Mobj) {
+    let touched: Vec<ThinkerId> = world
+        .mobjs()
+        .filter(|(_, special)| {
+            let block = special.radius + mo.radius;
+            (special.flags & MobjFlags::SPECIAL) != MobjFlags::NONE
+                && (special.x - mo.x).abs() < block
+                && (special.y - mo.y).abs() < block
+        })
+        .map(|(id, _)| id)
+        .collect();
+    for id in touched {
+        touch_special_thing(player, world, mo, id);
+    }
+}
+
+/// Take an item if the player can use it, like vanilla P_TouchSpecialThing
+///
+/// Items out of reach above or below, or not needed, are left where they are
+fn touch_special_thing(player: &mut Player, world: &mut World, mo: &mut Mobj, id: ThinkerId) {
+    let Some(special) = world.mobj(id) else {
+        return;
+    };
+    let delta = special.z - mo.z;
+    if delta > mo.height || delta < -8.0 || mo.health <= 0 {
+        return;
+    }
+    let kind = special.kind;
+    let dropped = (special.flags & MobjFlags::DROPPED) != MobjFlags::NONE;
+    let counted = (special.flags & MobjFlags::COUNTITEM) != MobjFlags::NONE;
+    let skill = world.skill;
+
+    if !give_item(player, mo, kind, dropped, skill) {
+        return;
+    }
+    if counted {
+        player.status.items += 1;
+    }
+    world.remove(id);
+}
+
+/// Give what an item holds and the message of its pickup, false when the player can not use it
+fn give_item(
+    player: &mut Player,
+    mo: &mut Mobj,
+    kind: MobjType,
+    dropped: bool,
+    skill: Skill,
+) -> bool {
+    let message = match kind {
+        // Armor
+        MobjType::GreenArmor if give_armor(player, 1) => "Picked up the armor.",
+        MobjType::BlueArmor if give_armor(player, 2) => "Picked up the MegaArmor!",
+
+        // Bonuses, always picked up
+        MobjType::HealthBonus => {
+            player.status.health = (player.status.health + 1).min(MAX_BONUS);
+            mo.health = player.status.health;
+            "Picked up a health bonus."
+        }
+        MobjType::ArmorBonus => {
+            player.status.armor = (player.status.armor + 1).min(MAX_BONUS);
+            if player.status.armor_type == 0 {
+                player.status.armor_type = 1;
+            }
+            "Picked up an armor bonus."
+        }
+        MobjType::SoulSphere => {
+            player.status.health = (player.status.health + 100).min(MAX_BONUS);
+            mo.health = player.status.health;
+            "Supercharge!"
+        }
+        MobjType::MegaSphere => {
+            player.status.health = MAX_BONUS;
+            mo.health = player.status.health;
+            give_armor(player, 2);
+            "MegaSphere!"
+        }
+
+        // Keys, always picked up but only a new one has a message
+        MobjType::BlueCard => return give_key(player, Key::BlueCard, "Picked up a blue keycard."),
+        MobjType::YellowCard => {
+            return give_key(player, Key::YellowCard, "Picked up a yellow keycard.")
+        }
+        MobjType::RedCard => return give_key(player, Key::RedCard, "Picked up a red keycard."),
+        MobjType::BlueSkull => {
+            return give_key(player, Key::BlueSkull, "Picked up a blue skull key.")
+        }
+        MobjType::YellowSkull => {
+            return give_key(player, Key::YellowSkull, "Picked up a yellow skull key.")
+        }
+        MobjType::RedSkull => return give_key(player, Key::RedSkull, "Picked up a red skull key."),
+
+        // Medical
+        MobjType::Stimpack if give_body(player, mo, 10) => "Picked up a stimpack.",
+        // Vanilla checks the health once raised, so the first message hardly ever shows
+        MobjType::Medikit if give_body(player, mo, 25) => {
+            if player.status.health < 25 {
+                "Picked up a medikit that you REALLY need!"
+            } else {
+                "Picked up a medikit."
+            }
+        }
+
+        // Powerups
+        MobjType::Invulnerability if give_power(player, mo, Power::Invulnerability) => {
+            "Invulnerability!"
+        }
+        MobjType::Berserk if give_power(player, mo, Power::Strength) => {
+            if player.status.ready_weapon != Weapon::Fist {
+                player.pending_weapon = Some(Weapon::Fist);
+            }
+            "Berserk!"
+        }
+        MobjType::Invisibility if give_power(player, mo, Power::Invisibility) => {
+            "Partial Invisibility"
+        }
+        MobjType::RadSuit if give_power(player, mo, Power::IronFeet) => "Radiation Shielding Suit",
+        MobjType::ComputerMap if give_power(player, mo, Power::AllMap) => "Computer Area Map",
+        MobjType::LightAmp if give_power(player, mo, Power::Infrared) => {
+            "Light Amplification Visor"
+        }
+
+        // Ammo, a dropped clip holds half of one
+        MobjType::Clip if give_ammo(player, AmmoType::Clip, i32::from(!dropped), skill) => {
+            "Picked up a clip."
+        }
+        MobjType::AmmoBox if give_ammo(player, AmmoType::Clip, 5, skill) => {
+            "Picked up a box of bullets."
+        }
+        MobjType::RocketAmmo if give_ammo(player, AmmoType::Missile, 1, skill) => {
+            "Picked up a rocket."
+        }
+        MobjType::RocketBox if give_ammo(player, AmmoType::Missile, 5, skill) => {
+            "Picked up a box of rockets."
+        }
+        MobjType::Cell if give_ammo(player, AmmoType::Cell, 1, skill) => {
+            "Picked up an energy cell."
+        }
+        MobjType::CellPack if give_ammo(player, AmmoType::Cell, 5, skill) => {
+            "Picked up an energy cell pack."
+        }
+        MobjType::Shells if give_ammo(player, AmmoType::Shell, 1, skill) => {
+            "Picked up 4 shotgun shells."
+        }
+        MobjType::ShellBox if give_ammo(player, AmmoType::Shell, 5, skill) => {
+            "Picked up a box of shotgun shells."
+        }
+        MobjType::Backpack => {
+            if !player.status.backpack {
+                for max in &mut player.status.max_ammo {
+                    *max *= 2;
+                }
+                player.status.backpack = true;
+            }
+            for ammo in [
+                AmmoType::Clip,
+                AmmoType::Shell,
+                AmmoType::Cell,
+                AmmoType::Missile,
+            ] {
+                give_ammo(player, ammo, 1, skill);
+            }
+            "Picked up a backpack full of ammo!"
+        }
+
+        // Weapons, the ones dropped by monsters hold less ammo
+        MobjType::BfgGun if give_weapon(player, Weapon::Bfg, false, skill) => {
+            "You got the BFG9000!  Oh, yes."
+        }
+        MobjType::Chaingun if give_weapon(player, Weapon::Chaingun, dropped, skill) => {
+            "You got the chaingun!"
+        }
+        MobjType::Chainsaw if give_weapon(player, Weapon::Chainsaw, false, skill) => {
+            "A chainsaw!  Find some meat!"
+        }
+        MobjType::Launcher if give_weapon(player, Weapon::Missile, false, skill) => {
+            "You got the rocket launcher!"
+        }
+        MobjType::PlasmaGun if give_weapon(player, Weapon::Plasma, false, skill) => {
+            "You got the plasma gun!"
+        }
+        MobjType::Shotgun if give_weapon(player, Weapon::Shotgun, dropped, skill) => {
+            "You got the shotgun!"
+        }
+        MobjType::SuperShotgun if give_weapon(player, Weapon::SuperShotgun, dropped, skill) => {
+            "You got the super shotgun!"
+        }
+
+        _ => return false,
+    };
+    player.message = Some(message);
+    true
+}
+
+/// Heal up to the normal health, false when already there, like vanilla P_GiveBody
+fn give_body(player: &mut Player, mo: &mut Mobj, amount: i32) -> bool {
+    let status = &mut player.status;
+    if status.health >= MAXHEALTH {
+        return false;
+    }
+    status.health = (status.health + amount).min(MAXHEALTH);
+    mo.health = status.health;
+    true
+}
+
+/// Wear green (1) or blue (2) armor, false when the armor worn is as good, like vanilla
+/// P_GiveArmor
+fn give_armor(player: &mut Player, armor_type: i32) -> bool {
+    let status = &mut player.status;
+    let points = armor_type * 100;
+    if status.armor >= points {
+        return false;
+    }
+    status.armor_type = armor_type;
+    status.armor = points;
+    true
+}
+
+/// Give a key, with the message when it is new, like vanilla P_GiveCard
+///
+/// Always true, keys are picked up even when the player has them
+fn give_key(player: &mut Player, key: Key, message: &'static str) -> bool {
+    if !player.status.has_key(key) {
+        player.status.keys[key as usize] = true;
+        player.message = Some(message);
+    }
+    true
+}
+
+/// Start a powerup, false for one lasting the whole level that is already there, like
+/// vanilla P_GivePower
+fn give_power(player: &mut Player, mo: &mut Mobj, power: Power) -> bool {
+    let tics = match power {
+        Power::Invulnerability => INVULNTICS,
+        Power::Invisibility => {
+            mo.flags |= MobjFlags::SHADOW;
+            INVISTICS
+        }
+        Power::IronFeet => IRONTICS,
+        Power::Infrared => INFRATICS,
+        Power::Strength => {
+            give_body(player, mo, 100);
+            1
+        }
+        Power::AllMap => {
+            if player.status.has_power(power) {
+                return false;
+            }
+            1
+        }
+    };
+    player.status.powers[power as usize] = tics;
+    true
+}
+
+/// Give some clips of ammo, half a clip for 0, false when already full, like vanilla
+/// P_GiveAmmo
+///
+/// Running out of ammo before switches to a better weapon than the fist or the pistol
+fn give_ammo(player: &mut Player, ammo: AmmoType, clips: i32, skill: Skill) -> bool {
+    let status = &mut player.status;
+    let index = ammo as usize;
+    if status.ammo[index] == status.max_ammo[index] {
+        return false;
+    }
+    let mut amount = if clips == 0 {
+        CLIP_AMMO[index] / 2
+    } else {
+        clips * CLIP_AMMO[index]
+    };
+    // Twice as much on the easiest and hardest skills
+    if matches!(skill, Skill::Baby | Skill::Nightmare) {
+        amount *= 2;
+    }
+
+    let old = status.ammo[index];
+    status.ammo[index] = (old + amount).min(status.max_ammo[index]);
+    if old != 0 {
+        return true;
+    }
+
+    let ready = status.ready_weapon;
+    let switch = match ammo {
+        AmmoType::Clip if ready == Weapon::Fist => {
+            if status.has_weapon(Weapon::Chaingun) {
+                Some(Weapon::Chaingun)
+            } else {
+                Some(Weapon::Pistol)
+            }
+        }
+        AmmoType::Shell if matches!(ready, Weapon::Fist | Weapon::Pistol) => status
+            .has_weapon(Weapon::Shotgun)
+            .then_some(Weapon::Shotgun),
+        AmmoType::Cell if matches!(ready, Weapon::Fist | Weapon::Pistol) => {
+            status.has_weapon(Weapon::Plasma).then_some(Weapon::Plasma)
+        }
+        AmmoType::Missile if ready == Weapon::Fist => status
+            .has_weapon(Weapon::Missile)
+            .then_some(Weapon::Missile),
+        _ => None,
+    };
+    if switch.is_some() {
+        player.pending_weapon = switch;
+    }
+    true
+}
+
+/// Give a weapon with two clips of its ammo, one when dropped by a monster, false when the
+/// player has it and is full of ammo, like vanilla P_GiveWeapon
+///
+/// A new weapon is brought up right away
+fn give_weapon(player: &mut Player, weapon: Weapon, dropped: bool, skill: Skill) -> bool {
+    let gave_ammo = weapon
+        .ammo()
+        .is_some_and(|ammo| give_ammo(player, ammo, if dropped { 1 } else { 2 }, skill));
+    if player.status.has_weapon(weapon) {
+        return gave_ammo;
+    }
+    player.status.weapons[weapon as usize] = true;
+    player.pending_weapon = Some(weapon);
+    true
+}
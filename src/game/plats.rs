@@ -0,0 +1,219 @@
+//! Lifts and moving platforms, like vanilla p_plats.c
+
+use kabal_app::tic_runner::TICRATE;
+
+use super::{
+    specials::{
+        front_sector, highest_floor_surrounding, lowest_floor_surrounding, move_plane,
+        next_highest_floor, tagged_sectors, MoveResult, Plane,
+    },
+    world::{Thinker, World},
+};
+use crate::wad::map::{LineDef, SectorType, WadMap};
+
+/// Speed of a slow lift in map units per tic, like vanilla PLATSPEED
+const PLATSPEED: f32 = 1.0;
+/// Seconds a lift waits at the bottom or the top, like vanilla PLATWAIT
+const PLATWAIT: i32 = 3;
+
+/// What a platform does, like vanilla plattype_e
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PlatKind {
+    /// Up and down until stopped
+    PerpetualRaise,
+    /// A lift: down, wait, up and stay
+    DownWaitUpStay,
+    /// Raise by an amount and take the floor texture of the line front sector
+    RaiseAndChange,
+    RaiseToNearestAndChange,
+    /// A fast lift
+    BlazeDwus,
+}
+
+/// Where a platform is going, like vanilla plat_e
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PlatStatus {
+    Up,
+    Down,
+    Waiting,
+    /// Stopped by a line until started again
+    InStasis,
+}
+
+/// A platform moving its sector floor, like vanilla plat_t
+pub struct Plat {
+    pub kind: PlatKind,
+    pub sector: usize,
+    pub speed: f32,
+    pub low: f32,
+    pub high: f32,
+    /// Tics to wait at each end
+    pub wait: i32,
+    /// Tics left before moving again
+    pub count: i32,
+    pub status: PlatStatus,
+    /// Status before being stopped
+    pub old_status: PlatStatus,
+    pub crush: bool,
+    pub tag: i16,
+}
+
+impl Plat {
+    /// Move for one tic, like vanilla T_PlatRaise
+    ///
+    /// Returns false once the platform is done
+    pub fn think(&mut self, map: &mut WadMap, world: &mut World) -> bool {
+        match self.status {
+            PlatStatus::Up => {
+                let result = move_plane(
+                    map,
+                    world,
+                    self.sector,
+                    self.speed,
+                    self.high,
+                    self.crush,
+                    Plane::Floor,
+                    1,
+                );
+                if result == MoveResult::Crushed && !self.crush {
+                    self.count = self.wait;
+                    self.status = PlatStatus::Down;
+                } else if result == MoveResult::PastDest {
+                    self.count = self.wait;
+                    self.status = PlatStatus::Waiting;
+                    if self.kind != PlatKind::PerpetualRaise {
+                        return false;
+                    }
+                }
+            }
+            PlatStatus::Down => {
+                let result = move_plane(
+                    map,
+                    world,
+                    self.sector,
+                    self.speed,
+                    self.low,
+                    false,
+                    Plane::Floor,
+                    -1,
+                );
+                if result == MoveResult::PastDest {
+                    self.count = self.wait;
+                    self.status = PlatStatus::Waiting;
+                }
+            }
+            PlatStatus::Waiting => {
+                self.count -= 1;
+                if self.count == 0 {
+                    self.status = if map.sectors[self.sector].floor == self.low {
+                        PlatStatus::Up
+                    } else {
+                        PlatStatus::Down
+                    };
+                }
+            }
+            PlatStatus::InStasis => (),
+        }
+        true
+    }
+}
+
+/// Start the platforms of the sectors tagged like a line, like vanilla EV_DoPlat
+///
+/// `amount` is the height raised by RaiseAndChange. Returns true when a platform started
+pub fn do_plat(
+    map: &mut WadMap,
+    world: &mut World,
+    line: &LineDef,
+    kind: PlatKind,
+    amount: f32,
+) -> bool {
+    if kind == PlatKind::PerpetualRaise {
+        activate_in_stasis(world, line.sector_tag);
+    }
+
+    let mut started = false;
+    for sector in tagged_sectors(map, line.sector_tag) {
+        if world.sector_mover(sector).is_some() {
+            continue;
+        }
+        started = true;
+
+        let floor = map.sectors[sector].floor;
+        let mut plat = Plat {
+            kind,
+            sector,
+            speed: PLATSPEED,
+            low: floor,
+            high: floor,
+            wait: 0,
+            count: 0,
+            status: PlatStatus::Up,
+            old_status: PlatStatus::Up,
+            crush: false,
+            tag: line.sector_tag,
+        };
+        match kind {
+            PlatKind::RaiseToNearestAndChange => {
+                plat.speed = PLATSPEED / 2.0;
+                plat.high = next_highest_floor(map, sector, floor);
+                let front = front_sector(map, line);
+                map.sectors[sector].floor_tex = map.sectors[front].floor_tex;
+                map.sectors[sector].stype = SectorType::Normal;
+            }
+            PlatKind::RaiseAndChange => {
+                plat.speed = PLATSPEED / 2.0;
+                plat.high = floor + amount;
+                let front = front_sector(map, line);
+                map.sectors[sector].floor_tex = map.sectors[front].floor_tex;
+            }
+            PlatKind::DownWaitUpStay | PlatKind::BlazeDwus => {
+                plat.speed = if kind == PlatKind::BlazeDwus {
+                    PLATSPEED * 8.0
+                } else {
+                    PLATSPEED * 4.0
+                };
+                plat.low = lowest_floor_surrounding(map, sector);
+                plat.wait = TICRATE as i32 * PLATWAIT;
+                plat.status = PlatStatus::Down;
+            }
+            PlatKind::PerpetualRaise => {
+                plat.low = lowest_floor_surrounding(map, sector);
+                plat.high = highest_floor_surrounding(map, sector).max(floor);
+                plat.wait = TICRATE as i32 * PLATWAIT;
+                // Either way first
+                plat.status = if (world.random.gameplay() & 1) == 0 {
+                    PlatStatus::Up
+                } else {
+                    PlatStatus::Down
+                };
+            }
+        }
+        world.add_mover(sector, Thinker::Plat(plat));
+    }
+    started
+}
+
+/// Start again the platforms with a tag stopped by a line, like vanilla P_ActivateInStasis
+fn activate_in_stasis(world: &mut World, tag: i16) {
+    for (_, thinker) in world.iter_mut() {
+        if let Thinker::Plat(plat) = thinker {
+            if plat.tag == tag && plat.status == PlatStatus::InStasis {
+                plat.status = plat.old_status;
+            }
+        }
+    }
+}
+
+/// Stop the moving platforms with the tag of a line, like vanilla EV_StopPlat
+pub fn stop_plat(world: &mut World, line: &LineDef) {
+    let tag = line.sector_tag;
+    for (_, thinker) in world.iter_mut() {
+        if let Thinker::Plat(plat) = thinker {
+            if plat.tag == tag && plat.status != PlatStatus::InStasis {
+                plat.old_status = plat.status;
+                plat.status = PlatStatus::InStasis;
+            }
+        }
+    }
+}
@@ -0,0 +1,564 @@
+use cgmath::{Deg, Rad};
+
+use super::{
+    info::MobjFlags,
+    interaction::hurt,
+    missiles::angle_between,
+    mobj::Mobj,
+    movement::{line_to_use, set_position, xy_movement, z_movement, GRAVITY},
+    pickups::touch_special_things,
+    skill::Skill,
+    ticcmd::{Buttons, TicCmd},
+    triggers::{cross_special_lines, use_special_line},
+    weapons::{move_psprites, setup_psprites, slot_weapon, Psprite, NUM_PSPRITES},
+    world::{ThinkerId, World},
+};
+use crate::wad::map::{SectorType, WadMap};
+
+/// Height of the eyes above the floor, like vanilla VIEWHEIGHT
+pub const VIEWHEIGHT: f32 = 41.0;
+/// Highest view bobbing, like vanilla MAXBOB
+const MAXBOB: f32 = 16.0;
+/// Tics of a whole bobbing cycle
+const BOB_PERIOD: f32 = 20.0;
+/// Lowest eye height of a dead player
+const DEAD_VIEWHEIGHT: f32 = 6.0;
+/// Degrees a dead player turns each tic toward its killer, a vanilla angle of ANG5
+const DEAD_TURN: f32 = 5.0;
+/// Health at or below which a KillEnd sector ends the level
+const KILL_END_HEALTH: i32 = 10;
+
+/// Weapons in vanilla order, the index is also the slot shown on the status bar minus one
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Weapon {
+    Fist,
+    Pistol,
+    Shotgun,
+    Chaingun,
+    Missile,
+    Plasma,
+    Bfg,
+    Chainsaw,
+    SuperShotgun,
+}
+
+pub const NUM_WEAPONS: usize = 9;
+
+/// Ammo types in vanilla order, the index of the ammo arrays
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AmmoType {
+    Clip,
+    Shell,
+    Cell,
+    Missile,
+}
+
+pub const NUM_AMMO: usize = 4;
+
+impl Weapon {
+    pub fn from_index(index: usize) -> Self {
+        match index {
+            0 => Weapon::Fist,
+            1 => Weapon::Pistol,
+            2 => Weapon::Shotgun,
+            3 => Weapon::Chaingun,
+            4 => Weapon::Missile,
+            5 => Weapon::Plasma,
+            6 => Weapon::Bfg,
+            7 => Weapon::Chainsaw,
+            _ => Weapon::SuperShotgun,
+        }
+    }
+
+    /// Ammo used by the weapon, None for melee weapons
+    pub fn ammo(&self) -> Option<AmmoType> {
+        match self {
+            Weapon::Fist | Weapon::Chainsaw => None,
+            Weapon::Pistol | Weapon::Chaingun => Some(AmmoType::Clip),
+            Weapon::Shotgun | Weapon::SuperShotgun => Some(AmmoType::Shell),
+            Weapon::Missile => Some(AmmoType::Missile),
+            Weapon::Plasma | Weapon::Bfg => Some(AmmoType::Cell),
+        }
+    }
+}
+
+/// Keys in vanilla order, also the order of the STKEYS graphics
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Key {
+    BlueCard,
+    YellowCard,
+    RedCard,
+    BlueSkull,
+    YellowSkull,
+    RedSkull,
+}
+
+pub const NUM_KEYS: usize = 6;
+
+/// Powerups in vanilla order, the index of the powers array
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Power {
+    Invulnerability,
+    /// Berserk, it counts up and never runs out
+    Strength,
+    Invisibility,
+    /// Radiation suit
+    IronFeet,
+    /// Computer area map, it lasts the whole level
+    AllMap,
+    /// Light amplification visor
+    Infrared,
+}
+
+pub const NUM_POWERS: usize = 6;
+
+/// Everything the status bar shows about the player
+#[derive(Clone)]
+pub struct PlayerStatus {
+    pub health: i32,
+    pub armor: i32,
+    /// 1 for the green armor saving a third of the damage, 2 for the blue one saving half, 0
+    /// without armor
+    pub armor_type: i32,
+    pub ammo: [i32; NUM_AMMO],
+    pub max_ammo: [i32; NUM_AMMO],
+    pub keys: [bool; NUM_KEYS],
+    /// Tics left of each powerup, 0 when the player does not have it
+    pub powers: [i32; NUM_POWERS],
+    /// Picked up a backpack, the ammo limits are doubled
+    pub backpack: bool,
+    pub weapons: [bool; NUM_WEAPONS],
+    pub ready_weapon: Weapon,
+    pub god_mode: bool,
+    /// Walk through walls, toggled from the console
+    pub no_clip: bool,
+    /// Items counted for the intermission picked up in the level
+    pub items: i32,
+    /// Secret sectors entered in the level
+    pub secrets: i32,
+}
+
+impl PlayerStatus {
+    /// Ammo count of the ready weapon, None for melee weapons
+    pub fn ready_ammo(&self) -> Option<i32> {
+        self.ready_weapon
+            .ammo()
+            .map(|ammo| self.ammo[ammo as usize])
+    }
+
+    pub fn has_weapon(&self, weapon: Weapon) -> bool {
+        self.weapons[weapon as usize]
+    }
+
+    pub fn has_key(&self, key: Key) -> bool {
+        self.keys[key as usize]
+    }
+
+    pub fn has_power(&self, power: Power) -> bool {
+        self.powers[power as usize] != 0
+    }
+
+    /// Drop what does not go to the next level, like vanilla G_PlayerFinishLevel
+    pub fn finish_level(&mut self) {
+        self.keys = [false; NUM_KEYS];
+        self.powers = [0; NUM_POWERS];
+        self.items = 0;
+        self.secrets = 0;
+    }
+}
+
+impl Default for PlayerStatus {
+    /// A player entering the first level: fist, pistol and 50 bullets
+    fn default() -> Self {
+        let mut weapons = [false; NUM_WEAPONS];
+        weapons[Weapon::Fist as usize] = true;
+        weapons[Weapon::Pistol as usize] = true;
+
+        PlayerStatus {
+            health: 100,
+            armor: 0,
+            armor_type: 0,
+            ammo: [50, 0, 0, 0],
+            max_ammo: [200, 50, 300, 50],
+            keys: [false; NUM_KEYS],
+            powers: [0; NUM_POWERS],
+            backpack: false,
+            weapons,
+            ready_weapon: Weapon::Pistol,
+            god_mode: false,
+            no_clip: false,
+            items: 0,
+            secrets: 0,
+        }
+    }
+}
+
+/// The player in the level: body, status and point of view
+pub struct Player {
+    /// Body in the world
+    pub mobj: ThinkerId,
+    pub status: PlayerStatus,
+    /// Height of the eyes in the map, bobbing included
+    pub view_z: f32,
+    /// Eyes above the feet, lowered for a while when stepping up or landing
+    pub view_height: f32,
+    pub delta_view_height: f32,
+    /// Bobbing amplitude from the speed
+    pub bob: f32,
+    pub tics: u32,
+    /// Message for the player, like a missing key
+    pub message: Option<&'static str>,
+    /// Use is held since an earlier tic, it must be released to use again
+    pub use_down: bool,
+    /// Moved by a teleporter, the view jumps there instead of moving smoothly
+    pub teleported: bool,
+    /// What was asked for this tic
+    pub cmd: TicCmd,
+    /// Weapon and muzzle flash seen in first person
+    pub psprites: [Psprite; NUM_PSPRITES],
+    /// Weapon to bring up once the ready one is down
+    pub pending_weapon: Option<Weapon>,
+    /// Attack is held since an earlier tic, the rocket launcher and the BFG wait for a release
+    pub attack_down: bool,
+    /// Shots fired in a row holding attack, the first one is accurate
+    pub refire: i32,
+    /// Lighting added by the muzzle flash
+    pub extra_light: i32,
+    /// Who hurt the player last, a dead player looks at it
+    pub attacker: ThinkerId,
+    /// Dead and use was pressed, the level starts over
+    pub reborn: bool,
+}
+
+impl Player {
+    /// A new player in the body spawned on the player start, like vanilla P_SpawnPlayer
+    ///
+    /// The status is the one kept from the previous level, or a default one
+    pub fn spawn(map: &mut WadMap, world: &mut World, status: PlayerStatus) -> Self {
+        let id = world.player.mobj;
+        let mut player = Player {
+            status,
+            mobj: id,
+            ..Player::default()
+        };
+        world.with_mobj(id, |world, mo| {
+            mo.health = player.status.health;
+            player.view_z = mo.z + VIEWHEIGHT;
+            setup_psprites(&mut player, map, world, mo);
+        });
+        player
+    }
+
+    /// Put the eyes at a map position, for noclip flying and loaded games
+    pub fn set_view_position(
+        &mut self,
+        map: &WadMap,
+        world: &mut World,
+        x: f32,
+        y: f32,
+        view_z: f32,
+    ) {
+        // Taken out so the body is linked at its new position
+        let moved = world.with_mobj(self.mobj, |_, mo| {
+            set_position(map, mo, x, y);
+            mo.z = view_z - VIEWHEIGHT;
+            mo.momx = 0.0;
+            mo.momy = 0.0;
+            mo.momz = 0.0;
+        });
+        if moved.is_none() {
+            return;
+        }
+        self.view_z = view_z;
+        self.view_height = VIEWHEIGHT;
+        self.delta_view_height = 0.0;
+    }
+
+    /// Run one tic facing `angle`, like vanilla P_PlayerThink
+    pub fn think(&mut self, map: &mut WadMap, world: &mut World, cmd: &TicCmd, angle: Deg<f32>) {
+        self.cmd = *cmd;
+        world.with_mobj(self.mobj, |world, mo| {
+            // Like vanilla CF_NOCLIP
+            mo.flags.set(MobjFlags::NOCLIP, self.status.no_clip);
+            if mo.health <= 0 {
+                self.death_think(map, world, mo, cmd);
+                return;
+            }
+            self.move_body(map, world, mo, cmd, angle);
+            self.in_special_sector(map, world, mo);
+
+            if let Some(weapon) = cmd
+                .weapon_slot()
+                .and_then(|slot| slot_weapon(&self.status, slot))
+            {
+                self.pending_weapon = Some(weapon);
+            }
+
+            if (cmd.buttons & Buttons::USE) == Buttons::NONE {
+                self.use_down = false;
+            } else if !self.use_down {
+                self.use_down = true;
+                if let Some((line, side)) = line_to_use(map, mo) {
+                    use_special_line(map, world, mo, Some(self), line, side);
+                }
+            }
+
+            move_psprites(self, map, world, mo);
+            self.tick_powers(mo);
+        });
+    }
+
+    /// Run down the powerups, like the end of vanilla P_PlayerThink
+    fn tick_powers(&mut self, mo: &mut Mobj) {
+        let powers = &mut self.status.powers;
+        for power in [Power::Invulnerability, Power::IronFeet, Power::Infrared] {
+            if powers[power as usize] > 0 {
+                powers[power as usize] -= 1;
+            }
+        }
+        if powers[Power::Strength as usize] > 0 {
+            powers[Power::Strength as usize] += 1;
+        }
+        if powers[Power::Invisibility as usize] > 0 {
+            powers[Power::Invisibility as usize] -= 1;
+            if powers[Power::Invisibility as usize] == 0 {
+                mo.flags.remove(MobjFlags::SHADOW);
+            }
+        }
+    }
+
+    /// Hurt by the floor or find a secret, like vanilla P_PlayerInSpecialSector
+    ///
+    /// Floors hurt every 32 tics, not through a radiation suit except sometimes for the worst
+    /// ones. A KillEnd sector also ends the level once the health is low
+    fn in_special_sector(&mut self, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+        let sector = map.sector_at(mo.x, mo.y);
+        // Only the feet on the floor
+        if mo.z != map.sectors[sector].floor {
+            return;
+        }
+
+        let hurt_tic = (world.tic & 0x1f) == 0;
+        let suit = self.status.has_power(Power::IronFeet);
+        let damage = match map.sectors[sector].stype {
+            SectorType::MediumDamage if !suit => 10,
+            SectorType::LightDamage if !suit => 5,
+            SectorType::HeavyDamage | SectorType::DamageBlink
+                if !suit || world.random.gameplay() < 5 =>
+            {
+                20
+            }
+            SectorType::KillEnd => {
+                self.status.god_mode = false;
+                20
+            }
+            SectorType::Secret => {
+                self.status.secrets += 1;
+                self.message = Some("A secret is revealed!");
+                map.sectors[sector].stype = SectorType::Normal;
+                0
+            }
+            _ => 0,
+        };
+        if damage > 0 && hurt_tic {
+            // The player is out of the world while it thinks, so it hurts itself
+            let damage = if world.skill == Skill::Baby {
+                damage / 2
+            } else {
+                damage
+            };
+            self.take_damage(map, world, mo, None, damage);
+        }
+        if map.sectors[sector].stype == SectorType::KillEnd && self.status.health <= KILL_END_HEALTH
+        {
+            world.exit_level = true;
+        }
+    }
+
+    /// Damage reaching the player once pushed away, like the player part of vanilla
+    /// P_DamageMobj
+    ///
+    /// The armor absorbs part of it, god mode and invulnerability all of it but telefrags
+    pub fn take_damage(
+        &mut self,
+        map: &mut WadMap,
+        world: &mut World,
+        mo: &mut Mobj,
+        source: Option<&Mobj>,
+        damage: i32,
+    ) {
+        let mut damage = damage;
+        // Never killed on the floor ending the level, it needs the player alive
+        if map.sectors[map.sector_at(mo.x, mo.y)].stype == SectorType::KillEnd
+            && damage >= mo.health
+        {
+            damage = mo.health - 1;
+        }
+        let invulnerable = self.status.god_mode || self.status.has_power(Power::Invulnerability);
+        if invulnerable && damage < 1000 {
+            return;
+        }
+        if self.status.armor_type != 0 {
+            let mut saved = if self.status.armor_type == 1 {
+                damage / 3
+            } else {
+                damage / 2
+            };
+            // The armor is used up
+            if self.status.armor <= saved {
+                saved = self.status.armor;
+                self.status.armor_type = 0;
+            }
+            self.status.armor -= saved;
+            damage -= saved;
+        }
+        self.status.health = (self.status.health - damage).max(0);
+        self.attacker = source.map_or(ThinkerId::NONE, |source| source.id);
+        hurt(map, world, mo, source, damage);
+    }
+
+    /// Sink to the floor and look at the killer until use is pressed, like vanilla
+    /// P_DeathThink
+    fn death_think(&mut self, map: &mut WadMap, world: &mut World, mo: &mut Mobj, cmd: &TicCmd) {
+        move_psprites(self, map, world, mo);
+
+        // The body still slides and falls
+        if mo.momx != 0.0 || mo.momy != 0.0 {
+            xy_movement(map, world, mo, false);
+        }
+        z_movement(mo);
+
+        if self.view_height > DEAD_VIEWHEIGHT {
+            self.view_height -= 1.0;
+        }
+        self.view_height = self.view_height.max(DEAD_VIEWHEIGHT);
+        self.delta_view_height = 0.0;
+        self.calc_height(mo);
+
+        if let Some(attacker) = world
+            .mobj(self.attacker)
+            .filter(|attacker| attacker.id != mo.id)
+        {
+            let exact = angle_between(mo.x, mo.y, attacker.x, attacker.y);
+            let delta = (exact - mo.angle + 180.0).rem_euclid(360.0) - 180.0;
+            mo.angle = if delta.abs() < DEAD_TURN {
+                exact
+            } else {
+                mo.angle + DEAD_TURN.copysign(delta)
+            }
+            .rem_euclid(360.0);
+        }
+
+        if (cmd.buttons & Buttons::USE) != Buttons::NONE {
+            self.reborn = true;
+        }
+    }
+
+    /// Movement and view height of a tic, like vanilla P_MovePlayer and P_CalcHeight
+    fn move_body(
+        &mut self,
+        map: &mut WadMap,
+        world: &mut World,
+        mo: &mut Mobj,
+        cmd: &TicCmd,
+        angle: Deg<f32>,
+    ) {
+        self.tics += 1;
+        mo.angle = angle.0;
+
+        // Only feet on the floor can push, not right after a teleport
+        if mo.reaction_time > 0 {
+            mo.reaction_time -= 1;
+        } else if mo.z <= mo.floor_z {
+            let angle = Rad::from(angle).0;
+            thrust(mo, angle, cmd.forward_move as f32 / 32.0);
+            thrust(
+                mo,
+                angle - std::f32::consts::FRAC_PI_2,
+                cmd.side_move as f32 / 32.0,
+            );
+        }
+
+        let pushing = cmd.forward_move != 0 || cmd.side_move != 0;
+        if mo.momx != 0.0 || mo.momy != 0.0 {
+            let from = (mo.x, mo.y);
+            xy_movement(map, world, mo, pushing);
+            touch_special_things(self, world, mo);
+            cross_special_lines(map, world, mo, Some(self), from);
+        }
+
+        // Stepping up lowers the eyes, they come back up smoothly
+        if mo.z < mo.floor_z {
+            self.view_height -= mo.floor_z - mo.z;
+            self.delta_view_height = (VIEWHEIGHT - self.view_height) / 8.0;
+        }
+        let landing = z_movement(mo);
+        // Squat after a hard landing
+        if landing > GRAVITY * 8.0 {
+            self.delta_view_height = -landing / 8.0;
+        }
+
+        self.calc_height(mo);
+    }
+
+    /// Eye height with the view bobbing, like vanilla P_CalcHeight
+    fn calc_height(&mut self, mo: &Mobj) {
+        self.bob = ((mo.momx * mo.momx + mo.momy * mo.momy) / 4.0).min(MAXBOB);
+
+        if mo.z > mo.floor_z || mo.health <= 0 {
+            self.view_z = (mo.z + self.view_height).min(mo.ceiling_z - 4.0);
+            return;
+        }
+
+        let phase = self.tics as f32 / BOB_PERIOD * std::f32::consts::TAU;
+        let bob = self.bob / 2.0 * phase.sin();
+
+        self.view_height += self.delta_view_height;
+        if self.view_height > VIEWHEIGHT {
+            self.view_height = VIEWHEIGHT;
+            self.delta_view_height = 0.0;
+        }
+        if self.view_height < VIEWHEIGHT / 2.0 {
+            self.view_height = VIEWHEIGHT / 2.0;
+            if self.delta_view_height <= 0.0 {
+                self.delta_view_height = f32::EPSILON;
+            }
+        }
+        if self.delta_view_height != 0.0 {
+            self.delta_view_height += 0.25;
+        }
+
+        self.view_z = (mo.z + self.view_height + bob).min(mo.ceiling_z - 4.0);
+    }
+}
+
+fn thrust(mo: &mut Mobj, angle: f32, speed: f32) {
+    mo.momx += speed * angle.cos();
+    mo.momy += speed * angle.sin();
+}
+
+impl Default for Player {
+    /// A player not in a level yet
+    fn default() -> Self {
+        Player {
+            mobj: ThinkerId::NONE,
+            status: PlayerStatus::default(),
+            view_z: VIEWHEIGHT,
+            view_height: VIEWHEIGHT,
+            delta_view_height: 0.0,
+            bob: 0.0,
+            tics: 0,
+            message: None,
+            use_down: false,
+            teleported: false,
+            cmd: TicCmd::default(),
+            psprites: [Psprite::default(); NUM_PSPRITES],
+            pending_weapon: None,
+            attack_down: false,
+            refire: 0,
+            extra_light: 0,
+            attacker: ThinkerId::NONE,
+            reborn: false,
+        }
+    }
+}
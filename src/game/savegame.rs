@@ -0,0 +1,946 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use super::{
+    ceilings::{Ceiling, CeilingKind},
+    doors::{Door, DoorKind},
+    enemy::MoveDir,
+    floors::{FloorKind, FloorMove},
+    info::{MobjFlags, MobjType},
+    lights::{FireFlicker, Glow, LightFlash, Strobe},
+    mobj::Mobj,
+    plats::{Plat, PlatKind, PlatStatus},
+    player::{Player, PlayerStatus, Weapon},
+    skill::Skill,
+    states::StateNum,
+    switches::Button,
+    weapons::Psprite,
+    world::{Thinker, ThinkerId, World},
+};
+use crate::wad::map::{LinedefFlags, SectorType, SidePart, WadMap};
+
+/// Number of slots in the load and save menus
+pub const SAVE_SLOTS: usize = 6;
+/// Size of the description at the start of a save file, like vanilla SAVESTRINGSIZE
+pub const SAVESTRINGSIZE: usize = 24;
+const VERSION: &[u8; 16] = b"kabal save v4\0\0\0";
+
+const DOOR_KINDS: [DoorKind; 8] = [
+    DoorKind::Normal,
+    DoorKind::Close30ThenOpen,
+    DoorKind::Close,
+    DoorKind::Open,
+    DoorKind::RaiseIn5Mins,
+    DoorKind::BlazeRaise,
+    DoorKind::BlazeOpen,
+    DoorKind::BlazeClose,
+];
+const PLAT_KINDS: [PlatKind; 5] = [
+    PlatKind::PerpetualRaise,
+    PlatKind::DownWaitUpStay,
+    PlatKind::RaiseAndChange,
+    PlatKind::RaiseToNearestAndChange,
+    PlatKind::BlazeDwus,
+];
+const PLAT_STATUSES: [PlatStatus; 4] = [
+    PlatStatus::Up,
+    PlatStatus::Down,
+    PlatStatus::Waiting,
+    PlatStatus::InStasis,
+];
+const FLOOR_KINDS: [FloorKind; 14] = [
+    FloorKind::LowerFloor,
+    FloorKind::LowerFloorToLowest,
+    FloorKind::TurboLower,
+    FloorKind::RaiseFloor,
+    FloorKind::RaiseFloorToNearest,
+    FloorKind::RaiseToTexture,
+    FloorKind::LowerAndChange,
+    FloorKind::RaiseFloor24,
+    FloorKind::RaiseFloor24AndChange,
+    FloorKind::RaiseFloorCrush,
+    FloorKind::RaiseFloorTurbo,
+    FloorKind::DonutRaise,
+    FloorKind::RaiseFloor512,
+    FloorKind::BuildStairs,
+];
+const CEILING_KINDS: [CeilingKind; 6] = [
+    CeilingKind::LowerToFloor,
+    CeilingKind::RaiseToHighest,
+    CeilingKind::LowerAndCrush,
+    CeilingKind::CrushAndRaise,
+    CeilingKind::FastCrushAndRaise,
+    CeilingKind::SilentCrushAndRaise,
+];
+const SECTOR_TYPES: [SectorType; 16] = [
+    SectorType::Normal,
+    SectorType::BlinkRandom,
+    SectorType::BlinkHalf,
+    SectorType::BlinkFull,
+    SectorType::DamageBlink,
+    SectorType::MediumDamage,
+    SectorType::LightDamage,
+    SectorType::Oscillate,
+    SectorType::Secret,
+    SectorType::CeilDoorOpen,
+    SectorType::KillEnd,
+    SectorType::SyncBlinkHalf,
+    SectorType::SyncBlinkFull,
+    SectorType::CeilDoorClose,
+    SectorType::HeavyDamage,
+    SectorType::Flicker,
+];
+const MOVE_DIRS: [MoveDir; 9] = [
+    MoveDir::East,
+    MoveDir::NorthEast,
+    MoveDir::North,
+    MoveDir::NorthWest,
+    MoveDir::West,
+    MoveDir::SouthWest,
+    MoveDir::South,
+    MoveDir::SouthEast,
+    MoveDir::None,
+];
+const SIDE_PARTS: [SidePart; 3] = [SidePart::Upper, SidePart::Middle, SidePart::Lower];
+
+/// Kind of each thinker in a save, like vanilla thinkerclass_t and specials_e
+const THINKER_MOBJ: u8 = 0;
+const THINKER_DOOR: u8 = 1;
+const THINKER_PLAT: u8 = 2;
+const THINKER_FLOOR: u8 = 3;
+const THINKER_CEILING: u8 = 4;
+const THINKER_FIRE_FLICKER: u8 = 5;
+const THINKER_LIGHT_FLASH: u8 = 6;
+const THINKER_STROBE: u8 = 7;
+const THINKER_GLOW: u8 = 8;
+
+/// What is kept of a game in a save slot
+pub struct SaveGame {
+    pub description: String,
+    pub skill: Skill,
+    pub map: String,
+    /// The level as it was: sectors, lines, sides, thinkers and player, like the body of a
+    /// vanilla save
+    level: Vec<u8>,
+}
+
+fn slot_path(slot: usize) -> PathBuf {
+    PathBuf::from(format!("doomsav{}.dsg", slot))
+}
+
+impl SaveGame {
+    /// Keep the current state of a level, like vanilla G_DoSaveGame
+    pub fn new(description: String, map: &WadMap, world: &World) -> Self {
+        let mut writer = Writer::default();
+        archive_world(&mut writer, map);
+        archive_thinkers(&mut writer, world);
+        archive_level(&mut writer, world);
+        SaveGame {
+            description,
+            skill: world.skill,
+            map: map.name.clone(),
+            level: writer.content,
+        }
+    }
+
+    /// Description of a slot, None when it is empty
+    pub fn describe(slot: usize) -> Option<String> {
+        let content = std::fs::read(slot_path(slot)).ok()?;
+        content.get(..SAVESTRINGSIZE).map(read_string)
+    }
+
+    pub fn read(slot: usize) -> Result<Self, String> {
+        let content = std::fs::read(slot_path(slot)).map_err(|e| e.to_string())?;
+        Self::from_bytes(&content).ok_or_else(|| format!("Slot {} is not a valid save", slot))
+    }
+
+    fn from_bytes(content: &[u8]) -> Option<Self> {
+        let body = SAVESTRINGSIZE + VERSION.len();
+        if content.get(SAVESTRINGSIZE..body) != Some(VERSION.as_slice()) {
+            return None;
+        }
+
+        let mut reader = Reader::new(content);
+        reader.offset = body;
+        let skill = Skill::from_index(reader.byte()? as usize);
+        let map = read_string(reader.bytes(8)?);
+
+        Some(SaveGame {
+            description: read_string(&content[..SAVESTRINGSIZE]),
+            skill,
+            map,
+            level: content[reader.offset..].to_vec(),
+        })
+    }
+
+    pub fn write(&self, slot: usize) -> Result<(), String> {
+        std::fs::write(slot_path(slot), self.to_bytes()).map_err(|e| e.to_string())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut content = Vec::new();
+        content.extend(fixed_string(&self.description, SAVESTRINGSIZE));
+        content.extend(VERSION);
+        content.push(self.skill as u8);
+        content.extend(fixed_string(&self.map, 8));
+        content.extend(&self.level);
+        content
+    }
+
+    /// Put the saved level back over the freshly loaded map and an empty world, like vanilla
+    /// G_DoLoadGame
+    pub fn restore(&self, map: &mut WadMap, world: &mut World) -> Result<(), String> {
+        let invalid = || format!("The save of {} does not match the map", self.map);
+        let mut reader = Reader::new(&self.level);
+        unarchive_world(&mut reader, map).ok_or_else(invalid)?;
+        unarchive_thinkers(&mut reader, map, world).ok_or_else(invalid)?;
+        unarchive_level(&mut reader, map, world).ok_or_else(invalid)?;
+        world.skill = self.skill;
+        Ok(())
+    }
+}
+
+/// Writes the values of a save one after the other
+#[derive(Default)]
+struct Writer {
+    content: Vec<u8>,
+    /// Position from 1 of each thinker in the save, references to thinkers are written as it
+    positions: HashMap<ThinkerId, u32>,
+}
+
+impl Writer {
+    fn byte(&mut self, value: u8) {
+        self.content.push(value);
+    }
+
+    fn short(&mut self, value: i16) {
+        self.content.extend(value.to_le_bytes());
+    }
+
+    fn int(&mut self, value: i32) {
+        self.content.extend(value.to_le_bytes());
+    }
+
+    fn float(&mut self, value: f32) {
+        self.content.extend(value.to_le_bytes());
+    }
+
+    fn name(&mut self, name: [u8; 8]) {
+        self.content.extend(name);
+    }
+
+    /// A thinker the level refers to, 0 when it is gone
+    fn thinker(&mut self, id: ThinkerId) {
+        let position = self.positions.get(&id).copied().unwrap_or(0);
+        self.int(position as i32);
+    }
+}
+
+/// Reads the values of a save one after the other
+struct Reader<'a> {
+    content: &'a [u8],
+    offset: usize,
+    /// Thinker restored for each position in the save
+    ids: Vec<ThinkerId>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(content: &'a [u8]) -> Self {
+        Reader {
+            content,
+            offset: 0,
+            ids: Vec::new(),
+        }
+    }
+
+    fn bytes(&mut self, count: usize) -> Option<&[u8]> {
+        let bytes = self.content.get(self.offset..self.offset + count)?;
+        self.offset += count;
+        Some(bytes)
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn short(&mut self) -> Option<i16> {
+        self.bytes(2)
+            .map(|bytes| i16::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn word(&mut self) -> Option<[u8; 4]> {
+        self.bytes(4).map(|bytes| bytes.try_into().unwrap())
+    }
+
+    fn int(&mut self) -> Option<i32> {
+        self.word().map(i32::from_le_bytes)
+    }
+
+    fn float(&mut self) -> Option<f32> {
+        self.word().map(f32::from_le_bytes)
+    }
+
+    fn name(&mut self) -> Option<[u8; 8]> {
+        self.bytes(8).map(|bytes| bytes.try_into().unwrap())
+    }
+
+    /// One of the values of a fieldless enum, written as its index
+    fn variant<T: Copy>(&mut self, all: &[T]) -> Option<T> {
+        all.get(self.byte()? as usize).copied()
+    }
+
+    /// Position of a thinker in the save, 0 when it was gone
+    fn position(&mut self) -> Option<usize> {
+        usize::try_from(self.int()?).ok()
+    }
+
+    /// A thinker the level refers to, once every thinker was restored
+    fn thinker(&mut self) -> Option<ThinkerId> {
+        let position = self.position()?;
+        Some(self.thinker_at(position))
+    }
+
+    fn thinker_at(&self, position: usize) -> ThinkerId {
+        position
+            .checked_sub(1)
+            .and_then(|index| self.ids.get(index))
+            .copied()
+            .unwrap_or(ThinkerId::NONE)
+    }
+}
+
+/// Sectors, lines and sides, the parts of the map changed while playing, like vanilla
+/// P_ArchiveWorld
+fn archive_world(writer: &mut Writer, map: &WadMap) {
+    writer.int(map.sectors.len() as i32);
+    for sector in &map.sectors {
+        writer.float(sector.floor);
+        writer.float(sector.ceiling);
+        writer.name(sector.floor_tex);
+        writer.name(sector.ceil_tex);
+        writer.short(sector.lighting);
+        writer.short(sector.stype as i16);
+        writer.short(sector.tag);
+    }
+    writer.int(map.linedefs.len() as i32);
+    for line in &map.linedefs {
+        writer.short({ line.flags }.bits());
+        writer.short(line.special_type);
+        writer.short(line.sector_tag);
+    }
+    writer.int(map.sidedefs.len() as i32);
+    for side in &map.sidedefs {
+        writer.short(side.x_offset);
+        writer.short(side.y_offset);
+        writer.name(side.upper_tex);
+        writer.name(side.lower_tex);
+        writer.name(side.middle_tex);
+    }
+}
+
+fn unarchive_world(reader: &mut Reader, map: &mut WadMap) -> Option<()> {
+    if reader.position()? != map.sectors.len() {
+        return None;
+    }
+    for sector in &mut map.sectors {
+        sector.floor = reader.float()?;
+        sector.ceiling = reader.float()?;
+        sector.floor_tex = reader.name()?;
+        sector.ceil_tex = reader.name()?;
+        sector.lighting = reader.short()?;
+        let stype = reader.short()?;
+        sector.stype = *SECTOR_TYPES.iter().find(|&&kind| kind as i16 == stype)?;
+        sector.tag = reader.short()?;
+    }
+    if reader.position()? != map.linedefs.len() {
+        return None;
+    }
+    for line in &mut map.linedefs {
+        line.flags = LinedefFlags::from_bits_truncate(reader.short()?);
+        line.special_type = reader.short()?;
+        line.sector_tag = reader.short()?;
+    }
+    if reader.position()? != map.sidedefs.len() {
+        return None;
+    }
+    for side in &mut map.sidedefs {
+        side.x_offset = reader.short()?;
+        side.y_offset = reader.short()?;
+        side.upper_tex = reader.name()?;
+        side.lower_tex = reader.name()?;
+        side.middle_tex = reader.name()?;
+    }
+    Some(())
+}
+
+/// Every thinker in running order, like vanilla P_ArchiveThinkers and P_ArchiveSpecials
+///
+/// Thinkers refer to each other by their position in the save
+fn archive_thinkers(writer: &mut Writer, world: &World) {
+    writer.positions = world
+        .iter()
+        .enumerate()
+        .map(|(index, (id, _))| (id, index as u32 + 1))
+        .collect();
+    writer.int(writer.positions.len() as i32);
+    for (_, thinker) in world.iter() {
+        match thinker {
+            Thinker::Mobj(mo) => {
+                writer.byte(THINKER_MOBJ);
+                archive_mobj(writer, mo);
+            }
+            Thinker::Door(door) => {
+                writer.byte(THINKER_DOOR);
+                writer.byte(door.kind as u8);
+                writer.int(door.sector as i32);
+                writer.float(door.top_height);
+                writer.float(door.speed);
+                writer.int(door.direction);
+                writer.int(door.top_wait);
+                writer.int(door.top_countdown);
+            }
+            Thinker::Plat(plat) => {
+                writer.byte(THINKER_PLAT);
+                writer.byte(plat.kind as u8);
+                writer.int(plat.sector as i32);
+                writer.float(plat.speed);
+                writer.float(plat.low);
+                writer.float(plat.high);
+                writer.int(plat.wait);
+                writer.int(plat.count);
+                writer.byte(plat.status as u8);
+                writer.byte(plat.old_status as u8);
+                writer.byte(plat.crush as u8);
+                writer.short(plat.tag);
+            }
+            Thinker::Floor(floor) => {
+                writer.byte(THINKER_FLOOR);
+                writer.byte(floor.kind as u8);
+                writer.byte(floor.crush as u8);
+                writer.int(floor.sector as i32);
+                writer.int(floor.direction);
+                writer.short(floor.new_special as i16);
+                writer.name(floor.texture);
+                writer.float(floor.dest);
+                writer.float(floor.speed);
+            }
+            Thinker::Ceiling(ceiling) => {
+                writer.byte(THINKER_CEILING);
+                writer.byte(ceiling.kind as u8);
+                writer.int(ceiling.sector as i32);
+                writer.float(ceiling.bottom);
+                writer.float(ceiling.top);
+                writer.float(ceiling.speed);
+                writer.byte(ceiling.crush as u8);
+                writer.int(ceiling.direction);
+                writer.short(ceiling.tag);
+                writer.int(ceiling.old_direction);
+            }
+            Thinker::FireFlicker(flicker) => {
+                writer.byte(THINKER_FIRE_FLICKER);
+                writer.int(flicker.sector as i32);
+                writer.int(flicker.count);
+                writer.short(flicker.max_light);
+                writer.short(flicker.min_light);
+            }
+            Thinker::LightFlash(flash) => {
+                writer.byte(THINKER_LIGHT_FLASH);
+                writer.int(flash.sector as i32);
+                writer.int(flash.count);
+                writer.short(flash.max_light);
+                writer.short(flash.min_light);
+                writer.int(flash.max_time);
+                writer.int(flash.min_time);
+            }
+            Thinker::Strobe(strobe) => {
+                writer.byte(THINKER_STROBE);
+                writer.int(strobe.sector as i32);
+                writer.int(strobe.count);
+                writer.short(strobe.min_light);
+                writer.short(strobe.max_light);
+                writer.int(strobe.dark_time);
+                writer.int(strobe.bright_time);
+            }
+            Thinker::Glow(glow) => {
+                writer.byte(THINKER_GLOW);
+                writer.int(glow.sector as i32);
+                writer.short(glow.min_light);
+                writer.short(glow.max_light);
+                writer.int(glow.direction);
+            }
+        }
+    }
+}
+
+fn archive_mobj(writer: &mut Writer, mo: &Mobj) {
+    writer.int(mo.kind as i32);
+    for value in [
+        mo.x,
+        mo.y,
+        mo.z,
+        mo.angle,
+        mo.momx,
+        mo.momy,
+        mo.momz,
+        mo.radius,
+        mo.height,
+        mo.floor_z,
+        mo.ceiling_z,
+    ] {
+        writer.float(value);
+    }
+    writer.int(mo.flags.bits() as i32);
+    writer.int(mo.health);
+    writer.int(mo.reaction_time);
+    writer.int(mo.state as i32);
+    writer.int(mo.tics);
+    writer.thinker(mo.target);
+    writer.thinker(mo.tracer);
+    writer.int(mo.threshold);
+    writer.byte(mo.move_dir as u8);
+    writer.int(mo.move_count);
+}
+
+/// Mobj of a save, with the positions of its target and tracer to resolve once every thinker
+/// is back
+fn unarchive_mobj(reader: &mut Reader) -> Option<(Mobj, usize, usize)> {
+    let kind = MobjType::from_index(reader.int()? as usize)?;
+    let mut floats = [0.0; 11];
+    for value in &mut floats {
+        *value = reader.float()?;
+    }
+    let [x, y, z, angle, momx, momy, momz, radius, height, floor_z, ceiling_z] = floats;
+    let flags = MobjFlags::from_bits_truncate(reader.int()? as u32);
+    let health = reader.int()?;
+    let reaction_time = reader.int()?;
+    let state = StateNum::from_index(reader.int()? as usize)?;
+    let tics = reader.int()?;
+    let target = reader.position()?;
+    let tracer = reader.position()?;
+    let threshold = reader.int()?;
+    let move_dir = reader.variant(&MOVE_DIRS)?;
+    let move_count = reader.int()?;
+
+    let info = state.info();
+    let mo = Mobj {
+        id: ThinkerId::NONE,
+        kind,
+        info: kind.info(),
+        x,
+        y,
+        z,
+        angle,
+        momx,
+        momy,
+        momz,
+        radius,
+        height,
+        floor_z,
+        ceiling_z,
+        flags,
+        health,
+        reaction_time,
+        state,
+        tics,
+        sprite: info.sprite,
+        frame: info.frame,
+        target: ThinkerId::NONE,
+        tracer: ThinkerId::NONE,
+        threshold,
+        move_dir,
+        move_count,
+    };
+    Some((mo, target, tracer))
+}
+
+fn unarchive_thinkers(reader: &mut Reader, map: &WadMap, world: &mut World) -> Option<()> {
+    let count = reader.position()?;
+    // Targets and tracers of the mobjs, set once they all are back
+    let mut links = Vec::new();
+    for _ in 0..count {
+        let thinker = match reader.byte()? {
+            THINKER_MOBJ => {
+                let (mo, target, tracer) = unarchive_mobj(reader)?;
+                links.push((reader.ids.len(), target, tracer));
+                Thinker::Mobj(mo)
+            }
+            THINKER_DOOR => Thinker::Door(Door {
+                kind: reader.variant(&DOOR_KINDS)?,
+                sector: reader.position()?,
+                top_height: reader.float()?,
+                speed: reader.float()?,
+                direction: reader.int()?,
+                top_wait: reader.int()?,
+                top_countdown: reader.int()?,
+            }),
+            THINKER_PLAT => Thinker::Plat(Plat {
+                kind: reader.variant(&PLAT_KINDS)?,
+                sector: reader.position()?,
+                speed: reader.float()?,
+                low: reader.float()?,
+                high: reader.float()?,
+                wait: reader.int()?,
+                count: reader.int()?,
+                status: reader.variant(&PLAT_STATUSES)?,
+                old_status: reader.variant(&PLAT_STATUSES)?,
+                crush: reader.byte()? != 0,
+                tag: reader.short()?,
+            }),
+            THINKER_FLOOR => Thinker::Floor(FloorMove {
+                kind: reader.variant(&FLOOR_KINDS)?,
+                crush: reader.byte()? != 0,
+                sector: reader.position()?,
+                direction: reader.int()?,
+                new_special: {
+                    let stype = reader.short()?;
+                    *SECTOR_TYPES.iter().find(|&&kind| kind as i16 == stype)?
+                },
+                texture: reader.name()?,
+                dest: reader.float()?,
+                speed: reader.float()?,
+            }),
+            THINKER_CEILING => Thinker::Ceiling(Ceiling {
+                kind: reader.variant(&CEILING_KINDS)?,
+                sector: reader.position()?,
+                bottom: reader.float()?,
+                top: reader.float()?,
+                speed: reader.float()?,
+                crush: reader.byte()? != 0,
+                direction: reader.int()?,
+                tag: reader.short()?,
+                old_direction: reader.int()?,
+            }),
+            THINKER_FIRE_FLICKER => Thinker::FireFlicker(FireFlicker {
+                sector: reader.position()?,
+                count: reader.int()?,
+                max_light: reader.short()?,
+                min_light: reader.short()?,
+            }),
+            THINKER_LIGHT_FLASH => Thinker::LightFlash(LightFlash {
+                sector: reader.position()?,
+                count: reader.int()?,
+                max_light: reader.short()?,
+                min_light: reader.short()?,
+                max_time: reader.int()?,
+                min_time: reader.int()?,
+            }),
+            THINKER_STROBE => Thinker::Strobe(Strobe {
+                sector: reader.position()?,
+                count: reader.int()?,
+                min_light: reader.short()?,
+                max_light: reader.short()?,
+                dark_time: reader.int()?,
+                bright_time: reader.int()?,
+            }),
+            THINKER_GLOW => Thinker::Glow(Glow {
+                sector: reader.position()?,
+                min_light: reader.short()?,
+                max_light: reader.short()?,
+                direction: reader.int()?,
+            }),
+            _ => return None,
+        };
+
+        let id = match thinker {
+            Thinker::Mobj(_) => world.add(thinker),
+            // Movers keep their sector busy, like vanilla specialdata
+            Thinker::Door(Door { sector, .. })
+            | Thinker::Plat(Plat { sector, .. })
+            | Thinker::Floor(FloorMove { sector, .. })
+            | Thinker::Ceiling(Ceiling { sector, .. }) => {
+                map.sectors.get(sector)?;
+                world.add_mover(sector, thinker)
+            }
+            Thinker::FireFlicker(FireFlicker { sector, .. })
+            | Thinker::LightFlash(LightFlash { sector, .. })
+            | Thinker::Strobe(Strobe { sector, .. })
+            | Thinker::Glow(Glow { sector, .. }) => {
+                map.sectors.get(sector)?;
+                world.add(thinker)
+            }
+        };
+        reader.ids.push(id);
+    }
+
+    for (index, target, tracer) in links {
+        let (target, tracer) = (reader.thinker_at(target), reader.thinker_at(tracer));
+        let mo = world.mobj_mut(reader.ids[index])?;
+        mo.target = target;
+        mo.tracer = tracer;
+    }
+    Some(())
+}
+
+/// The player and what the level keeps besides its thinkers
+fn archive_level(writer: &mut Writer, world: &World) {
+    writer.int(world.tic as i32);
+    writer.int(world.total_secrets);
+
+    let player = &world.player;
+    writer.thinker(player.mobj);
+    write_status(&player.status, &mut writer.content);
+    for value in [
+        player.view_z,
+        player.view_height,
+        player.delta_view_height,
+        player.bob,
+    ] {
+        writer.float(value);
+    }
+    writer.int(player.tics as i32);
+    writer.byte(player.use_down as u8);
+    for psprite in &player.psprites {
+        writer.int(psprite.state as i32);
+        writer.int(psprite.tics);
+        writer.float(psprite.sx);
+        writer.float(psprite.sy);
+    }
+    // No pending weapon is written past the last one
+    writer.byte(player.pending_weapon.map_or(u8::MAX, |weapon| weapon as u8));
+    writer.byte(player.attack_down as u8);
+    writer.int(player.refire);
+    writer.int(player.extra_light);
+    writer.thinker(player.attacker);
+
+    writer.int(world.buttons.len() as i32);
+    for button in &world.buttons {
+        writer.int(button.line as i32);
+        writer.byte(button.part as u8);
+        writer.name(button.texture);
+        writer.int(button.timer as i32);
+    }
+
+    // Sorted so a save of the same level is always the same
+    let mut sound_targets: Vec<_> = world.sound_targets.iter().collect();
+    sound_targets.sort_unstable();
+    writer.int(sound_targets.len() as i32);
+    for (&sector, &id) in sound_targets {
+        writer.int(sector as i32);
+        writer.thinker(id);
+    }
+
+    writer.int(world.brain.targets.len() as i32);
+    for &id in &world.brain.targets {
+        writer.thinker(id);
+    }
+    writer.int(world.brain.next as i32);
+    writer.byte(world.brain.easy as u8);
+}
+
+fn unarchive_level(reader: &mut Reader, map: &WadMap, world: &mut World) -> Option<()> {
+    world.tic = reader.int()? as u32;
+    // Like vanilla G_InitNew, a loaded game draws from the start of the table
+    world.random.clear();
+    world.total_secrets = reader.int()?;
+
+    let mut player = Player {
+        mobj: reader.thinker()?,
+        status: read_status(reader)?,
+        view_z: reader.float()?,
+        view_height: reader.float()?,
+        delta_view_height: reader.float()?,
+        bob: reader.float()?,
+        tics: reader.int()? as u32,
+        use_down: reader.byte()? != 0,
+        ..Player::default()
+    };
+    for psprite in &mut player.psprites {
+        *psprite = Psprite {
+            state: StateNum::from_index(reader.int()? as usize)?,
+            tics: reader.int()?,
+            sx: reader.float()?,
+            sy: reader.float()?,
+        };
+    }
+    player.pending_weapon = match reader.byte()? {
+        u8::MAX => None,
+        index => Some(Weapon::from_index(index as usize)),
+    };
+    player.attack_down = reader.byte()? != 0;
+    player.refire = reader.int()?;
+    player.extra_light = reader.int()?;
+    player.attacker = reader.thinker()?;
+    world.mobj(player.mobj)?;
+    world.player = player;
+
+    for _ in 0..reader.position()? {
+        let button = Button {
+            line: reader.position()?,
+            part: reader.variant(&SIDE_PARTS)?,
+            texture: reader.name()?,
+            // A button whose time is up was already turned back
+            timer: u32::try_from(reader.int()?)
+                .ok()
+                .filter(|&timer| timer > 0)?,
+        };
+        map.linedefs.get(button.line)?;
+        world.buttons.push(button);
+    }
+
+    for _ in 0..reader.position()? {
+        let sector = reader.position()?;
+        let id = reader.thinker()?;
+        world.sound_targets.insert(sector, id);
+    }
+
+    for _ in 0..reader.position()? {
+        let id = reader.thinker()?;
+        world.brain.targets.push(id);
+    }
+    world.brain.next = reader.position()?;
+    world.brain.easy = reader.byte()? != 0;
+    Some(())
+}
+
+/// Status in the order it is written: the counts, then the flags and the ready weapon
+fn write_status(status: &PlayerStatus, content: &mut Vec<u8>) {
+    for value in [status.health, status.armor, status.armor_type]
+        .iter()
+        .chain(&status.ammo)
+        .chain(&status.max_ammo)
+        .chain(&status.powers)
+        .chain(&[status.items, status.secrets])
+    {
+        content.extend(value.to_le_bytes());
+    }
+
+    for &flag in status.keys.iter().chain(&status.weapons).chain(&[
+        status.backpack,
+        status.god_mode,
+        status.no_clip,
+    ]) {
+        content.push(flag as u8);
+    }
+    content.push(status.ready_weapon as u8);
+}
+
+fn read_status(reader: &mut Reader) -> Option<PlayerStatus> {
+    let mut status = PlayerStatus {
+        health: reader.int()?,
+        armor: reader.int()?,
+        armor_type: reader.int()?,
+        ..PlayerStatus::default()
+    };
+    for value in status
+        .ammo
+        .iter_mut()
+        .chain(&mut status.max_ammo)
+        .chain(&mut status.powers)
+        .chain([&mut status.items, &mut status.secrets])
+    {
+        *value = reader.int()?;
+    }
+
+    for flag in status.keys.iter_mut().chain(&mut status.weapons).chain([
+        &mut status.backpack,
+        &mut status.god_mode,
+        &mut status.no_clip,
+    ]) {
+        *flag = reader.byte()? != 0;
+    }
+    status.ready_weapon = Weapon::from_index(reader.byte()? as usize);
+    Some(status)
+}
+
+fn read_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).to_string()
+}
+
+fn fixed_string(text: &str, size: usize) -> Vec<u8> {
+    let mut bytes = text.as_bytes().to_vec();
+    bytes.resize(size, 0);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::specials::spawn_specials,
+        wad::test_wad::{room_map, ROOM_MAP},
+    };
+
+    /// The room after a few seconds of play, the imp has woken up and fired
+    fn played_room() -> (WadMap, World) {
+        let mut map = room_map();
+        let mut world = World::new();
+        world.spawn_map_things(&map, Skill::Medium);
+        spawn_specials(&mut map, &mut world);
+        world.player = Player::spawn(&mut map, &mut world, PlayerStatus::default());
+        for _ in 0..200 {
+            world.tick(&mut map);
+        }
+        (map, world)
+    }
+
+    fn load(content: &[u8]) -> Result<(WadMap, World), String> {
+        let save = SaveGame::from_bytes(content).ok_or("not a save")?;
+        let mut map = room_map();
+        let mut world = World::new();
+        save.restore(&mut map, &mut world)?;
+        Ok((map, world))
+    }
+
+    #[test]
+    fn loaded_level_goes_on_like_the_saved_one() {
+        let (mut map, mut world) = played_room();
+        let content = SaveGame::new("room".to_string(), &map, &world).to_bytes();
+        let save = SaveGame::from_bytes(&content).unwrap();
+        assert_eq!(save.description, "room");
+        assert_eq!(save.map, ROOM_MAP);
+
+        let (mut loaded_map, mut loaded) = load(&content).unwrap();
+        // The random table starts over on load
+        assert_eq!(loaded.random.indices(), (0, 0));
+        world.random.clear();
+        assert_eq!(loaded.hash(&loaded_map), world.hash(&map));
+        for _ in 0..100 {
+            world.tick(&mut map);
+            loaded.tick(&mut loaded_map);
+        }
+        assert_eq!(loaded.hash(&loaded_map), world.hash(&map));
+    }
+
+    #[test]
+    fn truncated_saves_are_refused() {
+        let (map, world) = played_room();
+        let content = SaveGame::new("room".to_string(), &map, &world).to_bytes();
+        for length in 0..content.len() {
+            assert!(load(&content[..length]).is_err(), "{} bytes", length);
+        }
+    }
+
+    #[test]
+    fn corrupt_saves_are_refused() {
+        let (mut map, mut world) = played_room();
+        let mut content = SaveGame::new("room".to_string(), &map, &world).to_bytes();
+        content[SAVESTRINGSIZE] ^= 0xff;
+        assert!(load(&content).is_err());
+
+        // A thinker of no known kind, after the counts and the sectors, lines and sides
+        let mut save = SaveGame::new("room".to_string(), &map, &world);
+        let first_kind =
+            16 + map.sectors.len() * 30 + map.linedefs.len() * 6 + map.sidedefs.len() * 28;
+        assert_eq!(save.level[first_kind], THINKER_MOBJ);
+        save.level[first_kind] = 99;
+        assert!(load(&save.to_bytes()).is_err());
+
+        // A button whose time is up
+        world.buttons.push(Button {
+            line: 0,
+            part: SidePart::Middle,
+            texture: map.sidedefs[0].middle_tex,
+            timer: 0,
+        });
+        let content = SaveGame::new("room".to_string(), &map, &world).to_bytes();
+        assert!(load(&content).is_err());
+        world.buttons[0].timer = 1;
+        let content = SaveGame::new("room".to_string(), &map, &world).to_bytes();
+        assert_eq!(load(&content).unwrap().1.buttons.len(), 1);
+
+        // A save of another map
+        map.linedefs.pop();
+        let content = SaveGame::new("room".to_string(), &map, &world).to_bytes();
+        assert!(load(&content).is_err());
+    }
+}
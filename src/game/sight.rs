@@ -0,0 +1,55 @@
+//! Line of sight between mobjs, like vanilla p_sight.c
+
+use cgmath::Vector2;
+
+use super::{
+    mobj::Mobj,
+    movement::{intercept, line_opening, line_points, line_sectors},
+};
+use crate::wad::map::WadMap;
+
+/// True when `t1` can see some of `t2`, like vanilla P_CheckSight
+///
+/// Walls block the view, the openings of two-sided lines narrow it. The reject table is not
+/// used
+pub fn check_sight(map: &WadMap, t1: &Mobj, t2: &Mobj) -> bool {
+    let start = Vector2::new(t1.x, t1.y);
+    let end = Vector2::new(t2.x, t2.y);
+    let delta = end - start;
+
+    // Slopes over the whole distance from the eyes to the top and bottom of t2
+    let eye_z = t1.z + t1.height - t1.height / 4.0;
+    let mut top_slope = t2.z + t2.height - eye_z;
+    let mut bottom_slope = t2.z - eye_z;
+
+    let lines = map.lines_in_box(
+        start.x.min(end.x),
+        start.y.min(end.y),
+        start.x.max(end.x),
+        start.y.max(end.y),
+    );
+    for index in lines {
+        let line = &map.linedefs[index];
+        let (v1, v2) = line_points(map, line);
+        let Some(frac) = intercept(start, delta, v1, v2) else {
+            continue;
+        };
+        let (front, Some(back)) = line_sectors(map, line) else {
+            return false;
+        };
+        let (open_top, open_bottom) = line_opening(map, line);
+        if open_bottom >= open_top {
+            return false;
+        }
+        if front.floor != back.floor {
+            bottom_slope = bottom_slope.max((open_bottom - eye_z) / frac);
+        }
+        if front.ceiling != back.ceiling {
+            top_slope = top_slope.min((open_top - eye_z) / frac);
+        }
+        if top_slope <= bottom_slope {
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,21 @@
+/// Difficulty levels in vanilla order
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Skill {
+    Baby,
+    Easy,
+    Medium,
+    Hard,
+    Nightmare,
+}
+
+impl Skill {
+    pub fn from_index(index: usize) -> Self {
+        match index {
+            0 => Skill::Baby,
+            1 => Skill::Easy,
+            2 => Skill::Medium,
+            3 => Skill::Hard,
+            _ => Skill::Nightmare,
+        }
+    }
+}
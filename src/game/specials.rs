@@ -0,0 +1,410 @@
+//! Sector lookups and plane moves shared by doors, lifts, floors and ceilings, like vanilla p_spec.c
+
+use super::{
+    ceilings::{crush_stop, do_ceiling, CeilingKind},
+    doors::{do_door, spawn_door_close_in_30, spawn_door_raise_in_5_mins, vertical_door, DoorKind},
+    floors::{build_stairs, do_donut, do_floor, FloorKind, StairKind},
+    info::{MobjFlags, MobjType},
+    interaction::damage_mobj,
+    lights::{
+        light_turn_on, spawn_fire_flicker, spawn_glowing_light, spawn_light_flash,
+        spawn_strobe_flash, start_light_strobing, turn_tag_lights_off, FASTDARK, SLOWDARK,
+    },
+    mobj::Mobj,
+    movement::height_clip,
+    plats::{do_plat, stop_plat, PlatKind},
+    states::StateNum,
+    world::{Thinker, ThinkerId, World},
+};
+use crate::wad::map::{LineDef, LinedefFlags, SectorType, WadMap};
+
+/// Damage done by a crusher every 4 tics
+const CRUSH_DAMAGE: i32 = 10;
+
+/// Floor or ceiling of a sector
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Plane {
+    Floor,
+    Ceiling,
+}
+
+/// Outcome of a plane move, like vanilla result_e
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoveResult {
+    Ok,
+    /// A mobj is in the way
+    Crushed,
+    /// The destination is reached
+    PastDest,
+}
+
+/// Sector movement, light change or level exit started by a line special
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LineAction {
+    /// The door on the back of the line itself
+    ManualDoor(DoorKind),
+    Door(DoorKind),
+    /// A platform and the height raised by RaiseAndChange
+    Plat(PlatKind, f32),
+    StopPlat,
+    Floor(FloorKind),
+    Stairs(StairKind),
+    Donut,
+    Ceiling(CeilingKind),
+    CrushStop,
+    /// Raise the ceiling to the highest around and lower the floor to the lowest
+    CeilingAndFloor,
+    /// Set the light of the tagged sectors, 0 takes the brightest light around
+    LightTurnOn(i16),
+    /// Make the tagged sectors blink slowly
+    StartLightStrobing,
+    /// Set the tagged sectors to the darkest light around
+    TurnTagLightsOff,
+    ExitLevel,
+    SecretExitLevel,
+}
+
+/// Sector movement, light change or exit of a line special, like the mover, light and exit
+/// cases of vanilla P_CrossSpecialLine, P_UseSpecialLine and P_ShootSpecialLine
+pub fn line_action(special: i16) -> Option<LineAction> {
+    use LineAction::*;
+    let action = match special {
+        1 | 26 | 27 | 28 => ManualDoor(DoorKind::Normal),
+        31..=34 => ManualDoor(DoorKind::Open),
+        117 => ManualDoor(DoorKind::BlazeRaise),
+        118 => ManualDoor(DoorKind::BlazeOpen),
+
+        4 | 29 | 63 | 90 => Door(DoorKind::Normal),
+        2 | 46 | 61 | 86 | 103 => Door(DoorKind::Open),
+        3 | 42 | 50 | 75 => Door(DoorKind::Close),
+        16 | 76 => Door(DoorKind::Close30ThenOpen),
+        105 | 108 | 111 | 114 => Door(DoorKind::BlazeRaise),
+        99 | 106 | 109 | 112 | 115 | 133..=137 => Door(DoorKind::BlazeOpen),
+        107 | 110 | 113 | 116 => Door(DoorKind::BlazeClose),
+
+        10 | 21 | 62 | 88 => Plat(PlatKind::DownWaitUpStay, 0.0),
+        120..=123 => Plat(PlatKind::BlazeDwus, 0.0),
+        53 | 87 => Plat(PlatKind::PerpetualRaise, 0.0),
+        20 | 22 | 47 | 68 | 95 => Plat(PlatKind::RaiseToNearestAndChange, 0.0),
+        15 | 66 => Plat(PlatKind::RaiseAndChange, 24.0),
+        14 | 67 => Plat(PlatKind::RaiseAndChange, 32.0),
+        54 | 89 => StopPlat,
+
+        5 | 24 | 64 | 91 | 101 => Floor(FloorKind::RaiseFloor),
+        55 | 56 | 65 | 94 => Floor(FloorKind::RaiseFloorCrush),
+        18 | 69 | 119 | 128 => Floor(FloorKind::RaiseFloorToNearest),
+        129..=132 => Floor(FloorKind::RaiseFloorTurbo),
+        58 | 92 => Floor(FloorKind::RaiseFloor24),
+        59 | 93 => Floor(FloorKind::RaiseFloor24AndChange),
+        30 | 96 => Floor(FloorKind::RaiseToTexture),
+        140 => Floor(FloorKind::RaiseFloor512),
+        19 | 45 | 83 | 102 => Floor(FloorKind::LowerFloor),
+        23 | 38 | 60 | 82 => Floor(FloorKind::LowerFloorToLowest),
+        36 | 70 | 71 | 98 => Floor(FloorKind::TurboLower),
+        37 | 84 => Floor(FloorKind::LowerAndChange),
+        7 | 8 => Stairs(StairKind::Build8),
+        100 | 127 => Stairs(StairKind::Turbo16),
+        9 => Donut,
+
+        6 | 77 => Ceiling(CeilingKind::FastCrushAndRaise),
+        25 | 49 | 73 => Ceiling(CeilingKind::CrushAndRaise),
+        141 => Ceiling(CeilingKind::SilentCrushAndRaise),
+        44 | 72 => Ceiling(CeilingKind::LowerAndCrush),
+        41 | 43 => Ceiling(CeilingKind::LowerToFloor),
+        57 | 74 => CrushStop,
+        40 => CeilingAndFloor,
+
+        12 | 80 => LightTurnOn(0),
+        13 | 81 | 138 => LightTurnOn(255),
+        35 | 79 | 139 => LightTurnOn(35),
+        17 => StartLightStrobing,
+        104 => TurnTagLightsOff,
+
+        11 | 52 => ExitLevel,
+        51 | 124 => SecretExitLevel,
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// Start the sector movement, light change or exit of a line, returns true when something
+/// started or stopped
+pub fn start_line_action(
+    map: &mut WadMap,
+    world: &mut World,
+    line: usize,
+    action: LineAction,
+    by_player: bool,
+) -> bool {
+    let line = map.linedefs[line];
+    match action {
+        LineAction::ManualDoor(kind) => vertical_door(map, world, &line, kind, by_player),
+        LineAction::Door(kind) => do_door(map, world, &line, kind),
+        LineAction::Plat(kind, amount) => do_plat(map, world, &line, kind, amount),
+        LineAction::StopPlat => {
+            stop_plat(world, &line);
+            true
+        }
+        LineAction::Floor(kind) => do_floor(map, world, &line, kind),
+        LineAction::Stairs(kind) => build_stairs(map, world, &line, kind),
+        LineAction::Donut => do_donut(map, world, &line),
+        LineAction::Ceiling(kind) => do_ceiling(map, world, &line, kind),
+        LineAction::CrushStop => crush_stop(world, &line),
+        LineAction::CeilingAndFloor => {
+            // The floor does not move when the ceiling started first, like vanilla
+            let ceiling = do_ceiling(map, world, &line, CeilingKind::RaiseToHighest);
+            let floor = do_floor(map, world, &line, FloorKind::LowerFloorToLowest);
+            ceiling || floor
+        }
+        LineAction::LightTurnOn(bright) => {
+            light_turn_on(map, &line, bright);
+            true
+        }
+        LineAction::StartLightStrobing => {
+            start_light_strobing(map, world, &line);
+            true
+        }
+        LineAction::TurnTagLightsOff => {
+            turn_tag_lights_off(map, &line);
+            true
+        }
+        LineAction::ExitLevel => {
+            world.exit_level = true;
+            true
+        }
+        LineAction::SecretExitLevel => {
+            world.exit_level = true;
+            world.secret_exit = true;
+            true
+        }
+    }
+}
+
+/// Sectors with a tag, like vanilla P_FindSectorFromLineTag
+pub fn tagged_sectors(map: &WadMap, tag: i16) -> Vec<usize> {
+    map.sectors
+        .iter()
+        .enumerate()
+        .filter(|(_, sector)| sector.tag == tag)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Sector on the front side of a line
+pub fn front_sector(map: &WadMap, line: &LineDef) -> usize {
+    map.sidedefs[line.front_sidedef as usize].sector as usize
+}
+
+/// Sector on the back side of a line, None for a wall
+pub fn back_sector(map: &WadMap, line: &LineDef) -> Option<usize> {
+    (line.back_sidedef != -1).then(|| map.sidedefs[line.back_sidedef as usize].sector as usize)
+}
+
+/// Sector on the other side of a line of a sector, like vanilla getNextSector
+pub fn next_sector(map: &WadMap, line: &LineDef, sector: usize) -> Option<usize> {
+    if (line.flags & LinedefFlags::TWO_SIDED) == LinedefFlags::NONE {
+        return None;
+    }
+    let front = front_sector(map, line);
+    if front == sector {
+        back_sector(map, line)
+    } else {
+        Some(front)
+    }
+}
+
+/// Sectors sharing a two-sided line with a sector
+pub fn neighbours(map: &WadMap, sector: usize) -> impl Iterator<Item = usize> + '_ {
+    map.sector_lines[sector]
+        .iter()
+        .filter_map(move |&line| next_sector(map, &map.linedefs[line], sector))
+}
+
+/// Lowest floor around a sector, its own included, like vanilla P_FindLowestFloorSurrounding
+pub fn lowest_floor_surrounding(map: &WadMap, sector: usize) -> f32 {
+    neighbours(map, sector)
+        .map(|other| map.sectors[other].floor)
+        .fold(map.sectors[sector].floor, f32::min)
+}
+
+/// Highest floor around a sector, like vanilla P_FindHighestFloorSurrounding
+pub fn highest_floor_surrounding(map: &WadMap, sector: usize) -> f32 {
+    neighbours(map, sector)
+        .map(|other| map.sectors[other].floor)
+        .fold(-500.0, f32::max)
+}
+
+/// Lowest floor around a sector above a height, the height when there is none,
+/// like vanilla P_FindNextHighestFloor
+pub fn next_highest_floor(map: &WadMap, sector: usize, height: f32) -> f32 {
+    neighbours(map, sector)
+        .map(|other| map.sectors[other].floor)
+        .filter(|&floor| floor > height)
+        .reduce(f32::min)
+        .unwrap_or(height)
+}
+
+/// Lowest ceiling around a sector, like vanilla P_FindLowestCeilingSurrounding
+pub fn lowest_ceiling_surrounding(map: &WadMap, sector: usize) -> f32 {
+    neighbours(map, sector)
+        .map(|other| map.sectors[other].ceiling)
+        .fold(f32::MAX, f32::min)
+}
+
+/// Highest ceiling around a sector, like vanilla P_FindHighestCeilingSurrounding
+pub fn highest_ceiling_surrounding(map: &WadMap, sector: usize) -> f32 {
+    neighbours(map, sector)
+        .map(|other| map.sectors[other].ceiling)
+        .fold(0.0, f32::max)
+}
+
+/// Darkest light around a sector, at most a light level, like vanilla P_FindMinSurroundingLight
+pub fn min_surrounding_light(map: &WadMap, sector: usize, max: i16) -> i16 {
+    neighbours(map, sector)
+        .map(|other| map.sectors[other].lighting)
+        .fold(max, i16::min)
+}
+
+fn plane_height(map: &mut WadMap, sector: usize, plane: Plane) -> &mut f32 {
+    let sector = &mut map.sectors[sector];
+    match plane {
+        Plane::Floor => &mut sector.floor,
+        Plane::Ceiling => &mut sector.ceiling,
+    }
+}
+
+/// Move a floor or a ceiling toward a height, 1 is up and -1 down, like vanilla T_MovePlane
+///
+/// The plane goes back when a mobj does not fit, unless it crushes it
+#[allow(clippy::too_many_arguments)]
+pub fn move_plane(
+    map: &mut WadMap,
+    world: &mut World,
+    sector: usize,
+    speed: f32,
+    dest: f32,
+    crush: bool,
+    plane: Plane,
+    direction: i32,
+) -> MoveResult {
+    let last = *plane_height(map, sector, plane);
+    let past_dest = if direction < 0 {
+        last - speed < dest
+    } else {
+        last + speed > dest
+    };
+
+    if past_dest {
+        *plane_height(map, sector, plane) = dest;
+        if change_sector(map, world, sector, crush) {
+            *plane_height(map, sector, plane) = last;
+            change_sector(map, world, sector, crush);
+        }
+        return MoveResult::PastDest;
+    }
+
+    *plane_height(map, sector, plane) = last + speed * direction as f32;
+    if !change_sector(map, world, sector, crush) {
+        return MoveResult::Ok;
+    }
+    // A rising ceiling never stops, a crusher keeps going down on what is below
+    let squeezing = (plane == Plane::Floor) == (direction > 0);
+    if plane == Plane::Ceiling && !squeezing {
+        return MoveResult::Ok;
+    }
+    if squeezing && crush {
+        return MoveResult::Crushed;
+    }
+    *plane_height(map, sector, plane) = last;
+    change_sector(map, world, sector, crush);
+    MoveResult::Crushed
+}
+
+/// Fit the mobjs touching a sector after it moved, like vanilla P_ChangeSector
+///
+/// Returns true when one does not fit anymore, crushers hurt it every 4 tics
+pub fn change_sector(map: &mut WadMap, world: &mut World, sector: usize, crush: bool) -> bool {
+    let (left, bottom, right, top) = map.sector_box(sector);
+    let touching = world
+        .mobjs()
+        .filter(|(_, mo)| {
+            mo.x + mo.radius >= left
+                && mo.x - mo.radius <= right
+                && mo.y + mo.radius >= bottom
+                && mo.y - mo.radius <= top
+        })
+        .map(|(id, _)| id)
+        .collect::<Vec<ThinkerId>>();
+
+    let hurt = crush && world.tic.is_multiple_of(4);
+    let mut no_fit = false;
+    for id in touching {
+        let Some(mo) = world.mobj_mut(id) else {
+            continue;
+        };
+        if height_clip(map, mo) {
+            continue;
+        }
+
+        // Crunch bodies to giblets
+        if mo.health <= 0 {
+            mo.set_state(StateNum::Gibs);
+            mo.flags.remove(MobjFlags::SOLID);
+            mo.height = 0.0;
+            mo.radius = 0.0;
+            continue;
+        }
+        // Crunch dropped items
+        if (mo.flags & MobjFlags::DROPPED) != MobjFlags::NONE {
+            world.remove(id);
+            continue;
+        }
+        // Decorations and items do not block
+        if (mo.flags & MobjFlags::SHOOTABLE) == MobjFlags::NONE {
+            continue;
+        }
+
+        no_fit = true;
+        if hurt {
+            // Blood spurting from the middle of what is left
+            let spot = world.with_mobj(id, |world, mo| {
+                damage_mobj(map, world, mo, None, None, CRUSH_DAMAGE);
+                (mo.x, mo.y, mo.z + mo.height / 2.0)
+            });
+            let Some((x, y, z)) = spot else {
+                continue;
+            };
+            let mut blood = Mobj::spawn(map, world, MobjType::Blood, x, y);
+            blood.z = z;
+            blood.momx = world.random.sub_random() as f32 / 16.0;
+            blood.momy = world.random.sub_random() as f32 / 16.0;
+            world.add(Thinker::Mobj(blood));
+        }
+    }
+    no_fit
+}
+
+/// Start the lights and movers of the sector types acting on their own and count the secrets,
+/// like vanilla P_SpawnSpecials
+pub fn spawn_specials(map: &mut WadMap, world: &mut World) {
+    for sector in 0..map.sectors.len() {
+        match map.sectors[sector].stype {
+            SectorType::BlinkRandom => spawn_light_flash(map, world, sector),
+            SectorType::BlinkHalf => spawn_strobe_flash(map, world, sector, FASTDARK, false),
+            SectorType::BlinkFull => spawn_strobe_flash(map, world, sector, SLOWDARK, false),
+            SectorType::DamageBlink => {
+                spawn_strobe_flash(map, world, sector, FASTDARK, false);
+                // Still hurts the player
+                map.sectors[sector].stype = SectorType::DamageBlink;
+            }
+            SectorType::Oscillate => spawn_glowing_light(map, world, sector),
+            SectorType::Secret => world.total_secrets += 1,
+            SectorType::CeilDoorOpen => spawn_door_close_in_30(map, world, sector),
+            SectorType::SyncBlinkHalf => spawn_strobe_flash(map, world, sector, SLOWDARK, true),
+            SectorType::SyncBlinkFull => spawn_strobe_flash(map, world, sector, FASTDARK, true),
+            SectorType::CeilDoorClose => spawn_door_raise_in_5_mins(map, world, sector),
+            SectorType::Flicker => spawn_fire_flicker(map, world, sector),
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,1137 @@
+//! Vanilla sprite and state tables from info.c
+
+/// Frame bit drawing the sprite at full brightness
+pub const FULLBRIGHT: u32 = 0x8000;
+/// Bits of the frame number without the brightness
+pub const FRAME_MASK: u32 = 0x7fff;
+
+macro_rules! sprites {
+    ($($name:ident),* $(,)?) => {
+        /// Sprites in vanilla order, named after their 4 letters lump prefix
+        #[allow(clippy::upper_case_acronyms)]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        pub enum SpriteNum {
+            $($name),*
+        }
+
+        impl SpriteNum {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(SpriteNum::$name => stringify!($name)),*
+                }
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+sprites! {
+    TROO, SHTG, PUNG, PISG, PISF, SHTF, SHT2, CHGG, CHGF, MISG, MISF, SAWG, PLSG, PLSF, BFGG,
+    BFGF, BLUD, PUFF, BAL1, BAL2, PLSS, PLSE, MISL, BFS1, BFE1, BFE2, TFOG, IFOG, PLAY, POSS,
+    SPOS, VILE, FIRE, FATB, FBXP, SKEL, MANF, FATT, CPOS, SARG, HEAD, BAL7, BOSS, BOS2, SKUL,
+    SPID, BSPI, APLS, APBX, CYBR, PAIN, SSWV, KEEN, BBRN, BOSF, ARM1, ARM2, BAR1, BEXP, FCAN,
+    BON1, BON2, BKEY, RKEY, YKEY, BSKU, RSKU, YSKU, STIM, MEDI, SOUL, PINV, PSTR, PINS, MEGA,
+    SUIT, PMAP, PVIS, CLIP, AMMO, ROCK, BROK, CELL, CELP, SHEL, SBOX, BPAK, BFUG, MGUN, CSAW,
+    LAUN, PLAS, SHOT, SGN2, COLU, SMT2, GOR1, POL2, POL5, POL4, POL3, POL1, POL6, GOR2, GOR3,
+    GOR4, GOR5, SMIT, COL1, COL2, COL3, COL4, CAND, CBRA, COL6, TRE1, TRE2, ELEC, CEYE, FSKU,
+    COL5, TBLU, TGRN, TRED, SMBT, SMGT, SMRT, HDB1, HDB2, HDB3, HDB4, HDB5, HDB6, POB1, POB2,
+    BRS1, TLMP, TLP2,
+}
+
+/// Code run when a state is entered, named after the vanilla A_ functions
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ActionFn {
+    Light0,
+    WeaponReady,
+    Lower,
+    Raise,
+    Punch,
+    ReFire,
+    FirePistol,
+    Light1,
+    FireShotgun,
+    Light2,
+    FireShotgun2,
+    CheckReload,
+    OpenShotgun2,
+    LoadShotgun2,
+    CloseShotgun2,
+    FireCGun,
+    GunFlash,
+    FireMissile,
+    Saw,
+    FirePlasma,
+    BfgSound,
+    FireBfg,
+    BfgSpray,
+    Explode,
+    Pain,
+    PlayerScream,
+    Fall,
+    XScream,
+    Look,
+    Chase,
+    FaceTarget,
+    PosAttack,
+    Scream,
+    SPosAttack,
+    VileChase,
+    VileStart,
+    VileTarget,
+    VileAttack,
+    StartFire,
+    Fire,
+    FireCrackle,
+    Tracer,
+    SkelWhoosh,
+    SkelFist,
+    SkelMissile,
+    FatRaise,
+    FatAttack1,
+    FatAttack2,
+    FatAttack3,
+    BossDeath,
+    CPosAttack,
+    CPosRefire,
+    TroopAttack,
+    SargAttack,
+    HeadAttack,
+    BruisAttack,
+    SkullAttack,
+    Metal,
+    SpidRefire,
+    BabyMetal,
+    BspiAttack,
+    Hoof,
+    CyberAttack,
+    PainAttack,
+    PainDie,
+    KeenDie,
+    BrainPain,
+    BrainScream,
+    BrainDie,
+    BrainAwake,
+    BrainSpit,
+    SpawnSound,
+    SpawnFly,
+    BrainExplode,
+}
+
+/// One step of an animation, like vanilla state_t
+#[allow(dead_code)]
+pub struct State {
+    pub sprite: SpriteNum,
+    /// Frame index, 0 is 'A', with the FULLBRIGHT bit
+    pub frame: u32,
+    /// Duration in tics, -1 stays forever
+    pub tics: i32,
+    pub action: Option<ActionFn>,
+    pub next: StateNum,
+}
+
+macro_rules! states {
+    ($($name:ident = ($sprite:ident, $frame:expr, $tics:expr, $action:expr, $next:ident)),* $(,)?) => {
+        /// Every state in vanilla order
+        #[allow(dead_code)]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        pub enum StateNum {
+            $($name),*
+        }
+
+        const STATES: &[State] = {
+            use ActionFn::*;
+            const B: u32 = FULLBRIGHT;
+            &[$(State {
+                sprite: SpriteNum::$sprite,
+                frame: $frame,
+                tics: $tics,
+                action: $action,
+                next: StateNum::$next,
+            }),*]
+        };
+
+        const STATE_NUMS: &[StateNum] = &[$(StateNum::$name),*];
+    };
+}
+
+impl StateNum {
+    pub fn info(&self) -> &'static State {
+        &STATES[*self as usize]
+    }
+
+    /// State at a position of the vanilla table, None past its end
+    pub fn from_index(index: usize) -> Option<StateNum> {
+        STATE_NUMS.get(index).copied()
+    }
+}
+
+#[rustfmt::skip]
+states! {
+    Null = (TROO, 0, -1, None, Null),
+    LightDone = (SHTG, 4, 0, Some(Light0), Null),
+    Punch = (PUNG, 0, 1, Some(WeaponReady), Punch),
+    PunchDown = (PUNG, 0, 1, Some(Lower), PunchDown),
+    PunchUp = (PUNG, 0, 1, Some(Raise), PunchUp),
+    Punch1 = (PUNG, 1, 4, None, Punch2),
+    Punch2 = (PUNG, 2, 4, Some(Punch), Punch3),
+    Punch3 = (PUNG, 3, 5, None, Punch4),
+    Punch4 = (PUNG, 2, 4, None, Punch5),
+    Punch5 = (PUNG, 1, 5, Some(ReFire), Punch),
+    Pistol = (PISG, 0, 1, Some(WeaponReady), Pistol),
+    PistolDown = (PISG, 0, 1, Some(Lower), PistolDown),
+    PistolUp = (PISG, 0, 1, Some(Raise), PistolUp),
+    Pistol1 = (PISG, 0, 4, None, Pistol2),
+    Pistol2 = (PISG, 1, 6, Some(FirePistol), Pistol3),
+    Pistol3 = (PISG, 2, 4, None, Pistol4),
+    Pistol4 = (PISG, 1, 5, Some(ReFire), Pistol),
+    PistolFlash = (PISF, B, 7, Some(Light1), LightDone),
+    Sgun = (SHTG, 0, 1, Some(WeaponReady), Sgun),
+    SgunDown = (SHTG, 0, 1, Some(Lower), SgunDown),
+    SgunUp = (SHTG, 0, 1, Some(Raise), SgunUp),
+    Sgun1 = (SHTG, 0, 3, None, Sgun2),
+    Sgun2 = (SHTG, 0, 7, Some(FireShotgun), Sgun3),
+    Sgun3 = (SHTG, 1, 5, None, Sgun4),
+    Sgun4 = (SHTG, 2, 5, None, Sgun5),
+    Sgun5 = (SHTG, 3, 4, None, Sgun6),
+    Sgun6 = (SHTG, 2, 5, None, Sgun7),
+    Sgun7 = (SHTG, 1, 5, None, Sgun8),
+    Sgun8 = (SHTG, 0, 3, None, Sgun9),
+    Sgun9 = (SHTG, 0, 7, Some(ReFire), Sgun),
+    SgunFlash1 = (SHTF, B, 4, Some(Light1), SgunFlash2),
+    SgunFlash2 = (SHTF, B | 1, 3, Some(Light2), LightDone),
+    Dsgun = (SHT2, 0, 1, Some(WeaponReady), Dsgun),
+    DsgunDown = (SHT2, 0, 1, Some(Lower), DsgunDown),
+    DsgunUp = (SHT2, 0, 1, Some(Raise), DsgunUp),
+    Dsgun1 = (SHT2, 0, 3, None, Dsgun2),
+    Dsgun2 = (SHT2, 0, 7, Some(FireShotgun2), Dsgun3),
+    Dsgun3 = (SHT2, 1, 7, None, Dsgun4),
+    Dsgun4 = (SHT2, 2, 7, Some(CheckReload), Dsgun5),
+    Dsgun5 = (SHT2, 3, 7, Some(OpenShotgun2), Dsgun6),
+    Dsgun6 = (SHT2, 4, 7, None, Dsgun7),
+    Dsgun7 = (SHT2, 5, 7, Some(LoadShotgun2), Dsgun8),
+    Dsgun8 = (SHT2, 6, 6, None, Dsgun9),
+    Dsgun9 = (SHT2, 7, 6, Some(CloseShotgun2), Dsgun10),
+    Dsgun10 = (SHT2, 0, 5, Some(ReFire), Dsgun),
+    Dsnr1 = (SHT2, 1, 7, None, Dsnr2),
+    Dsnr2 = (SHT2, 0, 3, None, DsgunDown),
+    DsgunFlash1 = (SHT2, B | 8, 5, Some(Light1), DsgunFlash2),
+    DsgunFlash2 = (SHT2, B | 9, 4, Some(Light2), LightDone),
+    Chain = (CHGG, 0, 1, Some(WeaponReady), Chain),
+    ChainDown = (CHGG, 0, 1, Some(Lower), ChainDown),
+    ChainUp = (CHGG, 0, 1, Some(Raise), ChainUp),
+    Chain1 = (CHGG, 0, 4, Some(FireCGun), Chain2),
+    Chain2 = (CHGG, 1, 4, Some(FireCGun), Chain3),
+    Chain3 = (CHGG, 1, 0, Some(ReFire), Chain),
+    ChainFlash1 = (CHGF, B, 5, Some(Light1), LightDone),
+    ChainFlash2 = (CHGF, B | 1, 5, Some(Light2), LightDone),
+    Missile = (MISG, 0, 1, Some(WeaponReady), Missile),
+    MissileDown = (MISG, 0, 1, Some(Lower), MissileDown),
+    MissileUp = (MISG, 0, 1, Some(Raise), MissileUp),
+    Missile1 = (MISG, 1, 8, Some(GunFlash), Missile2),
+    Missile2 = (MISG, 1, 12, Some(FireMissile), Missile3),
+    Missile3 = (MISG, 1, 0, Some(ReFire), Missile),
+    MissileFlash1 = (MISF, B, 3, Some(Light1), MissileFlash2),
+    MissileFlash2 = (MISF, B | 1, 4, None, MissileFlash3),
+    MissileFlash3 = (MISF, B | 2, 4, Some(Light2), MissileFlash4),
+    MissileFlash4 = (MISF, B | 3, 4, Some(Light2), LightDone),
+    Saw = (SAWG, 2, 4, Some(WeaponReady), SawB),
+    SawB = (SAWG, 3, 4, Some(WeaponReady), Saw),
+    SawDown = (SAWG, 2, 1, Some(Lower), SawDown),
+    SawUp = (SAWG, 2, 1, Some(Raise), SawUp),
+    Saw1 = (SAWG, 0, 4, Some(Saw), Saw2),
+    Saw2 = (SAWG, 1, 4, Some(Saw), Saw3),
+    Saw3 = (SAWG, 1, 0, Some(ReFire), Saw),
+    Plasma = (PLSG, 0, 1, Some(WeaponReady), Plasma),
+    PlasmaDown = (PLSG, 0, 1, Some(Lower), PlasmaDown),
+    PlasmaUp = (PLSG, 0, 1, Some(Raise), PlasmaUp),
+    Plasma1 = (PLSG, 0, 3, Some(FirePlasma), Plasma2),
+    Plasma2 = (PLSG, 1, 20, Some(ReFire), Plasma),
+    PlasmaFlash1 = (PLSF, B, 4, Some(Light1), LightDone),
+    PlasmaFlash2 = (PLSF, B | 1, 4, Some(Light1), LightDone),
+    Bfg = (BFGG, 0, 1, Some(WeaponReady), Bfg),
+    BfgDown = (BFGG, 0, 1, Some(Lower), BfgDown),
+    BfgUp = (BFGG, 0, 1, Some(Raise), BfgUp),
+    Bfg1 = (BFGG, 0, 20, Some(BfgSound), Bfg2),
+    Bfg2 = (BFGG, 1, 10, Some(GunFlash), Bfg3),
+    Bfg3 = (BFGG, 1, 10, Some(FireBfg), Bfg4),
+    Bfg4 = (BFGG, 1, 20, Some(ReFire), Bfg),
+    BfgFlash1 = (BFGF, B, 11, Some(Light1), BfgFlash2),
+    BfgFlash2 = (BFGF, B | 1, 6, Some(Light2), LightDone),
+    Blood1 = (BLUD, 2, 8, None, Blood2),
+    Blood2 = (BLUD, 1, 8, None, Blood3),
+    Blood3 = (BLUD, 0, 8, None, Null),
+    Puff1 = (PUFF, B, 4, None, Puff2),
+    Puff2 = (PUFF, 1, 4, None, Puff3),
+    Puff3 = (PUFF, 2, 4, None, Puff4),
+    Puff4 = (PUFF, 3, 4, None, Null),
+    Tball1 = (BAL1, B, 4, None, Tball2),
+    Tball2 = (BAL1, B | 1, 4, None, Tball1),
+    TballX1 = (BAL1, B | 2, 6, None, TballX2),
+    TballX2 = (BAL1, B | 3, 6, None, TballX3),
+    TballX3 = (BAL1, B | 4, 6, None, Null),
+    Rball1 = (BAL2, B, 4, None, Rball2),
+    Rball2 = (BAL2, B | 1, 4, None, Rball1),
+    RballX1 = (BAL2, B | 2, 6, None, RballX2),
+    RballX2 = (BAL2, B | 3, 6, None, RballX3),
+    RballX3 = (BAL2, B | 4, 6, None, Null),
+    PlasBall = (PLSS, B, 6, None, PlasBall2),
+    PlasBall2 = (PLSS, B | 1, 6, None, PlasBall),
+    PlasExp = (PLSE, B, 4, None, PlasExp2),
+    PlasExp2 = (PLSE, B | 1, 4, None, PlasExp3),
+    PlasExp3 = (PLSE, B | 2, 4, None, PlasExp4),
+    PlasExp4 = (PLSE, B | 3, 4, None, PlasExp5),
+    PlasExp5 = (PLSE, B | 4, 4, None, Null),
+    Rocket = (MISL, B, 1, None, Rocket),
+    BfgShot = (BFS1, B, 4, None, BfgShot2),
+    BfgShot2 = (BFS1, B | 1, 4, None, BfgShot),
+    BfgLand = (BFE1, B, 8, None, BfgLand2),
+    BfgLand2 = (BFE1, B | 1, 8, None, BfgLand3),
+    BfgLand3 = (BFE1, B | 2, 8, Some(BfgSpray), BfgLand4),
+    BfgLand4 = (BFE1, B | 3, 8, None, BfgLand5),
+    BfgLand5 = (BFE1, B | 4, 8, None, BfgLand6),
+    BfgLand6 = (BFE1, B | 5, 8, None, Null),
+    BfgExp = (BFE2, B, 8, None, BfgExp2),
+    BfgExp2 = (BFE2, B | 1, 8, None, BfgExp3),
+    BfgExp3 = (BFE2, B | 2, 8, None, BfgExp4),
+    BfgExp4 = (BFE2, B | 3, 8, None, Null),
+    Explode1 = (MISL, B | 1, 8, Some(Explode), Explode2),
+    Explode2 = (MISL, B | 2, 6, None, Explode3),
+    Explode3 = (MISL, B | 3, 4, None, Null),
+    Tfog = (TFOG, B, 6, None, Tfog01),
+    Tfog01 = (TFOG, B | 1, 6, None, Tfog02),
+    Tfog02 = (TFOG, B, 6, None, Tfog2),
+    Tfog2 = (TFOG, B | 1, 6, None, Tfog3),
+    Tfog3 = (TFOG, B | 2, 6, None, Tfog4),
+    Tfog4 = (TFOG, B | 3, 6, None, Tfog5),
+    Tfog5 = (TFOG, B | 4, 6, None, Tfog6),
+    Tfog6 = (TFOG, B | 5, 6, None, Tfog7),
+    Tfog7 = (TFOG, B | 6, 6, None, Tfog8),
+    Tfog8 = (TFOG, B | 7, 6, None, Tfog9),
+    Tfog9 = (TFOG, B | 8, 6, None, Tfog10),
+    Tfog10 = (TFOG, B | 9, 6, None, Null),
+    Ifog = (IFOG, B, 6, None, Ifog01),
+    Ifog01 = (IFOG, B | 1, 6, None, Ifog02),
+    Ifog02 = (IFOG, B, 6, None, Ifog2),
+    Ifog2 = (IFOG, B | 1, 6, None, Ifog3),
+    Ifog3 = (IFOG, B | 2, 6, None, Ifog4),
+    Ifog4 = (IFOG, B | 3, 6, None, Ifog5),
+    Ifog5 = (IFOG, B | 4, 6, None, Null),
+    Play = (PLAY, 0, -1, None, Null),
+    PlayRun1 = (PLAY, 0, 4, None, PlayRun2),
+    PlayRun2 = (PLAY, 1, 4, None, PlayRun3),
+    PlayRun3 = (PLAY, 2, 4, None, PlayRun4),
+    PlayRun4 = (PLAY, 3, 4, None, PlayRun1),
+    PlayAtk1 = (PLAY, 4, 12, None, Play),
+    PlayAtk2 = (PLAY, B | 5, 6, None, PlayAtk1),
+    PlayPain = (PLAY, 6, 4, None, PlayPain2),
+    PlayPain2 = (PLAY, 6, 4, Some(Pain), Play),
+    PlayDie1 = (PLAY, 7, 10, None, PlayDie2),
+    PlayDie2 = (PLAY, 8, 10, Some(PlayerScream), PlayDie3),
+    PlayDie3 = (PLAY, 9, 10, Some(Fall), PlayDie4),
+    PlayDie4 = (PLAY, 10, 10, None, PlayDie5),
+    PlayDie5 = (PLAY, 11, 10, None, PlayDie6),
+    PlayDie6 = (PLAY, 12, 10, None, PlayDie7),
+    PlayDie7 = (PLAY, 13, -1, None, Null),
+    PlayXdie1 = (PLAY, 14, 5, None, PlayXdie2),
+    PlayXdie2 = (PLAY, 15, 5, Some(XScream), PlayXdie3),
+    PlayXdie3 = (PLAY, 16, 5, Some(Fall), PlayXdie4),
+    PlayXdie4 = (PLAY, 17, 5, None, PlayXdie5),
+    PlayXdie5 = (PLAY, 18, 5, None, PlayXdie6),
+    PlayXdie6 = (PLAY, 19, 5, None, PlayXdie7),
+    PlayXdie7 = (PLAY, 20, 5, None, PlayXdie8),
+    PlayXdie8 = (PLAY, 21, 5, None, PlayXdie9),
+    PlayXdie9 = (PLAY, 22, -1, None, Null),
+    PossStnd = (POSS, 0, 10, Some(Look), PossStnd2),
+    PossStnd2 = (POSS, 1, 10, Some(Look), PossStnd),
+    PossRun1 = (POSS, 0, 4, Some(Chase), PossRun2),
+    PossRun2 = (POSS, 0, 4, Some(Chase), PossRun3),
+    PossRun3 = (POSS, 1, 4, Some(Chase), PossRun4),
+    PossRun4 = (POSS, 1, 4, Some(Chase), PossRun5),
+    PossRun5 = (POSS, 2, 4, Some(Chase), PossRun6),
+    PossRun6 = (POSS, 2, 4, Some(Chase), PossRun7),
+    PossRun7 = (POSS, 3, 4, Some(Chase), PossRun8),
+    PossRun8 = (POSS, 3, 4, Some(Chase), PossRun1),
+    PossAtk1 = (POSS, 4, 10, Some(FaceTarget), PossAtk2),
+    PossAtk2 = (POSS, 5, 8, Some(PosAttack), PossAtk3),
+    PossAtk3 = (POSS, 4, 8, None, PossRun1),
+    PossPain = (POSS, 6, 3, None, PossPain2),
+    PossPain2 = (POSS, 6, 3, Some(Pain), PossRun1),
+    PossDie1 = (POSS, 7, 5, None, PossDie2),
+    PossDie2 = (POSS, 8, 5, Some(Scream), PossDie3),
+    PossDie3 = (POSS, 9, 5, Some(Fall), PossDie4),
+    PossDie4 = (POSS, 10, 5, None, PossDie5),
+    PossDie5 = (POSS, 11, -1, None, Null),
+    PossXdie1 = (POSS, 12, 5, None, PossXdie2),
+    PossXdie2 = (POSS, 13, 5, Some(XScream), PossXdie3),
+    PossXdie3 = (POSS, 14, 5, Some(Fall), PossXdie4),
+    PossXdie4 = (POSS, 15, 5, None, PossXdie5),
+    PossXdie5 = (POSS, 16, 5, None, PossXdie6),
+    PossXdie6 = (POSS, 17, 5, None, PossXdie7),
+    PossXdie7 = (POSS, 18, 5, None, PossXdie8),
+    PossXdie8 = (POSS, 19, 5, None, PossXdie9),
+    PossXdie9 = (POSS, 20, -1, None, Null),
+    PossRaise1 = (POSS, 10, 5, None, PossRaise2),
+    PossRaise2 = (POSS, 9, 5, None, PossRaise3),
+    PossRaise3 = (POSS, 8, 5, None, PossRaise4),
+    PossRaise4 = (POSS, 7, 5, None, PossRun1),
+    SposStnd = (SPOS, 0, 10, Some(Look), SposStnd2),
+    SposStnd2 = (SPOS, 1, 10, Some(Look), SposStnd),
+    SposRun1 = (SPOS, 0, 3, Some(Chase), SposRun2),
+    SposRun2 = (SPOS, 0, 3, Some(Chase), SposRun3),
+    SposRun3 = (SPOS, 1, 3, Some(Chase), SposRun4),
+    SposRun4 = (SPOS, 1, 3, Some(Chase), SposRun5),
+    SposRun5 = (SPOS, 2, 3, Some(Chase), SposRun6),
+    SposRun6 = (SPOS, 2, 3, Some(Chase), SposRun7),
+    SposRun7 = (SPOS, 3, 3, Some(Chase), SposRun8),
+    SposRun8 = (SPOS, 3, 3, Some(Chase), SposRun1),
+    SposAtk1 = (SPOS, 4, 10, Some(FaceTarget), SposAtk2),
+    SposAtk2 = (SPOS, B | 5, 10, Some(SPosAttack), SposAtk3),
+    SposAtk3 = (SPOS, 4, 10, None, SposRun1),
+    SposPain = (SPOS, 6, 3, None, SposPain2),
+    SposPain2 = (SPOS, 6, 3, Some(Pain), SposRun1),
+    SposDie1 = (SPOS, 7, 5, None, SposDie2),
+    SposDie2 = (SPOS, 8, 5, Some(Scream), SposDie3),
+    SposDie3 = (SPOS, 9, 5, Some(Fall), SposDie4),
+    SposDie4 = (SPOS, 10, 5, None, SposDie5),
+    SposDie5 = (SPOS, 11, -1, None, Null),
+    SposXdie1 = (SPOS, 12, 5, None, SposXdie2),
+    SposXdie2 = (SPOS, 13, 5, Some(XScream), SposXdie3),
+    SposXdie3 = (SPOS, 14, 5, Some(Fall), SposXdie4),
+    SposXdie4 = (SPOS, 15, 5, None, SposXdie5),
+    SposXdie5 = (SPOS, 16, 5, None, SposXdie6),
+    SposXdie6 = (SPOS, 17, 5, None, SposXdie7),
+    SposXdie7 = (SPOS, 18, 5, None, SposXdie8),
+    SposXdie8 = (SPOS, 19, 5, None, SposXdie9),
+    SposXdie9 = (SPOS, 20, -1, None, Null),
+    SposRaise1 = (SPOS, 11, 5, None, SposRaise2),
+    SposRaise2 = (SPOS, 10, 5, None, SposRaise3),
+    SposRaise3 = (SPOS, 9, 5, None, SposRaise4),
+    SposRaise4 = (SPOS, 8, 5, None, SposRaise5),
+    SposRaise5 = (SPOS, 7, 5, None, SposRun1),
+    VileStnd = (VILE, 0, 10, Some(Look), VileStnd2),
+    VileStnd2 = (VILE, 1, 10, Some(Look), VileStnd),
+    VileRun1 = (VILE, 0, 2, Some(VileChase), VileRun2),
+    VileRun2 = (VILE, 0, 2, Some(VileChase), VileRun3),
+    VileRun3 = (VILE, 1, 2, Some(VileChase), VileRun4),
+    VileRun4 = (VILE, 1, 2, Some(VileChase), VileRun5),
+    VileRun5 = (VILE, 2, 2, Some(VileChase), VileRun6),
+    VileRun6 = (VILE, 2, 2, Some(VileChase), VileRun7),
+    VileRun7 = (VILE, 3, 2, Some(VileChase), VileRun8),
+    VileRun8 = (VILE, 3, 2, Some(VileChase), VileRun9),
+    VileRun9 = (VILE, 4, 2, Some(VileChase), VileRun10),
+    VileRun10 = (VILE, 4, 2, Some(VileChase), VileRun11),
+    VileRun11 = (VILE, 5, 2, Some(VileChase), VileRun12),
+    VileRun12 = (VILE, 5, 2, Some(VileChase), VileRun1),
+    VileAtk1 = (VILE, B | 6, 0, Some(VileStart), VileAtk2),
+    VileAtk2 = (VILE, B | 6, 10, Some(FaceTarget), VileAtk3),
+    VileAtk3 = (VILE, B | 7, 8, Some(VileTarget), VileAtk4),
+    VileAtk4 = (VILE, B | 8, 8, Some(FaceTarget), VileAtk5),
+    VileAtk5 = (VILE, B | 9, 8, Some(FaceTarget), VileAtk6),
+    VileAtk6 = (VILE, B | 10, 8, Some(FaceTarget), VileAtk7),
+    VileAtk7 = (VILE, B | 11, 8, Some(FaceTarget), VileAtk8),
+    VileAtk8 = (VILE, B | 12, 8, Some(FaceTarget), VileAtk9),
+    VileAtk9 = (VILE, B | 13, 8, Some(FaceTarget), VileAtk10),
+    VileAtk10 = (VILE, B | 14, 8, Some(VileAttack), VileAtk11),
+    VileAtk11 = (VILE, B | 15, 20, None, VileRun1),
+    VileHeal1 = (VILE, B | 26, 10, None, VileHeal2),
+    VileHeal2 = (VILE, B | 27, 10, None, VileHeal3),
+    VileHeal3 = (VILE, B | 28, 10, None, VileRun1),
+    VilePain = (VILE, 16, 5, None, VilePain2),
+    VilePain2 = (VILE, 16, 5, Some(Pain), VileRun1),
+    VileDie1 = (VILE, 16, 7, None, VileDie2),
+    VileDie2 = (VILE, 17, 7, Some(Scream), VileDie3),
+    VileDie3 = (VILE, 18, 7, Some(Fall), VileDie4),
+    VileDie4 = (VILE, 19, 7, None, VileDie5),
+    VileDie5 = (VILE, 20, 7, None, VileDie6),
+    VileDie6 = (VILE, 21, 7, None, VileDie7),
+    VileDie7 = (VILE, 22, 7, None, VileDie8),
+    VileDie8 = (VILE, 23, 5, None, VileDie9),
+    VileDie9 = (VILE, 24, 5, None, VileDie10),
+    VileDie10 = (VILE, 25, -1, None, Null),
+    Fire1 = (FIRE, B, 2, Some(StartFire), Fire2),
+    Fire2 = (FIRE, B | 1, 2, Some(Fire), Fire3),
+    Fire3 = (FIRE, B, 2, Some(Fire), Fire4),
+    Fire4 = (FIRE, B | 1, 2, Some(Fire), Fire5),
+    Fire5 = (FIRE, B | 2, 2, Some(FireCrackle), Fire6),
+    Fire6 = (FIRE, B | 1, 2, Some(Fire), Fire7),
+    Fire7 = (FIRE, B | 2, 2, Some(Fire), Fire8),
+    Fire8 = (FIRE, B | 1, 2, Some(Fire), Fire9),
+    Fire9 = (FIRE, B | 2, 2, Some(Fire), Fire10),
+    Fire10 = (FIRE, B | 3, 2, Some(Fire), Fire11),
+    Fire11 = (FIRE, B | 2, 2, Some(Fire), Fire12),
+    Fire12 = (FIRE, B | 3, 2, Some(Fire), Fire13),
+    Fire13 = (FIRE, B | 2, 2, Some(Fire), Fire14),
+    Fire14 = (FIRE, B | 3, 2, Some(Fire), Fire15),
+    Fire15 = (FIRE, B | 4, 2, Some(Fire), Fire16),
+    Fire16 = (FIRE, B | 3, 2, Some(Fire), Fire17),
+    Fire17 = (FIRE, B | 4, 2, Some(Fire), Fire18),
+    Fire18 = (FIRE, B | 3, 2, Some(Fire), Fire19),
+    Fire19 = (FIRE, B | 4, 2, Some(FireCrackle), Fire20),
+    Fire20 = (FIRE, B | 5, 2, Some(Fire), Fire21),
+    Fire21 = (FIRE, B | 4, 2, Some(Fire), Fire22),
+    Fire22 = (FIRE, B | 5, 2, Some(Fire), Fire23),
+    Fire23 = (FIRE, B | 4, 2, Some(Fire), Fire24),
+    Fire24 = (FIRE, B | 5, 2, Some(Fire), Fire25),
+    Fire25 = (FIRE, B | 6, 2, Some(Fire), Fire26),
+    Fire26 = (FIRE, B | 7, 2, Some(Fire), Fire27),
+    Fire27 = (FIRE, B | 6, 2, Some(Fire), Fire28),
+    Fire28 = (FIRE, B | 7, 2, Some(Fire), Fire29),
+    Fire29 = (FIRE, B | 6, 2, Some(Fire), Fire30),
+    Fire30 = (FIRE, B | 7, 2, Some(Fire), Null),
+    Smoke1 = (PUFF, 1, 4, None, Smoke2),
+    Smoke2 = (PUFF, 2, 4, None, Smoke3),
+    Smoke3 = (PUFF, 1, 4, None, Smoke4),
+    Smoke4 = (PUFF, 2, 4, None, Smoke5),
+    Smoke5 = (PUFF, 3, 4, None, Null),
+    Tracer = (FATB, B, 2, Some(Tracer), Tracer2),
+    Tracer2 = (FATB, B | 1, 2, Some(Tracer), Tracer),
+    TraceExp1 = (FBXP, B, 8, None, TraceExp2),
+    TraceExp2 = (FBXP, B | 1, 6, None, TraceExp3),
+    TraceExp3 = (FBXP, B | 2, 4, None, Null),
+    SkelStnd = (SKEL, 0, 10, Some(Look), SkelStnd2),
+    SkelStnd2 = (SKEL, 1, 10, Some(Look), SkelStnd),
+    SkelRun1 = (SKEL, 0, 2, Some(Chase), SkelRun2),
+    SkelRun2 = (SKEL, 0, 2, Some(Chase), SkelRun3),
+    SkelRun3 = (SKEL, 1, 2, Some(Chase), SkelRun4),
+    SkelRun4 = (SKEL, 1, 2, Some(Chase), SkelRun5),
+    SkelRun5 = (SKEL, 2, 2, Some(Chase), SkelRun6),
+    SkelRun6 = (SKEL, 2, 2, Some(Chase), SkelRun7),
+    SkelRun7 = (SKEL, 3, 2, Some(Chase), SkelRun8),
+    SkelRun8 = (SKEL, 3, 2, Some(Chase), SkelRun9),
+    SkelRun9 = (SKEL, 4, 2, Some(Chase), SkelRun10),
+    SkelRun10 = (SKEL, 4, 2, Some(Chase), SkelRun11),
+    SkelRun11 = (SKEL, 5, 2, Some(Chase), SkelRun12),
+    SkelRun12 = (SKEL, 5, 2, Some(Chase), SkelRun1),
+    SkelFist1 = (SKEL, 6, 0, Some(FaceTarget), SkelFist2),
+    SkelFist2 = (SKEL, 6, 6, Some(SkelWhoosh), SkelFist3),
+    SkelFist3 = (SKEL, 7, 6, Some(FaceTarget), SkelFist4),
+    SkelFist4 = (SKEL, 8, 6, Some(SkelFist), SkelRun1),
+    SkelMiss1 = (SKEL, B | 9, 0, Some(FaceTarget), SkelMiss2),
+    SkelMiss2 = (SKEL, B | 9, 10, Some(FaceTarget), SkelMiss3),
+    SkelMiss3 = (SKEL, 10, 10, Some(SkelMissile), SkelMiss4),
+    SkelMiss4 = (SKEL, 10, 10, Some(FaceTarget), SkelRun1),
+    SkelPain = (SKEL, 11, 5, None, SkelPain2),
+    SkelPain2 = (SKEL, 11, 5, Some(Pain), SkelRun1),
+    SkelDie1 = (SKEL, 11, 7, None, SkelDie2),
+    SkelDie2 = (SKEL, 12, 7, None, SkelDie3),
+    SkelDie3 = (SKEL, 13, 7, Some(Scream), SkelDie4),
+    SkelDie4 = (SKEL, 14, 7, Some(Fall), SkelDie5),
+    SkelDie5 = (SKEL, 15, 7, None, SkelDie6),
+    SkelDie6 = (SKEL, 16, -1, None, Null),
+    SkelRaise1 = (SKEL, 16, 5, None, SkelRaise2),
+    SkelRaise2 = (SKEL, 15, 5, None, SkelRaise3),
+    SkelRaise3 = (SKEL, 14, 5, None, SkelRaise4),
+    SkelRaise4 = (SKEL, 13, 5, None, SkelRaise5),
+    SkelRaise5 = (SKEL, 12, 5, None, SkelRaise6),
+    SkelRaise6 = (SKEL, 11, 5, None, SkelRun1),
+    FatShot1 = (MANF, B, 4, None, FatShot2),
+    FatShot2 = (MANF, B | 1, 4, None, FatShot1),
+    FatShotX1 = (MISL, B | 1, 8, None, FatShotX2),
+    FatShotX2 = (MISL, B | 2, 6, None, FatShotX3),
+    FatShotX3 = (MISL, B | 3, 4, None, Null),
+    FattStnd = (FATT, 0, 15, Some(Look), FattStnd2),
+    FattStnd2 = (FATT, 1, 15, Some(Look), FattStnd),
+    FattRun1 = (FATT, 0, 4, Some(Chase), FattRun2),
+    FattRun2 = (FATT, 0, 4, Some(Chase), FattRun3),
+    FattRun3 = (FATT, 1, 4, Some(Chase), FattRun4),
+    FattRun4 = (FATT, 1, 4, Some(Chase), FattRun5),
+    FattRun5 = (FATT, 2, 4, Some(Chase), FattRun6),
+    FattRun6 = (FATT, 2, 4, Some(Chase), FattRun7),
+    FattRun7 = (FATT, 3, 4, Some(Chase), FattRun8),
+    FattRun8 = (FATT, 3, 4, Some(Chase), FattRun9),
+    FattRun9 = (FATT, 4, 4, Some(Chase), FattRun10),
+    FattRun10 = (FATT, 4, 4, Some(Chase), FattRun11),
+    FattRun11 = (FATT, 5, 4, Some(Chase), FattRun12),
+    FattRun12 = (FATT, 5, 4, Some(Chase), FattRun1),
+    FattAtk1 = (FATT, 6, 20, Some(FatRaise), FattAtk2),
+    FattAtk2 = (FATT, B | 7, 10, Some(FatAttack1), FattAtk3),
+    FattAtk3 = (FATT, 8, 5, Some(FaceTarget), FattAtk4),
+    FattAtk4 = (FATT, 6, 5, Some(FaceTarget), FattAtk5),
+    FattAtk5 = (FATT, B | 7, 10, Some(FatAttack2), FattAtk6),
+    FattAtk6 = (FATT, 8, 5, Some(FaceTarget), FattAtk7),
+    FattAtk7 = (FATT, 6, 5, Some(FaceTarget), FattAtk8),
+    FattAtk8 = (FATT, B | 7, 10, Some(FatAttack3), FattAtk9),
+    FattAtk9 = (FATT, 8, 5, Some(FaceTarget), FattAtk10),
+    FattAtk10 = (FATT, 6, 5, Some(FaceTarget), FattRun1),
+    FattPain = (FATT, 9, 3, None, FattPain2),
+    FattPain2 = (FATT, 9, 3, Some(Pain), FattRun1),
+    FattDie1 = (FATT, 10, 6, None, FattDie2),
+    FattDie2 = (FATT, 11, 6, Some(Scream), FattDie3),
+    FattDie3 = (FATT, 12, 6, Some(Fall), FattDie4),
+    FattDie4 = (FATT, 13, 6, None, FattDie5),
+    FattDie5 = (FATT, 14, 6, None, FattDie6),
+    FattDie6 = (FATT, 15, 6, None, FattDie7),
+    FattDie7 = (FATT, 16, 6, None, FattDie8),
+    FattDie8 = (FATT, 17, 6, None, FattDie9),
+    FattDie9 = (FATT, 18, 6, None, FattDie10),
+    FattDie10 = (FATT, 19, -1, Some(BossDeath), Null),
+    FattRaise1 = (FATT, 17, 5, None, FattRaise2),
+    FattRaise2 = (FATT, 16, 5, None, FattRaise3),
+    FattRaise3 = (FATT, 15, 5, None, FattRaise4),
+    FattRaise4 = (FATT, 14, 5, None, FattRaise5),
+    FattRaise5 = (FATT, 13, 5, None, FattRaise6),
+    FattRaise6 = (FATT, 12, 5, None, FattRaise7),
+    FattRaise7 = (FATT, 11, 5, None, FattRaise8),
+    FattRaise8 = (FATT, 10, 5, None, FattRun1),
+    CposStnd = (CPOS, 0, 10, Some(Look), CposStnd2),
+    CposStnd2 = (CPOS, 1, 10, Some(Look), CposStnd),
+    CposRun1 = (CPOS, 0, 3, Some(Chase), CposRun2),
+    CposRun2 = (CPOS, 0, 3, Some(Chase), CposRun3),
+    CposRun3 = (CPOS, 1, 3, Some(Chase), CposRun4),
+    CposRun4 = (CPOS, 1, 3, Some(Chase), CposRun5),
+    CposRun5 = (CPOS, 2, 3, Some(Chase), CposRun6),
+    CposRun6 = (CPOS, 2, 3, Some(Chase), CposRun7),
+    CposRun7 = (CPOS, 3, 3, Some(Chase), CposRun8),
+    CposRun8 = (CPOS, 3, 3, Some(Chase), CposRun1),
+    CposAtk1 = (CPOS, 4, 10, Some(FaceTarget), CposAtk2),
+    CposAtk2 = (CPOS, B | 5, 4, Some(CPosAttack), CposAtk3),
+    CposAtk3 = (CPOS, B | 4, 4, Some(CPosAttack), CposAtk4),
+    CposAtk4 = (CPOS, 5, 1, Some(CPosRefire), CposAtk2),
+    CposPain = (CPOS, 6, 3, None, CposPain2),
+    CposPain2 = (CPOS, 6, 3, Some(Pain), CposRun1),
+    CposDie1 = (CPOS, 7, 5, None, CposDie2),
+    CposDie2 = (CPOS, 8, 5, Some(Scream), CposDie3),
+    CposDie3 = (CPOS, 9, 5, Some(Fall), CposDie4),
+    CposDie4 = (CPOS, 10, 5, None, CposDie5),
+    CposDie5 = (CPOS, 11, 5, None, CposDie6),
+    CposDie6 = (CPOS, 12, 5, None, CposDie7),
+    CposDie7 = (CPOS, 13, -1, None, Null),
+    CposXdie1 = (CPOS, 14, 5, None, CposXdie2),
+    CposXdie2 = (CPOS, 15, 5, Some(XScream), CposXdie3),
+    CposXdie3 = (CPOS, 16, 5, Some(Fall), CposXdie4),
+    CposXdie4 = (CPOS, 17, 5, None, CposXdie5),
+    CposXdie5 = (CPOS, 18, 5, None, CposXdie6),
+    CposXdie6 = (CPOS, 19, -1, None, Null),
+    CposRaise1 = (CPOS, 13, 5, None, CposRaise2),
+    CposRaise2 = (CPOS, 12, 5, None, CposRaise3),
+    CposRaise3 = (CPOS, 11, 5, None, CposRaise4),
+    CposRaise4 = (CPOS, 10, 5, None, CposRaise5),
+    CposRaise5 = (CPOS, 9, 5, None, CposRaise6),
+    CposRaise6 = (CPOS, 8, 5, None, CposRaise7),
+    CposRaise7 = (CPOS, 7, 5, None, CposRun1),
+    TrooStnd = (TROO, 0, 10, Some(Look), TrooStnd2),
+    TrooStnd2 = (TROO, 1, 10, Some(Look), TrooStnd),
+    TrooRun1 = (TROO, 0, 3, Some(Chase), TrooRun2),
+    TrooRun2 = (TROO, 0, 3, Some(Chase), TrooRun3),
+    TrooRun3 = (TROO, 1, 3, Some(Chase), TrooRun4),
+    TrooRun4 = (TROO, 1, 3, Some(Chase), TrooRun5),
+    TrooRun5 = (TROO, 2, 3, Some(Chase), TrooRun6),
+    TrooRun6 = (TROO, 2, 3, Some(Chase), TrooRun7),
+    TrooRun7 = (TROO, 3, 3, Some(Chase), TrooRun8),
+    TrooRun8 = (TROO, 3, 3, Some(Chase), TrooRun1),
+    TrooAtk1 = (TROO, 4, 8, Some(FaceTarget), TrooAtk2),
+    TrooAtk2 = (TROO, 5, 8, Some(FaceTarget), TrooAtk3),
+    TrooAtk3 = (TROO, 6, 6, Some(TroopAttack), TrooRun1),
+    TrooPain = (TROO, 7, 2, None, TrooPain2),
+    TrooPain2 = (TROO, 7, 2, Some(Pain), TrooRun1),
+    TrooDie1 = (TROO, 8, 8, None, TrooDie2),
+    TrooDie2 = (TROO, 9, 8, Some(Scream), TrooDie3),
+    TrooDie3 = (TROO, 10, 6, None, TrooDie4),
+    TrooDie4 = (TROO, 11, 6, Some(Fall), TrooDie5),
+    TrooDie5 = (TROO, 12, -1, None, Null),
+    TrooXdie1 = (TROO, 13, 5, None, TrooXdie2),
+    TrooXdie2 = (TROO, 14, 5, Some(XScream), TrooXdie3),
+    TrooXdie3 = (TROO, 15, 5, None, TrooXdie4),
+    TrooXdie4 = (TROO, 16, 5, Some(Fall), TrooXdie5),
+    TrooXdie5 = (TROO, 17, 5, None, TrooXdie6),
+    TrooXdie6 = (TROO, 18, 5, None, TrooXdie7),
+    TrooXdie7 = (TROO, 19, 5, None, TrooXdie8),
+    TrooXdie8 = (TROO, 20, -1, None, Null),
+    TrooRaise1 = (TROO, 12, 8, None, TrooRaise2),
+    TrooRaise2 = (TROO, 11, 8, None, TrooRaise3),
+    TrooRaise3 = (TROO, 10, 6, None, TrooRaise4),
+    TrooRaise4 = (TROO, 9, 6, None, TrooRaise5),
+    TrooRaise5 = (TROO, 8, 6, None, TrooRun1),
+    SargStnd = (SARG, 0, 10, Some(Look), SargStnd2),
+    SargStnd2 = (SARG, 1, 10, Some(Look), SargStnd),
+    SargRun1 = (SARG, 0, 2, Some(Chase), SargRun2),
+    SargRun2 = (SARG, 0, 2, Some(Chase), SargRun3),
+    SargRun3 = (SARG, 1, 2, Some(Chase), SargRun4),
+    SargRun4 = (SARG, 1, 2, Some(Chase), SargRun5),
+    SargRun5 = (SARG, 2, 2, Some(Chase), SargRun6),
+    SargRun6 = (SARG, 2, 2, Some(Chase), SargRun7),
+    SargRun7 = (SARG, 3, 2, Some(Chase), SargRun8),
+    SargRun8 = (SARG, 3, 2, Some(Chase), SargRun1),
+    SargAtk1 = (SARG, 4, 8, Some(FaceTarget), SargAtk2),
+    SargAtk2 = (SARG, 5, 8, Some(FaceTarget), SargAtk3),
+    SargAtk3 = (SARG, 6, 8, Some(SargAttack), SargRun1),
+    SargPain = (SARG, 7, 2, None, SargPain2),
+    SargPain2 = (SARG, 7, 2, Some(Pain), SargRun1),
+    SargDie1 = (SARG, 8, 8, None, SargDie2),
+    SargDie2 = (SARG, 9, 8, Some(Scream), SargDie3),
+    SargDie3 = (SARG, 10, 4, None, SargDie4),
+    SargDie4 = (SARG, 11, 4, Some(Fall), SargDie5),
+    SargDie5 = (SARG, 12, 4, None, SargDie6),
+    SargDie6 = (SARG, 13, -1, None, Null),
+    SargRaise1 = (SARG, 13, 5, None, SargRaise2),
+    SargRaise2 = (SARG, 12, 5, None, SargRaise3),
+    SargRaise3 = (SARG, 11, 5, None, SargRaise4),
+    SargRaise4 = (SARG, 10, 5, None, SargRaise5),
+    SargRaise5 = (SARG, 9, 5, None, SargRaise6),
+    SargRaise6 = (SARG, 8, 5, None, SargRun1),
+    HeadStnd = (HEAD, 0, 10, Some(Look), HeadStnd),
+    HeadRun1 = (HEAD, 0, 3, Some(Chase), HeadRun1),
+    HeadAtk1 = (HEAD, 1, 5, Some(FaceTarget), HeadAtk2),
+    HeadAtk2 = (HEAD, 2, 5, Some(FaceTarget), HeadAtk3),
+    HeadAtk3 = (HEAD, B | 3, 5, Some(HeadAttack), HeadRun1),
+    HeadPain = (HEAD, 4, 3, None, HeadPain2),
+    HeadPain2 = (HEAD, 4, 3, Some(Pain), HeadPain3),
+    HeadPain3 = (HEAD, 5, 6, None, HeadRun1),
+    HeadDie1 = (HEAD, 6, 8, None, HeadDie2),
+    HeadDie2 = (HEAD, 7, 8, Some(Scream), HeadDie3),
+    HeadDie3 = (HEAD, 8, 8, None, HeadDie4),
+    HeadDie4 = (HEAD, 9, 8, None, HeadDie5),
+    HeadDie5 = (HEAD, 10, 8, Some(Fall), HeadDie6),
+    HeadDie6 = (HEAD, 11, -1, None, Null),
+    HeadRaise1 = (HEAD, 11, 8, None, HeadRaise2),
+    HeadRaise2 = (HEAD, 10, 8, None, HeadRaise3),
+    HeadRaise3 = (HEAD, 9, 8, None, HeadRaise4),
+    HeadRaise4 = (HEAD, 8, 8, None, HeadRaise5),
+    HeadRaise5 = (HEAD, 7, 8, None, HeadRaise6),
+    HeadRaise6 = (HEAD, 6, 8, None, HeadRun1),
+    BrBall1 = (BAL7, B, 4, None, BrBall2),
+    BrBall2 = (BAL7, B | 1, 4, None, BrBall1),
+    BrBallX1 = (BAL7, B | 2, 6, None, BrBallX2),
+    BrBallX2 = (BAL7, B | 3, 6, None, BrBallX3),
+    BrBallX3 = (BAL7, B | 4, 6, None, Null),
+    BossStnd = (BOSS, 0, 10, Some(Look), BossStnd2),
+    BossStnd2 = (BOSS, 1, 10, Some(Look), BossStnd),
+    BossRun1 = (BOSS, 0, 3, Some(Chase), BossRun2),
+    BossRun2 = (BOSS, 0, 3, Some(Chase), BossRun3),
+    BossRun3 = (BOSS, 1, 3, Some(Chase), BossRun4),
+    BossRun4 = (BOSS, 1, 3, Some(Chase), BossRun5),
+    BossRun5 = (BOSS, 2, 3, Some(Chase), BossRun6),
+    BossRun6 = (BOSS, 2, 3, Some(Chase), BossRun7),
+    BossRun7 = (BOSS, 3, 3, Some(Chase), BossRun8),
+    BossRun8 = (BOSS, 3, 3, Some(Chase), BossRun1),
+    BossAtk1 = (BOSS, 4, 8, Some(FaceTarget), BossAtk2),
+    BossAtk2 = (BOSS, 5, 8, Some(FaceTarget), BossAtk3),
+    BossAtk3 = (BOSS, 6, 8, Some(BruisAttack), BossRun1),
+    BossPain = (BOSS, 7, 2, None, BossPain2),
+    BossPain2 = (BOSS, 7, 2, Some(Pain), BossRun1),
+    BossDie1 = (BOSS, 8, 8, None, BossDie2),
+    BossDie2 = (BOSS, 9, 8, Some(Scream), BossDie3),
+    BossDie3 = (BOSS, 10, 8, None, BossDie4),
+    BossDie4 = (BOSS, 11, 8, Some(Fall), BossDie5),
+    BossDie5 = (BOSS, 12, 8, None, BossDie6),
+    BossDie6 = (BOSS, 13, 8, None, BossDie7),
+    BossDie7 = (BOSS, 14, -1, Some(BossDeath), Null),
+    BossRaise1 = (BOSS, 14, 8, None, BossRaise2),
+    BossRaise2 = (BOSS, 13, 8, None, BossRaise3),
+    BossRaise3 = (BOSS, 12, 8, None, BossRaise4),
+    BossRaise4 = (BOSS, 11, 8, None, BossRaise5),
+    BossRaise5 = (BOSS, 10, 8, None, BossRaise6),
+    BossRaise6 = (BOSS, 9, 8, None, BossRaise7),
+    BossRaise7 = (BOSS, 8, 8, None, BossRun1),
+    Bos2Stnd = (BOS2, 0, 10, Some(Look), Bos2Stnd2),
+    Bos2Stnd2 = (BOS2, 1, 10, Some(Look), Bos2Stnd),
+    Bos2Run1 = (BOS2, 0, 3, Some(Chase), Bos2Run2),
+    Bos2Run2 = (BOS2, 0, 3, Some(Chase), Bos2Run3),
+    Bos2Run3 = (BOS2, 1, 3, Some(Chase), Bos2Run4),
+    Bos2Run4 = (BOS2, 1, 3, Some(Chase), Bos2Run5),
+    Bos2Run5 = (BOS2, 2, 3, Some(Chase), Bos2Run6),
+    Bos2Run6 = (BOS2, 2, 3, Some(Chase), Bos2Run7),
+    Bos2Run7 = (BOS2, 3, 3, Some(Chase), Bos2Run8),
+    Bos2Run8 = (BOS2, 3, 3, Some(Chase), Bos2Run1),
+    Bos2Atk1 = (BOS2, 4, 8, Some(FaceTarget), Bos2Atk2),
+    Bos2Atk2 = (BOS2, 5, 8, Some(FaceTarget), Bos2Atk3),
+    Bos2Atk3 = (BOS2, 6, 8, Some(BruisAttack), Bos2Run1),
+    Bos2Pain = (BOS2, 7, 2, None, Bos2Pain2),
+    Bos2Pain2 = (BOS2, 7, 2, Some(Pain), Bos2Run1),
+    Bos2Die1 = (BOS2, 8, 8, None, Bos2Die2),
+    Bos2Die2 = (BOS2, 9, 8, Some(Scream), Bos2Die3),
+    Bos2Die3 = (BOS2, 10, 8, None, Bos2Die4),
+    Bos2Die4 = (BOS2, 11, 8, Some(Fall), Bos2Die5),
+    Bos2Die5 = (BOS2, 12, 8, None, Bos2Die6),
+    Bos2Die6 = (BOS2, 13, 8, None, Bos2Die7),
+    Bos2Die7 = (BOS2, 14, -1, Some(BossDeath), Null),
+    Bos2Raise1 = (BOS2, 14, 8, None, Bos2Raise2),
+    Bos2Raise2 = (BOS2, 13, 8, None, Bos2Raise3),
+    Bos2Raise3 = (BOS2, 12, 8, None, Bos2Raise4),
+    Bos2Raise4 = (BOS2, 11, 8, None, Bos2Raise5),
+    Bos2Raise5 = (BOS2, 10, 8, None, Bos2Raise6),
+    Bos2Raise6 = (BOS2, 9, 8, None, Bos2Raise7),
+    Bos2Raise7 = (BOS2, 8, 8, None, Bos2Run1),
+    SkullStnd = (SKUL, B, 10, Some(Look), SkullStnd2),
+    SkullStnd2 = (SKUL, B | 1, 10, Some(Look), SkullStnd),
+    SkullRun1 = (SKUL, B, 6, Some(Chase), SkullRun2),
+    SkullRun2 = (SKUL, B | 1, 6, Some(Chase), SkullRun1),
+    SkullAtk1 = (SKUL, B | 2, 10, Some(FaceTarget), SkullAtk2),
+    SkullAtk2 = (SKUL, B | 3, 4, Some(SkullAttack), SkullAtk3),
+    SkullAtk3 = (SKUL, B | 2, 4, None, SkullAtk4),
+    SkullAtk4 = (SKUL, B | 3, 4, None, SkullAtk3),
+    SkullPain = (SKUL, B | 4, 3, None, SkullPain2),
+    SkullPain2 = (SKUL, B | 4, 3, Some(Pain), SkullRun1),
+    SkullDie1 = (SKUL, B | 5, 6, None, SkullDie2),
+    SkullDie2 = (SKUL, B | 6, 6, Some(Scream), SkullDie3),
+    SkullDie3 = (SKUL, B | 7, 6, None, SkullDie4),
+    SkullDie4 = (SKUL, B | 8, 6, Some(Fall), SkullDie5),
+    SkullDie5 = (SKUL, 9, 6, None, SkullDie6),
+    SkullDie6 = (SKUL, 10, 6, None, Null),
+    SpidStnd = (SPID, 0, 10, Some(Look), SpidStnd2),
+    SpidStnd2 = (SPID, 1, 10, Some(Look), SpidStnd),
+    SpidRun1 = (SPID, 0, 3, Some(Metal), SpidRun2),
+    SpidRun2 = (SPID, 0, 3, Some(Chase), SpidRun3),
+    SpidRun3 = (SPID, 1, 3, Some(Chase), SpidRun4),
+    SpidRun4 = (SPID, 1, 3, Some(Chase), SpidRun5),
+    SpidRun5 = (SPID, 2, 3, Some(Metal), SpidRun6),
+    SpidRun6 = (SPID, 2, 3, Some(Chase), SpidRun7),
+    SpidRun7 = (SPID, 3, 3, Some(Chase), SpidRun8),
+    SpidRun8 = (SPID, 3, 3, Some(Chase), SpidRun9),
+    SpidRun9 = (SPID, 4, 3, Some(Metal), SpidRun10),
+    SpidRun10 = (SPID, 4, 3, Some(Chase), SpidRun11),
+    SpidRun11 = (SPID, 5, 3, Some(Chase), SpidRun12),
+    SpidRun12 = (SPID, 5, 3, Some(Chase), SpidRun1),
+    SpidAtk1 = (SPID, B, 20, Some(FaceTarget), SpidAtk2),
+    SpidAtk2 = (SPID, B | 6, 4, Some(SPosAttack), SpidAtk3),
+    SpidAtk3 = (SPID, B | 7, 4, Some(SPosAttack), SpidAtk4),
+    SpidAtk4 = (SPID, B | 7, 1, Some(SpidRefire), SpidAtk2),
+    SpidPain = (SPID, 8, 3, None, SpidPain2),
+    SpidPain2 = (SPID, 8, 3, Some(Pain), SpidRun1),
+    SpidDie1 = (SPID, 9, 20, Some(Scream), SpidDie2),
+    SpidDie2 = (SPID, 10, 10, Some(Fall), SpidDie3),
+    SpidDie3 = (SPID, 11, 10, None, SpidDie4),
+    SpidDie4 = (SPID, 12, 10, None, SpidDie5),
+    SpidDie5 = (SPID, 13, 10, None, SpidDie6),
+    SpidDie6 = (SPID, 14, 10, None, SpidDie7),
+    SpidDie7 = (SPID, 15, 10, None, SpidDie8),
+    SpidDie8 = (SPID, 16, 10, None, SpidDie9),
+    SpidDie9 = (SPID, 17, 10, None, SpidDie10),
+    SpidDie10 = (SPID, 18, 30, None, SpidDie11),
+    SpidDie11 = (SPID, 18, -1, Some(BossDeath), Null),
+    BspiStnd = (BSPI, 0, 10, Some(Look), BspiStnd2),
+    BspiStnd2 = (BSPI, 1, 10, Some(Look), BspiStnd),
+    BspiSight = (BSPI, 0, 20, None, BspiRun1),
+    BspiRun1 = (BSPI, 0, 3, Some(BabyMetal), BspiRun2),
+    BspiRun2 = (BSPI, 0, 3, Some(Chase), BspiRun3),
+    BspiRun3 = (BSPI, 1, 3, Some(Chase), BspiRun4),
+    BspiRun4 = (BSPI, 1, 3, Some(Chase), BspiRun5),
+    BspiRun5 = (BSPI, 2, 3, Some(Chase), BspiRun6),
+    BspiRun6 = (BSPI, 2, 3, Some(Chase), BspiRun7),
+    BspiRun7 = (BSPI, 3, 3, Some(BabyMetal), BspiRun8),
+    BspiRun8 = (BSPI, 3, 3, Some(Chase), BspiRun9),
+    BspiRun9 = (BSPI, 4, 3, Some(Chase), BspiRun10),
+    BspiRun10 = (BSPI, 4, 3, Some(Chase), BspiRun11),
+    BspiRun11 = (BSPI, 5, 3, Some(Chase), BspiRun12),
+    BspiRun12 = (BSPI, 5, 3, Some(Chase), BspiRun1),
+    BspiAtk1 = (BSPI, B, 20, Some(FaceTarget), BspiAtk2),
+    BspiAtk2 = (BSPI, B | 6, 4, Some(BspiAttack), BspiAtk3),
+    BspiAtk3 = (BSPI, B | 7, 4, None, BspiAtk4),
+    BspiAtk4 = (BSPI, B | 7, 1, Some(SpidRefire), BspiAtk2),
+    BspiPain = (BSPI, 8, 3, None, BspiPain2),
+    BspiPain2 = (BSPI, 8, 3, Some(Pain), BspiRun1),
+    BspiDie1 = (BSPI, 9, 20, Some(Scream), BspiDie2),
+    BspiDie2 = (BSPI, 10, 7, Some(Fall), BspiDie3),
+    BspiDie3 = (BSPI, 11, 7, None, BspiDie4),
+    BspiDie4 = (BSPI, 12, 7, None, BspiDie5),
+    BspiDie5 = (BSPI, 13, 7, None, BspiDie6),
+    BspiDie6 = (BSPI, 14, 7, None, BspiDie7),
+    BspiDie7 = (BSPI, 15, -1, Some(BossDeath), Null),
+    BspiRaise1 = (BSPI, 15, 5, None, BspiRaise2),
+    BspiRaise2 = (BSPI, 14, 5, None, BspiRaise3),
+    BspiRaise3 = (BSPI, 13, 5, None, BspiRaise4),
+    BspiRaise4 = (BSPI, 12, 5, None, BspiRaise5),
+    BspiRaise5 = (BSPI, 11, 5, None, BspiRaise6),
+    BspiRaise6 = (BSPI, 10, 5, None, BspiRaise7),
+    BspiRaise7 = (BSPI, 9, 5, None, BspiRun1),
+    ArachPlaz = (APLS, B, 5, None, ArachPlaz2),
+    ArachPlaz2 = (APLS, B | 1, 5, None, ArachPlaz),
+    ArachPlex = (APBX, B, 5, None, ArachPlex2),
+    ArachPlex2 = (APBX, B | 1, 5, None, ArachPlex3),
+    ArachPlex3 = (APBX, B | 2, 5, None, ArachPlex4),
+    ArachPlex4 = (APBX, B | 3, 5, None, ArachPlex5),
+    ArachPlex5 = (APBX, B | 4, 5, None, Null),
+    CyberStnd = (CYBR, 0, 10, Some(Look), CyberStnd2),
+    CyberStnd2 = (CYBR, 1, 10, Some(Look), CyberStnd),
+    CyberRun1 = (CYBR, 0, 3, Some(Hoof), CyberRun2),
+    CyberRun2 = (CYBR, 0, 3, Some(Chase), CyberRun3),
+    CyberRun3 = (CYBR, 1, 3, Some(Chase), CyberRun4),
+    CyberRun4 = (CYBR, 1, 3, Some(Chase), CyberRun5),
+    CyberRun5 = (CYBR, 2, 3, Some(Chase), CyberRun6),
+    CyberRun6 = (CYBR, 2, 3, Some(Chase), CyberRun7),
+    CyberRun7 = (CYBR, 3, 3, Some(Metal), CyberRun8),
+    CyberRun8 = (CYBR, 3, 3, Some(Chase), CyberRun1),
+    CyberAtk1 = (CYBR, 4, 6, Some(FaceTarget), CyberAtk2),
+    CyberAtk2 = (CYBR, 5, 12, Some(CyberAttack), CyberAtk3),
+    CyberAtk3 = (CYBR, 4, 12, Some(FaceTarget), CyberAtk4),
+    CyberAtk4 = (CYBR, 5, 12, Some(CyberAttack), CyberAtk5),
+    CyberAtk5 = (CYBR, 4, 12, Some(FaceTarget), CyberAtk6),
+    CyberAtk6 = (CYBR, 5, 12, Some(CyberAttack), CyberRun1),
+    CyberPain = (CYBR, 6, 10, Some(Pain), CyberRun1),
+    CyberDie1 = (CYBR, 7, 10, None, CyberDie2),
+    CyberDie2 = (CYBR, 8, 10, Some(Scream), CyberDie3),
+    CyberDie3 = (CYBR, 9, 10, None, CyberDie4),
+    CyberDie4 = (CYBR, 10, 10, None, CyberDie5),
+    CyberDie5 = (CYBR, 11, 10, None, CyberDie6),
+    CyberDie6 = (CYBR, 12, 10, Some(Fall), CyberDie7),
+    CyberDie7 = (CYBR, 13, 10, None, CyberDie8),
+    CyberDie8 = (CYBR, 14, 10, None, CyberDie9),
+    CyberDie9 = (CYBR, 15, 30, None, CyberDie10),
+    CyberDie10 = (CYBR, 15, -1, Some(BossDeath), Null),
+    PainStnd = (PAIN, 0, 10, Some(Look), PainStnd),
+    PainRun1 = (PAIN, 0, 3, Some(Chase), PainRun2),
+    PainRun2 = (PAIN, 0, 3, Some(Chase), PainRun3),
+    PainRun3 = (PAIN, 1, 3, Some(Chase), PainRun4),
+    PainRun4 = (PAIN, 1, 3, Some(Chase), PainRun5),
+    PainRun5 = (PAIN, 2, 3, Some(Chase), PainRun6),
+    PainRun6 = (PAIN, 2, 3, Some(Chase), PainRun1),
+    PainAtk1 = (PAIN, 3, 5, Some(FaceTarget), PainAtk2),
+    PainAtk2 = (PAIN, 4, 5, Some(FaceTarget), PainAtk3),
+    PainAtk3 = (PAIN, B | 5, 5, Some(FaceTarget), PainAtk4),
+    PainAtk4 = (PAIN, B | 5, 0, Some(PainAttack), PainRun1),
+    PainPain = (PAIN, 6, 6, None, PainPain2),
+    PainPain2 = (PAIN, 6, 6, Some(Pain), PainRun1),
+    PainDie1 = (PAIN, B | 7, 8, None, PainDie2),
+    PainDie2 = (PAIN, B | 8, 8, Some(Scream), PainDie3),
+    PainDie3 = (PAIN, B | 9, 8, None, PainDie4),
+    PainDie4 = (PAIN, B | 10, 8, None, PainDie5),
+    PainDie5 = (PAIN, B | 11, 8, Some(PainDie), PainDie6),
+    PainDie6 = (PAIN, B | 12, 8, None, Null),
+    PainRaise1 = (PAIN, 12, 8, None, PainRaise2),
+    PainRaise2 = (PAIN, 11, 8, None, PainRaise3),
+    PainRaise3 = (PAIN, 10, 8, None, PainRaise4),
+    PainRaise4 = (PAIN, 9, 8, None, PainRaise5),
+    PainRaise5 = (PAIN, 8, 8, None, PainRaise6),
+    PainRaise6 = (PAIN, 7, 8, None, PainRun1),
+    SswvStnd = (SSWV, 0, 10, Some(Look), SswvStnd2),
+    SswvStnd2 = (SSWV, 1, 10, Some(Look), SswvStnd),
+    SswvRun1 = (SSWV, 0, 3, Some(Chase), SswvRun2),
+    SswvRun2 = (SSWV, 0, 3, Some(Chase), SswvRun3),
+    SswvRun3 = (SSWV, 1, 3, Some(Chase), SswvRun4),
+    SswvRun4 = (SSWV, 1, 3, Some(Chase), SswvRun5),
+    SswvRun5 = (SSWV, 2, 3, Some(Chase), SswvRun6),
+    SswvRun6 = (SSWV, 2, 3, Some(Chase), SswvRun7),
+    SswvRun7 = (SSWV, 3, 3, Some(Chase), SswvRun8),
+    SswvRun8 = (SSWV, 3, 3, Some(Chase), SswvRun1),
+    SswvAtk1 = (SSWV, 4, 10, Some(FaceTarget), SswvAtk2),
+    SswvAtk2 = (SSWV, 5, 10, Some(FaceTarget), SswvAtk3),
+    SswvAtk3 = (SSWV, B | 6, 4, Some(CPosAttack), SswvAtk4),
+    SswvAtk4 = (SSWV, 5, 6, Some(FaceTarget), SswvAtk5),
+    SswvAtk5 = (SSWV, B | 6, 4, Some(CPosAttack), SswvAtk6),
+    SswvAtk6 = (SSWV, 5, 1, Some(CPosRefire), SswvAtk2),
+    SswvPain = (SSWV, 7, 3, None, SswvPain2),
+    SswvPain2 = (SSWV, 7, 3, Some(Pain), SswvRun1),
+    SswvDie1 = (SSWV, 8, 5, None, SswvDie2),
+    SswvDie2 = (SSWV, 9, 5, Some(Scream), SswvDie3),
+    SswvDie3 = (SSWV, 10, 5, Some(Fall), SswvDie4),
+    SswvDie4 = (SSWV, 11, 5, None, SswvDie5),
+    SswvDie5 = (SSWV, 12, -1, None, Null),
+    SswvXdie1 = (SSWV, 13, 5, None, SswvXdie2),
+    SswvXdie2 = (SSWV, 14, 5, Some(XScream), SswvXdie3),
+    SswvXdie3 = (SSWV, 15, 5, Some(Fall), SswvXdie4),
+    SswvXdie4 = (SSWV, 16, 5, None, SswvXdie5),
+    SswvXdie5 = (SSWV, 17, 5, None, SswvXdie6),
+    SswvXdie6 = (SSWV, 18, 5, None, SswvXdie7),
+    SswvXdie7 = (SSWV, 19, 5, None, SswvXdie8),
+    SswvXdie8 = (SSWV, 20, 5, None, SswvXdie9),
+    SswvXdie9 = (SSWV, 21, -1, None, Null),
+    SswvRaise1 = (SSWV, 12, 5, None, SswvRaise2),
+    SswvRaise2 = (SSWV, 11, 5, None, SswvRaise3),
+    SswvRaise3 = (SSWV, 10, 5, None, SswvRaise4),
+    SswvRaise4 = (SSWV, 9, 5, None, SswvRaise5),
+    SswvRaise5 = (SSWV, 8, 5, None, SswvRun1),
+    KeenStnd = (KEEN, 0, -1, None, KeenStnd),
+    CommKeen = (KEEN, 0, 6, None, CommKeen2),
+    CommKeen2 = (KEEN, 1, 6, None, CommKeen3),
+    CommKeen3 = (KEEN, 2, 6, Some(Scream), CommKeen4),
+    CommKeen4 = (KEEN, 3, 6, None, CommKeen5),
+    CommKeen5 = (KEEN, 4, 6, None, CommKeen6),
+    CommKeen6 = (KEEN, 5, 6, None, CommKeen7),
+    CommKeen7 = (KEEN, 6, 6, None, CommKeen8),
+    CommKeen8 = (KEEN, 7, 6, None, CommKeen9),
+    CommKeen9 = (KEEN, 8, 6, None, CommKeen10),
+    CommKeen10 = (KEEN, 9, 6, None, CommKeen11),
+    CommKeen11 = (KEEN, 10, 6, Some(KeenDie), CommKeen12),
+    CommKeen12 = (KEEN, 11, -1, None, Null),
+    KeenPain = (KEEN, 12, 4, None, KeenPain2),
+    KeenPain2 = (KEEN, 12, 8, Some(Pain), KeenStnd),
+    Brain = (BBRN, 0, -1, None, Null),
+    BrainPain = (BBRN, 1, 36, Some(BrainPain), Brain),
+    BrainDie1 = (BBRN, 0, 100, Some(BrainScream), BrainDie2),
+    BrainDie2 = (BBRN, 0, 10, None, BrainDie3),
+    BrainDie3 = (BBRN, 0, 10, None, BrainDie4),
+    BrainDie4 = (BBRN, 0, -1, Some(BrainDie), Null),
+    BrainEye = (SSWV, 0, 10, Some(Look), BrainEye),
+    BrainEyeSee = (SSWV, 0, 181, Some(BrainAwake), BrainEye1),
+    BrainEye1 = (SSWV, 0, 150, Some(BrainSpit), BrainEye1),
+    Spawn1 = (BOSF, B, 3, Some(SpawnSound), Spawn2),
+    Spawn2 = (BOSF, B | 1, 3, Some(SpawnFly), Spawn3),
+    Spawn3 = (BOSF, B | 2, 3, Some(SpawnFly), Spawn4),
+    Spawn4 = (BOSF, B | 3, 3, Some(SpawnFly), Spawn1),
+    SpawnFire1 = (FIRE, B, 4, Some(Fire), SpawnFire2),
+    SpawnFire2 = (FIRE, B | 1, 4, Some(Fire), SpawnFire3),
+    SpawnFire3 = (FIRE, B | 2, 4, Some(Fire), SpawnFire4),
+    SpawnFire4 = (FIRE, B | 3, 4, Some(Fire), SpawnFire5),
+    SpawnFire5 = (FIRE, B | 4, 4, Some(Fire), SpawnFire6),
+    SpawnFire6 = (FIRE, B | 5, 4, Some(Fire), SpawnFire7),
+    SpawnFire7 = (FIRE, B | 6, 4, Some(Fire), SpawnFire8),
+    SpawnFire8 = (FIRE, B | 7, 4, Some(Fire), Null),
+    BrainExplode1 = (MISL, B | 1, 10, None, BrainExplode2),
+    BrainExplode2 = (MISL, B | 2, 10, None, BrainExplode3),
+    BrainExplode3 = (MISL, B | 3, 10, Some(BrainExplode), Null),
+    Arm1 = (ARM1, 0, 6, None, Arm1A),
+    Arm1A = (ARM1, B | 1, 7, None, Arm1),
+    Arm2 = (ARM2, 0, 6, None, Arm2A),
+    Arm2A = (ARM2, B | 1, 6, None, Arm2),
+    Bar1 = (BAR1, 0, 6, None, Bar2),
+    Bar2 = (BAR1, 1, 6, None, Bar1),
+    Bexp = (BEXP, B, 5, None, Bexp2),
+    Bexp2 = (BEXP, B | 1, 5, Some(Scream), Bexp3),
+    Bexp3 = (BEXP, B | 2, 5, None, Bexp4),
+    Bexp4 = (BEXP, B | 3, 10, Some(Explode), Bexp5),
+    Bexp5 = (BEXP, B | 4, 10, None, Null),
+    Bbar1 = (FCAN, B, 4, None, Bbar2),
+    Bbar2 = (FCAN, B | 1, 4, None, Bbar3),
+    Bbar3 = (FCAN, B | 2, 4, None, Bbar1),
+    Bon1 = (BON1, 0, 6, None, Bon1A),
+    Bon1A = (BON1, 1, 6, None, Bon1B),
+    Bon1B = (BON1, 2, 6, None, Bon1C),
+    Bon1C = (BON1, 3, 6, None, Bon1D),
+    Bon1D = (BON1, 2, 6, None, Bon1E),
+    Bon1E = (BON1, 1, 6, None, Bon1),
+    Bon2 = (BON2, 0, 6, None, Bon2A),
+    Bon2A = (BON2, 1, 6, None, Bon2B),
+    Bon2B = (BON2, 2, 6, None, Bon2C),
+    Bon2C = (BON2, 3, 6, None, Bon2D),
+    Bon2D = (BON2, 2, 6, None, Bon2E),
+    Bon2E = (BON2, 1, 6, None, Bon2),
+    Bkey = (BKEY, 0, 10, None, Bkey2),
+    Bkey2 = (BKEY, B | 1, 10, None, Bkey),
+    Rkey = (RKEY, 0, 10, None, Rkey2),
+    Rkey2 = (RKEY, B | 1, 10, None, Rkey),
+    Ykey = (YKEY, 0, 10, None, Ykey2),
+    Ykey2 = (YKEY, B | 1, 10, None, Ykey),
+    Bskull = (BSKU, 0, 10, None, Bskull2),
+    Bskull2 = (BSKU, B | 1, 10, None, Bskull),
+    Rskull = (RSKU, 0, 10, None, Rskull2),
+    Rskull2 = (RSKU, B | 1, 10, None, Rskull),
+    Yskull = (YSKU, 0, 10, None, Yskull2),
+    Yskull2 = (YSKU, B | 1, 10, None, Yskull),
+    Stim = (STIM, 0, -1, None, Null),
+    Medi = (MEDI, 0, -1, None, Null),
+    Soul = (SOUL, B, 6, None, Soul2),
+    Soul2 = (SOUL, B | 1, 6, None, Soul3),
+    Soul3 = (SOUL, B | 2, 6, None, Soul4),
+    Soul4 = (SOUL, B | 3, 6, None, Soul5),
+    Soul5 = (SOUL, B | 2, 6, None, Soul6),
+    Soul6 = (SOUL, B | 1, 6, None, Soul),
+    Pinv = (PINV, B, 6, None, Pinv2),
+    Pinv2 = (PINV, B | 1, 6, None, Pinv3),
+    Pinv3 = (PINV, B | 2, 6, None, Pinv4),
+    Pinv4 = (PINV, B | 3, 6, None, Pinv),
+    Pstr = (PSTR, B, -1, None, Null),
+    Pins = (PINS, B, 6, None, Pins2),
+    Pins2 = (PINS, B | 1, 6, None, Pins3),
+    Pins3 = (PINS, B | 2, 6, None, Pins4),
+    Pins4 = (PINS, B | 3, 6, None, Pins),
+    Mega = (MEGA, B, 6, None, Mega2),
+    Mega2 = (MEGA, B | 1, 6, None, Mega3),
+    Mega3 = (MEGA, B | 2, 6, None, Mega4),
+    Mega4 = (MEGA, B | 3, 6, None, Mega),
+    Suit = (SUIT, B, -1, None, Null),
+    Pmap = (PMAP, B, 6, None, Pmap2),
+    Pmap2 = (PMAP, B | 1, 6, None, Pmap3),
+    Pmap3 = (PMAP, B | 2, 6, None, Pmap4),
+    Pmap4 = (PMAP, B | 3, 6, None, Pmap5),
+    Pmap5 = (PMAP, B | 2, 6, None, Pmap6),
+    Pmap6 = (PMAP, B | 1, 6, None, Pmap),
+    Pvis = (PVIS, B, 6, None, Pvis2),
+    Pvis2 = (PVIS, 1, 6, None, Pvis),
+    Clip = (CLIP, 0, -1, None, Null),
+    Ammo = (AMMO, 0, -1, None, Null),
+    Rock = (ROCK, 0, -1, None, Null),
+    Brok = (BROK, 0, -1, None, Null),
+    Cell = (CELL, 0, -1, None, Null),
+    Celp = (CELP, 0, -1, None, Null),
+    Shel = (SHEL, 0, -1, None, Null),
+    Sbox = (SBOX, 0, -1, None, Null),
+    Bpak = (BPAK, 0, -1, None, Null),
+    Bfug = (BFUG, 0, -1, None, Null),
+    Mgun = (MGUN, 0, -1, None, Null),
+    Csaw = (CSAW, 0, -1, None, Null),
+    Laun = (LAUN, 0, -1, None, Null),
+    Plas = (PLAS, 0, -1, None, Null),
+    Shot = (SHOT, 0, -1, None, Null),
+    Shot2 = (SGN2, 0, -1, None, Null),
+    Colu = (COLU, B, -1, None, Null),
+    Stalag = (SMT2, 0, -1, None, Null),
+    BloodyTwitch = (GOR1, 0, 10, None, BloodyTwitch2),
+    BloodyTwitch2 = (GOR1, 1, 15, None, BloodyTwitch3),
+    BloodyTwitch3 = (GOR1, 2, 8, None, BloodyTwitch4),
+    BloodyTwitch4 = (GOR1, 1, 6, None, BloodyTwitch),
+    DeadTorso = (PLAY, 13, -1, None, Null),
+    DeadBottom = (PLAY, 18, -1, None, Null),
+    HeadsOnStick = (POL2, 0, -1, None, Null),
+    Gibs = (POL5, 0, -1, None, Null),
+    HeadOnAStick = (POL4, 0, -1, None, Null),
+    HeadCandles = (POL3, B, 6, None, HeadCandles2),
+    HeadCandles2 = (POL3, B | 1, 6, None, HeadCandles),
+    DeadStick = (POL1, 0, -1, None, Null),
+    LiveStick = (POL6, 0, 6, None, LiveStick2),
+    LiveStick2 = (POL6, 1, 8, None, LiveStick),
+    Meat2 = (GOR2, 0, -1, None, Null),
+    Meat3 = (GOR3, 0, -1, None, Null),
+    Meat4 = (GOR4, 0, -1, None, Null),
+    Meat5 = (GOR5, 0, -1, None, Null),
+    Stalagtite = (SMIT, 0, -1, None, Null),
+    TallGrnCol = (COL1, 0, -1, None, Null),
+    ShrtGrnCol = (COL2, 0, -1, None, Null),
+    TallRedCol = (COL3, 0, -1, None, Null),
+    ShrtRedCol = (COL4, 0, -1, None, Null),
+    Candlestik = (CAND, B, -1, None, Null),
+    Candelabra = (CBRA, B, -1, None, Null),
+    SkullCol = (COL6, 0, -1, None, Null),
+    TorchTree = (TRE1, 0, -1, None, Null),
+    BigTree = (TRE2, 0, -1, None, Null),
+    TechPillar = (ELEC, 0, -1, None, Null),
+    EvilEye = (CEYE, B, 6, None, EvilEye2),
+    EvilEye2 = (CEYE, B | 1, 6, None, EvilEye3),
+    EvilEye3 = (CEYE, B | 2, 6, None, EvilEye4),
+    EvilEye4 = (CEYE, B | 1, 6, None, EvilEye),
+    FloatSkull = (FSKU, B, 6, None, FloatSkull2),
+    FloatSkull2 = (FSKU, B | 1, 6, None, FloatSkull3),
+    FloatSkull3 = (FSKU, B | 2, 6, None, FloatSkull),
+    HeartCol = (COL5, 0, 14, None, HeartCol2),
+    HeartCol2 = (COL5, 1, 14, None, HeartCol),
+    BlueTorch = (TBLU, B, 4, None, BlueTorch2),
+    BlueTorch2 = (TBLU, B | 1, 4, None, BlueTorch3),
+    BlueTorch3 = (TBLU, B | 2, 4, None, BlueTorch4),
+    BlueTorch4 = (TBLU, B | 3, 4, None, BlueTorch),
+    GreenTorch = (TGRN, B, 4, None, GreenTorch2),
+    GreenTorch2 = (TGRN, B | 1, 4, None, GreenTorch3),
+    GreenTorch3 = (TGRN, B | 2, 4, None, GreenTorch4),
+    GreenTorch4 = (TGRN, B | 3, 4, None, GreenTorch),
+    RedTorch = (TRED, B, 4, None, RedTorch2),
+    RedTorch2 = (TRED, B | 1, 4, None, RedTorch3),
+    RedTorch3 = (TRED, B | 2, 4, None, RedTorch4),
+    RedTorch4 = (TRED, B | 3, 4, None, RedTorch),
+    BTorchShrt = (SMBT, B, 4, None, BTorchShrt2),
+    BTorchShrt2 = (SMBT, B | 1, 4, None, BTorchShrt3),
+    BTorchShrt3 = (SMBT, B | 2, 4, None, BTorchShrt4),
+    BTorchShrt4 = (SMBT, B | 3, 4, None, BTorchShrt),
+    GTorchShrt = (SMGT, B, 4, None, GTorchShrt2),
+    GTorchShrt2 = (SMGT, B | 1, 4, None, GTorchShrt3),
+    GTorchShrt3 = (SMGT, B | 2, 4, None, GTorchShrt4),
+    GTorchShrt4 = (SMGT, B | 3, 4, None, GTorchShrt),
+    RTorchShrt = (SMRT, B, 4, None, RTorchShrt2),
+    RTorchShrt2 = (SMRT, B | 1, 4, None, RTorchShrt3),
+    RTorchShrt3 = (SMRT, B | 2, 4, None, RTorchShrt4),
+    RTorchShrt4 = (SMRT, B | 3, 4, None, RTorchShrt),
+    HangNoGuts = (HDB1, 0, -1, None, Null),
+    HangBNoBrain = (HDB2, 0, -1, None, Null),
+    HangTLookDn = (HDB3, 0, -1, None, Null),
+    HangTSkull = (HDB4, 0, -1, None, Null),
+    HangTLookUp = (HDB5, 0, -1, None, Null),
+    HangTNoBrain = (HDB6, 0, -1, None, Null),
+    ColonGibs = (POB1, 0, -1, None, Null),
+    SmallPool = (POB2, 0, -1, None, Null),
+    BrainStem = (BRS1, 0, -1, None, Null),
+    TechLamp = (TLMP, B, 4, None, TechLamp2),
+    TechLamp2 = (TLMP, B | 1, 4, None, TechLamp3),
+    TechLamp3 = (TLMP, B | 2, 4, None, TechLamp4),
+    TechLamp4 = (TLMP, B | 3, 4, None, TechLamp),
+    Tech2Lamp = (TLP2, B, 4, None, Tech2Lamp2),
+    Tech2Lamp2 = (TLP2, B | 1, 4, None, Tech2Lamp3),
+    Tech2Lamp3 = (TLP2, B | 2, 4, None, Tech2Lamp4),
+    Tech2Lamp4 = (TLP2, B | 3, 4, None, Tech2Lamp),
+}
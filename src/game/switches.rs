@@ -0,0 +1,86 @@
+//! Switch textures turned on by used lines, like vanilla p_switch.c
+
+use kabal_app::tic_runner::TICRATE;
+
+use super::world::World;
+use crate::wad::map::{SidePart, WadMap};
+
+/// Tics before a repeatable switch turns off again, like vanilla BUTTONTIME
+const BUTTONTIME: u32 = TICRATE;
+
+/// A repeatable switch waiting to turn off, like vanilla button_t
+pub struct Button {
+    pub line: usize,
+    pub part: SidePart,
+    /// Texture put back when the time is up
+    pub texture: [u8; 8],
+    pub timer: u32,
+}
+
+/// Swap the switch texture on the front of a line, like vanilla P_ChangeSwitchTexture
+///
+/// Repeatable switches turn off again after a while
+pub fn change_switch_texture(map: &mut WadMap, world: &mut World, line: usize, repeat: bool) {
+    let side = map.linedefs[line].front_sidedef as usize;
+    for part in [SidePart::Upper, SidePart::Middle, SidePart::Lower] {
+        let mut texture = map.sidedefs[side].texture(part);
+        texture.make_ascii_uppercase();
+        let Some(&other) = map.switch_textures.get(&texture) else {
+            continue;
+        };
+
+        map.sidedefs[side].set_texture(part, other);
+        if repeat && !world.buttons.iter().any(|button| button.line == line) {
+            world.buttons.push(Button {
+                line,
+                part,
+                texture,
+                timer: BUTTONTIME,
+            });
+        }
+        return;
+    }
+}
+
+/// Turn off the switches whose time is up, like the buttons of vanilla P_UpdateSpecials
+pub fn update_buttons(map: &mut WadMap, world: &mut World) {
+    world.buttons.retain_mut(|button| {
+        button.timer = button.timer.saturating_sub(1);
+        if button.timer > 0 {
+            return true;
+        }
+        let side = map.linedefs[button.line].front_sidedef as usize;
+        map.sidedefs[side].set_texture(button.part, button.texture);
+        false
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wad::test_wad::room_map;
+
+    #[test]
+    fn switch_turns_off_when_its_time_is_up() {
+        let mut map = room_map();
+        let mut world = World::new();
+        let side = map.linedefs[0].front_sidedef as usize;
+        let texture = map.sidedefs[side].texture(SidePart::Middle);
+        map.sidedefs[side].set_texture(SidePart::Middle, *b"SW2A\0\0\0\0");
+        // A timer already at 0 turns off on the next tic instead of wrapping around
+        for timer in [2, 0] {
+            world.buttons.push(Button {
+                line: 0,
+                part: SidePart::Middle,
+                texture,
+                timer,
+            });
+        }
+
+        update_buttons(&mut map, &mut world);
+        assert_eq!(world.buttons.len(), 1);
+        update_buttons(&mut map, &mut world);
+        assert!(world.buttons.is_empty());
+        assert_eq!(map.sidedefs[side].texture(SidePart::Middle), texture);
+    }
+}
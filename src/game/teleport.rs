@@ -0,0 +1,95 @@
+//! Teleporters, like vanilla p_telept.c
+
+use super::{
+    info::{MobjFlags, MobjType},
+    interaction::damage_mobj,
+    mobj::Mobj,
+    movement::{set_position, things_around},
+    specials::tagged_sectors,
+    world::{Thinker, ThinkerId, World},
+};
+use crate::wad::map::WadMap;
+
+/// Tics a teleported player can not move
+const TELEPORT_FREEZE: i32 = 18;
+/// Damage of a telefrag, enough to kill anything
+const TELEFRAG_DAMAGE: i32 = 10000;
+
+/// Put a mobj somewhere, killing the shootable things in the way, like vanilla P_TeleportMove
+///
+/// Only players telefrag, except on MAP30 where the monsters spawned by the boss do.
+/// Returns false when a thing blocks a mobj that can not telefrag
+pub fn teleport_move(map: &mut WadMap, world: &mut World, mo: &mut Mobj, x: f32, y: f32) -> bool {
+    let in_the_way = things_around(world, mo, x, y)
+        .filter(|(_, other)| {
+            (other.flags & MobjFlags::SHOOTABLE) != MobjFlags::NONE
+                && (other.x - x).abs() < other.radius + mo.radius
+                && (other.y - y).abs() < other.radius + mo.radius
+        })
+        .map(|(id, _)| id)
+        .collect::<Vec<ThinkerId>>();
+
+    let telefrags = mo.kind == MobjType::Player || map.name == "MAP30";
+    if !in_the_way.is_empty() && !telefrags {
+        return false;
+    }
+    for id in in_the_way {
+        world.with_mobj(id, |world, other| {
+            damage_mobj(map, world, other, Some(mo), Some(mo), TELEFRAG_DAMAGE)
+        });
+    }
+
+    set_position(map, mo, x, y);
+    true
+}
+
+/// Send a mobj crossing the front of a line to the teleport destination in the sectors
+/// tagged like the line, like vanilla EV_Teleport
+///
+/// Returns true when the mobj teleported
+pub fn teleport(
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    line: usize,
+    side: usize,
+) -> bool {
+    // Missiles go through, and a teleporter can be left from its back
+    if (mo.flags & MobjFlags::MISSILE) != MobjFlags::NONE || side == 1 {
+        return false;
+    }
+
+    for sector in tagged_sectors(map, map.linedefs[line].sector_tag) {
+        let destination = world.mobjs().find_map(|(_, dest)| {
+            (dest.kind == MobjType::TeleportMan && map.sector_at(dest.x, dest.y) == sector)
+                .then_some((dest.x, dest.y, dest.angle))
+        });
+        let Some((x, y, angle)) = destination else {
+            continue;
+        };
+
+        let (old_x, old_y, old_z) = (mo.x, mo.y, mo.z);
+        if !teleport_move(map, world, mo, x, y) {
+            return false;
+        }
+        mo.z = mo.floor_z;
+
+        // Fog where the mobj was, and in front of it where it is
+        let fog = Mobj::spawn_at(map, world, MobjType::TeleportFog, old_x, old_y, old_z);
+        world.add(Thinker::Mobj(fog));
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (fog_x, fog_y) = (x + 20.0 * cos, y + 20.0 * sin);
+        let fog = Mobj::spawn_at(map, world, MobjType::TeleportFog, fog_x, fog_y, mo.z);
+        world.add(Thinker::Mobj(fog));
+
+        if mo.kind == MobjType::Player {
+            mo.reaction_time = TELEPORT_FREEZE;
+        }
+        mo.angle = angle;
+        mo.momx = 0.0;
+        mo.momy = 0.0;
+        mo.momz = 0.0;
+        return true;
+    }
+    false
+}
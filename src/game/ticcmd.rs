@@ -0,0 +1,42 @@
+use bitflags::bitflags;
+
+/// Forward speeds when walking and running, like vanilla forwardmove
+pub const FORWARD_MOVE: [i32; 2] = [0x19, 0x32];
+/// Strafe speeds when walking and running, like vanilla sidemove
+pub const SIDE_MOVE: [i32; 2] = [0x18, 0x28];
+
+bitflags! {
+    /// Buttons held during a tic, like vanilla buttons_t
+    #[derive(Default)]
+    pub struct Buttons: u8 {
+        const NONE = 0;
+        const ATTACK = 0x01;
+        const USE = 0x02;
+        /// Switch to the weapon in the WEAPON bits
+        const CHANGE = 0x04;
+        /// Weapon slot minus one, shifted by WEAPON_SHIFT
+        const WEAPON = 0x38;
+    }
+}
+
+/// Position of the weapon slot in the buttons, like vanilla BT_WEAPONSHIFT
+pub const WEAPON_SHIFT: u8 = 3;
+
+/// What the player asks for during one tic, like vanilla ticcmd_t
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TicCmd {
+    pub forward_move: i32,
+    /// Positive to the right
+    pub side_move: i32,
+    pub buttons: Buttons,
+}
+
+impl TicCmd {
+    /// Weapon slot asked for, from 0 for the fist to 6 for the BFG
+    pub fn weapon_slot(&self) -> Option<usize> {
+        if (self.buttons & Buttons::CHANGE) == Buttons::NONE {
+            return None;
+        }
+        Some(((self.buttons & Buttons::WEAPON).bits() >> WEAPON_SHIFT) as usize)
+    }
+}
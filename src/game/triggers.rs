@@ -0,0 +1,423 @@
+//! Line specials set off by crossing, using or shooting lines, like vanilla
+//! P_CrossSpecialLine, P_UseSpecialLine and P_ShootSpecialLine
+
+use super::{
+    info::{MobjFlags, MobjType},
+    mobj::Mobj,
+    movement::crossed_special_lines,
+    player::{Key, Player},
+    specials::{line_action, start_line_action},
+    switches::change_switch_texture,
+    teleport::teleport,
+    world::World,
+};
+use crate::wad::map::{LinedefFlags, WadMap};
+
+/// How a line special is set off
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Trigger {
+    /// Crossing the line
+    Walk,
+    /// Using a switch on the line
+    Switch,
+    /// Using the door on the back of the line
+    Door,
+    /// Shooting the line
+    Gun,
+}
+
+/// How a line special is set off and if it can be again, from the vanilla line types
+pub fn line_trigger(special: i16) -> Option<(Trigger, bool)> {
+    let trigger = match special {
+        2..=6 | 8 | 10 | 12 | 13 | 16 | 17 | 19 | 22 | 25 | 30 | 35..=40 | 44 | 52..=54 => {
+            (Trigger::Walk, false)
+        }
+        56..=59 | 100 | 104 | 108..=110 | 119 | 121 | 124 | 125 | 130 | 141 => {
+            (Trigger::Walk, false)
+        }
+        72..=77 | 79..=84 | 86..=98 | 105..=107 | 120 | 126 | 128 | 129 => (Trigger::Walk, true),
+
+        7 | 9 | 11 | 14 | 15 | 18 | 20 | 21 | 23 | 29 | 41 | 49..=51 | 55 | 71 => {
+            (Trigger::Switch, false)
+        }
+        101..=103 | 111..=113 | 122 | 127 | 131 | 133 | 135 | 137 | 140 => (Trigger::Switch, false),
+        42 | 43 | 45 | 60..=70 | 99 | 114..=116 | 123 | 132 | 134 | 136 | 138 | 139 => {
+            (Trigger::Switch, true)
+        }
+
+        1 | 26..=28 | 117 => (Trigger::Door, true),
+        31..=34 | 118 => (Trigger::Door, false),
+
+        24 | 47 => (Trigger::Gun, false),
+        46 => (Trigger::Gun, true),
+        _ => return None,
+    };
+    Some(trigger)
+}
+
+/// Card and skull opening a locked line, either one does
+fn required_keys(special: i16) -> Option<(Key, Key)> {
+    match special {
+        26 | 32 | 99 | 133 => Some((Key::BlueCard, Key::BlueSkull)),
+        27 | 34 | 136 | 137 => Some((Key::YellowCard, Key::YellowSkull)),
+        28 | 33 | 134 | 135 => Some((Key::RedCard, Key::RedSkull)),
+        _ => None,
+    }
+}
+
+/// Message shown to a player missing a key, like vanilla PD_BLUEK and PD_BLUEO
+fn missing_key_message(key: Key, trigger: Trigger) -> &'static str {
+    match (key, trigger) {
+        (Key::BlueCard, Trigger::Door) => "You need a blue key to open this door",
+        (Key::YellowCard, Trigger::Door) => "You need a yellow key to open this door",
+        (Key::RedCard, Trigger::Door) => "You need a red key to open this door",
+        (Key::BlueCard, _) => "You need a blue key to activate this object",
+        (Key::YellowCard, _) => "You need a yellow key to activate this object",
+        _ => "You need a red key to activate this object",
+    }
+}
+
+/// Specials monsters set off, players set off the others
+fn monster_can_trigger(special: i16, trigger: Trigger) -> bool {
+    match trigger {
+        Trigger::Walk => matches!(special, 4 | 10 | 39 | 88 | 97 | 125 | 126),
+        // Monsters have no keys for the other doors
+        Trigger::Door => special == 1,
+        Trigger::Switch => false,
+        Trigger::Gun => special == 46,
+    }
+}
+
+/// Specials missiles set off by crossing their line, like the missile filter of vanilla
+/// P_CrossSpecialLine
+///
+/// Only teleporters, which then refuse to take them. The missiles of the player, the imp, the
+/// cacodemon and the barons set off nothing
+fn missile_can_trigger(kind: MobjType, special: i16) -> bool {
+    let ignored = matches!(
+        kind,
+        MobjType::Rocket
+            | MobjType::Plasma
+            | MobjType::Bfg
+            | MobjType::TroopShot
+            | MobjType::HeadShot
+            | MobjType::BruiserShot
+    );
+    !ignored && matches!(special, 39 | 97 | 125 | 126)
+}
+
+/// Set off the special of a line the way it was touched
+///
+/// Lines set off once lose their special, walk-over and gun lines even when nothing started
+fn trigger_line(
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    mut player: Option<&mut Player>,
+    line: usize,
+    side: usize,
+    trigger: Trigger,
+) -> bool {
+    let special = map.linedefs[line].special_type;
+    let Some((kind, repeat)) = line_trigger(special) else {
+        return false;
+    };
+    if kind != trigger {
+        return false;
+    }
+
+    let by_player = player.is_some();
+    match player.as_deref_mut() {
+        Some(player) => {
+            // Monster teleporters
+            if matches!(special, 125 | 126) {
+                return false;
+            }
+            if let Some((card, skull)) = required_keys(special) {
+                if !player.status.has_key(card) && !player.status.has_key(skull) {
+                    player.message = Some(missing_key_message(card, trigger));
+                    return false;
+                }
+            }
+        }
+        None => {
+            if (mo.flags & MobjFlags::MISSILE) != MobjFlags::NONE
+                && !missile_can_trigger(mo.kind, special)
+            {
+                return false;
+            }
+            if !monster_can_trigger(special, trigger) {
+                return false;
+            }
+            let secret = (map.linedefs[line].flags & LinedefFlags::SECRET) != LinedefFlags::NONE;
+            if trigger != Trigger::Walk && secret {
+                return false;
+            }
+        }
+    }
+
+    let started = match (special, line_action(special)) {
+        (39 | 97 | 125 | 126, _) => {
+            let teleported = teleport(map, world, mo, line, side);
+            if let Some(player) = player {
+                player.teleported |= teleported;
+            }
+            teleported
+        }
+        (_, Some(action)) => start_line_action(map, world, line, action, by_player),
+        _ => false,
+    };
+    // Gun lines are switches too, that do not need something to start
+    if (started && trigger == Trigger::Switch) || trigger == Trigger::Gun {
+        change_switch_texture(map, world, line, repeat);
+    }
+    if !repeat && (started || matches!(trigger, Trigger::Walk | Trigger::Gun)) {
+        map.linedefs[line].special_type = 0;
+    }
+    started
+}
+
+/// A mobj crossed a special line from a side, like vanilla P_CrossSpecialLine
+pub fn cross_special_line(
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    player: Option<&mut Player>,
+    line: usize,
+    side: usize,
+) {
+    trigger_line(map, world, mo, player, line, side, Trigger::Walk);
+}
+
+/// Set off the special lines crossed by a mobj moved from a point
+///
+/// The lines left are not crossed anymore once a teleporter moved the mobj
+pub fn cross_special_lines(
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    mut player: Option<&mut Player>,
+    from: (f32, f32),
+) {
+    let to = (mo.x, mo.y);
+    for (line, side) in crossed_special_lines(map, from, to) {
+        if (mo.x, mo.y) != to {
+            break;
+        }
+        cross_special_line(map, world, mo, player.as_deref_mut(), line, side);
+    }
+}
+
+/// A mobj used a special line from a side, like vanilla P_UseSpecialLine
+///
+/// Only the front side of switches and doors can be used. Returns true when something started
+pub fn use_special_line(
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    player: Option<&mut Player>,
+    line: usize,
+    side: usize,
+) -> bool {
+    if side != 0 {
+        return false;
+    }
+    let trigger = match line_trigger(map.linedefs[line].special_type) {
+        Some((Trigger::Door, _)) => Trigger::Door,
+        _ => Trigger::Switch,
+    };
+    trigger_line(map, world, mo, player, line, side, trigger)
+}
+
+/// A mobj shot a special line, like vanilla P_ShootSpecialLine
+pub fn shoot_special_line(
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    player: Option<&mut Player>,
+    line: usize,
+) {
+    trigger_line(map, world, mo, player, line, 0, Trigger::Gun);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wad::test_wad::steps_map;
+
+    /// Line between the first two steps, the third step is tagged for it
+    const LINE: usize = 3;
+
+    fn steps_with(special: i16) -> (WadMap, World) {
+        let mut map = steps_map();
+        map.linedefs[LINE].special_type = special;
+        map.linedefs[LINE].sector_tag = 1;
+        map.sectors[2].tag = 1;
+        (map, World::new())
+    }
+
+    fn line_special(map: &WadMap) -> i16 {
+        map.linedefs[LINE].special_type
+    }
+
+    /// Set off the line from its front the way a player touches it
+    fn by_player(map: &mut WadMap, world: &mut World, player: &mut Player, how: Trigger) -> bool {
+        let mut mo = Mobj::spawn(map, world, MobjType::Player, 64.0, 64.0);
+        trigger_line(map, world, &mut mo, Some(player), LINE, 0, how)
+    }
+
+    /// Set off the line from its front the way a monster or a missile touches it
+    fn by_thing(map: &mut WadMap, world: &mut World, kind: MobjType, how: Trigger) -> bool {
+        let mut mo = Mobj::spawn(map, world, kind, 64.0, 64.0);
+        trigger_line(map, world, &mut mo, None, LINE, 0, how)
+    }
+
+    #[test]
+    fn walk_lines_go_once_or_again() {
+        let mut player = Player::default();
+        // W1 door open stay
+        let (mut map, mut world) = steps_with(2);
+        assert!(by_player(&mut map, &mut world, &mut player, Trigger::Walk));
+        assert_eq!(line_special(&map), 0);
+
+        // WR door open stay
+        let (mut map, mut world) = steps_with(86);
+        assert!(by_player(&mut map, &mut world, &mut player, Trigger::Walk));
+        assert_eq!(line_special(&map), 86);
+        // Walk lines are not used
+        assert!(!by_player(
+            &mut map,
+            &mut world,
+            &mut player,
+            Trigger::Switch
+        ));
+    }
+
+    #[test]
+    fn switch_lines_go_once_or_again() {
+        let mut player = Player::default();
+        // S1 door open stay
+        let (mut map, mut world) = steps_with(103);
+        let mut mo = Mobj::spawn(&map, &mut world, MobjType::Player, 64.0, 64.0);
+        // Only from the front
+        assert!(!use_special_line(
+            &mut map,
+            &mut world,
+            &mut mo,
+            Some(&mut player),
+            LINE,
+            1
+        ));
+        assert!(use_special_line(
+            &mut map,
+            &mut world,
+            &mut mo,
+            Some(&mut player),
+            LINE,
+            0
+        ));
+        assert_eq!(line_special(&map), 0);
+
+        // SR door open stay
+        let (mut map, mut world) = steps_with(61);
+        assert!(by_player(
+            &mut map,
+            &mut world,
+            &mut player,
+            Trigger::Switch
+        ));
+        assert_eq!(line_special(&map), 61);
+    }
+
+    #[test]
+    fn gun_lines_go_once() {
+        let mut player = Player::default();
+        // G1 floor raise
+        let (mut map, mut world) = steps_with(24);
+        assert!(by_player(&mut map, &mut world, &mut player, Trigger::Gun));
+        assert_eq!(line_special(&map), 0);
+    }
+
+    #[test]
+    fn locked_doors_need_the_card_or_the_skull() {
+        let mut player = Player::default();
+        // DR blue door
+        let (mut map, mut world) = steps_with(26);
+        assert!(!by_player(&mut map, &mut world, &mut player, Trigger::Door));
+        assert_eq!(
+            player.message,
+            Some("You need a blue key to open this door")
+        );
+        player.status.keys[Key::BlueSkull as usize] = true;
+        assert!(by_player(&mut map, &mut world, &mut player, Trigger::Door));
+
+        // Monsters have no keys
+        let (mut map, mut world) = steps_with(26);
+        assert!(!by_thing(
+            &mut map,
+            &mut world,
+            MobjType::Troop,
+            Trigger::Door
+        ));
+    }
+
+    #[test]
+    fn exits_end_the_level() {
+        let mut player = Player::default();
+        // S1 exit
+        let (mut map, mut world) = steps_with(11);
+        assert!(by_player(
+            &mut map,
+            &mut world,
+            &mut player,
+            Trigger::Switch
+        ));
+        assert!(world.exit_level && !world.secret_exit);
+
+        // W1 secret exit
+        let (mut map, mut world) = steps_with(124);
+        assert!(by_player(&mut map, &mut world, &mut player, Trigger::Walk));
+        assert!(world.exit_level && world.secret_exit);
+    }
+
+    #[test]
+    fn monsters_only_set_off_their_lines() {
+        // WR door open stay, WR lift, W1 door
+        for (special, started) in [(86, false), (88, true), (4, true)] {
+            let (mut map, mut world) = steps_with(special);
+            let by_monster = by_thing(&mut map, &mut world, MobjType::Troop, Trigger::Walk);
+            assert_eq!(by_monster, started, "line type {}", special);
+        }
+    }
+
+    #[test]
+    fn missiles_only_go_through_teleporters() {
+        // WR door open stay, WR lift, W1 door
+        for special in [86, 88, 4] {
+            for kind in [MobjType::FatShot, MobjType::Rocket] {
+                let (mut map, mut world) = steps_with(special);
+                assert!(!by_thing(&mut map, &mut world, kind, Trigger::Walk));
+                assert_eq!(line_special(&map), special);
+            }
+        }
+
+        // A W1 teleporter does not take the missile but goes away, like vanilla
+        let (mut map, mut world) = steps_with(39);
+        assert!(!by_thing(
+            &mut map,
+            &mut world,
+            MobjType::FatShot,
+            Trigger::Walk
+        ));
+        assert_eq!(line_special(&map), 0);
+        // Rockets do not set off anything
+        let (mut map, mut world) = steps_with(39);
+        assert!(!by_thing(
+            &mut map,
+            &mut world,
+            MobjType::Rocket,
+            Trigger::Walk
+        ));
+        assert_eq!(line_special(&map), 39);
+    }
+}
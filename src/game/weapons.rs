@@ -0,0 +1,666 @@
+//! Player weapons and the first person sprites showing them, like vanilla p_pspr.c
+
+use super::{
+    attacks::{
+        aim_line_attack, auto_aim, line_attack, MELEERANGE, MISSILERANGE, MISSILE_AIM_RANGE,
+    },
+    enemy::noise_alert,
+    info::{MobjFlags, MobjType},
+    missiles::spawn_player_missile,
+    mobj::Mobj,
+    player::{AmmoType, Player, PlayerStatus, Power, Weapon, NUM_WEAPONS},
+    states::{ActionFn, StateNum},
+    ticcmd::Buttons,
+    world::World,
+};
+use crate::wad::map::WadMap;
+
+/// Height of a raised weapon on the screen, like vanilla WEAPONTOP
+const WEAPONTOP: f32 = 32.0;
+/// Height of a weapon out of sight, like vanilla WEAPONBOTTOM
+const WEAPONBOTTOM: f32 = 128.0;
+/// Pixels a weapon goes down or up each tic when switching
+const LOWERSPEED: f32 = 6.0;
+const RAISESPEED: f32 = 6.0;
+/// Cells used by a BFG shot, like vanilla BFGCELLS
+const BFGCELLS: i32 = 40;
+/// Degrees of one step of the random spread, a vanilla angle shifted by 18 bits
+const SPREAD: f32 = 360.0 / 16384.0;
+/// Tics of a whole weapon bobbing cycle
+const BOB_PERIOD: f32 = 64.0;
+
+/// Layers of the first person view, like vanilla psprnum_t
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PspriteLayer {
+    Weapon,
+    Flash,
+}
+
+pub const NUM_PSPRITES: usize = 2;
+
+/// A first person sprite, like vanilla pspdef_t
+#[derive(Debug, Copy, Clone)]
+pub struct Psprite {
+    /// Null when the layer shows nothing
+    pub state: StateNum,
+    /// Tics left before the next state, -1 stays forever
+    pub tics: i32,
+    /// Position on the 320x200 screen
+    pub sx: f32,
+    pub sy: f32,
+}
+
+impl Default for Psprite {
+    fn default() -> Self {
+        Psprite {
+            state: StateNum::Null,
+            tics: 0,
+            sx: 0.0,
+            sy: 0.0,
+        }
+    }
+}
+
+/// States of a weapon, like vanilla weaponinfo_t
+struct WeaponInfo {
+    up: StateNum,
+    down: StateNum,
+    ready: StateNum,
+    attack: StateNum,
+    flash: StateNum,
+}
+
+/// Weapon states in the order of the weapons
+const WEAPON_INFO: [WeaponInfo; NUM_WEAPONS] = {
+    use StateNum::*;
+    [
+        WeaponInfo {
+            up: PunchUp,
+            down: PunchDown,
+            ready: Punch,
+            attack: Punch1,
+            flash: Null,
+        },
+        WeaponInfo {
+            up: PistolUp,
+            down: PistolDown,
+            ready: Pistol,
+            attack: Pistol1,
+            flash: PistolFlash,
+        },
+        WeaponInfo {
+            up: SgunUp,
+            down: SgunDown,
+            ready: Sgun,
+            attack: Sgun1,
+            flash: SgunFlash1,
+        },
+        WeaponInfo {
+            up: ChainUp,
+            down: ChainDown,
+            ready: Chain,
+            attack: Chain1,
+            flash: ChainFlash1,
+        },
+        WeaponInfo {
+            up: MissileUp,
+            down: MissileDown,
+            ready: Missile,
+            attack: Missile1,
+            flash: MissileFlash1,
+        },
+        WeaponInfo {
+            up: PlasmaUp,
+            down: PlasmaDown,
+            ready: Plasma,
+            attack: Plasma1,
+            flash: PlasmaFlash1,
+        },
+        WeaponInfo {
+            up: BfgUp,
+            down: BfgDown,
+            ready: Bfg,
+            attack: Bfg1,
+            flash: BfgFlash1,
+        },
+        WeaponInfo {
+            up: SawUp,
+            down: SawDown,
+            ready: Saw,
+            attack: Saw1,
+            flash: Null,
+        },
+        WeaponInfo {
+            up: DsgunUp,
+            down: DsgunDown,
+            ready: Dsgun,
+            attack: Dsgun1,
+            flash: DsgunFlash1,
+        },
+    ]
+};
+
+/// Weapons of the slot keys, the chainsaw and the super shotgun share the fist and shotgun slots
+const SLOT_WEAPONS: [Weapon; 7] = [
+    Weapon::Fist,
+    Weapon::Pistol,
+    Weapon::Shotgun,
+    Weapon::Chaingun,
+    Weapon::Missile,
+    Weapon::Plasma,
+    Weapon::Bfg,
+];
+
+fn info(weapon: Weapon) -> &'static WeaponInfo {
+    &WEAPON_INFO[weapon as usize]
+}
+
+/// Weapon to switch to for a slot key, None when it is not owned or already in hand
+///
+/// Like the weapon change of vanilla P_PlayerThink
+pub fn slot_weapon(status: &PlayerStatus, slot: usize) -> Option<Weapon> {
+    let mut weapon = *SLOT_WEAPONS.get(slot)?;
+    // The fist is only taken back from the chainsaw when berserk
+    if weapon == Weapon::Fist
+        && status.has_weapon(Weapon::Chainsaw)
+        && !(status.ready_weapon == Weapon::Chainsaw && status.has_power(Power::Strength))
+    {
+        weapon = Weapon::Chainsaw;
+    }
+    if weapon == Weapon::Shotgun
+        && status.has_weapon(Weapon::SuperShotgun)
+        && status.ready_weapon != Weapon::SuperShotgun
+    {
+        weapon = Weapon::SuperShotgun;
+    }
+    (status.has_weapon(weapon) && weapon != status.ready_weapon).then_some(weapon)
+}
+
+/// Weapons in the order the next and previous weapon actions go through them, like the
+/// weapon_order_table of later ports
+const WEAPON_ORDER: [Weapon; NUM_WEAPONS] = [
+    Weapon::Fist,
+    Weapon::Chainsaw,
+    Weapon::Pistol,
+    Weapon::Shotgun,
+    Weapon::SuperShotgun,
+    Weapon::Chaingun,
+    Weapon::Missile,
+    Weapon::Plasma,
+    Weapon::Bfg,
+];
+
+/// Slot key of the owned weapon after the one coming up or in hand, before it when `step` is
+/// negative
+///
+/// The slot goes through the same rules as the keys, like G_NextWeapon of later ports
+pub fn next_weapon_slot(player: &Player, step: i32) -> Option<usize> {
+    let current = player.pending_weapon.unwrap_or(player.status.ready_weapon);
+    let start = WEAPON_ORDER.iter().position(|&weapon| weapon == current)?;
+    let count = WEAPON_ORDER.len() as i32;
+    let weapon = (1..count)
+        .map(|offset| {
+            WEAPON_ORDER[(start as i32 + offset * step.signum()).rem_euclid(count) as usize]
+        })
+        .find(|&weapon| player.status.has_weapon(weapon))?;
+    match weapon {
+        Weapon::Chainsaw => Some(0),
+        Weapon::SuperShotgun => Some(2),
+        _ => SLOT_WEAPONS.iter().position(|&slot| slot == weapon),
+    }
+}
+
+/// Bring the ready weapon up at the start of a level, like vanilla P_SetupPsprites
+pub fn setup_psprites(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    player.psprites = [Psprite::default(); NUM_PSPRITES];
+    player.pending_weapon = Some(player.status.ready_weapon);
+    bring_up_weapon(player, map, world, mo);
+}
+
+/// Run the first person sprites for a tic, like vanilla P_MovePsprites
+pub fn move_psprites(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    for layer in [PspriteLayer::Weapon, PspriteLayer::Flash] {
+        let psp = &mut player.psprites[layer as usize];
+        if psp.state == StateNum::Null || psp.tics == -1 {
+            continue;
+        }
+        psp.tics -= 1;
+        if psp.tics == 0 {
+            let next = psp.state.info().next;
+            set_psprite(player, map, world, mo, layer, next);
+        }
+    }
+
+    // The flash follows the weapon
+    let weapon = player.psprites[PspriteLayer::Weapon as usize];
+    let flash = &mut player.psprites[PspriteLayer::Flash as usize];
+    flash.sx = weapon.sx;
+    flash.sy = weapon.sy;
+}
+
+/// Enter a state and the following zero tic ones, running their actions, like vanilla
+/// P_SetPsprite
+fn set_psprite(
+    player: &mut Player,
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    layer: PspriteLayer,
+    state: StateNum,
+) {
+    let mut state = state;
+    loop {
+        let psp = &mut player.psprites[layer as usize];
+        if state == StateNum::Null {
+            psp.state = StateNum::Null;
+            return;
+        }
+        let info = state.info();
+        psp.state = state;
+        psp.tics = info.tics;
+
+        // The action can go to another state
+        if let Some(action) = info.action {
+            psprite_action(player, map, world, mo, action);
+            if player.psprites[layer as usize].state == StateNum::Null {
+                return;
+            }
+        }
+        let psp = &player.psprites[layer as usize];
+        if psp.tics != 0 {
+            return;
+        }
+        state = psp.state.info().next;
+    }
+}
+
+/// Run the code of a weapon state, named after the vanilla A_ functions
+fn psprite_action(
+    player: &mut Player,
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    action: ActionFn,
+) {
+    match action {
+        ActionFn::Light0 => player.extra_light = 0,
+        ActionFn::Light1 => player.extra_light = 1,
+        ActionFn::Light2 => player.extra_light = 2,
+        ActionFn::WeaponReady => weapon_ready(player, map, world, mo),
+        ActionFn::Lower => lower(player, map, world, mo),
+        ActionFn::Raise => raise(player, map, world, mo),
+        ActionFn::ReFire | ActionFn::CloseShotgun2 => refire(player, map, world, mo),
+        ActionFn::CheckReload => {
+            check_ammo(player, map, world, mo);
+        }
+        ActionFn::GunFlash => {
+            mo.set_state(StateNum::PlayAtk2);
+            let flash = info(player.status.ready_weapon).flash;
+            set_psprite(player, map, world, mo, PspriteLayer::Flash, flash);
+        }
+        ActionFn::Punch => punch(player, map, world, mo),
+        ActionFn::Saw => saw(player, map, world, mo),
+        ActionFn::FirePistol => fire_pistol(player, map, world, mo),
+        ActionFn::FireShotgun => fire_shotgun(player, map, world, mo),
+        ActionFn::FireShotgun2 => fire_shotgun2(player, map, world, mo),
+        ActionFn::FireCGun => fire_cgun(player, map, world, mo),
+        ActionFn::FireMissile => {
+            use_ammo(player, 1);
+            spawn_player_missile(map, world, mo, MobjType::Rocket);
+        }
+        ActionFn::FirePlasma => {
+            use_ammo(player, 1);
+            let flash = if (world.random.gameplay() & 1) != 0 {
+                StateNum::PlasmaFlash2
+            } else {
+                StateNum::PlasmaFlash1
+            };
+            set_psprite(player, map, world, mo, PspriteLayer::Flash, flash);
+            spawn_player_missile(map, world, mo, MobjType::Plasma);
+        }
+        ActionFn::FireBfg => {
+            use_ammo(player, BFGCELLS);
+            spawn_player_missile(map, world, mo, MobjType::Bfg);
+        }
+        // Only sounds, or actions of the mobj states
+        _ => (),
+    }
+}
+
+/// Fire, or put the weapon down to switch, or bob it while walking, like vanilla A_WeaponReady
+fn weapon_ready(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    // Done shooting
+    if mo.state == StateNum::PlayAtk1 || mo.state == StateNum::PlayAtk2 {
+        mo.set_state(StateNum::Play);
+    }
+
+    // A dead player puts the weapon away too
+    let weapon = player.status.ready_weapon;
+    if player.pending_weapon.is_some() || player.status.health <= 0 {
+        set_psprite(
+            player,
+            map,
+            world,
+            mo,
+            PspriteLayer::Weapon,
+            info(weapon).down,
+        );
+        return;
+    }
+
+    // The rocket launcher and the BFG need the trigger released between shots
+    if (player.cmd.buttons & Buttons::ATTACK) != Buttons::NONE {
+        if !player.attack_down || (weapon != Weapon::Missile && weapon != Weapon::Bfg) {
+            player.attack_down = true;
+            fire_weapon(player, map, world, mo);
+            return;
+        }
+    } else {
+        player.attack_down = false;
+    }
+
+    // Swing along a half circle below the top
+    let phase = world.tic as f32 / BOB_PERIOD * std::f32::consts::TAU;
+    let psp = &mut player.psprites[PspriteLayer::Weapon as usize];
+    psp.sx = 1.0 + player.bob * phase.cos();
+    psp.sy = WEAPONTOP + player.bob * (phase % std::f32::consts::PI).sin();
+}
+
+/// Start the attack of the ready weapon if there is ammo for it, like vanilla P_FireWeapon
+///
+/// The monsters hearing it wake up
+fn fire_weapon(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    if !check_ammo(player, map, world, mo) {
+        return;
+    }
+    mo.set_state(StateNum::PlayAtk1);
+    let attack = info(player.status.ready_weapon).attack;
+    set_psprite(player, map, world, mo, PspriteLayer::Weapon, attack);
+    noise_alert(map, world, mo);
+}
+
+/// Ammo used by a shot of a weapon
+fn shot_ammo(weapon: Weapon) -> i32 {
+    match weapon {
+        Weapon::Bfg => BFGCELLS,
+        Weapon::SuperShotgun => 2,
+        _ => 1,
+    }
+}
+
+/// Best weapon left with ammo, in the vanilla order of preference
+fn best_weapon(status: &PlayerStatus) -> Weapon {
+    let owned = |weapon| status.has_weapon(weapon);
+    let ammo = |ammo: AmmoType| status.ammo[ammo as usize];
+    if owned(Weapon::Plasma) && ammo(AmmoType::Cell) > 0 {
+        Weapon::Plasma
+    } else if owned(Weapon::SuperShotgun) && ammo(AmmoType::Shell) > 2 {
+        Weapon::SuperShotgun
+    } else if owned(Weapon::Chaingun) && ammo(AmmoType::Clip) > 0 {
+        Weapon::Chaingun
+    } else if owned(Weapon::Shotgun) && ammo(AmmoType::Shell) > 0 {
+        Weapon::Shotgun
+    } else if ammo(AmmoType::Clip) > 0 {
+        Weapon::Pistol
+    } else if owned(Weapon::Chainsaw) {
+        Weapon::Chainsaw
+    } else if owned(Weapon::Missile) && ammo(AmmoType::Missile) > 0 {
+        Weapon::Missile
+    } else if owned(Weapon::Bfg) && ammo(AmmoType::Cell) > BFGCELLS {
+        Weapon::Bfg
+    } else {
+        Weapon::Fist
+    }
+}
+
+/// Check there is ammo for a shot, or switch to another weapon, like vanilla P_CheckAmmo
+fn check_ammo(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) -> bool {
+    let weapon = player.status.ready_weapon;
+    let enough = weapon
+        .ammo()
+        .is_none_or(|ammo| player.status.ammo[ammo as usize] >= shot_ammo(weapon));
+    if enough {
+        return true;
+    }
+
+    player.pending_weapon = Some(best_weapon(&player.status));
+    set_psprite(
+        player,
+        map,
+        world,
+        mo,
+        PspriteLayer::Weapon,
+        info(weapon).down,
+    );
+    false
+}
+
+fn use_ammo(player: &mut Player, count: i32) {
+    if let Some(ammo) = player.status.ready_weapon.ammo() {
+        player.status.ammo[ammo as usize] -= count;
+    }
+}
+
+/// Fire again while the trigger is held, like vanilla A_ReFire
+fn refire(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    if (player.cmd.buttons & Buttons::ATTACK) != Buttons::NONE
+        && player.pending_weapon.is_none()
+        && player.status.health > 0
+    {
+        player.refire += 1;
+        fire_weapon(player, map, world, mo);
+    } else {
+        player.refire = 0;
+        check_ammo(player, map, world, mo);
+    }
+}
+
+/// Put the weapon down, then bring up the next one, like vanilla A_Lower
+fn lower(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    let psp = &mut player.psprites[PspriteLayer::Weapon as usize];
+    psp.sy += LOWERSPEED;
+    if psp.sy < WEAPONBOTTOM {
+        return;
+    }
+    // Nothing comes up for a dead player
+    if player.status.health <= 0 {
+        psp.sy = WEAPONBOTTOM;
+        return;
+    }
+    if let Some(weapon) = player.pending_weapon {
+        player.status.ready_weapon = weapon;
+    }
+    bring_up_weapon(player, map, world, mo);
+}
+
+/// Raise the weapon until it is ready, like vanilla A_Raise
+fn raise(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    let psp = &mut player.psprites[PspriteLayer::Weapon as usize];
+    psp.sy -= RAISESPEED;
+    if psp.sy > WEAPONTOP {
+        return;
+    }
+    psp.sy = WEAPONTOP;
+    let ready = info(player.status.ready_weapon).ready;
+    set_psprite(player, map, world, mo, PspriteLayer::Weapon, ready);
+}
+
+/// Start raising the pending weapon from the bottom, like vanilla P_BringUpWeapon
+fn bring_up_weapon(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    let weapon = player
+        .pending_weapon
+        .take()
+        .unwrap_or(player.status.ready_weapon);
+    let psp = &mut player.psprites[PspriteLayer::Weapon as usize];
+    psp.sx = 1.0;
+    psp.sy = WEAPONBOTTOM;
+    set_psprite(
+        player,
+        map,
+        world,
+        mo,
+        PspriteLayer::Weapon,
+        info(weapon).up,
+    );
+}
+
+/// Angle from a mobj to another, in degrees
+fn angle_to(mo: &Mobj, target: &Mobj) -> f32 {
+    (target.y - mo.y).atan2(target.x - mo.x).to_degrees()
+}
+
+/// Hit what is at arm's length and face it, ten times harder when berserk, like vanilla A_Punch
+fn punch(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    let mut damage = (world.random.gameplay() % 10 + 1) * 2;
+    if player.status.has_power(Power::Strength) {
+        damage *= 10;
+    }
+    let angle = mo.angle + world.random.sub_random() as f32 * SPREAD;
+    let (slope, target) = aim_line_attack(map, world, mo, angle, MELEERANGE);
+    line_attack(
+        map,
+        world,
+        mo,
+        Some(player),
+        angle,
+        MELEERANGE,
+        slope,
+        damage,
+    );
+
+    if let Some(target) = target.and_then(|target| world.mobj(target)) {
+        mo.angle = angle_to(mo, target);
+    }
+}
+
+/// Cut what is at arm's length and turn toward it bit by bit, like vanilla A_Saw
+fn saw(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    let damage = 2 * (world.random.gameplay() % 10 + 1);
+    let angle = mo.angle + world.random.sub_random() as f32 * SPREAD;
+    // A bit further than a punch so the puff sparks
+    let range = MELEERANGE + 1.0;
+    let (slope, target) = aim_line_attack(map, world, mo, angle, range);
+    line_attack(map, world, mo, Some(player), angle, range, slope, damage);
+
+    let Some(target) = target.and_then(|target| world.mobj(target)) else {
+        return;
+    };
+    let to_target = angle_to(mo, target);
+    let delta = (to_target - mo.angle + 180.0).rem_euclid(360.0) - 180.0;
+    let step = 90.0 / 20.0;
+    if delta < 0.0 {
+        mo.angle = if delta < -step {
+            to_target + 90.0 / 21.0
+        } else {
+            mo.angle - step
+        };
+    } else {
+        mo.angle = if delta > step {
+            to_target - 90.0 / 21.0
+        } else {
+            mo.angle + step
+        };
+    }
+    mo.flags |= MobjFlags::JUSTATTACKED;
+}
+
+/// Slope for bullets: straight ahead, or a bit to a side when a target is there, like
+/// vanilla P_BulletSlope
+fn bullet_slope(map: &WadMap, world: &World, mo: &Mobj) -> f32 {
+    auto_aim(map, world, mo, MISSILE_AIM_RANGE).map_or(0.0, |(_, slope)| slope)
+}
+
+/// A bullet going straight, or spread when not `accurate`, like vanilla P_GunShot
+fn gun_shot(
+    player: &mut Player,
+    map: &mut WadMap,
+    world: &mut World,
+    mo: &mut Mobj,
+    slope: f32,
+    accurate: bool,
+) {
+    let damage = 5 * (world.random.gameplay() % 3 + 1);
+    let mut angle = mo.angle;
+    if !accurate {
+        angle += world.random.sub_random() as f32 * SPREAD;
+    }
+    line_attack(
+        map,
+        world,
+        mo,
+        Some(player),
+        angle,
+        MISSILERANGE,
+        slope,
+        damage,
+    );
+}
+
+/// Shoot the ready weapon with its flash, the player body showing the shot
+fn start_shot(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj, ammo: i32) {
+    mo.set_state(StateNum::PlayAtk2);
+    use_ammo(player, ammo);
+    let flash = info(player.status.ready_weapon).flash;
+    set_psprite(player, map, world, mo, PspriteLayer::Flash, flash);
+}
+
+/// The first bullet of a burst goes straight, like vanilla A_FirePistol
+fn fire_pistol(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    start_shot(player, map, world, mo, 1);
+    let slope = bullet_slope(map, world, mo);
+    let accurate = player.refire == 0;
+    gun_shot(player, map, world, mo, slope, accurate);
+}
+
+/// Seven pellets, like vanilla A_FireShotgun
+fn fire_shotgun(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    start_shot(player, map, world, mo, 1);
+    let slope = bullet_slope(map, world, mo);
+    for _ in 0..7 {
+        gun_shot(player, map, world, mo, slope, false);
+    }
+}
+
+/// Twenty pellets spread wider and up and down, like vanilla A_FireShotgun2
+fn fire_shotgun2(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    start_shot(player, map, world, mo, 2);
+    let slope = bullet_slope(map, world, mo);
+    for _ in 0..20 {
+        let damage = 5 * (world.random.gameplay() % 3 + 1);
+        let angle = mo.angle + world.random.sub_random() as f32 * SPREAD * 2.0;
+        let slope = slope + world.random.sub_random() as f32 / 2048.0;
+        line_attack(
+            map,
+            world,
+            mo,
+            Some(player),
+            angle,
+            MISSILERANGE,
+            slope,
+            damage,
+        );
+    }
+}
+
+/// One bullet per frame of the chaingun with the matching flash, like vanilla A_FireCGun
+fn fire_cgun(player: &mut Player, map: &mut WadMap, world: &mut World, mo: &mut Mobj) {
+    let Some(ammo) = player.status.ready_weapon.ammo() else {
+        return;
+    };
+    if player.status.ammo[ammo as usize] == 0 {
+        return;
+    }
+
+    mo.set_state(StateNum::PlayAtk2);
+    use_ammo(player, 1);
+    let flash = if player.psprites[PspriteLayer::Weapon as usize].state == StateNum::Chain2 {
+        StateNum::ChainFlash2
+    } else {
+        StateNum::ChainFlash1
+    };
+    set_psprite(player, map, world, mo, PspriteLayer::Flash, flash);
+
+    let slope = bullet_slope(map, world, mo);
+    let accurate = player.refire == 0;
+    gun_shot(player, map, world, mo, slope, accurate);
+}
@@ -0,0 +1,544 @@
+use std::collections::HashMap;
+
+use kabal_core::random::Random;
+
+use super::{
+    ceilings::Ceiling,
+    doors::Door,
+    enemy::{float_to_target, skull_movement, Brain},
+    floors::FloorMove,
+    info::{MobjFlags, MobjType},
+    lights::{FireFlicker, Glow, LightFlash, Strobe},
+    missiles::missile_movement,
+    mobj::{tick_state, Mobj},
+    movement::{xy_movement, z_movement},
+    plats::Plat,
+    player::Player,
+    skill::Skill,
+    switches::{update_buttons, Button},
+    triggers::cross_special_lines,
+};
+use crate::wad::map::{WadMap, BLOCK_SIZE};
+
+/// Stable identifier of a thinker, never reused in a level
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThinkerId(u32);
+
+impl ThinkerId {
+    /// Refers to no thinker
+    pub const NONE: ThinkerId = ThinkerId(0);
+}
+
+/// Something updated once per tic, like vanilla thinker_t
+pub enum Thinker {
+    Mobj(Mobj),
+    Door(Door),
+    Plat(Plat),
+    Floor(FloorMove),
+    Ceiling(Ceiling),
+    FireFlicker(FireFlicker),
+    LightFlash(LightFlash),
+    Strobe(Strobe),
+    Glow(Glow),
+}
+
+struct Slot {
+    id: ThinkerId,
+    /// Taken out while the thinker runs
+    thinker: Option<Thinker>,
+    /// Removed during the tic, dropped once every thinker ran
+    removed: bool,
+}
+
+/// Mobjs in each blockmap cell, like vanilla blocklinks
+///
+/// A mobj is linked again when it is put back in the world after it ran or moved
+#[derive(Default)]
+struct BlockLinks {
+    /// Corner of the blockmap of the map
+    origin: (f32, f32),
+    cells: HashMap<(i32, i32), Vec<ThinkerId>>,
+    /// Cell of each linked mobj
+    linked: HashMap<ThinkerId, (i32, i32)>,
+}
+
+impl BlockLinks {
+    fn cell(&self, x: f32, y: f32) -> (i32, i32) {
+        let column = ((x - self.origin.0) / BLOCK_SIZE).floor() as i32;
+        let row = ((y - self.origin.1) / BLOCK_SIZE).floor() as i32;
+        (column, row)
+    }
+
+    /// Put a mobj in the cell of its center, unless nothing collides with it
+    fn link(&mut self, id: ThinkerId, mo: &Mobj) {
+        let cell = self.cell(mo.x, mo.y);
+        let linked = (mo.flags & MobjFlags::NOBLOCKMAP) == MobjFlags::NONE;
+        if linked && self.linked.get(&id) == Some(&cell) {
+            return;
+        }
+        self.unlink(id);
+        if linked {
+            self.cells.entry(cell).or_default().push(id);
+            self.linked.insert(id, cell);
+        }
+    }
+
+    fn unlink(&mut self, id: ThinkerId) {
+        if let Some(cell) = self.linked.remove(&id) {
+            if let Some(ids) = self.cells.get_mut(&cell) {
+                ids.retain(|&other| other != id);
+            }
+        }
+    }
+}
+
+/// Everything living in the level, updated in a fixed order so a game always plays the same
+pub struct World {
+    /// Thinkers in the order they were added, which is the order they run in
+    slots: Vec<Slot>,
+    next_id: u32,
+    /// Thinker moving each busy sector, like vanilla sector_t specialdata
+    sector_movers: HashMap<usize, ThinkerId>,
+    block_links: BlockLinks,
+    /// Repeatable switches waiting to turn off
+    pub buttons: Vec<Button>,
+    /// Tics run since the level started
+    pub tic: u32,
+    /// Random numbers, back at the start of the table on each level
+    pub random: Random,
+    /// The player, its body is one of the mobjs
+    pub player: Player,
+    /// Skill of the game, set when the map things are spawned
+    pub skill: Skill,
+    /// Last mobj heard in each sector, like vanilla sector_t soundtarget
+    pub sound_targets: HashMap<usize, ThinkerId>,
+    /// Targets of the cubes spat by the boss brain
+    pub brain: Brain,
+    /// Secret sectors in the level, like vanilla totalsecret
+    pub total_secrets: i32,
+    /// The level is over, like vanilla G_ExitLevel
+    pub exit_level: bool,
+    /// The level is over through its secret exit, like vanilla secretexit
+    pub secret_exit: bool,
+}
+
+impl World {
+    pub fn new() -> Self {
+        World {
+            slots: Vec::new(),
+            next_id: 1,
+            sector_movers: HashMap::new(),
+            block_links: BlockLinks::default(),
+            buttons: Vec::new(),
+            tic: 0,
+            random: Random::new(),
+            player: Player::default(),
+            skill: Skill::Medium,
+            sound_targets: HashMap::new(),
+            brain: Brain::default(),
+            total_secrets: 0,
+            exit_level: false,
+            secret_exit: false,
+        }
+    }
+
+    /// Spawn the things of the map in their order, the player body on the first player 1 start
+    pub fn spawn_map_things(&mut self, map: &WadMap, skill: Skill) {
+        self.skill = skill;
+        if let [x, y, ..] = map.blockmap[..] {
+            self.block_links.origin = (x as f32, y as f32);
+        }
+        for thing in &map.things {
+            if thing.doomednum == 1 && self.player.mobj == ThinkerId::NONE {
+                let (x, y) = (thing.x as f32, thing.y as f32);
+                let mut mo = Mobj::spawn(map, self, MobjType::Player, x, y);
+                mo.angle = thing.direction();
+                self.player.mobj = self.add(Thinker::Mobj(mo));
+            } else if let Some(mo) = Mobj::from_map_thing(map, self, thing) {
+                self.add(Thinker::Mobj(mo));
+            }
+        }
+    }
+
+    /// Add a thinker at the end of the list, it runs this tic when added by another thinker
+    pub fn add(&mut self, mut thinker: Thinker) -> ThinkerId {
+        let id = ThinkerId(self.next_id);
+        self.next_id += 1;
+        if let Thinker::Mobj(mo) = &mut thinker {
+            mo.id = id;
+            self.block_links.link(id, mo);
+        }
+        self.slots.push(Slot {
+            id,
+            thinker: Some(thinker),
+            removed: false,
+        });
+        id
+    }
+
+    /// Add a thinker moving a sector, the sector is busy until it is removed
+    pub fn add_mover(&mut self, sector: usize, thinker: Thinker) -> ThinkerId {
+        let id = self.add(thinker);
+        self.sector_movers.insert(sector, id);
+        id
+    }
+
+    /// Thinker moving a sector, if any
+    pub fn sector_mover(&self, sector: usize) -> Option<ThinkerId> {
+        self.sector_movers.get(&sector).copied()
+    }
+
+    /// True for a thinker still in the level but taken out of it while it runs
+    pub fn running(&self, id: ThinkerId) -> bool {
+        self.slots
+            .binary_search_by_key(&id, |slot| slot.id)
+            .is_ok_and(|index| {
+                let slot = &self.slots[index];
+                !slot.removed && slot.thinker.is_none()
+            })
+    }
+
+    /// Remove a thinker, it is safe to do while the thinkers run
+    pub fn remove(&mut self, id: ThinkerId) {
+        if let Some(slot) = self.slot_mut(id) {
+            slot.removed = true;
+        }
+        self.sector_movers.retain(|_, mover| *mover != id);
+        self.block_links.unlink(id);
+    }
+
+    fn slot_mut(&mut self, id: ThinkerId) -> Option<&mut Slot> {
+        // Ids grow with the position in the list
+        let index = self.slots.binary_search_by_key(&id, |slot| slot.id).ok()?;
+        Some(&mut self.slots[index])
+    }
+
+    pub fn get(&self, id: ThinkerId) -> Option<&Thinker> {
+        let index = self.slots.binary_search_by_key(&id, |slot| slot.id).ok()?;
+        let slot = &self.slots[index];
+        slot.thinker.as_ref().filter(|_| !slot.removed)
+    }
+
+    pub fn get_mut(&mut self, id: ThinkerId) -> Option<&mut Thinker> {
+        let slot = self.slot_mut(id)?;
+        if slot.removed {
+            return None;
+        }
+        slot.thinker.as_mut()
+    }
+
+    pub fn mobj(&self, id: ThinkerId) -> Option<&Mobj> {
+        match self.get(id)? {
+            Thinker::Mobj(mo) => Some(mo),
+            _ => None,
+        }
+    }
+
+    pub fn mobj_mut(&mut self, id: ThinkerId) -> Option<&mut Mobj> {
+        match self.get_mut(id)? {
+            Thinker::Mobj(mo) => Some(mo),
+            _ => None,
+        }
+    }
+
+    /// Run `f` on a mobj taken out of the world, so it can change the world meanwhile
+    ///
+    /// Returns None when there is no such mobj
+    pub fn with_mobj<T>(
+        &mut self,
+        id: ThinkerId,
+        f: impl FnOnce(&mut World, &mut Mobj) -> T,
+    ) -> Option<T> {
+        let slot = self.slot_mut(id).filter(|slot| !slot.removed)?;
+        if !matches!(slot.thinker, Some(Thinker::Mobj(_))) {
+            return None;
+        }
+        let Some(Thinker::Mobj(mut mo)) = slot.thinker.take() else {
+            return None;
+        };
+        let result = f(self, &mut mo);
+        if self.slot_mut(id).is_some_and(|slot| !slot.removed) {
+            self.block_links.link(id, &mo);
+        }
+        if let Some(slot) = self.slot_mut(id) {
+            slot.thinker = Some(Thinker::Mobj(mo));
+        }
+        Some(result)
+    }
+
+    /// Run `f` on the player taken out of the world, so it can change the world meanwhile
+    ///
+    /// Its body and a copy of its status stay in the world for what the player hits to read
+    pub fn with_player<T>(&mut self, f: impl FnOnce(&mut World, &mut Player) -> T) -> T {
+        let mut player = std::mem::take(&mut self.player);
+        self.player.mobj = player.mobj;
+        self.player.status = player.status.clone();
+        let result = f(self, &mut player);
+        self.player = player;
+        result
+    }
+
+    /// Every thinker still in the level, in running order
+    pub fn iter(&self) -> impl Iterator<Item = (ThinkerId, &Thinker)> {
+        self.slots
+            .iter()
+            .filter(|slot| !slot.removed)
+            .filter_map(|slot| Some((slot.id, slot.thinker.as_ref()?)))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ThinkerId, &mut Thinker)> {
+        self.slots
+            .iter_mut()
+            .filter(|slot| !slot.removed)
+            .filter_map(|slot| Some((slot.id, slot.thinker.as_mut()?)))
+    }
+
+    pub fn mobjs(&self) -> impl Iterator<Item = (ThinkerId, &Mobj)> {
+        self.iter().filter_map(|(id, thinker)| match thinker {
+            Thinker::Mobj(mo) => Some((id, mo)),
+            _ => None,
+        })
+    }
+
+    /// Mobjs linked in the blockmap cells touched by a box, in the order they run
+    pub fn mobjs_in_box(&self, left: f32, bottom: f32, right: f32, top: f32) -> Vec<ThinkerId> {
+        let (first_column, first_row) = self.block_links.cell(left, bottom);
+        let (last_column, last_row) = self.block_links.cell(right, top);
+        let mut ids = Vec::new();
+        for row in first_row..=last_row {
+            for column in first_column..=last_column {
+                if let Some(cell) = self.block_links.cells.get(&(column, row)) {
+                    ids.extend(cell);
+                }
+            }
+        }
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Run every thinker once, like vanilla P_RunThinkers
+    pub fn tick(&mut self, map: &mut WadMap) {
+        // Thinkers added meanwhile are at the end and run too
+        let mut index = 0;
+        while index < self.slots.len() {
+            if self.slots[index].removed {
+                index += 1;
+                continue;
+            }
+            let Some(mut thinker) = self.slots[index].thinker.take() else {
+                index += 1;
+                continue;
+            };
+            let keep = self.think(map, &mut thinker);
+            if let Thinker::Mobj(mo) = &thinker {
+                if !self.slots[index].removed {
+                    self.block_links.link(self.slots[index].id, mo);
+                }
+            }
+            let slot = &mut self.slots[index];
+            slot.thinker = Some(thinker);
+            if !keep {
+                let id = slot.id;
+                self.remove(id);
+            }
+            index += 1;
+        }
+
+        self.slots.retain(|slot| !slot.removed);
+        update_buttons(map, self);
+        // Counted once everything ran, like vanilla leveltime
+        self.tic += 1;
+    }
+
+    /// Update one thinker, returns false to remove it
+    fn think(&mut self, map: &mut WadMap, thinker: &mut Thinker) -> bool {
+        match thinker {
+            Thinker::Mobj(mo) => mobj_thinker(map, self, mo),
+            Thinker::Door(door) => door.think(map, self),
+            Thinker::Plat(plat) => plat.think(map, self),
+            Thinker::Floor(floor) => floor.think(map, self),
+            Thinker::Ceiling(ceiling) => ceiling.think(map, self),
+            Thinker::FireFlicker(flicker) => flicker.think(map, self),
+            Thinker::LightFlash(flash) => flash.think(map, self),
+            Thinker::Strobe(strobe) => strobe.think(map, self),
+            Thinker::Glow(glow) => glow.think(map, self),
+        }
+    }
+
+    /// Hash of everything in the level, two games in the same state have the same hash
+    ///
+    /// The cosmetic random index is left out, the status bar and the menus draw from it. So are
+    /// the thinker ids, a loaded level numbers its thinkers again
+    pub fn hash(&self, map: &WadMap) -> u64 {
+        let mut hasher = Fnv::new();
+        hasher.write(self.tic);
+        hasher.write(self.random.indices().0 as u32);
+        for sector in &map.sectors {
+            hasher.write(sector.floor.to_bits());
+            hasher.write(sector.ceiling.to_bits());
+            hasher.write(sector.lighting as u32);
+        }
+        for (_, thinker) in self.iter() {
+            match thinker {
+                Thinker::Mobj(mo) => {
+                    hasher.write(mo.kind as u32);
+                    for value in [mo.x, mo.y, mo.z, mo.angle, mo.momx, mo.momy, mo.momz] {
+                        hasher.write(value.to_bits());
+                    }
+                    hasher.write(mo.flags.bits());
+                    hasher.write(mo.health as u32);
+                    hasher.write(mo.state as u32);
+                    hasher.write(mo.tics as u32);
+                }
+                // Movers show in the sector heights and lights in the sector light
+                Thinker::Door(_)
+                | Thinker::Plat(_)
+                | Thinker::Floor(_)
+                | Thinker::Ceiling(_)
+                | Thinker::FireFlicker(_)
+                | Thinker::LightFlash(_)
+                | Thinker::Strobe(_)
+                | Thinker::Glow(_) => {}
+            }
+        }
+        hasher.0
+    }
+}
+
+/// Move a mobj and advance its state, like vanilla P_MobjThinker
+///
+/// The player moves with its own code, driven by the tic commands
+fn mobj_thinker(map: &mut WadMap, world: &mut World, mo: &mut Mobj) -> bool {
+    if (mo.flags & MobjFlags::MISSILE) != MobjFlags::NONE {
+        if !missile_movement(map, world, mo) {
+            return false;
+        }
+    } else if mo.kind != MobjType::Player {
+        let skull_fly = (mo.flags & MobjFlags::SKULLFLY) != MobjFlags::NONE;
+        if mo.momx != 0.0 || mo.momy != 0.0 || skull_fly {
+            let from = (mo.x, mo.y);
+            if skull_fly {
+                skull_movement(map, world, mo);
+            } else {
+                xy_movement(map, world, mo, false);
+            }
+            cross_special_lines(map, world, mo, None, from);
+        }
+        if mo.z != mo.floor_z || mo.momz != 0.0 {
+            float_to_target(world, mo);
+            z_movement(mo);
+        }
+    }
+    tick_state(map, world, mo)
+}
+
+/// FNV-1a, stable between builds unlike the std hasher
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf29ce484222325)
+    }
+
+    fn write(&mut self, value: u32) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wad::test_wad::room_map;
+
+    fn room_world(map: &WadMap) -> World {
+        let mut world = World::new();
+        world.spawn_map_things(map, Skill::Medium);
+        world
+    }
+
+    fn ids(world: &World) -> Vec<ThinkerId> {
+        world.iter().map(|(id, _)| id).collect()
+    }
+
+    #[test]
+    fn removal_waits_for_the_end_of_the_tic() {
+        let mut map = room_map();
+        let mut world = room_world(&map);
+        let before = ids(&world);
+        let (first, second) = (before[1], before[2]);
+
+        let added = world
+            .with_mobj(first, |world, mo| {
+                world.remove(first);
+                world.remove(second);
+                // Both slots stay until the tic ends, the running one gets its mobj back
+                assert_eq!(world.slots.len(), before.len());
+                assert!(world.mobj(second).is_none());
+                assert!(!world.running(first));
+                let puff = Mobj::spawn(&map, world, MobjType::Puff, mo.x, mo.y);
+                world.add(Thinker::Mobj(puff))
+            })
+            .unwrap();
+        assert!(world.mobj(first).is_none());
+        assert!(world
+            .mobjs_in_box(0.0, 0.0, 512.0, 512.0)
+            .iter()
+            .all(|&id| id != first && id != second));
+
+        world.tick(&mut map);
+        let after = ids(&world);
+        assert_eq!(world.slots.len(), before.len() - 1);
+        // The others keep their ids and order, the new one comes last with a new id
+        let kept: Vec<_> = before
+            .into_iter()
+            .filter(|&id| id != first && id != second)
+            .collect();
+        assert_eq!(after[..kept.len()], kept[..]);
+        assert_eq!(after.last(), Some(&added));
+        assert!(kept.iter().all(|&id| id < added));
+    }
+
+    #[test]
+    fn removed_ids_are_not_reused() {
+        let map = room_map();
+        let mut world = room_world(&map);
+        let last = *ids(&world).last().unwrap();
+        world.remove(last);
+        let puff = Mobj::spawn(&map, &mut world, MobjType::Puff, 64.0, 64.0);
+        let added = world.add(Thinker::Mobj(puff));
+        assert!(added > last);
+        assert!(world.get(last).is_none());
+    }
+
+    #[test]
+    fn identical_runs_have_the_same_hash() {
+        let run = || {
+            let mut map = room_map();
+            let mut world = room_world(&map);
+            let start = world.hash(&map);
+            for _ in 0..200 {
+                world.tick(&mut map);
+            }
+            // The imp woke up and drew numbers
+            assert_ne!(world.random.indices().0, 0);
+            (start, world.hash(&map))
+        };
+        let (start, end) = run();
+        assert_ne!(start, end);
+        assert_eq!(run(), (start, end));
+    }
+
+    #[test]
+    fn cosmetic_numbers_do_not_change_the_hash() {
+        let map = room_map();
+        let mut world = room_world(&map);
+        let hash = world.hash(&map);
+        world.random.cosmetic();
+        assert_eq!(world.hash(&map), hash);
+        world.random.gameplay();
+        assert_ne!(world.hash(&map), hash);
+    }
+}
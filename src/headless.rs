@@ -0,0 +1,307 @@
+use std::path::{Path, PathBuf};
+
+use cgmath::Deg;
+use glutin::api::egl::{device::Device, display::Display};
+use glutin::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
+use glutin::context::ContextAttributesBuilder;
+use glutin::prelude::*;
+use kabal_core::image::write_ppm;
+use kabal_render::doom_gl::DoomGl;
+use kabal_render::framebuffer::Framebuffer;
+use kabal_render::gl_renderer::GlRenderer;
+use kabal_render::screen2d::Screen2D;
+
+use crate::automap::Automap;
+use crate::camera::Camera;
+use crate::game::player::PlayerStatus;
+use crate::game::skill::Skill;
+use crate::game::world::World;
+use crate::render::software::SoftwareRenderer;
+use crate::status_bar::{HudMode, StatusBar};
+use crate::sys::content::Content;
+use crate::wad::file::WadFile;
+use crate::wad::map::WadMap;
+
+/// Everything needed to render a single image without a window
+pub struct HeadlessOptions {
+    pub wad: PathBuf,
+    pub map: String,
+    pub position: (f32, f32, f32),
+    pub angle: f32,
+    pub pitch: f32,
+    pub width: u32,
+    pub height: u32,
+    pub output: PathBuf,
+    /// Use the CPU renderer instead of GL
+    pub software: bool,
+    /// Draw the automap with what is seen from the position instead of the 3D view
+    pub automap: bool,
+    /// Draw the status bar or the fullscreen HUD over the view
+    pub hud: Option<HudMode>,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        HeadlessOptions {
+            wad: PathBuf::from("base/doom.wad"),
+            map: String::from("E1M1"),
+            position: (1056.0, -3616.0, 41.0),
+            angle: 90.0,
+            pitch: 0.0,
+            width: 640,
+            height: 400,
+            output: PathBuf::from("headless.ppm"),
+            software: false,
+            automap: false,
+            hud: None,
+        }
+    }
+}
+
+fn parse_floats(value: &str, count: usize) -> Result<Vec<f32>, String> {
+    let values = value
+        .split(',')
+        .map(|v| v.trim().parse::<f32>().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<f32>, String>>()?;
+    if values.len() != count {
+        return Err(format!("Expected {} values, got '{}'", count, value));
+    }
+    Ok(values)
+}
+
+impl HeadlessOptions {
+    /// Parse `--wad`, `--map`, `--pos x,y,z`, `--angle`, `--pitch`, `--size WxH`, `--output`,
+    /// `--software`, `--automap` and `--hud statusbar|fullscreen`
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut options = HeadlessOptions::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" => continue,
+                "--software" => {
+                    options.software = true;
+                    continue;
+                }
+                "--automap" => {
+                    options.automap = true;
+                    continue;
+                }
+                _ => (),
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", arg))?;
+            match arg.as_str() {
+                "--wad" => options.wad = PathBuf::from(value),
+                "--map" => options.map = value.to_ascii_uppercase(),
+                "--pos" => {
+                    let pos = parse_floats(value, 3)?;
+                    options.position = (pos[0], pos[1], pos[2]);
+                }
+                "--angle" => options.angle = parse_floats(value, 1)?[0],
+                "--pitch" => options.pitch = parse_floats(value, 1)?[0],
+                "--size" => {
+                    let (width, height) = value
+                        .split_once('x')
+                        .ok_or_else(|| format!("Invalid size '{}'", value))?;
+                    options.width = width.parse().map_err(|_| "Invalid width".to_string())?;
+                    options.height = height.parse().map_err(|_| "Invalid height".to_string())?;
+                }
+                "--output" => options.output = PathBuf::from(value),
+                "--hud" => {
+                    options.hud = match value.as_str() {
+                        "statusbar" => Some(HudMode::StatusBar),
+                        "fullscreen" => Some(HudMode::Fullscreen),
+                        _ => return Err(format!("Unknown HUD mode '{}'", value)),
+                    }
+                }
+                _ => return Err(format!("Unknown option {}", arg)),
+            }
+        }
+
+        // Only the 3D view has a CPU renderer
+        if options.software && (options.automap || options.hud.is_some()) {
+            return Err("--automap and --hud need the GL renderer, not --software".to_string());
+        }
+        Ok(options)
+    }
+}
+
+/// Create an EGL display straight on a device, no window system involved.
+/// Mesa exposes llvmpipe as a software device so this also works without a GPU.
+fn create_device_display() -> Result<Display, String> {
+    let devices = Device::query_devices().map_err(|e| e.to_string())?;
+    for device in devices {
+        if let Ok(display) = unsafe { Display::with_device(&device, None) } {
+            return Ok(display);
+        }
+    }
+    Err("No usable EGL device found".to_string())
+}
+
+fn camera(options: &HeadlessOptions) -> Camera {
+    let (x, y, z) = options.position;
+    let mut camera = Camera::from_map_position(x, y, z, Deg(options.angle), Deg(options.pitch));
+    camera.set_aspect_ratio(options.width as f32 / options.height as f32);
+    camera
+}
+
+/// Render with GL into an offscreen framebuffer
+fn render_gl(options: &HeadlessOptions) -> Result<Vec<u8>, String> {
+    let display = create_device_display()?;
+
+    let template = ConfigTemplateBuilder::new()
+        .with_alpha_size(8)
+        .with_surface_type(ConfigSurfaceTypes::empty())
+        .build();
+    let config = unsafe { display.find_configs(template) }
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or("No EGL config found")?;
+
+    let context_attributes = ContextAttributesBuilder::new().build(None);
+    let _context = unsafe { display.create_context(&config, &context_attributes) }
+        .map_err(|e| e.to_string())?
+        .make_current_surfaceless()
+        .map_err(|e| e.to_string())?;
+
+    DoomGl::new(&display);
+    let mut renderer = GlRenderer::new();
+
+    let framebuffer = Framebuffer::new(options.width as i32, options.height as i32);
+    framebuffer.bind();
+
+    let file = WadFile::new(Path::new(&options.wad))?;
+    let mut content = Content::new(file, &mut renderer);
+    let mut automap = Automap::new(&content.file, &mut renderer);
+    content.load_map(&options.map, &mut renderer)?;
+    let camera = camera(options);
+    automap.mark_seen_lines(&mut content.maps[0], &camera);
+    let map = &content.maps[0];
+
+    if options.automap {
+        automap.update(&camera);
+        automap.render(map, &camera, &mut renderer, options.width, options.height);
+    } else {
+        let mut world = World::new();
+        world.spawn_map_things(map, Skill::Medium);
+        let mobjs = world
+            .mobjs()
+            .filter(|(id, _)| *id != world.player.mobj)
+            .map(|(_, mo)| mo);
+        map.update_things(&content, mobjs);
+        map.render(&camera, &mut renderer);
+    }
+
+    if let Some(mode) = options.hud {
+        let mut status_bar = StatusBar::new(content.get_graphics());
+        status_bar.mode = mode;
+        let mut screen = Screen2D::new();
+        status_bar.draw(&PlayerStatus::default(), &mut screen);
+        screen.flush(&mut renderer, options.width, options.height);
+    }
+
+    let pixels = framebuffer.read_pixels();
+    framebuffer.unbind();
+    Ok(pixels)
+}
+
+/// Render on the CPU, no GL context is created at all
+fn render_software(options: &HeadlessOptions) -> Result<Vec<u8>, String> {
+    let file = WadFile::new(Path::new(&options.wad))?;
+    let map = WadMap::load(&options.map, &file)?;
+
+    let mut renderer =
+        SoftwareRenderer::new(&file, options.width as usize, options.height as usize);
+    renderer.render(&map, &camera(options));
+    Ok(renderer.to_rgba())
+}
+
+/// Render the requested map view offscreen and save it
+pub fn run(options: &HeadlessOptions) -> Result<(), String> {
+    let pixels = if options.software {
+        render_software(options)?
+    } else {
+        render_gl(options)?
+    };
+
+    write_ppm(
+        &options.output,
+        options.width as usize,
+        options.height as usize,
+        &pixels,
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wad::test_wad::{room_wad_path, PLAYER_START, ROOM_MAP};
+
+    const GOLDEN: &str = "tests/golden/room.ppm";
+    const GL_GOLDEN: &str = "tests/golden/room_gl.ppm";
+
+    #[test]
+    fn software_rejects_automap_and_hud() {
+        let args = |extra: &str| {
+            format!("--headless --software {}", extra)
+                .split_whitespace()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        assert!(HeadlessOptions::from_args(&args("")).is_ok());
+        assert!(HeadlessOptions::from_args(&args("--automap")).is_err());
+        assert!(HeadlessOptions::from_args(&args("--hud statusbar")).is_err());
+    }
+
+    /// Render the generated room from the player start
+    fn render_room(software: bool) -> Vec<u8> {
+        let wad = room_wad_path();
+        let output = wad.with_extension("ppm");
+        let (x, y, _) = PLAYER_START;
+        let options = HeadlessOptions {
+            wad: wad.clone(),
+            map: ROOM_MAP.to_string(),
+            position: (x as f32, y as f32, 41.0),
+            angle: 60.0,
+            width: 160,
+            height: 100,
+            output: output.clone(),
+            software,
+            ..HeadlessOptions::default()
+        };
+        let result = run(&options);
+        std::fs::remove_file(&wad).unwrap();
+        result.unwrap();
+        let frame = std::fs::read(&output).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        frame
+    }
+
+    /// Compare a frame with the stored one, set UPDATE_GOLDEN to write the frame again
+    fn assert_golden(frame: &[u8], golden: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(golden);
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, frame).unwrap();
+        }
+        let expected = std::fs::read(&path).unwrap();
+        assert!(frame == expected, "the frame differs from {}", golden);
+    }
+
+    #[test]
+    fn software_frame_matches_golden() {
+        assert_golden(&render_room(true), GOLDEN);
+    }
+
+    /// Skipped when no EGL device can be opened, the frame is the one of Mesa's llvmpipe
+    #[test]
+    fn gl_frame_matches_golden() {
+        if let Err(e) = create_device_display() {
+            eprintln!("skipping the GL frame: {}", e);
+            return;
+        }
+        assert_golden(&render_room(false), GL_GOLDEN);
+    }
+}
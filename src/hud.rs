@@ -0,0 +1,77 @@
+//! Message line at the top of the view, like vanilla hu_stuff.c
+
+use kabal_app::tic_runner::TICRATE;
+use kabal_render::screen2d::Screen2D;
+
+use crate::render::font::{Font, TextStyle};
+
+/// Tics a message stays on screen, like vanilla HU_MSGTIMEOUT
+const MESSAGE_TICS: u32 = 4 * TICRATE;
+/// Position of the message, like vanilla HU_MSGX and HU_MSGY
+const MESSAGE_X: f32 = 0.0;
+const MESSAGE_Y: f32 = 0.0;
+
+/// The last player message, shown for a few seconds
+#[derive(Default)]
+pub struct MessageLine {
+    message: Option<&'static str>,
+    tics: u32,
+}
+
+impl MessageLine {
+    /// Show a message in place of the current one
+    pub fn show(&mut self, message: &'static str) {
+        self.message = Some(message);
+        self.tics = MESSAGE_TICS;
+    }
+
+    /// Count down the message time, like vanilla HU_Ticker
+    pub fn tick(&mut self) {
+        self.tics = self.tics.saturating_sub(1);
+        if self.tics == 0 {
+            self.message = None;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.message = None;
+        self.tics = 0;
+    }
+
+    pub fn draw(&self, screen: &mut Screen2D, font: &Font) {
+        if let Some(message) = self.message {
+            font.draw(screen, MESSAGE_X, MESSAGE_Y, message, &TextStyle::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_goes_away_after_four_seconds() {
+        let mut line = MessageLine::default();
+        line.show("Picked up a clip.");
+        for _ in 1..MESSAGE_TICS {
+            line.tick();
+        }
+        assert_eq!(line.message, Some("Picked up a clip."));
+        line.tick();
+        assert_eq!(line.message, None);
+    }
+
+    #[test]
+    fn new_message_starts_over() {
+        let mut line = MessageLine::default();
+        line.show("Picked up a clip.");
+        for _ in 0..MESSAGE_TICS / 2 {
+            line.tick();
+        }
+        line.show("Picked up a stimpack.");
+        for _ in 1..MESSAGE_TICS {
+            line.tick();
+        }
+        assert_eq!(line.message, Some("Picked up a stimpack."));
+    }
+}
@@ -1,14 +1,25 @@
+pub mod bindings;
+
 use std::{cell::RefCell, rc::Rc};
 
 use winit::event::VirtualKeyCode;
 
+use bindings::{Action, Bindings, Button};
+
 pub trait InputListener {
-    fn on_input_change(&mut self, key: VirtualKeyCode, pressed: bool);
+    /// An action started or stopped, returns true to keep a press from the next listeners
+    fn on_action(&mut self, action: Action, pressed: bool) -> bool;
+    /// Every key press, whatever it is bound to
+    fn on_key_press(&mut self, _key: VirtualKeyCode) {}
     fn on_mouse_move(&mut self, delta: (f64, f64));
 }
 
+/// Turn button events into actions for the listeners, through the bindings
 pub struct Input {
-    pub pressed: Vec<VirtualKeyCode>,
+    pub pressed: Vec<Button>,
+    pub bindings: Bindings,
+    /// Wheel move not reaching a whole step yet
+    wheel: f32,
     pub listeners: Vec<Rc<RefCell<dyn InputListener>>>,
 }
 
@@ -16,31 +27,91 @@ impl Input {
     pub fn new() -> Self {
         Input {
             pressed: Vec::new(),
+            bindings: Bindings::new(),
+            wheel: 0.0,
             listeners: Vec::new(),
         }
     }
+
     pub fn register_input_event(&mut self, key: VirtualKeyCode, pressed: bool) {
-        let mut index = 0;
-        let was_pressed = self.pressed.iter().any(|&i| {
-            index += 1;
-            i == key
-        });
-        if pressed != was_pressed {
-            if pressed {
-                self.pressed.push(key);
-            } else {
-                self.pressed.remove(index - 1);
+        if pressed && !self.pressed.contains(&Button::Key(key)) {
+            self.listeners
+                .iter()
+                .for_each(|listener| listener.try_borrow_mut().unwrap().on_key_press(key));
+        }
+        self.register_button(Button::Key(key), pressed);
+    }
+
+    pub fn register_button(&mut self, button: Button, pressed: bool) {
+        let was_pressed = self.pressed.contains(&button);
+        if pressed == was_pressed {
+            return;
+        }
+
+        // An action bound to several buttons only changes with the first press or the last release
+        let actions = self
+            .bindings
+            .actions(button)
+            .map(|action| (action, self.action_held(action)))
+            .collect::<Vec<_>>();
+        if pressed {
+            self.pressed.push(button);
+        } else {
+            self.pressed.retain(|b| *b != button);
+        }
+        for (action, was_held) in actions {
+            if self.action_held(action) != was_held {
+                self.send_action(action, !was_held);
+            }
+        }
+    }
+
+    /// The wheel has no release, each whole step is a press and a release
+    ///
+    /// Listeners polling once per tic latch the press, like the camera until its next command
+    pub fn register_wheel(&mut self, delta: f32) {
+        self.wheel += delta;
+        let steps = self.wheel.trunc();
+        self.wheel -= steps;
+
+        let button = if steps > 0.0 {
+            Button::WheelUp
+        } else {
+            Button::WheelDown
+        };
+        for _ in 0..steps.abs() as i32 {
+            self.register_button(button, true);
+            self.register_button(button, false);
+        }
+    }
+
+    fn action_held(&self, action: Action) -> bool {
+        self.bindings
+            .buttons(action)
+            .iter()
+            .any(|button| self.pressed.contains(button))
+    }
+
+    fn send_action(&mut self, action: Action, pressed: bool) {
+        for listener in self.listeners.iter() {
+            let used = listener
+                .try_borrow_mut()
+                .unwrap()
+                .on_action(action, pressed);
+            // Releases reach everyone so nothing stays held
+            if used && pressed {
+                break;
             }
+        }
+    }
 
-            // Tell listeners
-            self.listeners.iter_mut().for_each(|listener| {
-                listener
-                    .try_borrow_mut()
-                    .unwrap()
-                    .on_input_change(key, pressed)
-            });
+    /// Release every held button, when something else takes the keyboard
+    pub fn release_all(&mut self) {
+        for button in self.pressed.clone() {
+            self.register_button(button, false);
         }
     }
+
     pub fn register_mouse_move(&mut self, delta: (f64, f64)) {
         self.listeners
             .iter_mut()
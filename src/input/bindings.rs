@@ -0,0 +1,279 @@
+use winit::event::{MouseButton, VirtualKeyCode};
+
+/// Something a button can be bound to
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    MoveForward,
+    MoveBackward,
+    StrafeLeft,
+    StrafeRight,
+    TurnLeft,
+    TurnRight,
+    /// Hold to strafe with the turn keys
+    Strafe,
+    Run,
+    Jump,
+    Crouch,
+    Use,
+    Fire,
+    NextWeapon,
+    PrevWeapon,
+    Weapon1,
+    Weapon2,
+    Weapon3,
+    Weapon4,
+    Weapon5,
+    Weapon6,
+    Weapon7,
+    Automap,
+    MapNorth,
+    MapSouth,
+    MapWest,
+    MapEast,
+    MapZoomIn,
+    MapZoomOut,
+    MapMaxZoom,
+    MapFollow,
+    MapGrid,
+    MapMark,
+    MapClearMarks,
+}
+
+/// Every action with its name in the config file and the console
+const ACTIONS: [(Action, &str); 33] = [
+    (Action::MoveForward, "forward"),
+    (Action::MoveBackward, "back"),
+    (Action::StrafeLeft, "moveleft"),
+    (Action::StrafeRight, "moveright"),
+    (Action::TurnLeft, "left"),
+    (Action::TurnRight, "right"),
+    (Action::Strafe, "strafe"),
+    (Action::Run, "speed"),
+    (Action::Jump, "jump"),
+    (Action::Crouch, "crouch"),
+    (Action::Use, "use"),
+    (Action::Fire, "fire"),
+    (Action::NextWeapon, "nextweapon"),
+    (Action::PrevWeapon, "prevweapon"),
+    (Action::Weapon1, "weapon1"),
+    (Action::Weapon2, "weapon2"),
+    (Action::Weapon3, "weapon3"),
+    (Action::Weapon4, "weapon4"),
+    (Action::Weapon5, "weapon5"),
+    (Action::Weapon6, "weapon6"),
+    (Action::Weapon7, "weapon7"),
+    (Action::Automap, "automap"),
+    (Action::MapNorth, "map_north"),
+    (Action::MapSouth, "map_south"),
+    (Action::MapWest, "map_west"),
+    (Action::MapEast, "map_east"),
+    (Action::MapZoomIn, "map_zoomin"),
+    (Action::MapZoomOut, "map_zoomout"),
+    (Action::MapMaxZoom, "map_maxzoom"),
+    (Action::MapFollow, "map_follow"),
+    (Action::MapGrid, "map_grid"),
+    (Action::MapMark, "map_mark"),
+    (Action::MapClearMarks, "map_clearmarks"),
+];
+
+/// Prefix of the bindings in the config file
+const CONFIG_PREFIX: &str = "key_";
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        ACTIONS
+            .iter()
+            .find(|(action, _)| action == self)
+            .map(|(_, name)| *name)
+            .unwrap()
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTIONS
+            .iter()
+            .find(|(_, n)| n.eq_ignore_ascii_case(name))
+            .map(|(action, _)| *action)
+    }
+}
+
+/// A keyboard key, a mouse button or a wheel direction
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Button {
+    Key(VirtualKeyCode),
+    Mouse(MouseButton),
+    WheelUp,
+    WheelDown,
+}
+
+/// Keys that can be bound, named after their `VirtualKeyCode` variant
+#[rustfmt::skip]
+const KEYS: &[VirtualKeyCode] = {
+    use VirtualKeyCode::*;
+    &[
+        Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Key0,
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+        F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+        Up, Down, Left, Right, Insert, Delete, Home, End, PageUp, PageDown,
+        Space, Return, Tab, Back, Capital,
+        LShift, RShift, LControl, RControl, LAlt, RAlt,
+        Minus, Equals, Comma, Period, Slash, Backslash, Semicolon, Apostrophe,
+        LBracket, RBracket,
+        Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8,
+        Numpad9, NumpadAdd, NumpadSubtract, NumpadMultiply, NumpadDivide, NumpadEnter,
+        NumpadDecimal,
+    ]
+};
+
+impl Button {
+    /// Name in the config file: the key name, Mouse1 to Mouse3 (or more), WheelUp or WheelDown
+    pub fn name(&self) -> String {
+        match self {
+            Button::Key(key) => format!("{:?}", key),
+            Button::Mouse(MouseButton::Left) => String::from("Mouse1"),
+            Button::Mouse(MouseButton::Right) => String::from("Mouse2"),
+            Button::Mouse(MouseButton::Middle) => String::from("Mouse3"),
+            Button::Mouse(MouseButton::Other(n)) => format!("Mouse{}", n),
+            Button::WheelUp => String::from("WheelUp"),
+            Button::WheelDown => String::from("WheelDown"),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Button> {
+        if name.eq_ignore_ascii_case("WheelUp") {
+            return Some(Button::WheelUp);
+        }
+        if name.eq_ignore_ascii_case("WheelDown") {
+            return Some(Button::WheelDown);
+        }
+        if let Some(number) = name.strip_prefix("Mouse") {
+            return match number.parse().ok()? {
+                1 => Some(Button::Mouse(MouseButton::Left)),
+                2 => Some(Button::Mouse(MouseButton::Right)),
+                3 => Some(Button::Mouse(MouseButton::Middle)),
+                n => Some(Button::Mouse(MouseButton::Other(n))),
+            };
+        }
+        KEYS.iter()
+            .find(|key| format!("{:?}", key).eq_ignore_ascii_case(name))
+            .map(|key| Button::Key(*key))
+    }
+}
+
+/// Buttons bound to each action, an action can have several and a button can trigger several
+pub struct Bindings {
+    list: Vec<(Action, Vec<Button>)>,
+    /// Something changed since the config file was written
+    pub dirty: bool,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        use VirtualKeyCode as K;
+
+        let key = |keys: &[VirtualKeyCode]| keys.iter().map(|k| Button::Key(*k)).collect();
+        let list = vec![
+            (Action::MoveForward, key(&[K::W, K::Up])),
+            (Action::MoveBackward, key(&[K::S, K::Down])),
+            (Action::StrafeLeft, key(&[K::A])),
+            (Action::StrafeRight, key(&[K::D])),
+            (Action::TurnLeft, key(&[K::Left])),
+            (Action::TurnRight, key(&[K::Right])),
+            (Action::Strafe, key(&[K::LAlt])),
+            (Action::Run, key(&[K::LShift])),
+            (Action::Jump, key(&[K::Space])),
+            (Action::Crouch, key(&[K::C])),
+            (Action::Use, key(&[K::E])),
+            (
+                Action::Fire,
+                vec![Button::Mouse(MouseButton::Left), Button::Key(K::LControl)],
+            ),
+            (Action::NextWeapon, vec![Button::WheelUp]),
+            (Action::PrevWeapon, vec![Button::WheelDown]),
+            (Action::Weapon1, key(&[K::Key1])),
+            (Action::Weapon2, key(&[K::Key2])),
+            (Action::Weapon3, key(&[K::Key3])),
+            (Action::Weapon4, key(&[K::Key4])),
+            (Action::Weapon5, key(&[K::Key5])),
+            (Action::Weapon6, key(&[K::Key6])),
+            (Action::Weapon7, key(&[K::Key7])),
+            (Action::Automap, key(&[K::Tab])),
+            (Action::MapNorth, key(&[K::Up])),
+            (Action::MapSouth, key(&[K::Down])),
+            (Action::MapWest, key(&[K::Left])),
+            (Action::MapEast, key(&[K::Right])),
+            (Action::MapZoomIn, key(&[K::Equals, K::NumpadAdd])),
+            (Action::MapZoomOut, key(&[K::Minus, K::NumpadSubtract])),
+            (Action::MapMaxZoom, key(&[K::Key0])),
+            (Action::MapFollow, key(&[K::F])),
+            (Action::MapGrid, key(&[K::G])),
+            (Action::MapMark, key(&[K::M])),
+            (Action::MapClearMarks, key(&[K::C])),
+        ];
+
+        Bindings { list, dirty: false }
+    }
+
+    /// Actions triggered by a button
+    pub fn actions(&self, button: Button) -> impl Iterator<Item = Action> + '_ {
+        self.list
+            .iter()
+            .filter(move |(_, buttons)| buttons.contains(&button))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn buttons(&self, action: Action) -> &[Button] {
+        self.list
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map_or(&[], |(_, buttons)| buttons.as_slice())
+    }
+
+    /// Replace the buttons of an action
+    pub fn bind(&mut self, action: Action, buttons: Vec<Button>) {
+        if let Some((_, bound)) = self.list.iter_mut().find(|(a, _)| *a == action) {
+            if *bound != buttons {
+                *bound = buttons;
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Parse a list of button names separated by spaces
+    pub fn parse_buttons(text: &str) -> Result<Vec<Button>, String> {
+        text.split_whitespace()
+            .map(|name| Button::from_name(name).ok_or_else(|| format!("Unknown button {}", name)))
+            .collect()
+    }
+
+    /// Apply the `key_<action>` entries of the config file
+    pub fn load(&mut self, entries: &[(String, String)]) {
+        for (name, value) in entries {
+            let Some(action) = name.strip_prefix(CONFIG_PREFIX).and_then(Action::from_name) else {
+                continue;
+            };
+            match Self::parse_buttons(value) {
+                Ok(buttons) => self.bind(action, buttons),
+                Err(e) => eprintln!("Config: {}", e),
+            }
+        }
+        self.dirty = false;
+    }
+
+    /// Entries to write in the config file
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.list
+            .iter()
+            .map(|(action, buttons)| {
+                let names = buttons.iter().map(Button::name).collect::<Vec<_>>();
+                (
+                    format!("{}{}", CONFIG_PREFIX, action.name()),
+                    names.join(" "),
+                )
+            })
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Action, Vec<Button>)> {
+        self.list.iter()
+    }
+}
@@ -1,9 +1,27 @@
+mod automap;
 mod camera;
+mod console;
+mod game;
+mod headless;
+mod hud;
 mod input;
+mod menu;
 mod render;
+mod status_bar;
 mod sys;
 mod wad;
+use automap::Automap;
 use camera::Camera;
+use cgmath::{Deg, Vector2};
+use console::{Console, COMMANDS};
+use game::savegame::SaveGame;
+use game::skill::Skill;
+use game::{
+    player::{Player, PlayerStatus, Power},
+    specials::spawn_specials,
+    ticcmd::TicCmd,
+    world::World,
+};
 use glutin::context::{ContextApi, ContextAttributesBuilder, PossiblyCurrentContext};
 use glutin::prelude::*;
 use glutin::surface::{Surface, WindowSurface};
@@ -14,19 +32,33 @@ use glutin::{
     prelude::{GlConfig, GlDisplay},
 };
 use glutin_winit::{self, DisplayBuilder};
+use headless::HeadlessOptions;
+use hud::MessageLine;
+use input::bindings::{Action, Bindings, Button};
 use input::Input;
 use kabal_app::window::{KabalApp, ProgramProc};
+use kabal_core::image::write_ppm;
 use kabal_render::doom_gl::DoomGl;
+use kabal_render::gl_renderer::GlRenderer;
+use kabal_render::renderer::Renderer;
+use kabal_render::screen2d::Screen2D;
+use menu::{Menu, MenuAction};
 use raw_window_handle::HasRawWindowHandle;
+use render::font::{Align, Font, TextColor, TextStyle};
+use render::player_sprites::draw_player_sprites;
+use status_bar::{HudMode, StatusBar};
 use std::{cell::RefCell, num::NonZeroU32, path::Path, rc::Rc};
-use winit::event::VirtualKeyCode;
+use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
 use winit::{
     event::ElementState,
     window::{CursorGrabMode, WindowBuilder},
 };
 
+use sys::config::{read_config, write_config, CONFIG_FILE};
 use sys::content::Content;
+use sys::cvars::Cvars;
 use wad::file::WadFile;
+use wad::map::WadMap;
 
 const WINDOW_TITLE: &str = "DOOM";
 const WINDOW_WIDTH: u32 = 1680;
@@ -39,9 +71,26 @@ struct DoomApp {
 
     focused: bool,
 
+    renderer: Box<dyn Renderer>,
     content: Content,
     camera: Rc<RefCell<Camera>>,
+    automap: Rc<RefCell<Automap>>,
+    /// Everything moving in the level, the player included
+    world: World,
+    status_bar: StatusBar,
+    /// Pickup, key and secret messages over the view
+    messages: MessageLine,
+    screen: Screen2D,
+    font: Font,
+    menu: Menu,
+    console: Console,
+    cvars: Cvars,
+    /// Save the next frame before the console and the menus are drawn
+    screenshot: bool,
+    skill: Skill,
+    quit: bool,
     input: Input,
+    modifiers: ModifiersState,
 }
 
 impl DoomApp {
@@ -116,12 +165,32 @@ impl DoomApp {
         window.set_cursor_visible(false);
 
         DoomGl::new(&gl_display);
+        let mut renderer: Box<dyn Renderer> = Box::new(GlRenderer::new());
 
         let file = WadFile::new(Path::new("base/doom.wad")).unwrap();
-        let content = Content::new(file);
+        let content = Content::new(file, renderer.as_mut());
+        let episodes = (1..=4)
+            .take_while(|e| {
+                let name = format!("E{}M1", e);
+                content.file.directory.get_lump_index(&name).is_some()
+            })
+            .count();
+
+        let automap = Rc::new(RefCell::new(Automap::new(&content.file, renderer.as_mut())));
+
+        let status_bar = StatusBar::new(content.get_graphics());
+        let font = Font::new(&content.file, renderer.as_mut());
+
+        let console = Console::new(renderer.as_mut());
+        let mut cvars = Cvars::new();
+        let config = read_config(Path::new(CONFIG_FILE));
+        cvars.load(&config);
 
         let camera = Rc::new(RefCell::new(Camera::new()));
         let mut input = Input::new();
+        input.bindings.load(&config);
+        // The automap comes first to take the pan keys from the player
+        input.listeners.push(automap.clone());
         input.listeners.push(camera.clone());
 
         // Try setting vsync.
@@ -131,25 +200,469 @@ impl DoomApp {
             eprintln!("Error setting vsync: {:?}", res);
         }
 
-        DoomApp {
+        let mut app = DoomApp {
             window,
             surface,
             context,
             focused: true,
+            renderer,
             content,
             camera,
+            automap,
+            world: World::new(),
+            status_bar,
+            messages: MessageLine::default(),
+            screen: Screen2D::new(),
+            font,
+            menu: Menu::new(episodes),
+            console,
+            cvars,
+            screenshot: false,
+            skill: Skill::Medium,
+            quit: false,
             input,
+            modifiers: ModifiersState::empty(),
+        };
+        let first_map = app.first_map(0);
+        app.start_map(&first_map, PlayerStatus::default()).unwrap();
+        app
+    }
+
+    /// First level of an episode, MAP01 when the game has no episodes
+    fn first_map(&self, episode: usize) -> String {
+        if self
+            .content
+            .file
+            .directory
+            .get_lump_index("MAP01")
+            .is_some()
+        {
+            String::from("MAP01")
+        } else {
+            format!("E{}M1", episode + 1)
+        }
+    }
+
+    /// Go on to the next level with what the player has, or back to the title screen after the
+    /// last one of an episode
+    fn exit_level(&mut self) {
+        let mut status = std::mem::take(&mut self.world.player.status);
+        status.finish_level();
+        let Some(name) = next_map(&self.content.maps[0].name, self.world.secret_exit) else {
+            self.menu.in_game = false;
+            return;
+        };
+        if let Err(e) = self.start_map(&name, status) {
+            self.console.print(&format!("Can't start {}: {}", name, e));
+            self.menu.in_game = false;
+        }
+    }
+
+    /// Load a level and put the camera on the player 1 start
+    ///
+    /// The player keeps a status from the previous level, or starts with a default one. The
+    /// current level is kept when the new one can't be started
+    fn start_map(&mut self, name: &str, status: PlayerStatus) -> Result<(), String> {
+        let mut map = WadMap::new(name, &self.content, self.renderer.as_mut())?;
+        if !map.things.iter().any(|thing| thing.doomednum == 1) {
+            map.free(self.renderer.as_mut());
+            return Err(format!("No player start in {}", name));
+        }
+
+        let mut world = World::new();
+        world.spawn_map_things(&map, self.skill);
+        spawn_specials(&mut map, &mut world);
+        world.player = Player::spawn(&mut map, &mut world, status);
+        self.enter_level(map, world);
+        Ok(())
+    }
+
+    /// Load a saved game, the level is as it was when saved
+    ///
+    /// The current level is kept when the save does not match its map
+    fn load_game(&mut self, save: &SaveGame) -> Result<(), String> {
+        let mut map = WadMap::new(&save.map, &self.content, self.renderer.as_mut())?;
+        let mut world = World::new();
+        if let Err(e) = save.restore(&mut map, &mut world) {
+            map.free(self.renderer.as_mut());
+            return Err(e);
+        }
+        self.skill = save.skill;
+        self.enter_level(map, world);
+        Ok(())
+    }
+
+    /// Replace the level with a ready one, put the camera on the player body and start playing
+    fn enter_level(&mut self, map: WadMap, world: World) {
+        for map in self.content.maps.drain(..) {
+            map.free(self.renderer.as_mut());
+        }
+        self.content.maps.push(map);
+        self.world = world;
+
+        if let Some(mo) = self.world.mobj(self.world.player.mobj) {
+            let view_z = self.world.player.view_z;
+            *self.camera.borrow_mut() =
+                Camera::from_map_position(mo.x, mo.y, view_z, Deg(mo.angle), Deg(0.0));
+        }
+        self.automap.borrow_mut().clear();
+        self.messages.clear();
+        self.menu.in_game = true;
+    }
+
+    fn on_menu_action(&mut self, action: MenuAction) {
+        match action {
+            MenuAction::NewGame { episode, skill } => {
+                self.skill = skill;
+                let map = self.first_map(episode);
+                if let Err(e) = self.start_map(&map, PlayerStatus::default()) {
+                    eprintln!("Can't start {}: {}", map, e);
+                }
+            }
+            MenuAction::LoadGame(slot) => {
+                let result = SaveGame::read(slot).and_then(|save| self.load_game(&save));
+                if let Err(e) = result {
+                    eprintln!("Can't load slot {}: {}", slot, e);
+                }
+            }
+            MenuAction::SaveGame(slot, description) => {
+                let save = SaveGame::new(description, &self.content.maps[0], &self.world);
+                if let Err(e) = save.write(slot) {
+                    eprintln!("Can't save slot {}: {}", slot, e);
+                }
+            }
+            MenuAction::EndGame => self.menu.in_game = false,
+            MenuAction::Quit => self.quit = true,
+        }
+    }
+
+    /// The game is paused while the menu or the console is open
+    fn paused(&self) -> bool {
+        !self.focused || !self.menu.in_game || self.menu.active || self.console.active
+    }
+
+    /// Run a console line: a command, or a variable name with an optional new value
+    fn execute(&mut self, line: &str) {
+        let mut args = line.split_whitespace();
+        let Some(command) = args.next() else {
+            return;
+        };
+        let args = args.collect::<Vec<_>>();
+
+        match (command, args.as_slice()) {
+            ("map", [name]) => {
+                let name = name.to_uppercase();
+                match self.start_map(&name, PlayerStatus::default()) {
+                    Ok(()) => {
+                        self.menu.active = false;
+                        self.console.active = false;
+                    }
+                    Err(e) => self.console.print(&format!("Can't start {}: {}", name, e)),
+                }
+            }
+            ("noclip", []) => {
+                let status = &mut self.world.player.status;
+                status.no_clip = !status.no_clip;
+                let state = if status.no_clip { "ON" } else { "OFF" };
+                self.console.print(&format!("No Clipping Mode {}", state));
+            }
+            ("screenshot", []) => self.screenshot = true,
+            ("hash", []) => match self.content.maps.first() {
+                Some(map) => {
+                    let line = format!("Tic {} hash {:016x}", self.world.tic, self.world.hash(map));
+                    self.console.print(&line);
+                }
+                None => self.console.print("Not in a level"),
+            },
+            ("cvarlist", []) => {
+                for cvar in self.cvars.iter() {
+                    let line = format!("{} {}", cvar.name, cvar.value);
+                    self.console.print(&line);
+                }
+            }
+            ("help", []) => {
+                for (_, help) in COMMANDS {
+                    self.console.print(help);
+                }
+            }
+            ("bind", []) => {
+                for (action, buttons) in self.input.bindings.iter() {
+                    let names = buttons.iter().map(|b| b.name()).collect::<Vec<_>>();
+                    let line = format!("{} {}", action.name(), names.join(" "));
+                    self.console.print(&line);
+                }
+            }
+            ("bind", [action, buttons @ ..]) => {
+                let Some(action) = Action::from_name(action) else {
+                    self.console.print(&format!("Unknown action {}", action));
+                    return;
+                };
+                if buttons.is_empty() {
+                    let names = self.input.bindings.buttons(action).iter();
+                    let names = names.map(|b| b.name()).collect::<Vec<_>>();
+                    let line = format!("{} is bound to {}", action.name(), names.join(" "));
+                    self.console.print(&line);
+                    return;
+                }
+                match Bindings::parse_buttons(&buttons.join(" ")) {
+                    Ok(buttons) => self.input.bindings.bind(action, buttons),
+                    Err(e) => self.console.print(&e),
+                }
+            }
+            ("unbind", [action]) => match Action::from_name(action) {
+                Some(action) => self.input.bindings.bind(action, Vec::new()),
+                None => self.console.print(&format!("Unknown action {}", action)),
+            },
+            ("clear", []) => self.console.clear(),
+            ("quit", []) => self.quit = true,
+            (name, []) => match self.cvars.find(name) {
+                Some(cvar) => {
+                    let line = format!("{} is {} - {}", cvar.name, cvar.value, cvar.description);
+                    self.console.print(&line);
+                }
+                None => self.console.print(&format!("Unknown command {}", name)),
+            },
+            (name, [value]) if self.cvars.find(name).is_some() => {
+                if let Err(e) = self.cvars.set(name, value) {
+                    self.console.print(&e);
+                }
+            }
+            _ => match COMMANDS.iter().find(|(name, _)| *name == command) {
+                Some((_, help)) => self.console.print(help),
+                None => self.console.print(&format!("Unknown command {}", command)),
+            },
+        }
+    }
+
+    /// Push the variables to the parts of the game using them, and save them with the bindings when changed
+    fn apply_cvars(&mut self) {
+        {
+            let mut camera = self.camera.borrow_mut();
+            camera.move_speed = self.cvars.float("cl_movespeed");
+            // The menu sensitivity scales the console ones, 5 keeps them as they are
+            let scale = (self.cvars.int("mouse_sensitivity") + 1) as f32 / 6.0;
+            camera.sensitivity = Vector2::new(
+                self.cvars.float("m_sensitivity_x"),
+                self.cvars.float("m_sensitivity_y"),
+            ) * scale;
+            camera.smooth_mouse = self.cvars.bool("m_smooth");
+            camera.set_fov(Deg(self.cvars.float("fov")));
+        }
+        self.status_bar.mode = if self.cvars.int("screenblocks") >= 11 {
+            HudMode::Fullscreen
+        } else {
+            HudMode::StatusBar
+        };
+
+        if self.cvars.dirty || self.input.bindings.dirty {
+            self.cvars.dirty = false;
+            self.input.bindings.dirty = false;
+            let mut entries = self.cvars.entries();
+            entries.extend(self.input.bindings.entries());
+            if let Err(e) = write_config(Path::new(CONFIG_FILE), &entries) {
+                eprintln!("Can't write {}: {}", CONFIG_FILE, e);
+            }
+        }
+    }
+
+    /// Save the current frame as the first free DOOMnn.ppm
+    fn save_screenshot(&mut self, width: u32, height: u32) {
+        let Some(path) = (0..100)
+            .map(|i| format!("DOOM{:02}.ppm", i))
+            .find(|name| !Path::new(name).exists())
+        else {
+            self.console.print("Can't find a free screenshot name");
+            return;
+        };
+
+        let pixels = self.renderer.read_pixels(width, height);
+        match write_ppm(Path::new(&path), width as usize, height as usize, &pixels) {
+            Ok(()) => self.console.print(&format!("Wrote {}", path)),
+            Err(e) => self.console.print(&format!("Can't write {}: {}", path, e)),
         }
     }
 }
 
+/// Level after a map in the vanilla order, None after the last level of an episode
+///
+/// Secret exits lead to the secret levels, which lead back to the level after the one with
+/// their secret exit
+fn next_map(name: &str, secret: bool) -> Option<String> {
+    if let Some(number) = name.strip_prefix("MAP") {
+        let next = match (number.parse::<u32>().ok()?, secret) {
+            (15, true) => 31,
+            (31, true) => 32,
+            (30, _) => return None,
+            (31 | 32, _) => 16,
+            (number, _) => number + 1,
+        };
+        return Some(format!("MAP{:02}", next));
+    }
+    let (episode, number) = name.strip_prefix('E')?.split_once('M')?;
+    let (episode, number) = (episode.parse::<u32>().ok()?, number.parse::<u32>().ok()?);
+    let next = match (episode, number) {
+        (_, 8) => return None,
+        _ if secret => 9,
+        (1, 9) => 4,
+        (2, 9) => 6,
+        (3, 9) => 7,
+        (4, 9) => 3,
+        (_, number) => number + 1,
+    };
+    Some(format!("E{}M{}", episode, next))
+}
+
 impl KabalApp for DoomApp {
-    fn run_frame(&mut self, _delta_time: f32) {
-        if self.focused {
-            self.camera.try_borrow_mut().unwrap().update();
+    fn run_tic(&mut self) {
+        self.menu.ticker();
+        if self.paused() {
+            return;
+        }
+
+        let map = &mut self.content.maps[0];
+        let mut camera = self.camera.borrow_mut();
+        camera.tick();
+        let cmd = camera.ticcmd(&self.world.player);
+        let angle = camera.map_angle();
+        if self.world.player.status.no_clip {
+            // Fly like a free camera, the body follows and the player still fires and uses
+            camera.fly();
+            let (x, y, z) = camera.map_position();
+            let cmd = TicCmd {
+                forward_move: 0,
+                side_move: 0,
+                ..cmd
+            };
+            self.world.with_player(|world, player| {
+                player.set_view_position(map, world, x, y, z);
+                player.think(map, world, &cmd, angle);
+            });
+        } else {
+            self.world
+                .with_player(|world, player| player.think(map, world, &cmd, angle));
+            if let Some(mo) = self.world.mobj(self.world.player.mobj) {
+                if self.world.player.teleported {
+                    camera.teleport(mo.x, mo.y, self.world.player.view_z, Deg(mo.angle));
+                    self.world.player.teleported = false;
+                } else {
+                    camera.set_map_position(mo.x, mo.y, self.world.player.view_z);
+                    // Punches and the chainsaw turn the player toward what they hit
+                    if mo.angle != camera.map_angle().0 {
+                        camera.turn_to(Deg(mo.angle));
+                    }
+                }
+            }
+        }
+        drop(camera);
+        self.messages.tick();
+        if let Some(message) = self.world.player.message.take() {
+            self.console.print(message);
+            if self.cvars.bool("show_messages") {
+                self.messages.show(message);
+            }
+        }
+
+        self.world.tick(map);
+
+        if self.world.exit_level {
+            self.exit_level();
+        }
+        // A dead player starts the level over from scratch
+        if self.world.player.reborn {
+            let name = self.content.maps[0].name.clone();
+            if let Err(e) = self.start_map(&name, PlayerStatus::default()) {
+                self.console.print(&format!("Can't start {}: {}", name, e));
+            }
+        }
+
+        let mut automap = self.automap.borrow_mut();
+        automap.computer_map = self.world.player.status.has_power(Power::AllMap);
+        automap.tick(&self.content.maps[0]);
+        self.status_bar
+            .tick(&self.world.player.status, &mut self.world.random);
+    }
+
+    fn run_frame(&mut self, _delta_time: f32, fraction: f32, fps: f32) {
+        self.apply_cvars();
+
+        let (width, height): (u32, u32) = self.window.inner_size().into();
+        self.renderer.set_wireframe(self.cvars.bool("r_wireframe"));
+        if self.menu.in_game {
+            // Nothing moves between tics while paused
+            let fraction = if self.paused() { 1.0 } else { fraction };
+            let camera = self.camera.borrow().interpolated(fraction);
+            let mut automap = self.automap.try_borrow_mut().unwrap();
+            // The lines in view are mapped whether the automap is open or not
+            automap.mark_seen_lines(&mut self.content.maps[0], &camera);
+            automap.update(&camera);
+            let map = &self.content.maps[0];
+
+            if automap.active {
+                automap.render(map, &camera, self.renderer.as_mut(), width, height);
+            } else {
+                map.update_sectors(&self.content, self.renderer.as_mut());
+                map.update_sides(&self.content, self.renderer.as_mut());
+                // The player does not see their own body
+                let mobjs = self
+                    .world
+                    .mobjs()
+                    .filter(|(id, _)| *id != self.world.player.mobj)
+                    .map(|(_, mo)| mo);
+                map.update_things(&self.content, mobjs);
+                map.render(&camera, self.renderer.as_mut());
+
+                if let Some(mo) = self.world.mobj(self.world.player.mobj) {
+                    let sector = &map.sectors[map.sector_at(mo.x, mo.y)];
+                    draw_player_sprites(
+                        &mut self.screen,
+                        &self.content,
+                        &self.world.player.psprites,
+                        sector.lighting,
+                        self.world.player.extra_light,
+                        self.status_bar.view_lift(),
+                    );
+                }
+            }
+
+            self.status_bar
+                .draw(&self.world.player.status, &mut self.screen);
+            self.messages.draw(&mut self.screen, &self.font);
+        } else {
+            self.renderer.begin_frame([0.0, 0.0, 0.0, 1.0]);
+            if let Some(title) = self.content.get_graphics().find_texture("TITLEPIC") {
+                self.screen.draw_patch(0, 0, &title.patch());
+            }
+        }
+
+        self.renderer.set_wireframe(false);
+
+        if self.screenshot {
+            self.screenshot = false;
+            self.screen.flush(self.renderer.as_mut(), width, height);
+            self.save_screenshot(width, height);
         }
 
-        self.content.maps[0].render(&self.camera.try_borrow_mut().unwrap());
+        self.menu.draw(
+            &mut self.screen,
+            self.content.get_graphics(),
+            &self.font,
+            &self.cvars,
+        );
+        if self.cvars.bool("r_showfps") {
+            self.font.draw(
+                &mut self.screen,
+                318.0,
+                2.0,
+                &format!("FPS {:.0}", fps),
+                &TextStyle {
+                    color: TextColor::Gray,
+                    align: Align::Right,
+                    ..Default::default()
+                },
+            );
+        }
+        self.console.draw(&mut self.screen, &self.font);
+        self.screen.flush(self.renderer.as_mut(), width, height);
         self.surface.swap_buffers(&self.context).unwrap();
     }
 
@@ -166,6 +679,7 @@ impl KabalApp for DoomApp {
     }
 
     fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
         let mode = if focused {
             CursorGrabMode::Confined
         } else {
@@ -176,22 +690,161 @@ impl KabalApp for DoomApp {
     }
 
     fn on_keyboard_event(&mut self, key_code: VirtualKeyCode, state: ElementState) {
-        if self.focused {
-            self.input
-                .register_input_event(key_code, state == ElementState::Pressed)
+        if !self.focused {
+            return;
+        }
+        let pressed = state == ElementState::Pressed;
+
+        if self.console.active {
+            if pressed {
+                if let Some(line) = self
+                    .console
+                    .responder(key_code, self.modifiers, &self.cvars)
+                {
+                    self.execute(&line);
+                }
+            }
+            return;
+        }
+        if pressed && key_code == VirtualKeyCode::Grave {
+            self.console.active = true;
+            self.input.release_all();
+            return;
+        }
+
+        // Any key opens the menu on the title screen
+        if pressed && !self.menu.in_game && !self.menu.active {
+            self.menu.open();
+            return;
+        }
+        if pressed && (self.menu.active || key_code == VirtualKeyCode::Escape) {
+            if let Some(action) = self.menu.responder(key_code, &mut self.cvars) {
+                self.on_menu_action(action);
+            }
+            return;
+        }
+
+        self.input.register_input_event(key_code, pressed);
+
+        // Screen size keys, the automap uses them to zoom
+        if pressed && !self.automap.borrow().active {
+            let screenblocks = self.cvars.int("screenblocks");
+            match key_code {
+                VirtualKeyCode::Equals => self.cvars.set_int("screenblocks", screenblocks + 1),
+                VirtualKeyCode::Minus => self.cvars.set_int("screenblocks", screenblocks - 1),
+                _ => (),
+            }
         }
     }
 
     fn on_mouse_move(&mut self, x: f64, y: f64) {
-        if self.focused {
+        if self.focused && !self.menu.active && !self.console.active {
             self.input.register_mouse_move((x, y));
         }
     }
+
+    fn on_mouse_button(&mut self, button: MouseButton, state: ElementState) {
+        if !self.focused || self.console.active {
+            return;
+        }
+        let pressed = state == ElementState::Pressed;
+
+        // Clicks answer the menus like Return and Backspace, as in vanilla
+        if self.menu.active || !self.menu.in_game {
+            let key = match button {
+                MouseButton::Left => VirtualKeyCode::Return,
+                MouseButton::Right => VirtualKeyCode::Back,
+                _ => return,
+            };
+            if pressed {
+                self.on_keyboard_event(key, state);
+            }
+            return;
+        }
+        self.input.register_button(Button::Mouse(button), pressed);
+    }
+
+    fn on_mouse_wheel(&mut self, _x: f32, y: f32) {
+        if !self.focused {
+            return;
+        }
+        if self.console.active {
+            self.console.scroll(y.round() as i32);
+        } else if self.menu.active {
+            let key = if y > 0.0 {
+                VirtualKeyCode::Up
+            } else {
+                VirtualKeyCode::Down
+            };
+            for _ in 0..y.abs().round() as i32 {
+                self.on_keyboard_event(key, ElementState::Pressed);
+            }
+        } else {
+            self.input.register_wheel(y);
+        }
+    }
+
+    fn on_received_character(&mut self, c: char) {
+        if self.console.active {
+            self.console.on_char(c);
+        } else if self.menu.active {
+            self.menu.on_char(c);
+        }
+    }
+
+    fn on_modifiers_changed(&mut self, modifiers: ModifiersState) {
+        self.modifiers = modifiers;
+    }
+
+    fn exit_requested(&self) -> bool {
+        self.quit
+    }
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--headless") {
+        let result = HeadlessOptions::from_args(&args).and_then(|options| headless::run(&options));
+        if let Err(e) = result {
+            eprintln!("Headless rendering failed: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let proc = ProgramProc::new();
     let app = DoomApp::new(&proc.event_loop);
 
     proc.main_loop(app);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::next_map;
+
+    #[test]
+    fn secret_exits_lead_to_the_secret_levels_and_back() {
+        assert_eq!(next_map("E1M3", true).as_deref(), Some("E1M9"));
+        assert_eq!(next_map("E1M9", false).as_deref(), Some("E1M4"));
+        assert_eq!(next_map("E2M5", true).as_deref(), Some("E2M9"));
+        assert_eq!(next_map("E2M9", false).as_deref(), Some("E2M6"));
+        assert_eq!(next_map("E3M6", true).as_deref(), Some("E3M9"));
+        assert_eq!(next_map("E3M9", false).as_deref(), Some("E3M7"));
+        assert_eq!(next_map("E4M2", true).as_deref(), Some("E4M9"));
+        assert_eq!(next_map("E4M9", false).as_deref(), Some("E4M3"));
+        assert_eq!(next_map("MAP15", true).as_deref(), Some("MAP31"));
+        assert_eq!(next_map("MAP31", true).as_deref(), Some("MAP32"));
+        assert_eq!(next_map("MAP31", false).as_deref(), Some("MAP16"));
+        assert_eq!(next_map("MAP32", false).as_deref(), Some("MAP16"));
+    }
+
+    #[test]
+    fn normal_exits_go_to_the_next_map_until_the_last() {
+        assert_eq!(next_map("E1M1", false).as_deref(), Some("E1M2"));
+        assert_eq!(next_map("MAP09", false).as_deref(), Some("MAP10"));
+        assert_eq!(next_map("MAP15", false).as_deref(), Some("MAP16"));
+        assert_eq!(next_map("E1M8", false), None);
+        assert_eq!(next_map("E3M8", true), None);
+        assert_eq!(next_map("MAP30", false), None);
+    }
+}
@@ -0,0 +1,636 @@
+use kabal_render::screen2d::Screen2D;
+use winit::event::VirtualKeyCode;
+
+use crate::{
+    game::{
+        savegame::{SaveGame, SAVESTRINGSIZE, SAVE_SLOTS},
+        skill::Skill,
+    },
+    render::font::{Align, Font, TextStyle},
+    sys::{cvars::Cvars, textures::Textures},
+};
+
+const LINEHEIGHT: i32 = 16;
+const SKULLXOFF: i32 = -32;
+/// Tics between two frames of the skull cursor
+const SKULL_ANIM_TICS: u32 = 8;
+
+const QUITMSG: &str = "are you sure you want to\nquit this great game?\n\n(press y to quit.)";
+const NIGHTMARE: &str =
+    "are you sure? this skill level\nisn't even remotely fair.\n\npress y or n.";
+const ENDGAME: &str = "are you sure you want to end the game?\n\npress y or n.";
+const SAVEDEAD: &str = "you can't save if you aren't playing!\n\npress a key.";
+const EMPTYSTRING: &str = "empty slot";
+
+/// Everything the menu asks the game to do
+pub enum MenuAction {
+    NewGame { episode: usize, skill: Skill },
+    LoadGame(usize),
+    SaveGame(usize, String),
+    EndGame,
+    Quit,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum MenuId {
+    Main,
+    Episode,
+    NewGame,
+    Options,
+    Sound,
+    Load,
+    Save,
+    ReadThis1,
+    ReadThis2,
+}
+
+const MENU_COUNT: usize = 9;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ItemKind {
+    /// Blank line skipped by the cursor
+    Empty,
+    Select,
+    /// Changed with left and right
+    Slider,
+}
+
+struct MenuItem {
+    kind: ItemKind,
+    lump: &'static str,
+    hotkey: Option<VirtualKeyCode>,
+}
+
+const fn select(lump: &'static str, hotkey: VirtualKeyCode) -> MenuItem {
+    MenuItem {
+        kind: ItemKind::Select,
+        lump,
+        hotkey: Some(hotkey),
+    }
+}
+
+const fn slider(lump: &'static str, hotkey: VirtualKeyCode) -> MenuItem {
+    MenuItem {
+        kind: ItemKind::Slider,
+        lump,
+        hotkey: Some(hotkey),
+    }
+}
+
+const EMPTY: MenuItem = MenuItem {
+    kind: ItemKind::Empty,
+    lump: "",
+    hotkey: None,
+};
+
+/// Items and position of a menu, like vanilla menu_t
+struct MenuDef {
+    items: &'static [MenuItem],
+    x: i32,
+    y: i32,
+}
+
+const MAIN_MENU: MenuDef = MenuDef {
+    items: &[
+        select("M_NGAME", VirtualKeyCode::N),
+        select("M_OPTION", VirtualKeyCode::O),
+        select("M_LOADG", VirtualKeyCode::L),
+        select("M_SAVEG", VirtualKeyCode::S),
+        select("M_RDTHIS", VirtualKeyCode::R),
+        select("M_QUITG", VirtualKeyCode::Q),
+    ],
+    x: 97,
+    y: 64,
+};
+
+const EPISODE_MENU: MenuDef = MenuDef {
+    items: &[
+        select("M_EPI1", VirtualKeyCode::K),
+        select("M_EPI2", VirtualKeyCode::T),
+        select("M_EPI3", VirtualKeyCode::I),
+        select("M_EPI4", VirtualKeyCode::T),
+    ],
+    x: 48,
+    y: 63,
+};
+
+const NEW_GAME_MENU: MenuDef = MenuDef {
+    items: &[
+        select("M_JKILL", VirtualKeyCode::I),
+        select("M_ROUGH", VirtualKeyCode::H),
+        select("M_HURT", VirtualKeyCode::H),
+        select("M_ULTRA", VirtualKeyCode::U),
+        select("M_NMARE", VirtualKeyCode::N),
+    ],
+    x: 48,
+    y: 63,
+};
+
+const OPTIONS_MENU: MenuDef = MenuDef {
+    items: &[
+        select("M_ENDGAM", VirtualKeyCode::E),
+        select("M_MESSG", VirtualKeyCode::M),
+        select("M_DETAIL", VirtualKeyCode::G),
+        slider("M_SCRNSZ", VirtualKeyCode::S),
+        EMPTY,
+        slider("M_MSENS", VirtualKeyCode::M),
+        EMPTY,
+        select("M_SVOL", VirtualKeyCode::S),
+    ],
+    x: 60,
+    y: 37,
+};
+
+const OPTIONS_SCREEN_SIZE: usize = 3;
+const OPTIONS_MOUSE_SENS: usize = 5;
+
+const SOUND_MENU: MenuDef = MenuDef {
+    items: &[
+        slider("M_SFXVOL", VirtualKeyCode::S),
+        EMPTY,
+        slider("M_MUSVOL", VirtualKeyCode::M),
+        EMPTY,
+    ],
+    x: 80,
+    y: 64,
+};
+
+const SLOT_MENU: MenuDef = MenuDef {
+    items: &[
+        select("", VirtualKeyCode::Key1),
+        select("", VirtualKeyCode::Key2),
+        select("", VirtualKeyCode::Key3),
+        select("", VirtualKeyCode::Key4),
+        select("", VirtualKeyCode::Key5),
+        select("", VirtualKeyCode::Key6),
+    ],
+    x: 80,
+    y: 54,
+};
+
+const READ_THIS_MENU: MenuDef = MenuDef {
+    items: &[select("", VirtualKeyCode::Return)],
+    x: 330,
+    y: 175,
+};
+
+impl MenuId {
+    fn def(self) -> &'static MenuDef {
+        match self {
+            MenuId::Main => &MAIN_MENU,
+            MenuId::Episode => &EPISODE_MENU,
+            MenuId::NewGame => &NEW_GAME_MENU,
+            MenuId::Options => &OPTIONS_MENU,
+            MenuId::Sound => &SOUND_MENU,
+            MenuId::Load | MenuId::Save => &SLOT_MENU,
+            MenuId::ReadThis1 | MenuId::ReadThis2 => &READ_THIS_MENU,
+        }
+    }
+}
+
+/// What answering a message does
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Confirm {
+    /// Any key closes the message
+    None,
+    Quit,
+    Nightmare,
+    EndGame,
+}
+
+struct Message {
+    text: &'static str,
+    confirm: Confirm,
+}
+
+/// The vanilla menus drawn with the M_* patches
+pub struct Menu {
+    pub active: bool,
+    /// A level is being played, the title screen is shown otherwise
+    pub in_game: bool,
+    current: MenuId,
+    item_on: [usize; MENU_COUNT],
+    message: Option<Message>,
+
+    episodes: usize,
+    episode: usize,
+    save_slots: [Option<String>; SAVE_SLOTS],
+    /// Slot being named in the save menu
+    save_edit: Option<(usize, String)>,
+    /// Tics run since the start, for the skull animation
+    tic: u32,
+}
+
+impl Menu {
+    /// `episodes` is 0 for games without an episode menu
+    pub fn new(episodes: usize) -> Self {
+        let mut item_on = [0; MENU_COUNT];
+        item_on[MenuId::NewGame as usize] = Skill::Medium as usize;
+
+        Menu {
+            active: false,
+            in_game: false,
+            current: MenuId::Main,
+            item_on,
+            message: None,
+            episodes: episodes.min(EPISODE_MENU.items.len()),
+            episode: 0,
+            save_slots: Default::default(),
+            save_edit: None,
+            tic: 0,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.active = true;
+        self.set_menu(MenuId::Main);
+    }
+
+    /// Run one tic of the menu animations, like vanilla M_Ticker
+    pub fn ticker(&mut self) {
+        self.tic = self.tic.wrapping_add(1);
+    }
+
+    fn close(&mut self) {
+        self.active = false;
+        self.save_edit = None;
+    }
+
+    fn set_menu(&mut self, menu: MenuId) {
+        if menu == MenuId::Load || menu == MenuId::Save {
+            self.save_slots = std::array::from_fn(SaveGame::describe);
+        }
+        self.current = menu;
+    }
+
+    fn item_count(&self, menu: MenuId) -> usize {
+        match menu {
+            MenuId::Episode => self.episodes,
+            _ => menu.def().items.len(),
+        }
+    }
+
+    /// Menu shown when backing out of the current one
+    fn previous(&self) -> Option<MenuId> {
+        match self.current {
+            MenuId::Main => None,
+            MenuId::NewGame if self.episodes > 0 => Some(MenuId::Episode),
+            MenuId::Sound => Some(MenuId::Options),
+            MenuId::ReadThis2 => Some(MenuId::ReadThis1),
+            _ => Some(MenuId::Main),
+        }
+    }
+
+    fn start_message(&mut self, text: &'static str, confirm: Confirm) {
+        self.message = Some(Message { text, confirm });
+    }
+
+    /// Type a character in the save description being edited
+    pub fn on_char(&mut self, c: char) {
+        if let Some((_, description)) = &mut self.save_edit {
+            // The font only has the printable ASCII characters
+            if (c == ' ' || c.is_ascii_graphic()) && description.len() < SAVESTRINGSIZE - 1 {
+                description.push(c);
+            }
+        }
+    }
+
+    /// Handle a key press, Escape opens the menu when it is closed
+    pub fn responder(&mut self, key: VirtualKeyCode, cvars: &mut Cvars) -> Option<MenuAction> {
+        if !self.active {
+            if key == VirtualKeyCode::Escape {
+                self.open();
+            }
+            return None;
+        }
+
+        if let Some(message) = self.message.take() {
+            let yes = key == VirtualKeyCode::Y;
+            return match message.confirm {
+                Confirm::None => None,
+                Confirm::Quit if yes => Some(MenuAction::Quit),
+                Confirm::Nightmare if yes => {
+                    self.close();
+                    Some(MenuAction::NewGame {
+                        episode: self.episode,
+                        skill: Skill::Nightmare,
+                    })
+                }
+                Confirm::EndGame if yes => {
+                    self.current = MenuId::Main;
+                    Some(MenuAction::EndGame)
+                }
+                _ => None,
+            };
+        }
+
+        if let Some((slot, mut description)) = self.save_edit.take() {
+            match key {
+                VirtualKeyCode::Escape => (),
+                VirtualKeyCode::Return => {
+                    self.close();
+                    return Some(MenuAction::SaveGame(slot, description));
+                }
+                VirtualKeyCode::Back => {
+                    description.pop();
+                    self.save_edit = Some((slot, description));
+                }
+                // Characters come from on_char
+                _ => self.save_edit = Some((slot, description)),
+            }
+            return None;
+        }
+
+        let menu = self.current;
+        let count = self.item_count(menu);
+        let items = menu.def().items;
+        let item_on = self.item_on[menu as usize];
+
+        match key {
+            VirtualKeyCode::Escape => self.close(),
+            VirtualKeyCode::Back => {
+                if let Some(previous) = self.previous() {
+                    self.set_menu(previous);
+                }
+            }
+            VirtualKeyCode::Down | VirtualKeyCode::Up => {
+                let step = if key == VirtualKeyCode::Down {
+                    1
+                } else {
+                    count - 1
+                };
+                let mut next = item_on;
+                loop {
+                    next = (next + step) % count;
+                    if items[next].kind != ItemKind::Empty || next == item_on {
+                        break;
+                    }
+                }
+                self.item_on[menu as usize] = next;
+            }
+            VirtualKeyCode::Left if items[item_on].kind == ItemKind::Slider => {
+                self.change_slider(item_on, -1, cvars)
+            }
+            VirtualKeyCode::Right if items[item_on].kind == ItemKind::Slider => {
+                self.change_slider(item_on, 1, cvars)
+            }
+            VirtualKeyCode::Return => {
+                if items[item_on].kind == ItemKind::Slider {
+                    self.change_slider(item_on, 1, cvars);
+                } else {
+                    return self.choose(item_on, cvars);
+                }
+            }
+            _ => {
+                // Jump to the next item with this hotkey
+                if let Some(next) = (1..=count)
+                    .map(|i| (item_on + i) % count)
+                    .find(|&i| items[i].hotkey == Some(key))
+                {
+                    self.item_on[menu as usize] = next;
+                }
+            }
+        }
+        None
+    }
+
+    fn change_slider(&mut self, item: usize, step: i32, cvars: &mut Cvars) {
+        let name = match (self.current, item) {
+            (MenuId::Options, OPTIONS_SCREEN_SIZE) => "screenblocks",
+            (MenuId::Options, OPTIONS_MOUSE_SENS) => "mouse_sensitivity",
+            (MenuId::Sound, 0) => "sfx_volume",
+            (MenuId::Sound, 2) => "music_volume",
+            _ => return,
+        };
+        cvars.set_int(name, cvars.int(name) + step);
+    }
+
+    fn choose(&mut self, item: usize, cvars: &mut Cvars) -> Option<MenuAction> {
+        match (self.current, item) {
+            (MenuId::Main, 0) => {
+                if self.episodes > 0 {
+                    self.set_menu(MenuId::Episode);
+                } else {
+                    self.set_menu(MenuId::NewGame);
+                }
+            }
+            (MenuId::Main, 1) => self.set_menu(MenuId::Options),
+            (MenuId::Main, 2) => self.set_menu(MenuId::Load),
+            (MenuId::Main, 3) => {
+                if self.in_game {
+                    self.set_menu(MenuId::Save);
+                } else {
+                    self.start_message(SAVEDEAD, Confirm::None);
+                }
+            }
+            (MenuId::Main, 4) => self.set_menu(MenuId::ReadThis1),
+            (MenuId::Main, _) => self.start_message(QUITMSG, Confirm::Quit),
+            (MenuId::Episode, episode) => {
+                self.episode = episode;
+                self.set_menu(MenuId::NewGame);
+            }
+            (MenuId::NewGame, skill) => {
+                let skill = Skill::from_index(skill);
+                if skill == Skill::Nightmare {
+                    self.start_message(NIGHTMARE, Confirm::Nightmare);
+                } else {
+                    self.close();
+                    return Some(MenuAction::NewGame {
+                        episode: self.episode,
+                        skill,
+                    });
+                }
+            }
+            (MenuId::Options, 0) => {
+                if self.in_game {
+                    self.start_message(ENDGAME, Confirm::EndGame);
+                }
+            }
+            (MenuId::Options, 1) => cvars.set_bool("show_messages", !cvars.bool("show_messages")),
+            (MenuId::Options, 2) => cvars.set_int("detaillevel", 1 - cvars.int("detaillevel")),
+            (MenuId::Options, _) => self.set_menu(MenuId::Sound),
+            (MenuId::Load, slot) => {
+                if self.save_slots[slot].is_some() {
+                    self.close();
+                    return Some(MenuAction::LoadGame(slot));
+                }
+            }
+            (MenuId::Save, slot) => {
+                let description = self.save_slots[slot].clone().unwrap_or_default();
+                self.save_edit = Some((slot, description));
+            }
+            (MenuId::ReadThis1, _) => self.set_menu(MenuId::ReadThis2),
+            (MenuId::ReadThis2, _) => self.close(),
+            (MenuId::Sound, _) => (),
+        }
+        None
+    }
+
+    pub fn draw(&self, screen: &mut Screen2D, graphics: &Textures, font: &Font, cvars: &Cvars) {
+        if !self.active {
+            return;
+        }
+
+        let draw = |screen: &mut Screen2D, x: i32, y: i32, lump: &str| {
+            if let Some(texture) = graphics.find_texture(lump) {
+                screen.draw_patch(x, y, &texture.patch());
+            }
+        };
+
+        if let Some(message) = &self.message {
+            let lines = message.text.lines().count() as i32;
+            let y = 100 - lines * font.line_height() / 2;
+            let style = TextStyle {
+                align: Align::Center,
+                ..Default::default()
+            };
+            font.draw(screen, 160.0, y as f32, message.text, &style);
+            return;
+        }
+
+        let menu = self.current;
+        let def = menu.def();
+        match menu {
+            MenuId::Main => draw(screen, 94, 2, "M_DOOM"),
+            MenuId::Episode => draw(screen, 54, 38, "M_EPISOD"),
+            MenuId::NewGame => {
+                draw(screen, 96, 14, "M_NEWG");
+                draw(screen, 54, 38, "M_SKILL");
+            }
+            MenuId::Options => {
+                draw(screen, 108, 15, "M_OPTTTL");
+                let detail = if cvars.int("detaillevel") == 1 {
+                    "M_GDLOW"
+                } else {
+                    "M_GDHIGH"
+                };
+                draw(screen, def.x + 175, def.y + LINEHEIGHT * 2, detail);
+                let messages = if cvars.bool("show_messages") {
+                    "M_MSGON"
+                } else {
+                    "M_MSGOFF"
+                };
+                draw(screen, def.x + 120, def.y + LINEHEIGHT, messages);
+                self.draw_thermo(
+                    screen,
+                    graphics,
+                    def.x,
+                    def.y + LINEHEIGHT * (OPTIONS_SCREEN_SIZE as i32 + 1),
+                    9,
+                    cvars.int("screenblocks") - 3,
+                );
+                self.draw_thermo(
+                    screen,
+                    graphics,
+                    def.x,
+                    def.y + LINEHEIGHT * (OPTIONS_MOUSE_SENS as i32 + 1),
+                    10,
+                    cvars.int("mouse_sensitivity"),
+                );
+            }
+            MenuId::Sound => {
+                draw(screen, 60, 38, "M_SVOL");
+                let thermo_y = |item: i32| def.y + LINEHEIGHT * (item + 1);
+                self.draw_thermo(
+                    screen,
+                    graphics,
+                    def.x,
+                    thermo_y(0),
+                    16,
+                    cvars.int("sfx_volume"),
+                );
+                self.draw_thermo(
+                    screen,
+                    graphics,
+                    def.x,
+                    thermo_y(2),
+                    16,
+                    cvars.int("music_volume"),
+                );
+            }
+            MenuId::Load | MenuId::Save => {
+                let title = if menu == MenuId::Load {
+                    "M_LOADG"
+                } else {
+                    "M_SAVEG"
+                };
+                draw(screen, 72, 28, title);
+                for slot in 0..SAVE_SLOTS {
+                    let y = def.y + LINEHEIGHT * slot as i32;
+                    self.draw_save_border(screen, graphics, def.x, y + 7);
+                    let text = match &self.save_edit {
+                        Some((edit, description)) if *edit == slot => format!("{}_", description),
+                        _ => self.save_slots[slot]
+                            .clone()
+                            .unwrap_or_else(|| EMPTYSTRING.to_string()),
+                    };
+                    font.draw(screen, def.x as f32, y as f32, &text, &TextStyle::default());
+                }
+            }
+            MenuId::ReadThis1 | MenuId::ReadThis2 => {
+                let page = match (menu, graphics.find_texture("HELP2").is_some()) {
+                    (MenuId::ReadThis1, true) => "HELP2",
+                    _ => "HELP1",
+                };
+                draw(screen, 0, 0, page);
+                return;
+            }
+        }
+
+        for (i, item) in def.items.iter().take(self.item_count(menu)).enumerate() {
+            if !item.lump.is_empty() {
+                draw(screen, def.x, def.y + LINEHEIGHT * i as i32, item.lump);
+            }
+        }
+
+        let frame = self.tic / SKULL_ANIM_TICS;
+        let skull = if frame.is_multiple_of(2) {
+            "M_SKULL1"
+        } else {
+            "M_SKULL2"
+        };
+        let item_on = self.item_on[menu as usize] as i32;
+        draw(
+            screen,
+            def.x + SKULLXOFF,
+            def.y - 5 + item_on * LINEHEIGHT,
+            skull,
+        );
+    }
+
+    /// Slider of `width` steps with the dot on `value`, like vanilla M_DrawThermo
+    fn draw_thermo(
+        &self,
+        screen: &mut Screen2D,
+        graphics: &Textures,
+        x: i32,
+        y: i32,
+        width: i32,
+        value: i32,
+    ) {
+        let mut draw = |x: i32, lump: &str| {
+            if let Some(texture) = graphics.find_texture(lump) {
+                screen.draw_patch(x, y, &texture.patch());
+            }
+        };
+        draw(x, "M_THERML");
+        for i in 0..width {
+            draw(x + 8 + i * 8, "M_THERMM");
+        }
+        draw(x + 8 + width * 8, "M_THERMR");
+        draw(x + 8 + value * 8, "M_THERMO");
+    }
+
+    fn draw_save_border(&self, screen: &mut Screen2D, graphics: &Textures, x: i32, y: i32) {
+        let mut draw = |x: i32, lump: &str| {
+            if let Some(texture) = graphics.find_texture(lump) {
+                screen.draw_patch(x, y, &texture.patch());
+            }
+        };
+        draw(x - 8, "M_LSLEFT");
+        for i in 0..SAVESTRINGSIZE as i32 {
+            draw(x + i * 8, "M_LSCNTR");
+        }
+        draw(x + SAVESTRINGSIZE as i32 * 8, "M_LSRGHT");
+    }
+}
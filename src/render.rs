@@ -1,4 +1,8 @@
 pub mod flat_model;
-pub mod material;
+pub mod font;
 //pub mod model;
+pub mod player_sprites;
+pub mod software;
+pub mod sprite_model;
+pub mod view_clip;
 pub mod wall_model;
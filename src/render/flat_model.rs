@@ -1,196 +1,103 @@
-use std::rc::Rc;
-
-use cgmath::Matrix4;
-use kabal_render::doom_gl::{gl, DoomGl};
+use cgmath::Vector3;
+use kabal_render::renderer::{DrawBatch, GVertex, MeshId, Renderer};
 
 use crate::sys::textures::Texture;
 
-use super::material::{Material, MaterialParam, MaterialValue, Stride};
-
+/// Floor and ceiling of a sector, both share the same triangulation
 pub struct FlatModel {
     vbuffer: Vec<f32>,
-    ibuffer: Vec<u16>,
+    ibuffer: Vec<u32>,
     pub light: f32,
     pub ceil: f32,
     pub floor: f32,
-    material: Material,
-    ceil_texture: u32,
-    floor_texture: u32,
-    ceil_depth: u32,
-    floor_depth: u32,
-
-    height_att: Rc<MaterialParam>,
-    light_att: Rc<MaterialParam>,
-    persp_att: Rc<MaterialParam>,
-    view_att: Rc<MaterialParam>,
-    img_att: Rc<MaterialParam>,
-    sky_att: Rc<MaterialParam>,
-    depth_att: Rc<MaterialParam>,
-
-    vao: u32,
-    ib: u32,
-    vb: u32,
+    ceil_texture: Option<Texture>,
+    pub floor_texture: Texture,
+    ceil_mesh: Option<MeshId>,
+    floor_mesh: Option<MeshId>,
 }
 
-const FLAT_FRAG_STR: &str = include_str!("flat.frag");
-const FLAT_VERT_STR: &str = include_str!("flat.vert");
-
 impl FlatModel {
     pub fn new(
         vbuffer: Vec<f32>,
-        ibuffer: Vec<u16>,
+        ibuffer: Vec<u32>,
         ceil_texture: Option<&Texture>,
         floor_texture: &Texture,
     ) -> Self {
-        unsafe { DoomGl::gl().Disable(gl::CULL_FACE) };
-        let mut material = Material::new(FLAT_VERT_STR, FLAT_FRAG_STR);
-
-        let light_att = MaterialParam::from_uniform("light\0", &mut material);
-        let height_att = MaterialParam::from_uniform("height\0", &mut material);
-        let view_att = MaterialParam::from_uniform("view\0", &mut material);
-        let persp_att = MaterialParam::from_uniform("proj\0", &mut material);
-        let img_att = MaterialParam::from_uniform("image\0", &mut material);
-        let sky_att = MaterialParam::from_uniform("sky\0", &mut material);
-        let depth_att = MaterialParam::from_uniform("depth\0", &mut material);
-
-        height_att.set_value(MaterialValue::Float(0.0));
-
         FlatModel {
-            material,
             vbuffer,
             ibuffer,
-            persp_att,
-            view_att,
-            img_att,
-            sky_att,
-            height_att,
-            light_att,
-            depth_att,
             light: 1.0,
             ceil: 64.0,
             floor: 0.0,
-            vao: 0,
-            ib: 0,
-            vb: 0,
-            ceil_texture: match ceil_texture {
-                Some(t) => t.id,
-                _ => u32::MAX,
-            },
-            ceil_depth: match ceil_texture {
-                Some(t) => t.depth,
-                _ => u32::MAX,
-            },
-            floor_texture: floor_texture.id,
-            floor_depth: floor_texture.depth,
+            ceil_texture: ceil_texture.cloned(),
+            floor_texture: floor_texture.clone(),
+            ceil_mesh: None,
+            floor_mesh: None,
         }
     }
 
-    pub fn init(&mut self) {
-        let mut ib = unsafe { std::mem::zeroed() };
-        let mut vao = unsafe { std::mem::zeroed() };
-        let mut vb = unsafe { std::mem::zeroed() };
-
-        unsafe {
-            let gl = DoomGl::gl();
-            gl.GenBuffers(1, &mut vb);
-            gl.BindBuffer(gl::ARRAY_BUFFER, vb);
-            gl.BufferData(
-                gl::ARRAY_BUFFER,
-                (self.vbuffer.len() * std::mem::size_of::<f32>()) as gl::types::GLsizeiptr,
-                self.vbuffer.as_ptr() as *const _,
-                gl::DYNAMIC_DRAW,
-            );
-
-            // generate and bind the vao
-            gl.GenVertexArrays(1, &mut vao);
-            gl.BindVertexArray(vao);
+    /// Build the vertices of the plane at the given height, flats are tiled every 64 units
+    fn plane(&self, height: f32, texture: &Texture) -> Vec<GVertex> {
+        self.vbuffer
+            .chunks_exact(2)
+            .map(|p| GVertex {
+                pos: Vector3::new(-p[0], height, p[1]),
+                uv: Vector3::new(p[0] / 64.0, p[1] / 64.0, texture.depth as f32),
+                light: self.light,
+            })
+            .collect()
+    }
 
-            gl.GenBuffers(1, &mut ib);
-            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ib);
-            gl.BufferData(
-                gl::ELEMENT_ARRAY_BUFFER,
-                (self.ibuffer.len() * std::mem::size_of::<u16>()) as gl::types::GLsizeiptr,
-                self.ibuffer.as_ptr() as *const _,
-                gl::DYNAMIC_DRAW,
-            );
-            self.vao = vao;
-            self.ib = ib;
+    pub fn init(&mut self, renderer: &mut dyn Renderer) {
+        let floor = self.plane(self.floor, &self.floor_texture);
+        self.floor_mesh = Some(renderer.upload_mesh(&floor, &self.ibuffer));
 
-            assert!(gl.GetError() == 0);
+        if let Some(texture) = &self.ceil_texture {
+            let ceil = self.plane(self.ceil, texture);
+            self.ceil_mesh = Some(renderer.upload_mesh(&ceil, &self.ibuffer));
         }
-
-        let pos_att = MaterialParam::from_attrib("position\0", &mut self.material);
-
-        // Always bind stride after the buffer is bound
-        pos_att.set_value(MaterialValue::FloatStride(Stride {
-            count: 2,
-            stride: 2 * std::mem::size_of::<f32>(),
-            offset: 0,
-        }));
     }
 
-    pub fn render(&self, view: &Matrix4<f32>, persp: &Matrix4<f32>) {
-        self.view_att.set_value(MaterialValue::Matrix(*view));
-        self.persp_att.set_value(MaterialValue::Matrix(*persp));
-        self.img_att.set_value(MaterialValue::Int(0));
-        self.light_att.set_value(MaterialValue::Float(self.light));
-        self.sky_att.set_value(MaterialValue::Int(0));
-        self.depth_att.set_value(MaterialValue::Float(0.0f32));
-
-        let gl = DoomGl::gl();
-        unsafe {
-            DoomGl::gl().Disable(gl::CULL_FACE);
-
-            gl.BindVertexArray(self.vao);
-            gl.BindBuffer(gl::ARRAY_BUFFER, self.vb);
-            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.ib);
+    /// Upload the planes again after the heights or the floor texture changed
+    pub fn update(&mut self, renderer: &mut dyn Renderer) {
+        if let Some(mesh) = self.floor_mesh {
+            let floor = self.plane(self.floor, &self.floor_texture);
+            renderer.update_mesh(mesh, &floor, &self.ibuffer);
         }
 
-        // Always bind material after buffer is bound
-        self.material.bind();
-
-        unsafe {
-            // floor
-            if self.floor_texture != u32::MAX {
-                self.height_att.set_value(MaterialValue::Float(self.floor));
-                self.height_att.bind();
-                self.depth_att
-                    .set_value(MaterialValue::Float(self.floor_depth as f32));
-                self.depth_att.bind();
-
-                gl.ActiveTexture(gl::TEXTURE0);
-                gl.BindTexture(gl::TEXTURE_2D_ARRAY, self.floor_texture);
-                gl.DrawElements(
-                    gl::TRIANGLES,
-                    self.ibuffer.len() as i32,
-                    gl::UNSIGNED_SHORT,
-                    std::ptr::null(),
-                );
-            }
-
-            self.sky_att.set_value(MaterialValue::Int(0));
-            self.material.bind();
+        if let (Some(mesh), Some(texture)) = (self.ceil_mesh, &self.ceil_texture) {
+            let ceil = self.plane(self.ceil, texture);
+            renderer.update_mesh(mesh, &ceil, &self.ibuffer);
+        }
+    }
 
-            // Ceil
-            if self.ceil_texture != u32::MAX {
-                self.height_att.set_value(MaterialValue::Float(self.ceil));
-                self.height_att.bind();
-                self.depth_att
-                    .set_value(MaterialValue::Float(self.ceil_depth as f32));
-                self.depth_att.bind();
+    /// Free the meshes of both planes
+    pub fn free(&mut self, renderer: &mut dyn Renderer) {
+        for mesh in [self.floor_mesh.take(), self.ceil_mesh.take()]
+            .into_iter()
+            .flatten()
+        {
+            renderer.delete_mesh(mesh);
+        }
+    }
 
-                gl.ActiveTexture(gl::TEXTURE0);
-                gl.BindTexture(gl::TEXTURE_2D_ARRAY, self.ceil_texture);
-                gl.DrawElements(
-                    gl::TRIANGLES,
-                    self.ibuffer.len() as i32,
-                    gl::UNSIGNED_SHORT,
-                    std::ptr::null(),
-                );
-            }
+    pub fn render(&self, renderer: &mut dyn Renderer) {
+        if let Some(mesh) = self.floor_mesh {
+            renderer.draw_batch(&DrawBatch {
+                mesh,
+                texture: self.floor_texture.id,
+                cull_face: false,
+                depth_test: true,
+            });
+        }
 
-            gl.BindVertexArray(0);
-            assert!(gl.GetError() == 0);
+        if let (Some(mesh), Some(texture)) = (self.ceil_mesh, &self.ceil_texture) {
+            renderer.draw_batch(&DrawBatch {
+                mesh,
+                texture: texture.id,
+                cull_face: false,
+                depth_test: true,
+            });
         }
     }
 }
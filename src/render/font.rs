@@ -0,0 +1,214 @@
+use kabal_render::{
+    renderer::Renderer,
+    screen2d::{PatchImage, Screen2D},
+};
+
+use crate::wad::{file::WadFile, patches::load_image, playpal::PlayPal};
+
+/// Lump name prefix of the font glyphs, followed by the 3 digits ASCII code
+const HU_FONT: &str = "STCFN";
+const HU_FONTSTART: u8 = b'!';
+const HU_FONTEND: u8 = b'~';
+/// Advance of a space and of characters missing from the font
+const SPACE_WIDTH: i32 = 4;
+
+/// Red ramp the font is drawn with, replaced by the ramp of the translation
+const FONT_RAMP: (usize, usize) = (176, 16);
+
+/// Colour translations, like the Boom CR tables
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextColor {
+    Red,
+    Gray,
+    Green,
+    Brown,
+    Gold,
+    Blue,
+    White,
+    Yellow,
+}
+
+/// First index and length of the palette ramp of each translation
+const TRANSLATIONS: [(usize, usize); 8] = [
+    (176, 16),
+    (88, 16),
+    (112, 16),
+    (64, 16),
+    (160, 8),
+    (192, 16),
+    (80, 16),
+    (224, 8),
+];
+
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// How a text is drawn, x is the left edge, the center or the right edge depending on align
+#[derive(Debug, Copy, Clone)]
+pub struct TextStyle {
+    pub scale: f32,
+    pub color: TextColor,
+    pub align: Align,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        TextStyle {
+            scale: 1.0,
+            color: TextColor::Red,
+            align: Align::Left,
+        }
+    }
+}
+
+struct Glyph {
+    /// Advance to the next character
+    width: i32,
+    /// One image per colour translation
+    images: Vec<PatchImage>,
+}
+
+/// The HUD font, every glyph and translation is a layer of one texture array
+pub struct Font {
+    glyphs: Vec<Option<Glyph>>,
+    height: i32,
+}
+
+impl Font {
+    pub fn new(file: &WadFile, renderer: &mut dyn Renderer) -> Self {
+        let playpal = PlayPal::new(file);
+        let palette = &playpal.palettes[0];
+
+        let patches = (HU_FONTSTART..=HU_FONTEND)
+            .map(|c| {
+                file.get_section(&format!("{}{:03}", HU_FONT, c))
+                    .map(|lump| load_image(lump, palette))
+            })
+            .collect::<Vec<_>>();
+
+        // Glyphs are padded to the biggest one to share a texture array
+        let cell_width = patches.iter().flatten().map(|p| p.width).max().unwrap_or(0);
+        let cell_height = patches
+            .iter()
+            .flatten()
+            .map(|p| p.height)
+            .max()
+            .unwrap_or(0);
+        if cell_width == 0 || cell_height == 0 {
+            return Font {
+                glyphs: Vec::new(),
+                height: 0,
+            };
+        }
+
+        let mut layers = Vec::new();
+        for (start, length) in TRANSLATIONS {
+            for patch in patches.iter().flatten() {
+                // Stored bottom-up like every other texture
+                let mut buffer = vec![0u8; cell_width * cell_height * 4];
+                for y in 0..patch.height {
+                    for x in 0..patch.width {
+                        let Some(mut index) = patch.pixels[y * patch.width + x] else {
+                            continue;
+                        };
+                        let index_in_ramp = (index as usize).wrapping_sub(FONT_RAMP.0);
+                        if index_in_ramp < FONT_RAMP.1 {
+                            index = (start + index_in_ramp * length / FONT_RAMP.1) as u8;
+                        }
+                        let color = &palette.colors[index as usize];
+                        let offset = ((cell_height - 1 - y) * cell_width + x) * 4;
+                        buffer[offset..offset + 4]
+                            .copy_from_slice(&[color.r, color.g, color.b, 255]);
+                    }
+                }
+                layers.push(buffer);
+            }
+        }
+        let texture = renderer.upload_texture_array(cell_width as i32, cell_height as i32, &layers);
+
+        let glyph_count = patches.iter().flatten().count();
+        let mut layer = 0;
+        let glyphs = patches
+            .iter()
+            .map(|patch| {
+                let patch = patch.as_ref()?;
+                let images = (0..TRANSLATIONS.len())
+                    .map(|translation| PatchImage {
+                        texture,
+                        layer: (translation * glyph_count + layer) as u32,
+                        width: cell_width as i32,
+                        height: cell_height as i32,
+                        left_offset: patch.left,
+                        top_offset: patch.top,
+                    })
+                    .collect();
+                layer += 1;
+                Some(Glyph {
+                    width: patch.width as i32,
+                    images,
+                })
+            })
+            .collect();
+
+        Font {
+            glyphs,
+            height: patches
+                .iter()
+                .flatten()
+                .next()
+                .map_or(0, |p| p.height as i32),
+        }
+    }
+
+    fn glyph(&self, c: char) -> Option<&Glyph> {
+        let c = c.to_ascii_uppercase() as u32;
+        if c < HU_FONTSTART as u32 || c > HU_FONTEND as u32 {
+            return None;
+        }
+        self.glyphs
+            .get((c - HU_FONTSTART as u32) as usize)
+            .and_then(|glyph| glyph.as_ref())
+    }
+
+    /// Distance between two lines of text
+    pub fn line_height(&self) -> i32 {
+        self.height + 1
+    }
+
+    /// Width of a single line of text, unscaled
+    pub fn line_width(&self, line: &str) -> i32 {
+        line.chars()
+            .map(|c| self.glyph(c).map_or(SPACE_WIDTH, |glyph| glyph.width))
+            .sum()
+    }
+
+    /// Draw a text in screen space, each line is aligned on its own
+    pub fn draw(&self, screen: &mut Screen2D, x: f32, y: f32, text: &str, style: &TextStyle) {
+        for (row, line) in text.lines().enumerate() {
+            let width = self.line_width(line) as f32 * style.scale;
+            let mut x = match style.align {
+                Align::Left => x,
+                Align::Center => x - width / 2.0,
+                Align::Right => x - width,
+            };
+            let y = y + (row as i32 * self.line_height()) as f32 * style.scale;
+
+            for c in line.chars() {
+                match self.glyph(c) {
+                    Some(glyph) => {
+                        let image = &glyph.images[style.color as usize];
+                        screen.draw_patch_scaled(x, y, style.scale, image);
+                        x += glyph.width as f32 * style.scale;
+                    }
+                    None => x += SPACE_WIDTH as f32 * style.scale,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use kabal_render::screen2d::Screen2D;
+
+use crate::{
+    game::{
+        states::{StateNum, FRAME_MASK, FULLBRIGHT},
+        weapons::Psprite,
+    },
+    sys::content::Content,
+};
+
+/// Light levels added by each step of the muzzle flash, like vanilla LIGHTSEGSHIFT
+const EXTRA_LIGHT_STEP: i32 = 16;
+
+/// Draw the weapon and its flash over the view, like vanilla R_DrawPlayerSprites
+///
+/// `light_level` is the one of the sector the player is in, `lift` moves the sprites up with
+/// the middle of the view
+pub fn draw_player_sprites(
+    screen: &mut Screen2D,
+    content: &Content,
+    psprites: &[Psprite],
+    light_level: i16,
+    extra_light: i32,
+    lift: f32,
+) {
+    let light = (light_level as i32 + extra_light * EXTRA_LIGHT_STEP).clamp(0, 255) as f32 / 255.0;
+    for psp in psprites {
+        if psp.state == StateNum::Null {
+            continue;
+        }
+        let state = psp.state.info();
+        let frame = (state.frame & FRAME_MASK) as usize;
+        let Some(frame) = content.get_sprites().frame(state.sprite.name(), frame) else {
+            continue;
+        };
+        let Some(texture) = frame.rotations[0]
+            .as_ref()
+            .and_then(|rotation| content.get_sprite_textures().find_texture(&rotation.lump))
+        else {
+            continue;
+        };
+
+        let light = if (state.frame & FULLBRIGHT) != 0 {
+            1.0
+        } else {
+            light
+        };
+        screen.draw_patch_light(psp.sx, psp.sy - lift, 1.0, light, &texture.patch());
+    }
+}
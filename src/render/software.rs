@@ -0,0 +1,895 @@
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use crate::camera::Camera;
+use crate::render::view_clip::{norm_pi, norm_tau, ViewClip};
+use crate::wad::colormap::{ColorMap, NUM_COLORMAPS};
+use crate::wad::doom_textures::{DoomTexture, DoomTextures};
+use crate::wad::file::WadFile;
+use crate::wad::map::{LinedefFlags, Node, Sector, WadMap, NF_SUBSECTOR};
+use crate::wad::playpal::{Palette, PlayPal};
+
+const LIGHT_LEVELS: usize = 16;
+const LIGHT_SEG_SHIFT: i32 = 4;
+const MAX_LIGHT_SCALE: usize = 48;
+const MAX_LIGHT_Z: usize = 128;
+const LIGHT_Z_UNIT: f32 = 16.0;
+const DIST_MAP: i32 = 2;
+
+const SKY_FLAT: &str = "F_SKY1";
+const SKY_TEXTURE_MID: f32 = 100.0;
+
+/// Top value of a visplane column that has not been touched
+const UNSET: i32 = i32::MAX;
+
+fn texture_name(name: &[u8; 8]) -> String {
+    String::from_utf8_lossy(&name.to_ascii_uppercase())
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+/// Horizontal surface gathered during the BSP walk, drawn as spans afterwards
+struct Visplane {
+    height: f32,
+    flat: Option<String>,
+    sky: bool,
+    light: usize,
+    min_x: i32,
+    max_x: i32,
+    /// Indexed from x + 1 so that min_x - 1 and max_x + 1 are valid
+    top: Vec<i32>,
+    bottom: Vec<i32>,
+}
+
+impl Visplane {
+    fn new(height: f32, flat: Option<String>, sky: bool, light: usize, width: usize) -> Self {
+        Visplane {
+            height,
+            flat,
+            sky,
+            light,
+            min_x: width as i32,
+            max_x: -1,
+            top: vec![UNSET; width + 2],
+            bottom: vec![-1; width + 2],
+        }
+    }
+}
+
+/// A column of a two-sided middle texture, drawn back to front after the walls
+struct MaskedColumn {
+    x: i32,
+    scale: f32,
+    texture: String,
+    texture_column: i32,
+    texture_mid: f32,
+    colormap: usize,
+    top_clip: i32,
+    bottom_clip: i32,
+}
+
+/// Everything computed for the seg currently being stored, like the rw_* globals of vanilla
+struct WallSeg {
+    normal_angle: f32,
+    distance: f32,
+    offset: f32,
+    center_angle: f32,
+    light: usize,
+}
+
+pub struct SoftwareRenderer {
+    pub width: usize,
+    pub height: usize,
+    /// Palette indices, row major
+    pub screen: Vec<u8>,
+
+    textures: HashMap<String, DoomTexture>,
+    colormap: ColorMap,
+    palette: Palette,
+
+    center_x: f32,
+    center_y: f32,
+    projection: f32,
+    x_to_view_angle: Vec<f32>,
+    dist_scale: Vec<f32>,
+
+    view_x: f32,
+    view_y: f32,
+    view_z: f32,
+    view_angle: f32,
+    sky_texture: &'static str,
+
+    clip: ViewClip,
+    ceiling_clip: Vec<i32>,
+    floor_clip: Vec<i32>,
+    planes: Vec<Visplane>,
+    masked: Vec<MaskedColumn>,
+    span_start: Vec<i32>,
+}
+
+impl SoftwareRenderer {
+    pub fn new(file: &WadFile, width: usize, height: usize) -> Self {
+        let mut textures = HashMap::new();
+        for texture in DoomTextures::new(file).list {
+            let name = texture.name.trim_end_matches('\0').to_string();
+            textures.entry(name).or_insert(texture);
+        }
+
+        let center_x = width as f32 / 2.0;
+        // Vanilla uses a 90° horizontal field of view
+        let projection = center_x;
+
+        let x_to_view_angle = (0..=width)
+            .map(|x| ((center_x - x as f32) / projection).atan())
+            .collect::<Vec<f32>>();
+        let dist_scale = x_to_view_angle
+            .iter()
+            .map(|angle| 1.0 / angle.cos())
+            .collect();
+
+        SoftwareRenderer {
+            width,
+            height,
+            screen: vec![0; width * height],
+            textures,
+            colormap: ColorMap::new(file),
+            palette: PlayPal::new(file).palettes[0],
+            center_x,
+            center_y: height as f32 / 2.0,
+            projection,
+            x_to_view_angle,
+            dist_scale,
+            view_x: 0.0,
+            view_y: 0.0,
+            view_z: 0.0,
+            view_angle: 0.0,
+            sky_texture: "SKY1",
+            clip: ViewClip::new(width),
+            ceiling_clip: vec![-1; width],
+            floor_clip: vec![height as i32; width],
+            planes: Vec::new(),
+            masked: Vec::new(),
+            span_start: vec![0; height],
+        }
+    }
+
+    /// Convert the indexed screen to RGBA using the first palette
+    pub fn to_rgba(&self) -> Vec<u8> {
+        let mut rgba = Vec::with_capacity(self.screen.len() * 4);
+        for index in &self.screen {
+            let color = self.palette.colors[*index as usize];
+            rgba.extend_from_slice(&[color.r, color.g, color.b, 255]);
+        }
+        rgba
+    }
+
+    /// Render the map as seen from the camera into the screen buffer
+    pub fn render(&mut self, map: &WadMap, camera: &Camera) {
+        let (x, y, z) = camera.map_position();
+        self.view_x = x;
+        self.view_y = y;
+        self.view_z = z;
+        self.view_angle = norm_tau(camera.map_angle().0.to_radians());
+        self.center_y =
+            self.height as f32 / 2.0 - camera.pitch().0.to_radians().tan() * self.projection;
+        self.sky_texture = map.sky_texture();
+
+        self.screen.fill(0);
+        self.clip.reset(x, y, self.view_angle);
+        self.ceiling_clip.fill(-1);
+        self.floor_clip.fill(self.height as i32);
+        self.planes.clear();
+        self.masked.clear();
+
+        if map.nodes.is_empty() {
+            self.render_subsector(map, 0);
+        } else {
+            self.render_bsp_node(map, map.nodes.len() - 1);
+        }
+
+        self.draw_planes();
+        self.draw_masked();
+    }
+
+    fn render_bsp_node(&mut self, map: &WadMap, index: usize) {
+        if self.clip.is_full() {
+            return;
+        }
+
+        let node = map.nodes[index];
+        let side = node.point_on_side(self.view_x, self.view_y);
+        let bbox = node.bbox;
+
+        self.render_child(map, &node, side);
+        if self.clip.check_bbox(&bbox[side ^ 1]) {
+            self.render_child(map, &node, side ^ 1);
+        }
+    }
+
+    fn render_child(&mut self, map: &WadMap, node: &Node, side: usize) {
+        let child = node.children[side];
+        if child & NF_SUBSECTOR != 0 {
+            self.render_subsector(map, (child & !NF_SUBSECTOR) as usize);
+        } else {
+            self.render_bsp_node(map, child as usize);
+        }
+    }
+
+    fn is_sky(sector: &Sector) -> bool {
+        texture_name(&sector.ceil_tex) == SKY_FLAT
+    }
+
+    /// Find or create the visplane matching the parameters, sky planes are all merged
+    fn find_plane(&mut self, height: f32, flat: &[u8; 8], light: i16) -> usize {
+        let name = texture_name(flat);
+        let sky = name == SKY_FLAT;
+        let (height, light) = if sky {
+            (0.0, 0)
+        } else {
+            (
+                height,
+                (light.max(0) as usize >> LIGHT_SEG_SHIFT).min(LIGHT_LEVELS - 1),
+            )
+        };
+        let flat = if sky { None } else { Some(name) };
+
+        if let Some(index) = self.planes.iter().position(|plane| {
+            plane.height == height && plane.flat == flat && plane.sky == sky && plane.light == light
+        }) {
+            return index;
+        }
+
+        self.planes
+            .push(Visplane::new(height, flat, sky, light, self.width));
+        self.planes.len() - 1
+    }
+
+    /// Make sure the plane can take the [start, stop] range, split it in a new plane otherwise
+    fn check_plane(&mut self, index: usize, start: i32, stop: i32) -> usize {
+        let plane = &self.planes[index];
+        let (intersect_low, union_low) = if start < plane.min_x {
+            (plane.min_x, start)
+        } else {
+            (start, plane.min_x)
+        };
+        let (intersect_high, union_high) = if stop > plane.max_x {
+            (plane.max_x, stop)
+        } else {
+            (stop, plane.max_x)
+        };
+
+        let free = (intersect_low..=intersect_high).all(|x| plane.top[(x + 1) as usize] == UNSET);
+        if free {
+            let plane = &mut self.planes[index];
+            plane.min_x = union_low;
+            plane.max_x = union_high;
+            return index;
+        }
+
+        let mut new_plane = Visplane::new(
+            plane.height,
+            plane.flat.clone(),
+            plane.sky,
+            plane.light,
+            self.width,
+        );
+        new_plane.min_x = start;
+        new_plane.max_x = stop;
+        self.planes.push(new_plane);
+        self.planes.len() - 1
+    }
+
+    fn render_subsector(&mut self, map: &WadMap, index: usize) {
+        let subsector = map.subsectors[index];
+        let first_seg = map.segs[subsector.first_seg as usize];
+        let linedef = map.linedefs[first_seg.linedef as usize];
+        let side = if first_seg.side == 0 {
+            linedef.front_sidedef
+        } else {
+            linedef.back_sidedef
+        };
+        let sector = map.sectors[map.sidedefs[side as usize].sector as usize];
+
+        let floor_plane = if sector.floor < self.view_z {
+            Some(self.find_plane(sector.floor, &sector.floor_tex, sector.lighting))
+        } else {
+            None
+        };
+        let ceiling_plane = if sector.ceiling > self.view_z || SoftwareRenderer::is_sky(&sector) {
+            Some(self.find_plane(sector.ceiling, &sector.ceil_tex, sector.lighting))
+        } else {
+            None
+        };
+
+        let first = subsector.first_seg as usize;
+        let mut planes = (floor_plane, ceiling_plane);
+        for seg_index in first..first + subsector.seg_count as usize {
+            self.add_line(map, seg_index, &sector, &mut planes);
+        }
+    }
+
+    /// Clip a seg against the already drawn solid walls and store the visible parts
+    fn add_line(
+        &mut self,
+        map: &WadMap,
+        seg_index: usize,
+        front: &Sector,
+        planes: &mut (Option<usize>, Option<usize>),
+    ) {
+        let seg = map.segs[seg_index];
+        let v1 = map.vertexes[seg.start_vertex as usize];
+        let v2 = map.vertexes[seg.end_vertex as usize];
+
+        let (x1, x2, angle1) =
+            match self
+                .clip
+                .clip_span(v1.x as f32, v1.y as f32, v2.x as f32, v2.y as f32)
+            {
+                Some(span) => span,
+                None => return,
+            };
+
+        let linedef = map.linedefs[seg.linedef as usize];
+        let back_side = if seg.side == 0 {
+            linedef.back_sidedef
+        } else {
+            linedef.front_sidedef
+        };
+        let back = if back_side != -1 {
+            Some(map.sectors[map.sidedefs[back_side as usize].sector as usize])
+        } else {
+            None
+        };
+
+        let solid = match back {
+            None => true,
+            Some(back) => {
+                if back.ceiling <= front.floor || back.floor >= front.ceiling {
+                    true
+                } else {
+                    let side = if seg.side == 0 {
+                        linedef.front_sidedef
+                    } else {
+                        linedef.back_sidedef
+                    };
+                    let middle = texture_name(&map.sidedefs[side as usize].middle_tex);
+                    let identical = back.ceiling == front.ceiling
+                        && back.floor == front.floor
+                        && back.ceil_tex == front.ceil_tex
+                        && back.floor_tex == front.floor_tex
+                        && back.lighting == front.lighting
+                        && !self.textures.contains_key(&middle);
+                    if identical {
+                        // Nothing to draw, the line is only there for gameplay
+                        return;
+                    }
+                    false
+                }
+            }
+        };
+
+        // Store every visible run of columns
+        let mut x = x1;
+        while x < x2 {
+            if self.clip.solid[x as usize] {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < x2 && !self.clip.solid[x as usize] {
+                x += 1;
+            }
+            self.store_wall_range(
+                map,
+                seg_index,
+                angle1,
+                front,
+                back.as_ref(),
+                planes,
+                start,
+                x - 1,
+            );
+        }
+
+        if solid {
+            self.clip.mark_solid(x1, x2);
+        }
+    }
+
+    fn scale_from_global_angle(&self, wall: &WallSeg, visible_angle: f32) -> f32 {
+        let angle_a = FRAC_PI_2 + (visible_angle - self.view_angle);
+        let angle_b = FRAC_PI_2 + (visible_angle - wall.normal_angle);
+        let num = self.projection * angle_b.sin();
+        let den = wall.distance * angle_a.sin();
+
+        if den > num / 64.0 {
+            (num / den).clamp(1.0 / 256.0, 64.0)
+        } else {
+            64.0
+        }
+    }
+
+    /// Colormap used for a wall at the given scale
+    fn wall_colormap(&self, light: usize, scale: f32) -> usize {
+        let start_map = ((LIGHT_LEVELS - 1 - light) * 2 * NUM_COLORMAPS / LIGHT_LEVELS) as i32;
+        let index = ((scale * 16.0) as usize).min(MAX_LIGHT_SCALE - 1) as i32;
+        let level = start_map - index * 320 / self.width as i32 / DIST_MAP;
+        level.clamp(0, NUM_COLORMAPS as i32 - 1) as usize
+    }
+
+    /// Colormap used for a flat at the given distance
+    fn plane_colormap(&self, light: usize, distance: f32) -> usize {
+        let start_map = ((LIGHT_LEVELS - 1 - light) * 2 * NUM_COLORMAPS / LIGHT_LEVELS) as i32;
+        let index = ((distance / LIGHT_Z_UNIT) as usize).min(MAX_LIGHT_Z - 1) as i32;
+        let scale = 160 / (index + 1);
+        let level = start_map - scale / DIST_MAP;
+        level.clamp(0, NUM_COLORMAPS as i32 - 1) as usize
+    }
+
+    /// Draw a vertical textured column between yl and yh (both included)
+    #[allow(clippy::too_many_arguments)]
+    fn draw_column(
+        &mut self,
+        x: i32,
+        yl: i32,
+        yh: i32,
+        texture: &str,
+        texture_column: i32,
+        texture_mid: f32,
+        scale: f32,
+        colormap: usize,
+        masked: bool,
+    ) {
+        let texture = match self.textures.get(texture) {
+            Some(t) => t,
+            None => return,
+        };
+        let step = 1.0 / scale;
+        let mut frac = texture_mid + (yl as f32 - self.center_y) * step;
+        for y in yl..=yh {
+            let row = frac.floor() as i32;
+            frac += step;
+            if masked && !(0..texture.height).contains(&row) {
+                continue;
+            }
+            if let Some(pixel) = texture.texel(texture_column, row) {
+                self.screen[y as usize * self.width + x as usize] =
+                    self.colormap.maps[colormap][pixel as usize];
+            }
+        }
+    }
+
+    /// Draw the visible columns of a seg, like R_StoreWallRange
+    #[allow(clippy::too_many_arguments)]
+    fn store_wall_range(
+        &mut self,
+        map: &WadMap,
+        seg_index: usize,
+        angle1: f32,
+        front: &Sector,
+        back: Option<&Sector>,
+        planes: &mut (Option<usize>, Option<usize>),
+        start: i32,
+        stop: i32,
+    ) {
+        let seg = map.segs[seg_index];
+        let linedef = map.linedefs[seg.linedef as usize];
+        let side = if seg.side == 0 {
+            linedef.front_sidedef
+        } else {
+            linedef.back_sidedef
+        };
+        let sidedef = map.sidedefs[side as usize];
+        let v1 = map.vertexes[seg.start_vertex as usize];
+        let v2 = map.vertexes[seg.end_vertex as usize];
+
+        // Distance to the wall and texture offset
+        let seg_angle = (v2.y as f32 - v1.y as f32).atan2(v2.x as f32 - v1.x as f32);
+        let normal_angle = norm_tau(seg_angle + FRAC_PI_2);
+        let offset_angle = norm_pi(normal_angle - angle1).abs().min(FRAC_PI_2);
+        let hyp = (v1.x as f32 - self.view_x).hypot(v1.y as f32 - self.view_y);
+        let distance = hyp * offset_angle.cos();
+
+        let mut offset = hyp * offset_angle.sin();
+        if norm_tau(normal_angle - angle1) < PI {
+            offset = -offset;
+        }
+        offset += sidedef.x_offset as f32 + seg.offset as f32;
+
+        let mut light = (front.lighting.max(0) as i32) >> LIGHT_SEG_SHIFT;
+        if v1.y == v2.y {
+            light -= 1;
+        } else if v1.x == v2.x {
+            light += 1;
+        }
+
+        let wall = WallSeg {
+            normal_angle,
+            distance,
+            offset,
+            center_angle: FRAC_PI_2 + self.view_angle - normal_angle,
+            light: light.clamp(0, LIGHT_LEVELS as i32 - 1) as usize,
+        };
+
+        let scale1 = self.scale_from_global_angle(
+            &wall,
+            self.view_angle + self.x_to_view_angle[start as usize],
+        );
+        let scale_step = if stop > start {
+            let scale2 = self.scale_from_global_angle(
+                &wall,
+                self.view_angle + self.x_to_view_angle[stop as usize],
+            );
+            (scale2 - scale1) / (stop - start) as f32
+        } else {
+            0.0
+        };
+
+        // Heights relative to the viewer
+        let front_sky = SoftwareRenderer::is_sky(front);
+        let mut world_top = front.ceiling - self.view_z;
+        let world_bottom = front.floor - self.view_z;
+
+        let upper = texture_name(&sidedef.upper_tex);
+        let lower = texture_name(&sidedef.lower_tex);
+        let middle = texture_name(&sidedef.middle_tex);
+        let flags = linedef.flags;
+
+        let mut mid_texture = None;
+        let mut top_texture = None;
+        let mut bottom_texture = None;
+        let mut masked_texture = None;
+        let mut world_high = 0.0;
+        let mut world_low = 0.0;
+        let mut top_mid = 0.0;
+        let mut bottom_mid = 0.0;
+        let mut mid = 0.0;
+        let mut mark_floor = true;
+        let mut mark_ceiling = true;
+
+        match back {
+            None => {
+                if let Some(texture) = self.textures.get(&middle) {
+                    mid = if flags.contains(LinedefFlags::LOWER_TEX_UNPEGGED) {
+                        front.floor + texture.height as f32 - self.view_z
+                    } else {
+                        world_top
+                    };
+                    mid += sidedef.y_offset as f32;
+                    mid_texture = Some(middle);
+                }
+            }
+            Some(back) => {
+                world_high = back.ceiling - self.view_z;
+                world_low = back.floor - self.view_z;
+
+                // Hack to allow height changes in outdoor areas
+                if front_sky && SoftwareRenderer::is_sky(back) {
+                    world_top = world_high;
+                }
+
+                mark_floor = world_low != world_bottom
+                    || back.floor_tex != front.floor_tex
+                    || back.lighting != front.lighting;
+                mark_ceiling = world_high != world_top
+                    || back.ceil_tex != front.ceil_tex
+                    || back.lighting != front.lighting;
+                if back.ceiling <= front.floor || back.floor >= front.ceiling {
+                    // Closed door
+                    mark_floor = true;
+                    mark_ceiling = true;
+                }
+
+                if world_high < world_top {
+                    if let Some(texture) = self.textures.get(&upper) {
+                        top_mid = if flags.contains(LinedefFlags::UPPER_TEX_UNPEGGED) {
+                            world_top
+                        } else {
+                            back.ceiling + texture.height as f32 - self.view_z
+                        };
+                        top_mid += sidedef.y_offset as f32;
+                        top_texture = Some(upper);
+                    }
+                }
+                if world_low > world_bottom && self.textures.contains_key(&lower) {
+                    bottom_mid = if flags.contains(LinedefFlags::LOWER_TEX_UNPEGGED) {
+                        world_top
+                    } else {
+                        world_low
+                    };
+                    bottom_mid += sidedef.y_offset as f32;
+                    bottom_texture = Some(lower);
+                }
+                if let Some(texture) = self.textures.get(&middle) {
+                    let texture_mid = if flags.contains(LinedefFlags::LOWER_TEX_UNPEGGED) {
+                        front.floor.max(back.floor) + texture.height as f32 - self.view_z
+                    } else {
+                        front.ceiling.min(back.ceiling) - self.view_z
+                    };
+                    masked_texture = Some((middle, texture_mid + sidedef.y_offset as f32));
+                }
+            }
+        }
+
+        // Planes above or below the eye are never seen from this side
+        if front.floor >= self.view_z {
+            mark_floor = false;
+        }
+        if front.ceiling <= self.view_z && !front_sky {
+            mark_ceiling = false;
+        }
+
+        if mark_ceiling {
+            if let Some(plane) = planes.1 {
+                planes.1 = Some(self.check_plane(plane, start, stop));
+            } else {
+                mark_ceiling = false;
+            }
+        }
+        if mark_floor {
+            if let Some(plane) = planes.0 {
+                planes.0 = Some(self.check_plane(plane, start, stop));
+            } else {
+                mark_floor = false;
+            }
+        }
+
+        let mut scale = scale1;
+        for x in start..=stop {
+            let xu = x as usize;
+            let top_frac = self.center_y - world_top * scale;
+            let bottom_frac = self.center_y - world_bottom * scale;
+
+            let mut yl = top_frac.ceil() as i32;
+            if yl < self.ceiling_clip[xu] + 1 {
+                yl = self.ceiling_clip[xu] + 1;
+            }
+            let mut yh = bottom_frac.floor() as i32;
+            if yh >= self.floor_clip[xu] {
+                yh = self.floor_clip[xu] - 1;
+            }
+
+            if mark_ceiling {
+                let top = self.ceiling_clip[xu] + 1;
+                let bottom = (yl - 1).min(self.floor_clip[xu] - 1);
+                if top <= bottom {
+                    let plane = &mut self.planes[planes.1.unwrap()];
+                    plane.top[xu + 1] = top;
+                    plane.bottom[xu + 1] = bottom;
+                }
+            }
+            if mark_floor {
+                let top = (yh + 1).max(self.ceiling_clip[xu] + 1);
+                let bottom = self.floor_clip[xu] - 1;
+                if top <= bottom {
+                    let plane = &mut self.planes[planes.0.unwrap()];
+                    plane.top[xu + 1] = top;
+                    plane.bottom[xu + 1] = bottom;
+                }
+            }
+
+            let angle = norm_pi(wall.center_angle + self.x_to_view_angle[xu]);
+            let texture_column = (wall.offset - angle.tan() * wall.distance).floor() as i32;
+            let colormap = self.wall_colormap(wall.light, scale);
+
+            if let Some(texture) = &mid_texture {
+                self.draw_column(
+                    x,
+                    yl,
+                    yh,
+                    texture,
+                    texture_column,
+                    mid,
+                    scale,
+                    colormap,
+                    false,
+                );
+                self.ceiling_clip[xu] = self.height as i32;
+                self.floor_clip[xu] = -1;
+            } else {
+                if let Some(texture) = &top_texture {
+                    let pix_high = self.center_y - world_high * scale;
+                    let mut mid_y = pix_high.floor() as i32;
+                    if mid_y >= self.floor_clip[xu] {
+                        mid_y = self.floor_clip[xu] - 1;
+                    }
+                    if mid_y >= yl {
+                        self.draw_column(
+                            x,
+                            yl,
+                            mid_y,
+                            texture,
+                            texture_column,
+                            top_mid,
+                            scale,
+                            colormap,
+                            false,
+                        );
+                        self.ceiling_clip[xu] = mid_y;
+                    } else {
+                        self.ceiling_clip[xu] = yl - 1;
+                    }
+                } else if mark_ceiling {
+                    self.ceiling_clip[xu] = yl - 1;
+                }
+
+                if let Some(texture) = &bottom_texture {
+                    let pix_low = self.center_y - world_low * scale;
+                    let mut mid_y = pix_low.ceil() as i32;
+                    if mid_y <= self.ceiling_clip[xu] {
+                        mid_y = self.ceiling_clip[xu] + 1;
+                    }
+                    if mid_y <= yh {
+                        self.draw_column(
+                            x,
+                            mid_y,
+                            yh,
+                            texture,
+                            texture_column,
+                            bottom_mid,
+                            scale,
+                            colormap,
+                            false,
+                        );
+                        self.floor_clip[xu] = mid_y;
+                    } else {
+                        self.floor_clip[xu] = yh + 1;
+                    }
+                } else if mark_floor {
+                    self.floor_clip[xu] = yh + 1;
+                }
+
+                if let Some((texture, texture_mid)) = &masked_texture {
+                    self.masked.push(MaskedColumn {
+                        x,
+                        scale,
+                        texture: texture.clone(),
+                        texture_column,
+                        texture_mid: *texture_mid,
+                        colormap,
+                        top_clip: self.ceiling_clip[xu],
+                        bottom_clip: self.floor_clip[xu],
+                    });
+                }
+            }
+
+            scale += scale_step;
+        }
+    }
+
+    /// Draw one horizontal span of a flat
+    fn map_plane(&mut self, plane: usize, y: i32, x1: i32, x2: i32) {
+        let plane = &self.planes[plane];
+        let flat = match plane.flat.as_ref().and_then(|name| self.textures.get(name)) {
+            Some(flat) => flat,
+            None => return,
+        };
+
+        let plane_height = (plane.height - self.view_z).abs();
+        let dy = (y as f32 - self.center_y + 0.5).abs();
+        let distance = plane_height * self.center_x / dy;
+        let length = distance * self.dist_scale[x1 as usize];
+        let angle = self.view_angle + self.x_to_view_angle[x1 as usize];
+
+        let mut x_frac = self.view_x + angle.cos() * length;
+        let mut y_frac = -self.view_y - angle.sin() * length;
+        let base_angle = self.view_angle - FRAC_PI_2;
+        let x_step = distance * base_angle.cos() / self.center_x;
+        let y_step = -distance * base_angle.sin() / self.center_x;
+
+        let colormap = &self.colormap.maps[self.plane_colormap(plane.light, distance)];
+        let row = y as usize * self.width;
+        for x in x1..=x2 {
+            if let Some(pixel) = flat.texel(x_frac.floor() as i32, y_frac.floor() as i32) {
+                self.screen[row + x as usize] = colormap[pixel as usize];
+            }
+            x_frac += x_step;
+            y_frac += y_step;
+        }
+    }
+
+    /// Turn visplane columns into spans, like R_MakeSpans
+    fn make_spans(
+        &mut self,
+        plane: usize,
+        x: i32,
+        mut t1: i32,
+        mut b1: i32,
+        mut t2: i32,
+        mut b2: i32,
+    ) {
+        while t1 < t2 && t1 <= b1 {
+            self.map_plane(plane, t1, self.span_start[t1 as usize], x - 1);
+            t1 += 1;
+        }
+        while b1 > b2 && b1 >= t1 {
+            self.map_plane(plane, b1, self.span_start[b1 as usize], x - 1);
+            b1 -= 1;
+        }
+        while t2 < t1 && t2 <= b2 {
+            self.span_start[t2 as usize] = x;
+            t2 += 1;
+        }
+        while b2 > b1 && b2 >= t2 {
+            self.span_start[b2 as usize] = x;
+            b2 -= 1;
+        }
+    }
+
+    fn draw_sky(&mut self, plane: usize) {
+        let texture = match self.textures.get(self.sky_texture) {
+            Some(texture) => texture,
+            None => return,
+        };
+        let plane = &self.planes[plane];
+        let step = 320.0 / self.width as f32;
+
+        for x in plane.min_x..=plane.max_x {
+            let top = plane.top[(x + 1) as usize];
+            let bottom = plane.bottom[(x + 1) as usize];
+            if top > bottom {
+                continue;
+            }
+
+            // The sky wraps 4 times around the player, 1024 columns for 360°
+            let angle = norm_tau(self.view_angle + self.x_to_view_angle[x as usize]);
+            let column = (angle / TAU * 1024.0) as i32;
+            let mut frac = SKY_TEXTURE_MID + (top as f32 - self.center_y) * step;
+            for y in top..=bottom {
+                if let Some(pixel) = texture.texel(column, frac.floor() as i32) {
+                    self.screen[y as usize * self.width + x as usize] = pixel;
+                }
+                frac += step;
+            }
+        }
+    }
+
+    fn draw_planes(&mut self) {
+        for index in 0..self.planes.len() {
+            let plane = &self.planes[index];
+            if plane.min_x > plane.max_x {
+                continue;
+            }
+            if plane.sky {
+                self.draw_sky(index);
+                continue;
+            }
+
+            let (min_x, max_x) = (plane.min_x, plane.max_x);
+            for x in min_x..=max_x + 1 {
+                let plane = &self.planes[index];
+                let (t1, b1) = (plane.top[x as usize], plane.bottom[x as usize]);
+                let (t2, b2) = if x <= max_x {
+                    (plane.top[(x + 1) as usize], plane.bottom[(x + 1) as usize])
+                } else {
+                    (UNSET, -1)
+                };
+                self.make_spans(index, x, t1, b1, t2, b2);
+            }
+        }
+    }
+
+    /// Draw the two-sided middle textures, farthest first
+    fn draw_masked(&mut self) {
+        let masked = std::mem::take(&mut self.masked);
+        for column in masked.iter().rev() {
+            let top = self.center_y - column.texture_mid * column.scale;
+            let yl = (top.ceil() as i32).max(column.top_clip + 1);
+            let yh = (self.height as i32 - 1).min(column.bottom_clip - 1);
+            if yl <= yh {
+                self.draw_column(
+                    column.x,
+                    yl,
+                    yh,
+                    &column.texture,
+                    column.texture_column,
+                    column.texture_mid,
+                    column.scale,
+                    column.colormap,
+                    true,
+                );
+            }
+        }
+        self.masked = masked;
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use cgmath::Vector3;
+use kabal_render::renderer::{DrawBatch, GVertex, MeshId, Renderer, TextureId};
+
+use crate::{camera::Camera, sys::textures::Texture};
+
+/// A thing drawn as a quad always facing the camera
+pub struct Billboard {
+    /// Map position of the bottom of the thing
+    pub position: Vector3<f32>,
+    /// Facing angle in degrees
+    pub angle: f32,
+    pub light: f32,
+    /// Image and mirroring of each of the 8 rotations, front first
+    pub rotations: [Option<(Texture, bool)>; 8],
+}
+
+/// Every billboard of the map, refilled and rebuilt each frame and batched per texture array
+pub struct SpriteModel {
+    billboards: Vec<Billboard>,
+    meshes: HashMap<TextureId, MeshId>,
+}
+
+impl SpriteModel {
+    pub fn new() -> Self {
+        SpriteModel {
+            billboards: Vec::new(),
+            meshes: HashMap::new(),
+        }
+    }
+
+    pub fn push(&mut self, billboard: Billboard) {
+        self.billboards.push(billboard);
+    }
+
+    pub fn clear(&mut self) {
+        self.billboards.clear();
+    }
+
+    /// Free the mesh of every texture array
+    pub fn free(&mut self, renderer: &mut dyn Renderer) {
+        for (_, mesh) in self.meshes.drain() {
+            renderer.delete_mesh(mesh);
+        }
+    }
+
+    pub fn render(&mut self, camera: &Camera, renderer: &mut dyn Renderer) {
+        let (view_x, view_y, _) = camera.map_position();
+        let view_angle = camera.map_angle().0.to_radians();
+        // Screen right in map space, billboards only turn around the vertical axis
+        let right = (view_angle.sin(), -view_angle.cos());
+
+        let mut batches: HashMap<TextureId, (Vec<GVertex>, Vec<u32>)> = HashMap::new();
+        for billboard in &self.billboards {
+            let position = billboard.position;
+
+            // Pick the rotation from the angle the thing is seen at, like vanilla R_ProjectSprite
+            let angle = (position.y - view_y)
+                .atan2(position.x - view_x)
+                .to_degrees();
+            let rotation =
+                ((angle - billboard.angle + 202.5).rem_euclid(360.0) / 45.0) as usize % 8;
+            let (texture, flip) = match &billboard.rotations[rotation] {
+                Some((texture, flip)) => (texture, *flip),
+                None => continue,
+            };
+
+            let width = texture.width as f32;
+            let left = if flip {
+                width - texture.left_offset as f32
+            } else {
+                texture.left_offset as f32
+            };
+            let start = (position.x - right.0 * left, position.y - right.1 * left);
+            let end = (start.0 + right.0 * width, start.1 + right.1 * width);
+            let top = position.z + texture.top_offset as f32;
+            let bottom = top - texture.height as f32;
+            let (u_start, u_end) = if flip { (1.0, 0.0) } else { (0.0, 1.0) };
+
+            let vertex = |x: f32, y: f32, z: f32, u: f32, v: f32| GVertex {
+                pos: Vector3::new(-x, z, y),
+                uv: Vector3::new(u, v, texture.depth as f32),
+                light: billboard.light,
+            };
+
+            let (vertices, indices) = batches.entry(texture.id).or_default();
+            let startidx = vertices.len() as u32;
+            vertices.extend([
+                vertex(start.0, start.1, bottom, u_start, 0.0),
+                vertex(end.0, end.1, bottom, u_end, 0.0),
+                vertex(start.0, start.1, top, u_start, 1.0),
+                vertex(end.0, end.1, top, u_end, 1.0),
+            ]);
+            indices.extend([
+                startidx,
+                startidx + 1,
+                startidx + 2,
+                startidx + 2,
+                startidx + 1,
+                startidx + 3,
+            ]);
+        }
+
+        for (texture, (vertices, indices)) in batches {
+            let mesh = match self.meshes.get(&texture) {
+                Some(mesh) => {
+                    renderer.update_mesh(*mesh, &vertices, &indices);
+                    *mesh
+                }
+                None => {
+                    let mesh = renderer.upload_mesh(&vertices, &indices);
+                    self.meshes.insert(texture, mesh);
+                    mesh
+                }
+            };
+
+            renderer.draw_batch(&DrawBatch {
+                mesh,
+                texture,
+                cull_face: false,
+                depth_test: true,
+            });
+        }
+    }
+}
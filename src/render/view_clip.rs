@@ -0,0 +1,172 @@
+use std::f32::consts::{FRAC_PI_4, PI, TAU};
+
+/// Normalize an angle to [0, 2pi)
+pub fn norm_tau(angle: f32) -> f32 {
+    angle.rem_euclid(TAU)
+}
+
+/// Normalize an angle to [-pi, pi)
+pub fn norm_pi(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// Screen columns already covered by solid walls during a front to back BSP walk,
+/// the angle clipping part of vanilla R_AddLine
+pub struct ViewClip {
+    pub width: usize,
+    center_x: f32,
+    projection: f32,
+    view_x: f32,
+    view_y: f32,
+    view_angle: f32,
+    pub solid: Vec<bool>,
+    solid_count: usize,
+}
+
+impl ViewClip {
+    pub fn new(width: usize) -> Self {
+        let center_x = width as f32 / 2.0;
+        ViewClip {
+            width,
+            center_x,
+            // Vanilla uses a 90° horizontal field of view
+            projection: center_x,
+            view_x: 0.0,
+            view_y: 0.0,
+            view_angle: 0.0,
+            solid: vec![false; width],
+            solid_count: 0,
+        }
+    }
+
+    /// Start a new walk from a view point, angle in radians
+    pub fn reset(&mut self, view_x: f32, view_y: f32, view_angle: f32) {
+        self.view_x = view_x;
+        self.view_y = view_y;
+        self.view_angle = norm_tau(view_angle);
+        self.solid.fill(false);
+        self.solid_count = 0;
+    }
+
+    /// Every column is covered, nothing behind can be seen
+    pub fn is_full(&self) -> bool {
+        self.solid_count == self.width
+    }
+
+    /// Tell if any column of [x1, x2) is still open
+    pub fn is_visible(&self, x1: i32, x2: i32) -> bool {
+        (x1..x2).any(|x| !self.solid[x as usize])
+    }
+
+    /// Cover the columns [x1, x2)
+    pub fn mark_solid(&mut self, x1: i32, x2: i32) {
+        for x in x1..x2 {
+            if !self.solid[x as usize] {
+                self.solid[x as usize] = true;
+                self.solid_count += 1;
+            }
+        }
+    }
+
+    /// Convert a view relative angle (clipped to the field of view) to a screen column
+    pub fn angle_to_x(&self, angle: f32) -> i32 {
+        let x = self.center_x - norm_pi(angle).tan() * self.projection;
+        (x.round() as i32).clamp(0, self.width as i32)
+    }
+
+    /// Clip the angular span between two points to the field of view,
+    /// returns the covered columns [x1, x2) or None when nothing is visible
+    pub fn clip_span(&self, x1: f32, y1: f32, x2: f32, y2: f32) -> Option<(i32, i32, f32)> {
+        let angle1 = (y1 - self.view_y).atan2(x1 - self.view_x);
+        let angle2 = (y2 - self.view_y).atan2(x2 - self.view_x);
+
+        let span = norm_tau(angle1 - angle2);
+        if span >= PI {
+            // Back side, or the viewer is on the line
+            return None;
+        }
+
+        let clip_angle = FRAC_PI_4;
+        let mut relative1 = norm_tau(angle1 - self.view_angle);
+        let mut relative2 = norm_tau(angle2 - self.view_angle);
+
+        let tspan = norm_tau(relative1 + clip_angle);
+        if tspan > 2.0 * clip_angle {
+            if tspan - 2.0 * clip_angle >= span {
+                return None;
+            }
+            relative1 = clip_angle;
+        }
+        let tspan = norm_tau(clip_angle - relative2);
+        if tspan > 2.0 * clip_angle {
+            if tspan - 2.0 * clip_angle >= span {
+                return None;
+            }
+            relative2 = -clip_angle;
+        }
+
+        let sx1 = self.angle_to_x(relative1);
+        let sx2 = self.angle_to_x(relative2);
+        if sx1 >= sx2 {
+            return None;
+        }
+        Some((sx1, sx2, angle1))
+    }
+
+    /// Tell if any part of the bounding box may be visible
+    pub fn check_bbox(&self, bbox: &[i16; 4]) -> bool {
+        const TOP: usize = 0;
+        const BOTTOM: usize = 1;
+        const LEFT: usize = 2;
+        const RIGHT: usize = 3;
+        const CHECK_COORD: [[usize; 4]; 11] = [
+            [RIGHT, TOP, LEFT, BOTTOM],
+            [RIGHT, TOP, LEFT, TOP],
+            [RIGHT, BOTTOM, LEFT, TOP],
+            [0, 0, 0, 0],
+            [LEFT, TOP, LEFT, BOTTOM],
+            [0, 0, 0, 0],
+            [RIGHT, BOTTOM, RIGHT, TOP],
+            [0, 0, 0, 0],
+            [LEFT, TOP, RIGHT, BOTTOM],
+            [LEFT, BOTTOM, RIGHT, BOTTOM],
+            [LEFT, BOTTOM, RIGHT, TOP],
+        ];
+
+        let box_x = if self.view_x <= bbox[LEFT] as f32 {
+            0
+        } else if self.view_x < bbox[RIGHT] as f32 {
+            1
+        } else {
+            2
+        };
+        let box_y = if self.view_y >= bbox[TOP] as f32 {
+            0
+        } else if self.view_y > bbox[BOTTOM] as f32 {
+            1
+        } else {
+            2
+        };
+
+        let box_pos = box_y * 4 + box_x;
+        if box_pos == 5 {
+            return true;
+        }
+
+        let coords = CHECK_COORD[box_pos];
+        let (x1, y1) = (bbox[coords[0]] as f32, bbox[coords[1]] as f32);
+        let (x2, y2) = (bbox[coords[2]] as f32, bbox[coords[3]] as f32);
+
+        let angle1 = (y1 - self.view_y).atan2(x1 - self.view_x);
+        let angle2 = (y2 - self.view_y).atan2(x2 - self.view_x);
+        if norm_tau(angle1 - angle2) >= PI {
+            // Sitting on a line
+            return true;
+        }
+
+        match self.clip_span(x1, y1, x2, y2) {
+            Some((sx1, sx2, _)) => self.is_visible(sx1, sx2),
+            None => false,
+        }
+    }
+}
@@ -1,139 +1,115 @@
-use std::rc::Rc;
+use std::collections::HashSet;
 
-use cgmath::Matrix4;
-use kabal_render::doom_gl::{gl, DoomGl};
+use kabal_render::renderer::{DrawBatch, GVertex, MeshId, Renderer, TextureId};
 
-use crate::{
-    render::material::{MaterialValue, Stride},
-    sys::textures::Texture,
-};
-
-use super::material::{Material, MaterialParam};
+use crate::sys::textures::Texture;
 
+/// Every wall quad of the map sharing the same texture array
 pub struct WallModel {
-    ibuffer: Vec<u16>,
-    material: Material,
-    ib: u32,
-    view_att: Rc<MaterialParam>,
-    persp_att: Rc<MaterialParam>,
-    sky_att: Rc<MaterialParam>,
-    vao: u32,
-    img_att: Rc<MaterialParam>,
-    texture: u32,
+    vbuffer: Vec<GVertex>,
+    ibuffer: Vec<u32>,
+    /// Linedef of each quad, to build the quads of a line again
+    lines: Vec<usize>,
+    texture: TextureId,
+    mesh: Option<MeshId>,
+    /// The quads changed since they were last uploaded
+    dirty: bool,
 }
 
-const WALL_FRAG_STR: &str = include_str!("wall.frag");
-const WALL_VERT_STR: &str = include_str!("wall.vert");
-
 impl WallModel {
     pub fn new(texture: &Texture) -> Self {
-        unsafe { DoomGl::gl().Enable(gl::CULL_FACE) };
-        let mut material = Material::new(WALL_VERT_STR, WALL_FRAG_STR);
-
-        let view_att = MaterialParam::from_uniform("view\0", &mut material);
-        let persp_att = MaterialParam::from_uniform("proj\0", &mut material);
-        let img_att = MaterialParam::from_uniform("image\0", &mut material);
-        let sky_att = MaterialParam::from_uniform("sky\0", &mut material);
-
         WallModel {
+            vbuffer: Vec::new(),
             ibuffer: Vec::new(),
-            material,
-            ib: 0,
-            view_att,
-            persp_att,
-            sky_att,
-            vao: 0,
-            img_att,
+            lines: Vec::new(),
             texture: texture.id,
+            mesh: None,
+            dirty: true,
         }
     }
-    pub fn init(&mut self) {
-        let mut ib = unsafe { std::mem::zeroed() };
-        let mut vao = unsafe { std::mem::zeroed() };
 
-        unsafe {
-            let gl = DoomGl::gl();
-            // generate and bind the vao
-            gl.GenVertexArrays(1, &mut vao);
-            gl.BindVertexArray(vao);
-
-            gl.GenBuffers(1, &mut ib);
-            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ib);
-            gl.BufferData(
-                gl::ELEMENT_ARRAY_BUFFER,
-                (self.ibuffer.len() * std::mem::size_of::<u16>()) as gl::types::GLsizeiptr,
-                self.ibuffer.as_ptr() as *const _,
-                gl::DYNAMIC_DRAW,
-            );
-            self.vao = vao;
-            self.ib = ib;
-
-            assert!(gl.GetError() == 0);
+    /// Upload the quads when they changed, into the same mesh once it exists
+    pub fn refresh(&mut self, renderer: &mut dyn Renderer) {
+        if !self.dirty {
+            return;
         }
-
-        let pos_att = MaterialParam::from_attrib("position\0", &mut self.material);
-        let uv_att = MaterialParam::from_attrib("uv\0", &mut self.material);
-        let light_att = MaterialParam::from_attrib("light\0", &mut self.material);
-
-        // Always bind stride after the buffer is bound
-        pos_att.set_value(MaterialValue::FloatStride(Stride {
-            count: 3,
-            stride: 7 * std::mem::size_of::<f32>(),
-            offset: 0,
-        }));
-        uv_att.set_value(MaterialValue::FloatStride(Stride {
-            count: 3,
-            stride: 7 * std::mem::size_of::<f32>(),
-            offset: 3 * std::mem::size_of::<f32>(),
-        }));
-        light_att.set_value(MaterialValue::FloatStride(Stride {
-            count: 1,
-            stride: 7 * std::mem::size_of::<f32>(),
-            offset: 6 * std::mem::size_of::<f32>(),
-        }));
+        match self.mesh {
+            Some(mesh) => renderer.update_mesh(mesh, &self.vbuffer, &self.ibuffer),
+            None => self.mesh = Some(renderer.upload_mesh(&self.vbuffer, &self.ibuffer)),
+        }
+        self.dirty = false;
     }
 
-    pub fn append_indexes(&mut self, mut other: Vec<u16>) {
-        self.ibuffer.append(&mut other);
+    /// Free the mesh of the model
+    pub fn free(&mut self, renderer: &mut dyn Renderer) {
+        if let Some(mesh) = self.mesh.take() {
+            renderer.delete_mesh(mesh);
+        }
     }
 
-    pub fn render(&self, view: &Matrix4<f32>, persp: &Matrix4<f32>) {
-        self.view_att.set_value(MaterialValue::Matrix(*view));
-        self.persp_att.set_value(MaterialValue::Matrix(*persp));
-        self.img_att.set_value(MaterialValue::Int(0));
-        self.sky_att.set_value(MaterialValue::Int(0));
-
-        let gl = DoomGl::gl();
-        unsafe {
-            gl.Enable(gl::CULL_FACE);
+    pub fn texture(&self) -> TextureId {
+        self.texture
+    }
 
-            gl.BindVertexArray(self.vao);
-            gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.ib);
+    /// Show another layer of the texture array on the quad starting at a vertex
+    pub fn set_layer(&mut self, first_vertex: usize, depth: u32) {
+        for vertex in &mut self.vbuffer[first_vertex..first_vertex + 4] {
+            vertex.uv.z = depth as f32;
         }
+        self.dirty = true;
+    }
 
-        // Always bind material after buffer is bound
-        self.material.bind();
+    /// Add a quad of a line given as bottom start, bottom end, top start, top end, returns its
+    /// first vertex
+    pub fn append_quad(&mut self, line: usize, mut quad: Vec<GVertex>) -> usize {
+        let startidx = self.vbuffer.len() as u32;
+
+        self.vbuffer.append(&mut quad);
+        self.ibuffer.append(&mut vec![
+            startidx,
+            startidx + 1,
+            startidx + 2,
+            startidx + 2,
+            startidx + 1,
+            startidx + 3,
+        ]);
+        self.lines.push(line);
+        self.dirty = true;
+        startidx as usize
+    }
 
-        unsafe {
-            gl.ActiveTexture(gl::TEXTURE0);
-            gl.BindTexture(gl::TEXTURE_2D_ARRAY, self.texture);
-            gl.DrawElements(
-                gl::TRIANGLES,
-                self.ibuffer.len() as i32,
-                gl::UNSIGNED_SHORT,
-                std::ptr::null(),
-            );
-            gl.BindVertexArray(0);
-            assert!(gl.GetError() == 0);
+    /// Remove the quads of some lines, returns where the first vertex of every quad moved to
+    pub fn remove_lines(&mut self, lines: &HashSet<usize>) -> Vec<Option<usize>> {
+        let mut moved = Vec::with_capacity(self.lines.len());
+        let mut kept = 0;
+        for quad in 0..self.lines.len() {
+            if lines.contains(&self.lines[quad]) {
+                moved.push(None);
+                continue;
+            }
+            self.lines[kept] = self.lines[quad];
+            self.vbuffer.copy_within(quad * 4..quad * 4 + 4, kept * 4);
+            moved.push(Some(kept * 4));
+            kept += 1;
         }
+        if kept != self.lines.len() {
+            self.lines.truncate(kept);
+            self.vbuffer.truncate(kept * 4);
+            // Every quad uses the same 6 indices from its first vertex
+            self.ibuffer.truncate(kept * 6);
+            self.dirty = true;
+        }
+        moved
     }
-}
 
-impl Drop for WallModel {
-    fn drop(&mut self) {
-        unsafe {
-            DoomGl::gl().DeleteBuffers(1, &self.ib);
-            DoomGl::gl().DeleteVertexArrays(1, &self.vao);
+    pub fn render(&self, renderer: &mut dyn Renderer) {
+        if let Some(mesh) = self.mesh {
+            renderer.draw_batch(&DrawBatch {
+                mesh,
+                texture: self.texture,
+                cull_face: true,
+                depth_test: true,
+            });
         }
     }
 }
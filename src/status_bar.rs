@@ -0,0 +1,323 @@
+use kabal_app::tic_runner::TICRATE;
+use kabal_core::random::Random;
+use kabal_render::screen2d::{PatchImage, Screen2D, SCREEN_HEIGHT};
+
+use crate::{
+    game::player::{Key, PlayerStatus, Power, Weapon, NUM_KEYS},
+    sys::textures::Textures,
+};
+
+/// Top of the status bar in screen space
+const ST_Y: i32 = 168;
+
+const ST_AMMOX: i32 = 44;
+const ST_AMMOY: i32 = 171;
+const ST_HEALTHX: i32 = 90;
+const ST_HEALTHY: i32 = 171;
+const ST_ARMORX: i32 = 221;
+const ST_ARMORY: i32 = 171;
+const ST_ARMSBGX: i32 = 104;
+const ST_ARMSX: i32 = 111;
+const ST_ARMSY: i32 = 172;
+const ST_ARMSXSPACE: i32 = 12;
+const ST_ARMSYSPACE: i32 = 10;
+const ST_FACESX: i32 = 143;
+const ST_KEYSX: i32 = 239;
+const ST_KEYSY: [i32; 3] = [171, 181, 191];
+/// Current and max ammo columns, rows follow the bullets, shells, cells, rockets order
+const ST_AMMOCURX: i32 = 288;
+const ST_AMMOMAXX: i32 = 314;
+const ST_AMMOROWY: [i32; 4] = [173, 179, 191, 185];
+
+/// Fullscreen HUD layout
+const HUD_HEALTHX: i32 = 52;
+const HUD_ARMORX: i32 = 130;
+const HUD_AMMOX: i32 = 310;
+const HUD_Y: i32 = 171;
+
+const ST_NUMPAINFACES: usize = 5;
+const ST_NUMSTRAIGHTFACES: usize = 3;
+const ST_FACESTRIDE: usize = 8;
+const ST_TURNOFFSET: usize = ST_NUMSTRAIGHTFACES;
+const ST_OUCHOFFSET: usize = ST_TURNOFFSET + 2;
+const ST_EVILGRINOFFSET: usize = ST_OUCHOFFSET + 1;
+const ST_GODFACE: usize = ST_NUMPAINFACES * ST_FACESTRIDE;
+const ST_DEADFACE: usize = ST_GODFACE + 1;
+
+const ST_EVILGRINCOUNT: i32 = 2 * TICRATE as i32;
+const ST_STRAIGHTFACECOUNT: i32 = TICRATE as i32 / 2;
+const ST_TURNCOUNT: i32 = TICRATE as i32;
+/// Losing more health than this at once shows the ouch face
+const ST_MUCHPAIN: i32 = 20;
+
+/// How the player status is drawn over the 3D view
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HudMode {
+    StatusBar,
+    Fullscreen,
+}
+
+/// A row of digits drawn right aligned, like vanilla STlib_drawNum
+struct Digits {
+    numbers: [Option<PatchImage>; 10],
+    minus: Option<PatchImage>,
+}
+
+impl Digits {
+    fn new(graphics: &Textures, prefix: &str) -> Self {
+        Digits {
+            numbers: std::array::from_fn(|i| patch(graphics, &format!("{}{}", prefix, i))),
+            minus: patch(graphics, &format!("{}MINUS", prefix)),
+        }
+    }
+
+    /// Draw `value` with at most `width` digits, `x` being the right edge
+    fn draw(&self, screen: &mut Screen2D, x: i32, y: i32, value: i32, width: u32) {
+        let Some(zero) = &self.numbers[0] else {
+            return;
+        };
+        let digit_width = zero.width;
+        let max = 10i32.pow(width) - 1;
+        let negative = value < 0;
+        let mut value = value.abs().min(max);
+
+        let mut x = x;
+        loop {
+            x -= digit_width;
+            if let Some(digit) = &self.numbers[(value % 10) as usize] {
+                screen.draw_patch(x, y, digit);
+            }
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        if negative {
+            if let Some(minus) = &self.minus {
+                screen.draw_patch(x - 8, y, minus);
+            }
+        }
+    }
+}
+
+fn patch(graphics: &Textures, name: &str) -> Option<PatchImage> {
+    graphics.find_texture(name).map(|texture| texture.patch())
+}
+
+/// The classic status bar and its fullscreen variant
+pub struct StatusBar {
+    pub mode: HudMode,
+    background: Option<PatchImage>,
+    arms_background: Option<PatchImage>,
+    tall: Digits,
+    percent: Option<PatchImage>,
+    yellow: Digits,
+    gray: Digits,
+    keys: [Option<PatchImage>; NUM_KEYS],
+    faces: Vec<Option<PatchImage>>,
+
+    face_index: usize,
+    face_count: i32,
+    face_priority: i32,
+    old_health: i32,
+    old_weapons: Vec<bool>,
+    /// Cosmetic number drawn each tic, like vanilla st_randomnumber
+    random: i32,
+}
+
+impl StatusBar {
+    pub fn new(graphics: &Textures) -> Self {
+        let mut faces = Vec::new();
+        for pain in 0..ST_NUMPAINFACES {
+            for straight in 0..ST_NUMSTRAIGHTFACES {
+                faces.push(patch(graphics, &format!("STFST{}{}", pain, straight)));
+            }
+            faces.push(patch(graphics, &format!("STFTR{}0", pain)));
+            faces.push(patch(graphics, &format!("STFTL{}0", pain)));
+            faces.push(patch(graphics, &format!("STFOUCH{}", pain)));
+            faces.push(patch(graphics, &format!("STFEVL{}", pain)));
+            faces.push(patch(graphics, &format!("STFKILL{}", pain)));
+        }
+        faces.push(patch(graphics, "STFGOD0"));
+        faces.push(patch(graphics, "STFDEAD0"));
+
+        StatusBar {
+            mode: HudMode::StatusBar,
+            background: patch(graphics, "STBAR"),
+            arms_background: patch(graphics, "STARMS"),
+            tall: Digits::new(graphics, "STTNUM"),
+            percent: patch(graphics, "STTPRCNT"),
+            yellow: Digits::new(graphics, "STYSNUM"),
+            gray: Digits::new(graphics, "STGNUM"),
+            keys: std::array::from_fn(|i| patch(graphics, &format!("STKEYS{}", i))),
+            faces,
+            face_index: 0,
+            face_count: 0,
+            face_priority: 0,
+            old_health: -1,
+            old_weapons: Vec::new(),
+            random: 0,
+        }
+    }
+
+    /// Run one tic of the face animation, like vanilla ST_updateFaceWidget
+    ///
+    /// Attacker and rampage faces need the game simulation
+    pub fn tick(&mut self, status: &PlayerStatus, random: &mut Random) {
+        if self.old_health < 0 {
+            self.old_health = status.health;
+            self.old_weapons = status.weapons.to_vec();
+        }
+
+        self.random = random.cosmetic();
+
+        if self.face_priority < 10 && status.health <= 0 {
+            self.face_priority = 9;
+            self.face_index = ST_DEADFACE;
+            self.face_count = 1;
+        }
+
+        if self.face_priority < 9
+            && status
+                .weapons
+                .iter()
+                .zip(&self.old_weapons)
+                .any(|(new, old)| *new && !old)
+        {
+            self.face_priority = 8;
+            self.face_count = ST_EVILGRINCOUNT;
+            self.face_index = pain_offset(status.health) + ST_EVILGRINOFFSET;
+        }
+        self.old_weapons = status.weapons.to_vec();
+
+        if self.face_priority < 8 && status.health < self.old_health {
+            self.face_priority = 7;
+            self.face_count = ST_TURNCOUNT;
+            self.face_index = if self.old_health - status.health > ST_MUCHPAIN {
+                pain_offset(status.health) + ST_OUCHOFFSET
+            } else {
+                pain_offset(status.health)
+            };
+        }
+        self.old_health = status.health;
+
+        if self.face_priority < 5 && (status.god_mode || status.has_power(Power::Invulnerability)) {
+            self.face_priority = 4;
+            self.face_index = ST_GODFACE;
+            self.face_count = 1;
+        }
+
+        if self.face_count == 0 {
+            self.face_index = pain_offset(status.health) + self.random as usize % 3;
+            self.face_count = ST_STRAIGHTFACECOUNT;
+            self.face_priority = 0;
+        }
+        self.face_count -= 1;
+    }
+
+    /// How much higher the middle of the view is, the status bar leaving a smaller view
+    pub fn view_lift(&self) -> f32 {
+        match self.mode {
+            HudMode::StatusBar => (SCREEN_HEIGHT as i32 - ST_Y) as f32 / 2.0,
+            HudMode::Fullscreen => 0.0,
+        }
+    }
+
+    pub fn draw(&self, status: &PlayerStatus, screen: &mut Screen2D) {
+        match self.mode {
+            HudMode::StatusBar => self.draw_status_bar(status, screen),
+            HudMode::Fullscreen => self.draw_fullscreen(status, screen),
+        }
+    }
+
+    fn draw_status_bar(&self, status: &PlayerStatus, screen: &mut Screen2D) {
+        if let Some(background) = &self.background {
+            screen.draw_patch(0, ST_Y, background);
+        }
+        if let Some(arms_background) = &self.arms_background {
+            screen.draw_patch(ST_ARMSBGX, ST_Y, arms_background);
+        }
+
+        if let Some(ammo) = status.ready_ammo() {
+            self.tall.draw(screen, ST_AMMOX, ST_AMMOY, ammo, 3);
+        }
+        self.draw_percent(screen, ST_HEALTHX, ST_HEALTHY, status.health);
+        self.draw_percent(screen, ST_ARMORX, ST_ARMORY, status.armor);
+
+        // Slots 2 to 7, the super shotgun shares the shotgun slot
+        for slot in 0..6 {
+            let owned = match slot {
+                0 => status.has_weapon(Weapon::Pistol),
+                1 => status.has_weapon(Weapon::Shotgun) || status.has_weapon(Weapon::SuperShotgun),
+                2 => status.has_weapon(Weapon::Chaingun),
+                3 => status.has_weapon(Weapon::Missile),
+                4 => status.has_weapon(Weapon::Plasma),
+                _ => status.has_weapon(Weapon::Bfg),
+            };
+            let digits = if owned { &self.yellow } else { &self.gray };
+            if let Some(digit) = &digits.numbers[slot as usize + 2] {
+                let x = ST_ARMSX + (slot % 3) * ST_ARMSXSPACE;
+                let y = ST_ARMSY + (slot / 3) * ST_ARMSYSPACE;
+                screen.draw_patch(x, y, digit);
+            }
+        }
+
+        if let Some(face) = &self.faces[self.face_index] {
+            screen.draw_patch(ST_FACESX, ST_Y, face);
+        }
+
+        self.draw_keys(status, screen);
+
+        for (ammo, y) in ST_AMMOROWY.iter().enumerate() {
+            self.yellow
+                .draw(screen, ST_AMMOCURX, *y, status.ammo[ammo], 3);
+            self.yellow
+                .draw(screen, ST_AMMOMAXX, *y, status.max_ammo[ammo], 3);
+        }
+    }
+
+    fn draw_fullscreen(&self, status: &PlayerStatus, screen: &mut Screen2D) {
+        self.draw_percent(screen, HUD_HEALTHX, HUD_Y, status.health);
+        if status.armor > 0 {
+            self.draw_percent(screen, HUD_ARMORX, HUD_Y, status.armor);
+        }
+        if let Some(ammo) = status.ready_ammo() {
+            self.tall.draw(screen, HUD_AMMOX, HUD_Y, ammo, 3);
+        }
+        self.draw_keys(status, screen);
+    }
+
+    fn draw_percent(&self, screen: &mut Screen2D, x: i32, y: i32, value: i32) {
+        self.tall.draw(screen, x, y, value, 3);
+        if let Some(percent) = &self.percent {
+            screen.draw_patch(x, y, percent);
+        }
+    }
+
+    /// A skull replaces the card of the same colour
+    fn draw_keys(&self, status: &PlayerStatus, screen: &mut Screen2D) {
+        let colours = [
+            (Key::BlueCard, Key::BlueSkull),
+            (Key::YellowCard, Key::YellowSkull),
+            (Key::RedCard, Key::RedSkull),
+        ];
+        for ((card, skull), y) in colours.iter().zip(ST_KEYSY) {
+            let shown = if status.has_key(*skull) {
+                Some(*skull)
+            } else if status.has_key(*card) {
+                Some(*card)
+            } else {
+                None
+            };
+            if let Some(image) = shown.and_then(|card| self.keys[card as usize].as_ref()) {
+                screen.draw_patch(ST_KEYSX, y, image);
+            }
+        }
+    }
+}
+
+/// Face row matching the health, like vanilla ST_calcPainOffset
+fn pain_offset(health: i32) -> usize {
+    let health = health.clamp(0, 100) as usize;
+    ST_FACESTRIDE * (((100 - health) * ST_NUMPAINFACES) / 101)
+}
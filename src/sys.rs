@@ -1,2 +1,4 @@
+pub mod config;
 pub mod content;
+pub mod cvars;
 pub mod textures;
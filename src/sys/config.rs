@@ -0,0 +1,94 @@
+use std::path::Path;
+
+/// Settings file made of `name value` lines, like vanilla default.cfg
+pub const CONFIG_FILE: &str = "kabal.cfg";
+
+/// Every `name value` line of a config file, nothing when it does not exist yet
+pub fn read_config(path: &Path) -> Vec<(String, String)> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                return None;
+            }
+            // An empty value is kept, like an action bound to nothing
+            let (name, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            Some((name.to_string(), value.trim().trim_matches('"').to_string()))
+        })
+        .collect()
+}
+
+pub fn write_config(path: &Path, entries: &[(String, String)]) -> Result<(), String> {
+    let content = entries
+        .iter()
+        .map(|(name, value)| format!("{:<24}{}\n", name, value))
+        .collect::<String>();
+    std::fs::write(path, content).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::{MouseButton, VirtualKeyCode};
+
+    use super::*;
+    use crate::input::bindings::{Action, Bindings, Button};
+    use crate::sys::cvars::Cvars;
+
+    fn temp_config(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("doom-rs-test-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn missing_file_has_no_entries() {
+        assert!(read_config(&temp_config("missing.cfg")).is_empty());
+    }
+
+    #[test]
+    fn comments_and_quotes_are_left_out() {
+        let path = temp_config("quotes.cfg");
+        std::fs::write(&path, "// comment\n\nfov   \"100\"\nkey_jump\n").unwrap();
+        let entries = read_config(&path);
+        std::fs::remove_file(&path).unwrap();
+        let expected = [("fov", "100"), ("key_jump", "")].map(|(n, v)| (n.into(), v.into()));
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn bindings_and_variables_come_back_from_the_file() {
+        let mut cvars = Cvars::new();
+        cvars.set("fov", "100").unwrap();
+        let mut bindings = Bindings::new();
+        bindings.bind(
+            Action::MoveForward,
+            vec![
+                Button::Key(VirtualKeyCode::Up),
+                Button::Mouse(MouseButton::Other(4)),
+            ],
+        );
+        bindings.bind(Action::Jump, Vec::new());
+        bindings.bind(Action::Use, vec![Button::WheelDown]);
+
+        let mut entries = cvars.entries();
+        entries.extend(bindings.entries());
+        let path = temp_config("round_trip.cfg");
+        write_config(&path, &entries).unwrap();
+        let read = read_config(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, entries);
+
+        let mut loaded_cvars = Cvars::new();
+        loaded_cvars.load(&read);
+        assert_eq!(loaded_cvars.float("fov"), 100.0);
+        let mut loaded = Bindings::new();
+        loaded.load(&read);
+        assert_eq!(loaded.entries(), bindings.entries());
+        assert!(loaded.buttons(Action::Jump).is_empty());
+        assert_eq!(loaded.buttons(Action::Use), [Button::WheelDown]);
+        assert!(!loaded.dirty);
+    }
+}
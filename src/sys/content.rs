@@ -1,29 +1,70 @@
+use kabal_render::renderer::Renderer;
+
 use super::textures::Textures;
 use crate::wad::doom_textures::DoomTextures;
 use crate::wad::file::WadFile;
+use crate::wad::graphics::Graphics;
 use crate::wad::map::WadMap;
+use crate::wad::sprites::Sprites;
+use crate::wad::switches::Switches;
+
+/// Name prefixes of the screen graphics lumps
+const GRAPHICS_PREFIXES: &[&str] = &["ST", "M_", "HELP", "TITLEPIC"];
 
 pub struct Content {
     //pub textures: RefCell<Textures>,
     pub maps: Vec<WadMap>,
     pub file: WadFile,
     textures: Textures,
+    sprites: Sprites,
+    sprite_textures: Textures,
+    graphics: Textures,
+    switches: Switches,
 }
 
 impl Content {
-    pub fn new(file: WadFile) -> Self {
+    pub fn new(file: WadFile, renderer: &mut dyn Renderer) -> Self {
         let doom_textures = DoomTextures::new(&file);
+        let sprites = Sprites::new(&file);
+        let graphics = Graphics::new(&file, GRAPHICS_PREFIXES);
 
-        let mut content = Content {
+        Content {
             maps: Vec::new(),
+            textures: Textures::new(&doom_textures, renderer),
+            sprite_textures: Textures::new(&sprites.images, renderer),
+            graphics: Textures::new(&graphics.images, renderer),
+            switches: Switches::new(&file),
+            sprites,
             file,
-            textures: Textures::new(doom_textures),
-        };
-        content.maps.push(WadMap::new("E1M1", &content).unwrap());
-        content
+        }
+    }
+
+    /// Load a map by its lump name (E1M1, MAP01...) and add it to the map list
+    pub fn load_map(&mut self, name: &str, renderer: &mut dyn Renderer) -> Result<&WadMap, String> {
+        let map = WadMap::new(name, self, renderer)?;
+        self.maps.push(map);
+        Ok(self.maps.last().unwrap())
     }
 
     pub fn get_textures(&self) -> &Textures {
         &self.textures
     }
+
+    pub fn get_sprites(&self) -> &Sprites {
+        &self.sprites
+    }
+
+    /// Sprite images by lump name, kept apart as their names can clash with wall textures
+    pub fn get_sprite_textures(&self) -> &Textures {
+        &self.sprite_textures
+    }
+
+    /// Status bar, menu and font patches by lump name
+    pub fn get_graphics(&self) -> &Textures {
+        &self.graphics
+    }
+
+    pub fn get_switches(&self) -> &Switches {
+        &self.switches
+    }
 }
@@ -0,0 +1,337 @@
+/// Typed value of a console variable
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CvarValue {
+    Bool(bool),
+    Int(i32),
+    Float(f32),
+}
+
+impl CvarValue {
+    fn parse(&self, text: &str) -> Result<CvarValue, String> {
+        match self {
+            CvarValue::Bool(_) => match text {
+                "1" | "true" | "on" => Ok(CvarValue::Bool(true)),
+                "0" | "false" | "off" => Ok(CvarValue::Bool(false)),
+                _ => Err(format!("'{}' is not a boolean", text)),
+            },
+            CvarValue::Int(_) => text
+                .parse()
+                .map(CvarValue::Int)
+                .map_err(|_| format!("'{}' is not an integer", text)),
+            // Infinities and NaN would get through the clamp
+            CvarValue::Float(_) => text
+                .parse::<f32>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(CvarValue::Float)
+                .ok_or_else(|| format!("'{}' is not a number", text)),
+        }
+    }
+}
+
+impl std::fmt::Display for CvarValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CvarValue::Bool(value) => write!(f, "{}", *value as i32),
+            CvarValue::Int(value) => write!(f, "{}", value),
+            CvarValue::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+pub struct Cvar {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub value: CvarValue,
+    default: CvarValue,
+    /// Numbers are clamped to this range
+    range: (f32, f32),
+    /// Saved to the config file
+    archive: bool,
+}
+
+const fn cvar(
+    name: &'static str,
+    default: CvarValue,
+    range: (f32, f32),
+    archive: bool,
+    description: &'static str,
+) -> Cvar {
+    Cvar {
+        name,
+        description,
+        value: default,
+        default,
+        range,
+        archive,
+    }
+}
+
+const BOOL: (f32, f32) = (0.0, 1.0);
+
+/// Every variable known by the game, with its default value
+const CVARS: [Cvar; 13] = [
+    cvar(
+        "fov",
+        CvarValue::Float(90.0),
+        (30.0, 160.0),
+        true,
+        "Horizontal field of view in degrees on a 4:3 screen",
+    ),
+    cvar(
+        "cl_movespeed",
+        CvarValue::Float(512.0),
+        (0.0, 10000.0),
+        true,
+        "Fly speed in map units per second",
+    ),
+    cvar(
+        "m_sensitivity_x",
+        CvarValue::Float(0.5),
+        (0.0, 100.0),
+        true,
+        "Degrees turned per mouse count",
+    ),
+    cvar(
+        "m_sensitivity_y",
+        CvarValue::Float(0.35),
+        (0.0, 100.0),
+        true,
+        "Degrees looked up or down per mouse count",
+    ),
+    cvar(
+        "m_smooth",
+        CvarValue::Bool(true),
+        BOOL,
+        true,
+        "Average the last two mouse moves",
+    ),
+    cvar(
+        "r_wireframe",
+        CvarValue::Bool(false),
+        BOOL,
+        false,
+        "Draw the edges of the level geometry",
+    ),
+    cvar(
+        "r_showfps",
+        CvarValue::Bool(true),
+        BOOL,
+        true,
+        "Show the frame rate",
+    ),
+    cvar(
+        "screenblocks",
+        CvarValue::Int(10),
+        (3.0, 11.0),
+        true,
+        "Screen size, 11 hides the status bar",
+    ),
+    cvar(
+        "mouse_sensitivity",
+        CvarValue::Int(5),
+        (0.0, 9.0),
+        true,
+        "Menu mouse sensitivity",
+    ),
+    cvar(
+        "show_messages",
+        CvarValue::Bool(true),
+        BOOL,
+        true,
+        "Show the HUD messages",
+    ),
+    cvar(
+        "detaillevel",
+        CvarValue::Int(0),
+        (0.0, 1.0),
+        true,
+        "0 for high detail, 1 for low",
+    ),
+    cvar(
+        "sfx_volume",
+        CvarValue::Int(8),
+        (0.0, 15.0),
+        true,
+        "Sound effects volume",
+    ),
+    cvar(
+        "music_volume",
+        CvarValue::Int(8),
+        (0.0, 15.0),
+        true,
+        "Music volume",
+    ),
+];
+
+/// Console variables, set from the console, the menus or the config file
+pub struct Cvars {
+    list: Vec<Cvar>,
+    /// Something changed since the config file was written
+    pub dirty: bool,
+}
+
+impl Cvars {
+    pub fn new() -> Self {
+        Cvars {
+            list: CVARS.into(),
+            dirty: false,
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Cvar> {
+        self.list.iter().find(|cvar| cvar.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Cvar> {
+        self.list.iter()
+    }
+
+    fn set_value(&mut self, name: &str, value: CvarValue) -> Result<(), String> {
+        let cvar = self
+            .list
+            .iter_mut()
+            .find(|cvar| cvar.name == name)
+            .ok_or_else(|| format!("Unknown variable {}", name))?;
+        let (min, max) = cvar.range;
+        let value = match value {
+            CvarValue::Int(value) => CvarValue::Int(value.clamp(min as i32, max as i32)),
+            CvarValue::Float(value) => CvarValue::Float(value.clamp(min, max)),
+            value => value,
+        };
+        if cvar.value != value {
+            cvar.value = value;
+            self.dirty |= cvar.archive;
+        }
+        Ok(())
+    }
+
+    /// Parse and set a value, like the console does
+    pub fn set(&mut self, name: &str, text: &str) -> Result<(), String> {
+        let value = self
+            .find(name)
+            .ok_or_else(|| format!("Unknown variable {}", name))?
+            .default
+            .parse(text)?;
+        self.set_value(name, value)
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) {
+        self.set_value(name, CvarValue::Bool(value)).unwrap();
+    }
+
+    pub fn set_int(&mut self, name: &str, value: i32) {
+        self.set_value(name, CvarValue::Int(value)).unwrap();
+    }
+
+    pub fn bool(&self, name: &str) -> bool {
+        match self.find(name).map(|cvar| cvar.value) {
+            Some(CvarValue::Bool(value)) => value,
+            _ => panic!("{} is not a boolean variable", name),
+        }
+    }
+
+    pub fn int(&self, name: &str) -> i32 {
+        match self.find(name).map(|cvar| cvar.value) {
+            Some(CvarValue::Int(value)) => value,
+            _ => panic!("{} is not an integer variable", name),
+        }
+    }
+
+    pub fn float(&self, name: &str) -> f32 {
+        match self.find(name).map(|cvar| cvar.value) {
+            Some(CvarValue::Float(value)) => value,
+            _ => panic!("{} is not a float variable", name),
+        }
+    }
+
+    /// Apply the config file entries, unknown names are left to the caller
+    pub fn load(&mut self, entries: &[(String, String)]) {
+        for (name, value) in entries {
+            if self.find(name).is_some() {
+                if let Err(e) = self.set(name, value) {
+                    eprintln!("Config: {}", e);
+                }
+            }
+        }
+        self.dirty = false;
+    }
+
+    /// Entries to write in the config file
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.list
+            .iter()
+            .filter(|cvar| cvar.archive)
+            .map(|cvar| (cvar.name.to_string(), cvar.value.to_string()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn values_are_parsed_by_type() {
+        let boolean = CvarValue::Bool(false);
+        assert_eq!(boolean.parse("on"), Ok(CvarValue::Bool(true)));
+        assert_eq!(boolean.parse("0"), Ok(CvarValue::Bool(false)));
+        assert!(boolean.parse("2").is_err());
+        assert_eq!(CvarValue::Int(0).parse("-3"), Ok(CvarValue::Int(-3)));
+        assert!(CvarValue::Int(0).parse("1.5").is_err());
+
+        let float = CvarValue::Float(0.0);
+        assert_eq!(float.parse("1.5"), Ok(CvarValue::Float(1.5)));
+        for text in ["nan", "NaN", "inf", "-infinity", "1e39", ""] {
+            assert!(float.parse(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn numbers_are_clamped_to_their_range() {
+        let mut cvars = Cvars::new();
+        cvars.set("fov", "1000").unwrap();
+        assert_eq!(cvars.float("fov"), 160.0);
+        cvars.set("fov", "-5").unwrap();
+        assert_eq!(cvars.float("fov"), 30.0);
+        // A rejected value changes nothing
+        assert!(cvars.set("fov", "nan").is_err());
+        assert_eq!(cvars.float("fov"), 30.0);
+        assert!(cvars.set("no_such_variable", "1").is_err());
+    }
+
+    #[test]
+    fn load_skips_unknown_names_and_bad_values() {
+        let mut cvars = Cvars::new();
+        cvars.load(&[
+            entry("fov", "100"),
+            entry("m_sensitivity_x", "nan"),
+            entry("key_use", "E"),
+        ]);
+        assert_eq!(cvars.float("fov"), 100.0);
+        assert_eq!(cvars.float("m_sensitivity_x"), 0.5);
+        // Nothing to write back after loading
+        assert!(!cvars.dirty);
+    }
+
+    #[test]
+    fn entries_load_back_the_same() {
+        let mut cvars = Cvars::new();
+        cvars.set("fov", "100").unwrap();
+        cvars.set("m_sensitivity_y", "1.25").unwrap();
+        assert!(cvars.dirty);
+        let entries = cvars.entries();
+        assert!(entries.contains(&entry("fov", "100")));
+        assert!(entries
+            .iter()
+            .all(|(name, _)| cvars.find(name).unwrap().archive));
+
+        let mut loaded = Cvars::new();
+        loaded.load(&entries);
+        assert_eq!(loaded.entries(), entries);
+    }
+}
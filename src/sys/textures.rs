@@ -1,15 +1,35 @@
 use std::collections::HashMap;
 
-use kabal_render::doom_gl::DoomGl;
+use kabal_render::renderer::{Renderer, TextureId};
+use kabal_render::screen2d::PatchImage;
 
 use crate::wad::doom_textures::DoomTextures;
 
+#[derive(Clone)]
 pub struct Texture {
+    #[allow(dead_code)]
     pub name: String,
     pub width: i32,
     pub height: i32,
+    /// Patch origin offsets, only sprites have them
+    pub left_offset: i32,
+    pub top_offset: i32,
     pub depth: u32,
-    pub id: u32,
+    pub id: TextureId,
+}
+
+impl Texture {
+    /// Description used to draw the texture in screen space
+    pub fn patch(&self) -> PatchImage {
+        PatchImage {
+            texture: self.id,
+            layer: self.depth,
+            width: self.width,
+            height: self.height,
+            left_offset: self.left_offset,
+            top_offset: self.top_offset,
+        }
+    }
 }
 
 pub struct Textures {
@@ -17,55 +37,35 @@ pub struct Textures {
 }
 
 impl Textures {
-    pub fn new(doom_textures: DoomTextures) -> Self {
-        let gl = DoomGl::get();
-
-        // Convert doom textures to multiple textures of 1024x1024
-        let mut textures = Vec::new();
-
+    pub fn new(doom_textures: &DoomTextures, renderer: &mut dyn Renderer) -> Self {
         let mut texture_table = HashMap::new();
 
-        let mut current_width = 0;
-        let mut current_height = 0;
-        let mut current_texture_id = 0;
-        let mut current_depth = 0;
-        for texture in doom_textures.list.iter() {
-            // Check if we need a new texture
-            if texture.width != current_width || texture.height != current_height {
-                if current_height != 0 || current_width != 0 {
-                    // Generate texture
-                    gl.fill_texture_2d_array(
-                        current_texture_id,
-                        current_width,
-                        current_height,
-                        &textures,
-                    );
-                }
-                current_depth = 0;
-                current_height = texture.height;
-                current_width = texture.width;
-                textures.clear();
-                current_texture_id = gl.gen_texture_id();
-            }
-
-            textures.push(texture.buffer.clone());
-
-            let current_tile = Texture {
-                name: texture.name.clone(),
-                width: texture.width,
-                height: texture.height,
-                depth: current_depth,
-                id: current_texture_id,
-            };
+        // Textures are sorted by size, every run of the same size goes in one texture array
+        for group in doom_textures
+            .list
+            .chunk_by(|a, b| a.width == b.width && a.height == b.height)
+        {
+            let layers = group
+                .iter()
+                .map(|texture| texture.buffer.clone())
+                .collect::<Vec<Vec<u8>>>();
+            let id = renderer.upload_texture_array(group[0].width, group[0].height, &layers);
 
-            texture_table.insert(texture.name.clone(), current_tile);
+            for (depth, texture) in group.iter().enumerate() {
+                let tile = Texture {
+                    name: texture.name.clone(),
+                    width: texture.width,
+                    height: texture.height,
+                    left_offset: texture.left_offset,
+                    top_offset: texture.top_offset,
+                    depth: depth as u32,
+                    id,
+                };
 
-            current_depth += 1;
+                texture_table.insert(texture.name.clone(), tile);
+            }
         }
 
-        // for the last ones
-        gl.fill_texture_2d_array(current_texture_id, current_width, current_height, &textures);
-
         Textures {
             list: texture_table,
         }
@@ -1,7 +1,14 @@
+pub mod colormap;
 mod directory;
 pub mod doom_textures;
 pub mod file;
+pub mod graphics;
 mod info;
 pub mod map;
-mod patches;
-mod playpal;
+pub mod patches;
+pub mod playpal;
+pub mod sprites;
+pub mod switches;
+#[cfg(test)]
+pub mod test_wad;
+pub mod things;
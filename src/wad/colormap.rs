@@ -0,0 +1,21 @@
+use super::file::WadFile;
+
+/// Number of light levels in the COLORMAP lump, the following ones are the invulnerability and black maps
+pub const NUM_COLORMAPS: usize = 32;
+
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+pub struct ColorMap {
+    pub maps: [[u8; 256]; 34],
+}
+
+impl ColorMap {
+    pub fn new(file: &WadFile) -> Self {
+        if let Some(content) = file.get_section("COLORMAP") {
+            let (_, body, _) = unsafe { content[..].align_to::<ColorMap>() };
+            body[0]
+        } else {
+            panic!("No COLORMAP section found");
+        }
+    }
+}
@@ -1,12 +1,56 @@
 use std::str::FromStr;
 
-use super::{file::WadFile, patches::Patches, playpal::PlayPal};
+use super::{
+    file::WadFile,
+    patches::{Patch, Patches},
+    playpal::PlayPal,
+};
 
 pub struct DoomTexture {
     pub name: String,
     pub width: i32,
     pub height: i32,
     pub buffer: Vec<u8>,
+    /// Palette indices, column major and top-down like vanilla, None for holes
+    pub columns: Vec<Option<u8>>,
+    /// Patch origin offsets, only sprites have them
+    pub left_offset: i32,
+    pub top_offset: i32,
+}
+
+impl DoomTexture {
+    /// Palette index at a texel, coordinates wrap around the texture
+    pub fn texel(&self, x: i32, y: i32) -> Option<u8> {
+        let x = x.rem_euclid(self.width) as usize;
+        let y = y.rem_euclid(self.height) as usize;
+        self.columns[x * self.height as usize + y]
+    }
+
+    /// Convert a standalone patch, rows are flipped bottom-up like composite textures
+    pub fn from_patch(name: &str, patch: &Patch) -> Self {
+        let row_size = patch.width * 4;
+        let mut buffer = Vec::with_capacity(patch.image.len());
+        for row in patch.image.chunks_exact(row_size).rev() {
+            buffer.extend_from_slice(row);
+        }
+
+        let mut columns = vec![None; patch.width * patch.height];
+        for x in 0..patch.width {
+            for y in 0..patch.height {
+                columns[x * patch.height + y] = patch.pixels[y * patch.width + x];
+            }
+        }
+
+        DoomTexture {
+            name: name.to_string(),
+            width: patch.width as i32,
+            height: patch.height as i32,
+            buffer,
+            columns,
+            left_offset: patch.left,
+            top_offset: patch.top,
+        }
+    }
 }
 
 pub struct DoomTextures {
@@ -59,6 +103,7 @@ fn read_texture_section(file: &WadFile, section: &str, patches: &Patches) -> Vec
             // Compose texture
             let mut buffer = Vec::new();
             buffer.resize(4 * width * height, 1u8);
+            let mut columns = vec![None; width * height];
             for pinfo in patch_info.iter().take(texture_info[0].patch_count as usize) {
                 let patch = patches.get_patch(pinfo.patch as usize);
 
@@ -87,6 +132,15 @@ fn read_texture_section(file: &WadFile, section: &str, patches: &Patches) -> Vec
                                 buffer[index as usize + 3] = 255u8;
                             }
                         }
+
+                        let top_y = pinfo.origin_y as i32 + y as i32;
+                        if (0..width as i32).contains(&real_x)
+                            && (0..height as i32).contains(&top_y)
+                        {
+                            if let Some(pixel) = patch.pixels[y * patch.width + x] {
+                                columns[real_x as usize * height + top_y as usize] = Some(pixel);
+                            }
+                        }
                     }
                 }
             }
@@ -99,6 +153,9 @@ fn read_texture_section(file: &WadFile, section: &str, patches: &Patches) -> Vec
                 width: width as i32,
                 height: height as i32,
                 buffer,
+                columns,
+                left_offset: 0,
+                top_offset: 0,
             });
         }
     }
@@ -115,6 +172,7 @@ fn read_sky(patches: &Patches) -> Vec<DoomTexture> {
             // Compose texture
             let mut buffer = Vec::new();
             buffer.resize(4 * p.width * p.height, 1u8);
+            let mut columns = vec![None; p.width * p.height];
 
             for x in 0..p.width {
                 for y in 0..p.height {
@@ -124,6 +182,7 @@ fn read_sky(patches: &Patches) -> Vec<DoomTexture> {
                     buffer[index + 1] = p.image[index + 1];
                     buffer[index + 2] = p.image[index + 2];
                     buffer[index + 3] = 255u8;
+                    columns[x * p.height + y] = Some(p.pixels[y * p.width + x].unwrap_or(0));
                 }
             }
             result.push(DoomTexture {
@@ -131,6 +190,9 @@ fn read_sky(patches: &Patches) -> Vec<DoomTexture> {
                 width: p.width as i32,
                 height: p.height as i32,
                 buffer,
+                columns,
+                left_offset: 0,
+                top_offset: 0,
             });
         } else {
             break;
@@ -146,16 +208,17 @@ pub fn load_flat(file: &WadFile, name: &str) -> DoomTexture {
     // Read palettes
     let playpal = PlayPal::new(file);
 
-    let mut buffer = Vec::new();
-    buffer.resize(4 * 64 * 64_usize, 0u8);
+    let mut buffer = vec![0; 4 * 64 * 64_usize];
+    let mut columns = vec![None; 64 * 64];
 
-    for (i, pixel) in section.iter().enumerate() {
+    for (i, pixel) in section.iter().enumerate().take(64 * 64) {
         let color = &playpal.palettes[0].colors[*pixel as usize];
 
         buffer[i * 4] = color.r;
         buffer[i * 4 + 1] = color.g;
         buffer[i * 4 + 2] = color.b;
         buffer[i * 4 + 3] = 255;
+        columns[(i % 64) * 64 + i / 64] = Some(*pixel);
     }
 
     DoomTexture {
@@ -163,6 +226,9 @@ pub fn load_flat(file: &WadFile, name: &str) -> DoomTexture {
         width: 64,
         height: 64,
         buffer,
+        columns,
+        left_offset: 0,
+        top_offset: 0,
     }
 }
 
@@ -194,6 +260,11 @@ impl DoomTextures {
         list.extend(read_sky(&patches));
         list.extend(read_flats(file));
 
+        DoomTextures::from_list(list)
+    }
+
+    /// Sort by size so textures of the same size end up next to each other
+    pub fn from_list(mut list: Vec<DoomTexture>) -> Self {
         list.sort_by(|a, b| a.height.cmp(&b.height).then(a.width.cmp(&b.width)));
 
         DoomTextures { list }
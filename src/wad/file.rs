@@ -31,12 +31,16 @@ impl WadFile {
 
     pub fn get_section(&self, name: &str) -> Option<&[u8]> {
         if let Some(index) = self.directory.find_section(name, 0) {
-            let lump = self.directory.get_lump(index);
-            return Some(&self.content[lump.range()]);
+            return Some(self.get_lump_content(index));
         }
         None
     }
 
+    pub fn get_lump_content(&self, index: usize) -> &[u8] {
+        let lump = self.directory.get_lump(index);
+        &self.content[lump.range()]
+    }
+
     pub fn read_section<T: Copy>(&self, mapidx: usize, name: &str) -> Vec<T> {
         let index = self.directory.find_section(name, mapidx).unwrap();
         let lump = &self.directory.files[index];
@@ -48,8 +52,7 @@ impl WadFile {
         let count = lump.size as usize / mem::size_of::<T>();
 
         let mut result = Vec::new();
-        let mut buffer = Vec::new();
-        buffer.resize(mem::size_of::<T>(), 0u8);
+        let mut buffer = vec![0; mem::size_of::<T>()];
         for _ in 0..count {
             if self.reader.borrow_mut().read_exact(&mut buffer).is_ok() {
                 let (_, body, _) = unsafe { buffer.align_to::<T>() };
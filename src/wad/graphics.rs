@@ -0,0 +1,83 @@
+use super::{
+    doom_textures::{DoomTexture, DoomTextures},
+    file::WadFile,
+    patches::load_image,
+    playpal::PlayPal,
+};
+
+/// Namespaces holding sprites, flats and wall patches, never screen graphics
+const NAMESPACES: &[(&str, &str)] = &[
+    ("S_START", "S_END"),
+    ("SS_START", "SS_END"),
+    ("F_START", "F_END"),
+    ("FF_START", "FF_END"),
+    ("P_START", "P_END"),
+    ("PP_START", "PP_END"),
+];
+
+/// Screen graphics (status bar, menus, fonts), loose patch lumps found by name prefix
+pub struct Graphics {
+    /// One image per lump
+    pub images: DoomTextures,
+}
+
+impl Graphics {
+    pub fn new(file: &WadFile, prefixes: &[&str]) -> Self {
+        let playpal = PlayPal::new(file);
+
+        let mut namespace_end: Option<&str> = None;
+        let mut images = Vec::new();
+        for i in 0..file.directory.files.len() {
+            let lump = file.directory.get_lump(i);
+            let name = String::from_utf8_lossy(&lump.name).to_ascii_uppercase();
+            let name = name.trim_end_matches('\0');
+
+            // Skip everything inside a marker range
+            if let Some(end) = namespace_end {
+                if name == end {
+                    namespace_end = None;
+                }
+                continue;
+            }
+            if let Some((_, end)) = NAMESPACES.iter().find(|(start, _)| *start == name) {
+                namespace_end = Some(end);
+                continue;
+            }
+
+            if !prefixes.iter().any(|prefix| name.starts_with(prefix)) {
+                continue;
+            }
+            let content = file.get_lump_content(i);
+            if !is_patch(content) {
+                continue;
+            }
+
+            let image = load_image(content, &playpal.palettes[0]);
+            images.push(DoomTexture::from_patch(name, &image));
+        }
+
+        Graphics {
+            images: DoomTextures::from_list(images),
+        }
+    }
+}
+
+/// Check the header and column offsets of a lump look like a patch
+fn is_patch(content: &[u8]) -> bool {
+    if content.len() < 8 {
+        return false;
+    }
+    let width = i16::from_le_bytes([content[0], content[1]]);
+    let height = i16::from_le_bytes([content[2], content[3]]);
+    if width <= 0 || height <= 0 || width > 4096 || height > 4096 {
+        return false;
+    }
+    let columns_end = 8 + width as usize * 4;
+    if content.len() < columns_end {
+        return false;
+    }
+    content[8..columns_end].chunks_exact(4).all(|c| {
+        let offset = i32::from_le_bytes([c[0], c[1], c[2], c[3]]);
+        offset >= columns_end as i32 && (offset as usize) < content.len()
+    })
+}
@@ -1,21 +1,36 @@
 use core::str;
-use std::{cell::RefCell, collections::HashMap, str::FromStr};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
 use crate::{
     camera::Camera,
-    render::{flat_model::FlatModel, wall_model::WallModel},
+    game::{
+        info::MobjFlags,
+        mobj::Mobj,
+        states::{FRAME_MASK, FULLBRIGHT},
+    },
+    render::{
+        flat_model::FlatModel,
+        sprite_model::{Billboard, SpriteModel},
+        wall_model::WallModel,
+    },
     sys::textures::Texture,
 };
 
 //use super::doom_textures::Texture;
 use crate::sys::content::Content;
+use crate::wad::file::WadFile;
+use crate::wad::things::Thing;
 
 use bitflags::bitflags;
 use cgmath::{AbsDiffEq, InnerSpace, Matrix4, Vector2, Vector3};
-use kabal_render::doom_gl::{gl, DoomGl, GVertex};
+use kabal_render::renderer::{GVertex, Renderer, TextureId};
 
 bitflags! {
-    struct LinedefFlags: i16 {
+    pub struct LinedefFlags: i16 {
         const NONE = 0;
         const BLOCK_ALL = 0x0001;
         const BLOCK_MONSTERS = 0x0002;
@@ -25,14 +40,15 @@ bitflags! {
         const SECRET = 0x0020;
         const BLOCK_SOUND = 0x0040;
         const AUTO_MAP_NEVER = 0x0080;
-        const AUTO_MAP_ALWAYS = 0x0100;
+        /// Shown on the automap, set from the start or once seen, like vanilla ML_MAPPED
+        const MAPPED = 0x0100;
     }
 }
 
 #[repr(i16)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[allow(dead_code)]
-enum SectorType {
+pub enum SectorType {
     Normal = 0,
     BlinkRandom = 1,
     BlinkHalf = 2,
@@ -54,36 +70,74 @@ enum SectorType {
 #[repr(C, packed)]
 #[derive(Debug, Copy, Clone)]
 pub struct LineDef {
-    start_vertex: u16,
-    end_vertex: u16,
-    flags: LinedefFlags,
-    special_type: i16,
-    sector_tag: i16,
-    front_sidedef: i16,
-    back_sidedef: i16,
+    pub start_vertex: u16,
+    pub end_vertex: u16,
+    pub flags: LinedefFlags,
+    pub special_type: i16,
+    pub sector_tag: i16,
+    pub front_sidedef: i16,
+    pub back_sidedef: i16,
 }
 
 #[repr(C, packed)]
 #[derive(Debug, Copy, Clone)]
 pub struct SideDef {
-    x_offset: i16,
-    y_offset: i16,
-    upper_tex: [u8; 8],
-    lower_tex: [u8; 8],
-    middle_tex: [u8; 8],
-    sector: i16,
+    pub x_offset: i16,
+    pub y_offset: i16,
+    pub upper_tex: [u8; 8],
+    pub lower_tex: [u8; 8],
+    pub middle_tex: [u8; 8],
+    pub sector: i16,
+}
+
+/// One of the three textures of a sidedef
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SidePart {
+    Upper,
+    Middle,
+    Lower,
+}
+
+impl SideDef {
+    pub fn texture(&self, part: SidePart) -> [u8; 8] {
+        match part {
+            SidePart::Upper => self.upper_tex,
+            SidePart::Middle => self.middle_tex,
+            SidePart::Lower => self.lower_tex,
+        }
+    }
+
+    pub fn set_texture(&mut self, part: SidePart, name: [u8; 8]) {
+        match part {
+            SidePart::Upper => self.upper_tex = name,
+            SidePart::Middle => self.middle_tex = name,
+            SidePart::Lower => self.lower_tex = name,
+        }
+    }
+}
+
+/// Quad of a sidedef texture in the wall models, to change its texture in place
+struct WallQuad {
+    line: usize,
+    side: usize,
+    part: SidePart,
+    /// Uppercase name of the texture on the quad
+    texture: [u8; 8],
+    wall: usize,
+    first_vertex: usize,
 }
 
 #[repr(C, packed)]
 #[derive(Debug, Copy, Clone)]
 pub struct Vertex {
-    x: i16,
-    y: i16,
+    pub x: i16,
+    pub y: i16,
 }
 
+/// Sector as stored in the SECTORS lump
 #[repr(C, packed)]
 #[derive(Debug, Copy, Clone)]
-pub struct Sector {
+struct MapSector {
     floor: i16,
     ceiling: i16,
     floor_tex: [u8; 8],
@@ -93,28 +147,142 @@ pub struct Sector {
     tag: i16,
 }
 
+/// Sector of the level, doors, lifts and floors change it while playing
+#[derive(Debug, Copy, Clone)]
+pub struct Sector {
+    pub floor: f32,
+    pub ceiling: f32,
+    pub floor_tex: [u8; 8],
+    pub ceil_tex: [u8; 8],
+    pub lighting: i16,
+    pub stype: SectorType,
+    pub tag: i16,
+}
+
+impl Sector {
+    /// The ceiling is open to the sky
+    pub fn sky_ceiling(&self) -> bool {
+        self.ceil_tex.starts_with(b"F_SKY1")
+    }
+}
+
+impl From<MapSector> for Sector {
+    fn from(sector: MapSector) -> Self {
+        Sector {
+            floor: sector.floor as f32,
+            ceiling: sector.ceiling as f32,
+            floor_tex: sector.floor_tex,
+            ceil_tex: sector.ceil_tex,
+            lighting: sector.lighting,
+            stype: sector.stype,
+            tag: sector.tag,
+        }
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct Seg {
+    pub start_vertex: u16,
+    pub end_vertex: u16,
+    pub angle: i16,
+    pub linedef: u16,
+    pub side: i16,
+    pub offset: i16,
+}
+
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct SubSector {
+    pub seg_count: u16,
+    pub first_seg: u16,
+}
+
+/// Child index flag telling the child is a subsector and not a node
+pub const NF_SUBSECTOR: u16 = 0x8000;
+
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct Node {
+    pub x: i16,
+    pub y: i16,
+    pub dx: i16,
+    pub dy: i16,
+    /// Bounding boxes of the right then left child: top, bottom, left, right
+    pub bbox: [[i16; 4]; 2],
+    pub children: [u16; 2],
+}
+
+impl Node {
+    /// Which child the point is in, 0 for the right (front) side and 1 for the left (back)
+    pub fn point_on_side(&self, x: f32, y: f32) -> usize {
+        let (node_x, node_y) = (self.x as f32, self.y as f32);
+        let (node_dx, node_dy) = (self.dx as f32, self.dy as f32);
+
+        if self.dx == 0 {
+            return if x <= node_x {
+                (node_dy > 0.0) as usize
+            } else {
+                (node_dy < 0.0) as usize
+            };
+        }
+        if self.dy == 0 {
+            return if y <= node_y {
+                (node_dx < 0.0) as usize
+            } else {
+                (node_dx > 0.0) as usize
+            };
+        }
+
+        let left = node_dy * (x - node_x);
+        let right = (y - node_y) * node_dx;
+        (right >= left) as usize
+    }
+}
+
+/// Size of a blockmap cell in map units
+pub const BLOCK_SIZE: f32 = 128.0;
+
 pub struct WadMap {
-    linedefs: Vec<LineDef>,
-    sidedefs: Vec<SideDef>,
-    vertexes: Vec<Vertex>,
-    sectors: Vec<Sector>,
+    pub name: String,
+    pub linedefs: Vec<LineDef>,
+    pub sidedefs: Vec<SideDef>,
+    pub vertexes: Vec<Vertex>,
+    pub segs: Vec<Seg>,
+    pub subsectors: Vec<SubSector>,
+    pub nodes: Vec<Node>,
+    pub sectors: Vec<Sector>,
+    /// Lines around each sector, like vanilla sector_t lines
+    pub sector_lines: Vec<Vec<usize>>,
+    pub things: Vec<Thing>,
+    /// Raw BLOCKMAP lump: origin x & y, column & row count, then the block offsets and lists
+    pub blockmap: Vec<i16>,
+    /// Height of the textures on the sidedefs, floors can be raised by them
+    pub texture_heights: HashMap<[u8; 8], f32>,
+    /// Switch textures on the sidedefs and the texture each one swaps with
+    pub switch_textures: HashMap<[u8; 8], [u8; 8]>,
 
-    vbuffer: RefCell<Vec<GVertex>>,
     walls: RefCell<Vec<WallModel>>,
+    /// Wall model of each texture array
+    wall_per_texture: RefCell<HashMap<TextureId, usize>>,
+    wall_quads: RefCell<Vec<WallQuad>>,
     flats: RefCell<Vec<FlatModel>>,
+    sprites: RefCell<SpriteModel>,
 }
 
 impl WadMap {
-    /// Add a wall quad
+    /// Add a wall quad of a linedef
+    #[allow(clippy::too_many_arguments)]
     fn add_quad(
         &self,
         wall_index: usize,
+        linedef: usize,
         line: (u16, u16),
         heights: (f32, f32),
         texture: &Texture,
         texture_offset: (f32, f32),
         light: f32,
-    ) {
+    ) -> usize {
         let start = self.vertexes[line.0 as usize];
         let end = self.vertexes[line.1 as usize];
 
@@ -127,7 +295,7 @@ impl WadMap {
             texture_offset.1 / texture.height as f32,
         );
 
-        let mut quad_buffer = vec![
+        let quad = vec![
             GVertex {
                 pos: Vector3::new(-start.x as f32, heights.0, start.y as f32),
                 uv: Vector3::new(uv_offset.x, uv_offset.y, texture.depth as f32),
@@ -162,29 +330,22 @@ impl WadMap {
             },
         ];
 
-        let startidx = self.vbuffer.borrow().len() as u16;
-
-        self.vbuffer.borrow_mut().append(&mut quad_buffer);
-        self.walls.borrow_mut()[wall_index].append_indexes(vec![
-            startidx,
-            startidx + 1,
-            startidx + 2,
-            startidx + 2,
-            startidx + 1,
-            startidx + 3,
-        ]);
+        self.walls.borrow_mut()[wall_index].append_quad(linedef, quad)
     }
 
-    /// Prepare wall side rendering
+    /// Prepare wall side rendering, the quad of a sidedef texture can change texture later
+    #[allow(clippy::too_many_arguments)]
     fn prepare_line_render(
         &self,
-        model_per_texture: &mut HashMap<u32, usize>,
+        linedef: usize,
+        side: Option<(usize, SidePart)>,
         texture: &Texture,
         line: (u16, u16),
         heights: (f32, f32),
         texture_offset: (f32, f32),
         light: f32,
     ) {
+        let mut model_per_texture = self.wall_per_texture.borrow_mut();
         let wall_index = if let Some(i) = model_per_texture.get(&texture.id) {
             *i
         } else {
@@ -195,15 +356,34 @@ impl WadMap {
         };
 
         // push vertices
-        self.add_quad(wall_index, line, heights, texture, texture_offset, light);
+        let first_vertex = self.add_quad(
+            wall_index,
+            linedef,
+            line,
+            heights,
+            texture,
+            texture_offset,
+            light,
+        );
+        if let Some((side, part)) = side {
+            let mut name = self.sidedefs[side].texture(part);
+            name.make_ascii_uppercase();
+            self.wall_quads.borrow_mut().push(WallQuad {
+                line: linedef,
+                side,
+                part,
+                texture: name,
+                wall: wall_index,
+                first_vertex,
+            });
+        }
     }
 
-    /// Handle the wall model creation
-    fn prepare_wall_render(&self, content: &Content) {
-        let mut model_per_texture = HashMap::new();
-
+    /// Add the quads of some lines to the wall models
+    fn prepare_wall_render(&self, content: &Content, lines: impl IntoIterator<Item = usize>) {
         // Create walls buffers
-        for l in &self.linedefs {
+        for index in lines {
+            let l = &self.linedefs[index];
             // front
             assert!(l.front_sidedef != -1);
 
@@ -226,16 +406,16 @@ impl WadMap {
                 }
             }
 
-            let front_floor = front_sector.floor as f32;
-            let front_ceil = front_sector.ceiling as f32;
+            let front_floor = front_sector.floor;
+            let front_ceil = front_sector.ceiling;
             let front_light = front_sector.lighting as f32 / 255.0;
             let back_floor = if let Some(s) = back_sector {
-                s.floor as f32
+                s.floor
             } else {
                 front_floor
             };
             let back_ceil = if let Some(s) = back_sector {
-                s.ceiling as f32
+                s.ceiling
             } else {
                 front_ceil
             };
@@ -257,7 +437,7 @@ impl WadMap {
 
             // low
             if let Some(texture) = content.get_textures().find_texture(
-                &String::from_utf8(front_side.lower_tex.to_ascii_uppercase().to_vec()).unwrap(),
+                core::str::from_utf8(&front_side.lower_tex.to_ascii_uppercase()).unwrap(),
             ) {
                 let line_offset =
                     if (l.flags & LinedefFlags::LOWER_TEX_UNPEGGED) != LinedefFlags::NONE {
@@ -269,7 +449,8 @@ impl WadMap {
                     };
 
                 self.prepare_line_render(
-                    &mut model_per_texture,
+                    index,
+                    Some((l.front_sidedef as usize, SidePart::Lower)),
                     texture,
                     line,
                     (front_floor, back_floor),
@@ -280,7 +461,7 @@ impl WadMap {
 
             // mid
             if let Some(texture) = content.get_textures().find_texture(
-                &String::from_utf8(front_side.middle_tex.to_ascii_uppercase().to_vec()).unwrap(),
+                core::str::from_utf8(&front_side.middle_tex.to_ascii_uppercase()).unwrap(),
             ) {
                 let line_offset =
                     if (l.flags & LinedefFlags::LOWER_TEX_UNPEGGED) == LinedefFlags::NONE {
@@ -291,7 +472,8 @@ impl WadMap {
                         texture_offset
                     };
                 self.prepare_line_render(
-                    &mut model_per_texture,
+                    index,
+                    Some((l.front_sidedef as usize, SidePart::Middle)),
                     texture,
                     line,
                     (back_floor, back_ceil),
@@ -316,7 +498,8 @@ impl WadMap {
                         texture_offset
                     };
                 self.prepare_line_render(
-                    &mut model_per_texture,
+                    index,
+                    (!back_sector_is_sky).then_some((l.front_sidedef as usize, SidePart::Upper)),
                     texture,
                     line,
                     (back_ceil, front_ceil),
@@ -331,9 +514,10 @@ impl WadMap {
             if let Some(b) = back_side {
                 let texture_offset = (b.x_offset as f32, b.y_offset as f32);
                 // low
-                if let Some(texture) = content.get_textures().find_texture(
-                    &String::from_utf8(b.lower_tex.to_ascii_uppercase().to_vec()).unwrap(),
-                ) {
+                if let Some(texture) = content
+                    .get_textures()
+                    .find_texture(core::str::from_utf8(&b.lower_tex.to_ascii_uppercase()).unwrap())
+                {
                     let line_offset =
                         if (l.flags & LinedefFlags::LOWER_TEX_UNPEGGED) != LinedefFlags::NONE {
                             let off = (wall_extent.0 - wall_extent.1) / texture.height as f32;
@@ -344,7 +528,8 @@ impl WadMap {
                         };
 
                     self.prepare_line_render(
-                        &mut model_per_texture,
+                        index,
+                        Some((l.back_sidedef as usize, SidePart::Lower)),
                         texture,
                         line,
                         (back_floor, front_floor),
@@ -354,9 +539,10 @@ impl WadMap {
                 }
 
                 // mid
-                if let Some(texture) = content.get_textures().find_texture(
-                    &String::from_utf8(b.middle_tex.to_ascii_uppercase().to_vec()).unwrap(),
-                ) {
+                if let Some(texture) = content
+                    .get_textures()
+                    .find_texture(core::str::from_utf8(&b.middle_tex.to_ascii_uppercase()).unwrap())
+                {
                     let line_offset =
                         if (l.flags & LinedefFlags::LOWER_TEX_UNPEGGED) == LinedefFlags::NONE {
                             let off = (back_ceil - back_floor) / texture.height as f32;
@@ -366,7 +552,8 @@ impl WadMap {
                             texture_offset
                         };
                     self.prepare_line_render(
-                        &mut model_per_texture,
+                        index,
+                        Some((l.back_sidedef as usize, SidePart::Middle)),
                         texture,
                         line,
                         (front_floor, front_ceil),
@@ -376,9 +563,10 @@ impl WadMap {
                 }
 
                 // upper
-                if let Some(texture) = content.get_textures().find_texture(
-                    &String::from_utf8(b.upper_tex.to_ascii_uppercase().to_vec()).unwrap(),
-                ) {
+                if let Some(texture) = content
+                    .get_textures()
+                    .find_texture(core::str::from_utf8(&b.upper_tex.to_ascii_uppercase()).unwrap())
+                {
                     let line_offset =
                         if (l.flags & LinedefFlags::UPPER_TEX_UNPEGGED) != LinedefFlags::NONE {
                             let off = (back_ceil - front_ceil) / texture.height as f32;
@@ -388,7 +576,8 @@ impl WadMap {
                             texture_offset
                         };
                     self.prepare_line_render(
-                        &mut model_per_texture,
+                        index,
+                        Some((l.back_sidedef as usize, SidePart::Upper)),
                         texture,
                         line,
                         (front_ceil, back_ceil),
@@ -400,85 +589,21 @@ impl WadMap {
         }
     }
 
-    fn prepare_ground_ceil(&self, _content: &Content) {}
-
-    /// Prepare the vbuffer & ibuffer of the map
-    fn prepare_render(&self, content: &Content) {
-        self.prepare_wall_render(content);
-        self.prepare_ground_ceil(content);
-
-        let mut vb = unsafe { std::mem::zeroed() };
-        unsafe {
-            let gl = DoomGl::gl();
-            gl.GenBuffers(1, &mut vb);
-            assert!(gl.GetError() == 0);
-            gl.BindBuffer(gl::ARRAY_BUFFER, vb);
-            assert!(gl.GetError() == 0);
-            gl.BufferData(
-                gl::ARRAY_BUFFER,
-                (self.vbuffer.borrow().len() * std::mem::size_of::<GVertex>())
-                    as gl::types::GLsizeiptr,
-                self.vbuffer.borrow().as_ptr() as *const _,
-                gl::DYNAMIC_DRAW,
-            );
-            assert!(gl.GetError() == 0);
-        }
-
-        // Create actual sectors
-        for wall in self.walls.borrow_mut().iter_mut() {
-            wall.init();
-        }
-    }
-
-    /// Render the map
-    pub fn render(&self, camera: &Camera) {
-        unsafe {
-            let gl = DoomGl::gl();
-            gl.ClearColor(0.5, 0.0, 0.5, 1.0);
-            gl.Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
-
-            let view = Matrix4::look_at_rh(
-                camera.origin,
-                camera.origin + camera.direction * Vector3::unit_z(),
-                Vector3::unit_y(),
-            );
-
-            for s in self.walls.borrow().iter() {
-                s.render(&view, &camera.persp);
-            }
-
-            for f in self.flats.borrow().iter() {
-                f.render(&view, &camera.persp);
-            }
-        }
-    }
-
-    /// Load the map and prepare render
-    pub fn new(name: &str, content: &Content) -> Result<WadMap, String> {
-        let mapidx = match content.file.directory.find_section(name, 0) {
-            Some(i) => i,
-            None => return Err("Map not found".to_string()),
-        };
-
-        let linedefs: Vec<LineDef> = content.file.read_section(mapidx, "LINEDEFS");
-        let sidedefs: Vec<SideDef> = content.file.read_section(mapidx, "SIDEDEFS");
-        let vertexes: Vec<Vertex> = content.file.read_section(mapidx, "VERTEXES");
-        let sectors: Vec<Sector> = content.file.read_section(mapidx, "SECTORS");
-
-        let mut flats = Vec::new();
-
+    /// Triangulate every sector and create its floor & ceiling model
+    fn prepare_ground_ceil(&self, content: &Content, renderer: &mut dyn Renderer) {
         let mut sector_lines = Vec::new();
-        for sector_idx in 0..sectors.len() {
+        for sector_idx in 0..self.sectors.len() {
             sector_lines.push(Vec::new());
-            for (side_idx, sidedef) in sidedefs.as_slice().iter().enumerate() {
+            for (side_idx, sidedef) in self.sidedefs.as_slice().iter().enumerate() {
                 if sidedef.sector == sector_idx as i16 {
-                    for (linedef_idx, linedef) in linedefs.as_slice().iter().enumerate() {
+                    for (linedef_idx, linedef) in self.linedefs.as_slice().iter().enumerate() {
                         if linedef.front_sidedef == side_idx as i16
                             || linedef.back_sidedef == side_idx as i16
                         {
                             let start_vertex =
-                                &vertexes[linedefs[linedef_idx].start_vertex as usize];
-                            let end_vertex = &vertexes[linedefs[linedef_idx].end_vertex as usize];
+                                &self.vertexes[self.linedefs[linedef_idx].start_vertex as usize];
+                            let end_vertex =
+                                &self.vertexes[self.linedefs[linedef_idx].end_vertex as usize];
                             let start_point = [start_vertex.x as f32, start_vertex.y as f32];
                             let end_point = [end_vertex.x as f32, end_vertex.y as f32];
                             sector_lines[sector_idx].push((start_point, end_point));
@@ -560,41 +685,377 @@ impl WadMap {
             let ib = earcutr::earcut(&datas, &hole_idx, 2);
 
             let ceil_texture = content.get_textures().find_texture(
-                &String::from_utf8(sectors[sector_idx].ceil_tex.to_ascii_uppercase().to_vec())
+                core::str::from_utf8(&self.sectors[sector_idx].ceil_tex.to_ascii_uppercase())
                     .unwrap(),
             );
             let floor_texture = content
                 .get_textures()
                 .find_texture(
-                    &String::from_utf8(sectors[sector_idx].floor_tex.to_ascii_uppercase().to_vec())
+                    core::str::from_utf8(&self.sectors[sector_idx].floor_tex.to_ascii_uppercase())
                         .unwrap(),
                 )
                 .unwrap();
 
             let mut model = FlatModel::new(
                 datas,
-                ib.iter().map(|i| *i as u16).collect(),
+                ib.iter().map(|i| *i as u32).collect(),
                 ceil_texture,
                 floor_texture,
             );
-            model.light = sectors[sector_idx].lighting as f32 / 255.0;
-            model.floor = sectors[sector_idx].floor as f32;
-            model.ceil = sectors[sector_idx].ceiling as f32;
+            model.light = self.sectors[sector_idx].lighting as f32 / 255.0;
+            model.floor = self.sectors[sector_idx].floor;
+            model.ceil = self.sectors[sector_idx].ceiling;
+
+            model.init(renderer);
+            self.flats.borrow_mut().push(model);
+        }
+    }
+
+    /// Rebuild the geometry of the sectors that moved, changed light or floor texture since the
+    /// last frame
+    pub fn update_sectors(&self, content: &Content, renderer: &mut dyn Renderer) {
+        let mut lines = Vec::new();
+        for (index, (sector, flat)) in self
+            .sectors
+            .iter()
+            .zip(self.flats.borrow_mut().iter_mut())
+            .enumerate()
+        {
+            let floor_texture = content.get_textures().find_texture(
+                core::str::from_utf8(&sector.floor_tex.to_ascii_uppercase()).unwrap(),
+            );
+            let same_texture = floor_texture.is_none_or(|texture| {
+                texture.id == flat.floor_texture.id && texture.depth == flat.floor_texture.depth
+            });
+            let light = sector.lighting as f32 / 255.0;
+            if sector.floor == flat.floor
+                && sector.ceiling == flat.ceil
+                && light == flat.light
+                && same_texture
+            {
+                continue;
+            }
+
+            flat.floor = sector.floor;
+            flat.ceil = sector.ceiling;
+            flat.light = light;
+            if let Some(texture) = floor_texture {
+                flat.floor_texture = texture.clone();
+            }
+            flat.update(renderer);
+            lines.extend(&self.sector_lines[index]);
+        }
+        if !lines.is_empty() {
+            self.rebuild_lines(content, renderer, lines);
+        }
+    }
 
-            model.init();
-            flats.push(model);
+    /// Show the sidedef textures changed since the last frame, like pressed switches
+    ///
+    /// The quads take another layer of their texture array in place, the lines are built
+    /// again when the new texture is in another array
+    pub fn update_sides(&self, content: &Content, renderer: &mut dyn Renderer) {
+        let mut walls = self.walls.borrow_mut();
+        let mut quads = self.wall_quads.borrow_mut();
+        let mut rebuild = Vec::new();
+        for quad in quads.iter_mut() {
+            let mut name = self.sidedefs[quad.side].texture(quad.part);
+            name.make_ascii_uppercase();
+            if name == quad.texture {
+                continue;
+            }
+            quad.texture = name;
+
+            let texture = content
+                .get_textures()
+                .find_texture(core::str::from_utf8(&name).unwrap());
+            match texture {
+                Some(texture) if texture.id == walls[quad.wall].texture() => {
+                    walls[quad.wall].set_layer(quad.first_vertex, texture.depth);
+                }
+                _ => rebuild.push(quad.line),
+            }
+        }
+
+        if !rebuild.is_empty() {
+            drop((walls, quads));
+            self.rebuild_lines(content, renderer, rebuild);
+            return;
+        }
+        for wall in walls.iter_mut() {
+            wall.refresh(renderer);
+        }
+    }
+
+    /// Build the quads of some lines again, updating the meshes in place
+    fn rebuild_lines(&self, content: &Content, renderer: &mut dyn Renderer, mut lines: Vec<usize>) {
+        lines.sort_unstable();
+        lines.dedup();
+        let removed: HashSet<usize> = lines.iter().copied().collect();
+        {
+            let mut walls = self.walls.borrow_mut();
+            let moved: Vec<_> = walls
+                .iter_mut()
+                .map(|wall| wall.remove_lines(&removed))
+                .collect();
+            let mut quads = self.wall_quads.borrow_mut();
+            quads.retain(|quad| !removed.contains(&quad.line));
+            // The quads of the other lines are kept, only moved in their buffer
+            for quad in quads.iter_mut() {
+                quad.first_vertex = moved[quad.wall][quad.first_vertex / 4].unwrap();
+            }
+        }
+
+        self.prepare_wall_render(content, lines);
+        for wall in self.walls.borrow_mut().iter_mut() {
+            wall.refresh(renderer);
+        }
+    }
+
+    /// Replace the billboards with the current frame of every mobj
+    pub fn update_things<'a>(&self, content: &Content, mobjs: impl Iterator<Item = &'a Mobj>) {
+        let mut sprites = self.sprites.borrow_mut();
+        sprites.clear();
+        for mo in mobjs {
+            if (mo.flags & MobjFlags::NOSECTOR) != MobjFlags::NONE {
+                continue;
+            }
+            let frame = (mo.frame & FRAME_MASK) as usize;
+            let Some(frame) = content.get_sprites().frame(mo.sprite.name(), frame) else {
+                continue;
+            };
+
+            let rotations = frame.rotations.clone().map(|rotation| {
+                rotation.and_then(|r| {
+                    content
+                        .get_sprite_textures()
+                        .find_texture(&r.lump)
+                        .map(|texture| (texture.clone(), r.flip))
+                })
+            });
+
+            let light = if (mo.frame & FULLBRIGHT) != 0 {
+                1.0
+            } else {
+                self.sectors[self.sector_at(mo.x, mo.y)].lighting as f32 / 255.0
+            };
+
+            sprites.push(Billboard {
+                position: Vector3::new(mo.x, mo.y, mo.z),
+                angle: mo.angle,
+                light,
+                rotations,
+            });
+        }
+    }
+
+    /// Index of the sector containing a map point, found by walking the BSP tree
+    pub fn sector_at(&self, x: f32, y: f32) -> usize {
+        let mut subsector = 0;
+        if !self.nodes.is_empty() {
+            let mut index = self.nodes.len() - 1;
+            loop {
+                let node = self.nodes[index];
+                let child = node.children[node.point_on_side(x, y)];
+                if child & NF_SUBSECTOR != 0 {
+                    subsector = (child & !NF_SUBSECTOR) as usize;
+                    break;
+                }
+                index = child as usize;
+            }
         }
 
-        let map = WadMap {
+        let seg = self.segs[self.subsectors[subsector].first_seg as usize];
+        let linedef = self.linedefs[seg.linedef as usize];
+        let side = if seg.side == 0 {
+            linedef.front_sidedef
+        } else {
+            linedef.back_sidedef
+        };
+        self.sidedefs[side as usize].sector as usize
+    }
+
+    /// Bounding box of a sector: left, bottom, right and top
+    pub fn sector_box(&self, sector: usize) -> (f32, f32, f32, f32) {
+        let mut bbox = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for &index in &self.sector_lines[sector] {
+            let line = &self.linedefs[index];
+            for vertex in [line.start_vertex, line.end_vertex] {
+                let vertex = self.vertexes[vertex as usize];
+                let (x, y) = (vertex.x as f32, vertex.y as f32);
+                bbox = (bbox.0.min(x), bbox.1.min(y), bbox.2.max(x), bbox.3.max(y));
+            }
+        }
+        bbox
+    }
+
+    /// Lines in the blockmap cells touched by a box, every line when the map has no blockmap
+    pub fn lines_in_box(&self, left: f32, bottom: f32, right: f32, top: f32) -> Vec<usize> {
+        if self.blockmap.len() < 4 {
+            return (0..self.linedefs.len()).collect();
+        }
+        let (origin_x, origin_y) = (self.blockmap[0] as f32, self.blockmap[1] as f32);
+        let (columns, rows) = (self.blockmap[2] as i32, self.blockmap[3] as i32);
+        let block = |value: f32, origin: f32, count: i32| {
+            (((value - origin) / BLOCK_SIZE).floor() as i32).clamp(0, count - 1)
+        };
+
+        let mut lines = Vec::new();
+        for row in block(bottom, origin_y, rows)..=block(top, origin_y, rows) {
+            for column in block(left, origin_x, columns)..=block(right, origin_x, columns) {
+                let Some(&offset) = self.blockmap.get(4 + (row * columns + column) as usize) else {
+                    continue;
+                };
+                // Each list starts with a 0 and ends with -1
+                let list = self.blockmap.iter().skip(offset as u16 as usize + 1);
+                lines.extend(
+                    list.take_while(|&&line| line != -1)
+                        .map(|&line| line as u16 as usize),
+                );
+            }
+        }
+        lines.sort_unstable();
+        lines.dedup();
+        lines.retain(|&line| line < self.linedefs.len());
+        lines
+    }
+
+    /// Build the wall & flat meshes of the map and upload them
+    fn prepare_render(&self, content: &Content, renderer: &mut dyn Renderer) {
+        self.prepare_wall_render(content, 0..self.linedefs.len());
+        self.prepare_ground_ceil(content, renderer);
+
+        for wall in self.walls.borrow_mut().iter_mut() {
+            wall.refresh(renderer);
+        }
+    }
+
+    /// Free the wall, flat and sprite meshes once the map is left
+    pub fn free(&self, renderer: &mut dyn Renderer) {
+        for wall in self.walls.borrow_mut().iter_mut() {
+            wall.free(renderer);
+        }
+        for flat in self.flats.borrow_mut().iter_mut() {
+            flat.free(renderer);
+        }
+        self.sprites.borrow_mut().free(renderer);
+    }
+
+    /// Render the map
+    pub fn render(&self, camera: &Camera, renderer: &mut dyn Renderer) {
+        renderer.begin_frame([0.5, 0.0, 0.5, 1.0]);
+
+        let view = Matrix4::look_at_rh(
+            camera.origin,
+            camera.origin + camera.direction * Vector3::unit_z(),
+            Vector3::unit_y(),
+        );
+        renderer.set_view_projection(&view, &camera.persp);
+
+        for s in self.walls.borrow().iter() {
+            s.render(renderer);
+        }
+
+        for f in self.flats.borrow().iter() {
+            f.render(renderer);
+        }
+
+        self.sprites.borrow_mut().render(camera, renderer);
+    }
+
+    /// Name of the sky texture used by this map, it depends on the episode (or map number for Doom II)
+    pub fn sky_texture(&self) -> &'static str {
+        let name = self.name.as_bytes();
+        if name.starts_with(b"MAP") {
+            let number: u32 = self.name[3..].parse().unwrap_or(1);
+            match number {
+                0..=11 => "SKY1",
+                12..=20 => "SKY2",
+                _ => "SKY3",
+            }
+        } else {
+            match name.get(1) {
+                Some(b'2') => "SKY2",
+                Some(b'3') => "SKY3",
+                Some(b'4') => "SKY4",
+                _ => "SKY1",
+            }
+        }
+    }
+
+    /// Load the map lumps, no render resources are created
+    pub fn load(name: &str, file: &WadFile) -> Result<WadMap, String> {
+        let mapidx = match file.directory.find_section(name, 0) {
+            Some(i) => i,
+            None => return Err("Map not found".to_string()),
+        };
+
+        let linedefs: Vec<LineDef> = file.read_section(mapidx, "LINEDEFS");
+        let sidedefs: Vec<SideDef> = file.read_section(mapidx, "SIDEDEFS");
+        let sectors: Vec<Sector> = file
+            .read_section::<MapSector>(mapidx, "SECTORS")
+            .into_iter()
+            .map(Sector::from)
+            .collect();
+
+        // Like vanilla P_GroupLines, a line is listed once even when both sides are in the sector
+        let mut sector_lines = vec![Vec::new(); sectors.len()];
+        for (index, line) in linedefs.iter().enumerate() {
+            for side in [line.front_sidedef, line.back_sidedef] {
+                let Some(side) = sidedefs.get(side as usize) else {
+                    continue;
+                };
+                let lines: &mut Vec<usize> = &mut sector_lines[side.sector as usize];
+                if lines.last() != Some(&index) {
+                    lines.push(index);
+                }
+            }
+        }
+
+        Ok(WadMap {
+            name: name.to_string(),
+            vertexes: file.read_section(mapidx, "VERTEXES"),
+            segs: file.read_section(mapidx, "SEGS"),
+            subsectors: file.read_section(mapidx, "SSECTORS"),
+            nodes: file.read_section(mapidx, "NODES"),
+            things: file.read_section(mapidx, "THINGS"),
+            blockmap: file.read_section(mapidx, "BLOCKMAP"),
             linedefs,
             sidedefs,
             sectors,
-            vertexes,
-            vbuffer: RefCell::new(Vec::new()),
+            sector_lines,
+            texture_heights: HashMap::new(),
+            switch_textures: HashMap::new(),
             walls: RefCell::new(Vec::new()),
-            flats: RefCell::new(flats),
-        };
-        map.prepare_render(content);
+            wall_per_texture: RefCell::new(HashMap::new()),
+            wall_quads: RefCell::new(Vec::new()),
+            flats: RefCell::new(Vec::new()),
+            sprites: RefCell::new(SpriteModel::new()),
+        })
+    }
+
+    /// Load the map and prepare render
+    pub fn new(
+        name: &str,
+        content: &Content,
+        renderer: &mut dyn Renderer,
+    ) -> Result<WadMap, String> {
+        let mut map = WadMap::load(name, &content.file)?;
+        for side in &map.sidedefs {
+            for mut name in [side.upper_tex, side.lower_tex, side.middle_tex] {
+                name.make_ascii_uppercase();
+                if let Some(texture) = content
+                    .get_textures()
+                    .find_texture(core::str::from_utf8(&name).unwrap())
+                {
+                    map.texture_heights.insert(name, texture.height as f32);
+                }
+                if let Some(other) = content.get_switches().other(&name) {
+                    map.switch_textures.insert(name, other);
+                }
+            }
+        }
+        map.prepare_render(content, renderer);
         Ok(map)
     }
 }
@@ -6,7 +6,13 @@ use super::{
 pub struct Patch {
     pub width: usize,
     pub height: usize,
+    /// Offset of the patch origin from its left edge, used to center sprites
+    pub left: i32,
+    /// Offset of the patch origin from its top edge
+    pub top: i32,
     pub image: Vec<u8>,
+    /// Palette indices, row major, None where the patch is transparent
+    pub pixels: Vec<Option<u8>>,
     pub name: String,
 }
 
@@ -14,7 +20,7 @@ pub struct Patches {
     patches: Vec<Patch>,
 }
 
-fn load_image(image: &[u8], pal: &Palette) -> Patch {
+pub fn load_image(image: &[u8], pal: &Palette) -> Patch {
     #[repr(C, packed)]
     #[derive(Copy, Clone)]
     struct Header {
@@ -27,11 +33,15 @@ fn load_image(image: &[u8], pal: &Palette) -> Patch {
     let size = header[0].width as usize * header[0].height as usize;
     let mut buffer = Vec::with_capacity(size);
     buffer.resize(size * 4, 0u8);
+    let mut pixels = vec![None; size];
 
-    let (_, columns, _) = unsafe { image[std::mem::size_of::<Header>()..].align_to::<i32>() };
+    // Lumps are not always 4 bytes aligned so the column offsets are read byte by byte
+    let columns = image[std::mem::size_of::<Header>()..]
+        .chunks_exact(4)
+        .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]));
 
-    for (i, offset) in columns.iter().take(header[0].width as usize).enumerate() {
-        let mut offset = *offset as usize;
+    for (i, offset) in columns.take(header[0].width as usize).enumerate() {
+        let mut offset = offset as usize;
 
         let mut rowstart = 0u8;
         while rowstart != 255 {
@@ -52,6 +62,7 @@ fn load_image(image: &[u8], pal: &Palette) -> Patch {
                 buffer[index + 1] = color.g;
                 buffer[index + 2] = color.b;
                 buffer[index + 3] = 255;
+                pixels[index / 4] = Some(*pixel);
             }
             offset = end_offset + 1; // skip one dummy byte
         }
@@ -59,7 +70,10 @@ fn load_image(image: &[u8], pal: &Palette) -> Patch {
     Patch {
         width: header[0].width as usize,
         height: header[0].height as usize,
+        left: header[0].left as i32,
+        top: header[0].top as i32,
         image: buffer,
+        pixels,
         name: String::new(),
     }
 }
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use super::{
+    doom_textures::{DoomTexture, DoomTextures},
+    file::WadFile,
+    patches::load_image,
+    playpal::PlayPal,
+};
+
+/// Lump used for one rotation of a frame, mirrored rotations share the lump of the opposite side
+#[derive(Clone)]
+pub struct SpriteRotation {
+    pub lump: String,
+    pub flip: bool,
+}
+
+/// The 8 views of a frame, index 0 is the front and they go counter-clockwise around the thing
+#[derive(Clone, Default)]
+pub struct SpriteFrame {
+    pub rotations: [Option<SpriteRotation>; 8],
+}
+
+pub struct Sprites {
+    /// Frames of each sprite by its 4 letters name
+    frames: HashMap<String, Vec<SpriteFrame>>,
+    /// One image per sprite lump
+    pub images: DoomTextures,
+}
+
+impl Sprites {
+    /// Read every lump between S_START and S_END
+    pub fn new(file: &WadFile) -> Self {
+        let playpal = PlayPal::new(file);
+
+        let mut frames = HashMap::new();
+        let mut images = Vec::new();
+
+        if let (Some(start_index), Some(end_index)) = (
+            file.directory.get_lump_index("S_START"),
+            file.directory.get_lump_index("S_END"),
+        ) {
+            for i in start_index + 1..end_index {
+                let lump = file.directory.get_lump(i);
+                if lump.size == 0 {
+                    continue;
+                }
+
+                let name = lump.name.to_ascii_uppercase();
+                let name = String::from_utf8(name.to_vec()).unwrap();
+                let name = name.trim_end_matches('\0');
+                if name.len() < 6 {
+                    continue;
+                }
+
+                let sprite_frames = frames
+                    .entry(name[0..4].to_string())
+                    .or_insert_with(Vec::new);
+                let bytes = name.as_bytes();
+                add_rotation(sprite_frames, name, bytes[4], bytes[5], false);
+                // Second frame & rotation using the same image mirrored
+                if bytes.len() >= 8 {
+                    add_rotation(sprite_frames, name, bytes[6], bytes[7], true);
+                }
+
+                let image = load_image(file.get_lump_content(i), &playpal.palettes[0]);
+                images.push(DoomTexture::from_patch(name, &image));
+            }
+        }
+
+        Sprites {
+            frames,
+            images: DoomTextures::from_list(images),
+        }
+    }
+
+    /// Get a frame of a sprite, frame 0 being the 'A' frame
+    pub fn frame(&self, sprite: &str, frame: usize) -> Option<&SpriteFrame> {
+        self.frames.get(sprite).and_then(|frames| frames.get(frame))
+    }
+}
+
+fn add_rotation(frames: &mut Vec<SpriteFrame>, lump: &str, frame: u8, rotation: u8, flip: bool) {
+    let frame = frame.wrapping_sub(b'A') as usize;
+    let rotation = rotation.wrapping_sub(b'0') as usize;
+    if frame >= 29 || rotation > 8 {
+        return;
+    }
+
+    if frames.len() <= frame {
+        frames.resize(frame + 1, SpriteFrame::default());
+    }
+
+    let view = Some(SpriteRotation {
+        lump: lump.to_string(),
+        flip,
+    });
+    if rotation == 0 {
+        // Same image seen from every side
+        frames[frame].rotations = std::array::from_fn(|_| view.clone());
+    } else {
+        frames[frame].rotations[rotation - 1] = view;
+    }
+}
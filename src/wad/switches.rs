@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use super::file::WadFile;
+
+/// Switch textures off and on with the game they come with, like vanilla alphSwitchList
+const VANILLA_SWITCHES: &[(&str, &str, i16)] = &[
+    ("SW1BRCOM", "SW2BRCOM", 1),
+    ("SW1BRN1", "SW2BRN1", 1),
+    ("SW1BRN2", "SW2BRN2", 1),
+    ("SW1BRNGN", "SW2BRNGN", 1),
+    ("SW1BROWN", "SW2BROWN", 1),
+    ("SW1COMM", "SW2COMM", 1),
+    ("SW1COMP", "SW2COMP", 1),
+    ("SW1DIRT", "SW2DIRT", 1),
+    ("SW1EXIT", "SW2EXIT", 1),
+    ("SW1GRAY", "SW2GRAY", 1),
+    ("SW1GRAY1", "SW2GRAY1", 1),
+    ("SW1METAL", "SW2METAL", 1),
+    ("SW1PIPE", "SW2PIPE", 1),
+    ("SW1SLAD", "SW2SLAD", 1),
+    ("SW1STARG", "SW2STARG", 1),
+    ("SW1STON1", "SW2STON1", 1),
+    ("SW1STON2", "SW2STON2", 1),
+    ("SW1STONE", "SW2STONE", 1),
+    ("SW1STRTN", "SW2STRTN", 1),
+    ("SW1BLUE", "SW2BLUE", 2),
+    ("SW1CMT", "SW2CMT", 2),
+    ("SW1GARG", "SW2GARG", 2),
+    ("SW1GSTON", "SW2GSTON", 2),
+    ("SW1HOT", "SW2HOT", 2),
+    ("SW1LION", "SW2LION", 2),
+    ("SW1SATYR", "SW2SATYR", 2),
+    ("SW1SKIN", "SW2SKIN", 2),
+    ("SW1VINE", "SW2VINE", 2),
+    ("SW1WOOD", "SW2WOOD", 2),
+    ("SW1PANEL", "SW2PANEL", 3),
+    ("SW1ROCK", "SW2ROCK", 3),
+    ("SW1MET2", "SW2MET2", 3),
+    ("SW1WDMET", "SW2WDMET", 3),
+    ("SW1BRIK", "SW2BRIK", 3),
+    ("SW1MOD1", "SW2MOD1", 3),
+    ("SW1ZIM", "SW2ZIM", 3),
+    ("SW1STON6", "SW2STON6", 3),
+    ("SW1TEK", "SW2TEK", 3),
+    ("SW1MARB", "SW2MARB", 3),
+    ("SW1SKULL", "SW2SKULL", 3),
+];
+
+/// Size of a Boom SWITCHES entry: two 9 chars names and the game it comes with
+const SWITCHES_ENTRY_SIZE: usize = 20;
+
+/// Switches of the shareware game, of the registered one, and of Doom II too
+const SHAREWARE: i16 = 1;
+const REGISTERED: i16 = 2;
+const COMMERCIAL: i16 = 3;
+
+/// Switch game the WAD has the textures of, like the episode of vanilla P_InitSwitchList
+fn game_episode(file: &WadFile) -> i16 {
+    let has = |lump| file.directory.get_lump_index(lump).is_some();
+    if has("MAP01") {
+        COMMERCIAL
+    } else if has("E2M1") {
+        REGISTERED
+    } else {
+        SHAREWARE
+    }
+}
+
+/// Texture name as stored in a sidedef: uppercase and padded with zeros
+fn texture_name(name: &[u8]) -> [u8; 8] {
+    let mut result = [0; 8];
+    for (dst, src) in result.iter_mut().zip(name.iter().take_while(|&&c| c != 0)) {
+        *dst = src.to_ascii_uppercase();
+    }
+    result
+}
+
+/// Switch textures and the texture each one swaps with, like vanilla P_InitSwitchList
+pub struct Switches {
+    pairs: HashMap<[u8; 8], [u8; 8]>,
+}
+
+impl Switches {
+    /// The Boom SWITCHES lump when the WAD has one, the vanilla list otherwise
+    ///
+    /// Switches of a later game than the one of the WAD are left out, their textures are missing
+    pub fn new(file: &WadFile) -> Self {
+        let list: Vec<([u8; 8], [u8; 8], i16)> = match file.get_section("SWITCHES") {
+            Some(lump) => lump
+                .chunks_exact(SWITCHES_ENTRY_SIZE)
+                .map(|entry| {
+                    let episode = i16::from_le_bytes([entry[18], entry[19]]);
+                    (
+                        texture_name(&entry[0..9]),
+                        texture_name(&entry[9..18]),
+                        episode,
+                    )
+                })
+                // The list ends with an entry for no game
+                .take_while(|&(_, _, episode)| episode != 0)
+                .collect(),
+            None => VANILLA_SWITCHES
+                .iter()
+                .map(|&(off, on, episode)| {
+                    (
+                        texture_name(off.as_bytes()),
+                        texture_name(on.as_bytes()),
+                        episode,
+                    )
+                })
+                .collect(),
+        };
+
+        let episode = game_episode(file);
+        let mut pairs = HashMap::new();
+        for (off, on, _) in list.into_iter().filter(|entry| entry.2 <= episode) {
+            pairs.insert(off, on);
+            pairs.insert(on, off);
+        }
+        Switches { pairs }
+    }
+
+    /// Texture a switch texture swaps with, None when it is not a switch
+    pub fn other(&self, name: &[u8; 8]) -> Option<[u8; 8]> {
+        self.pairs.get(&texture_name(name)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wad::test_wad::room_wad_with;
+
+    fn switches_lump() -> Vec<u8> {
+        let mut lump = Vec::new();
+        for (off, on, episode) in [
+            ("SW1A", "SW2A", SHAREWARE),
+            ("SW1B", "SW2B", REGISTERED),
+            ("SW1C", "SW2C", COMMERCIAL),
+            ("", "", 0),
+        ] {
+            for name in [off, on] {
+                let mut field = [0; 9];
+                field[..name.len()].copy_from_slice(name.as_bytes());
+                lump.extend(field);
+            }
+            lump.extend(episode.to_le_bytes());
+        }
+        lump
+    }
+
+    fn has_switch(switches: &Switches, name: &str) -> bool {
+        switches.other(&texture_name(name.as_bytes())).is_some()
+    }
+
+    #[test]
+    fn switches_of_later_games_are_left_out() {
+        // The test WAD only has E1M1, like the shareware game
+        let switches = Switches::new(&room_wad_with(&[("SWITCHES", switches_lump())]));
+        assert!(has_switch(&switches, "SW2A"));
+        assert!(!has_switch(&switches, "SW1B"));
+        assert!(!has_switch(&switches, "SW1C"));
+
+        let switches = Switches::new(&room_wad_with(&[
+            ("SWITCHES", switches_lump()),
+            ("MAP01", Vec::new()),
+        ]));
+        assert!(["SW1A", "SW1B", "SW1C"]
+            .iter()
+            .all(|name| has_switch(&switches, name)));
+    }
+
+    #[test]
+    fn vanilla_list_follows_the_game() {
+        let switches = Switches::new(&room_wad_with(&[]));
+        assert!(has_switch(&switches, "SW1BRCOM"));
+        assert!(!has_switch(&switches, "SW1BLUE"));
+        assert!(!has_switch(&switches, "SW1PANEL"));
+    }
+}
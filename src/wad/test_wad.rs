@@ -0,0 +1,392 @@
+//! A small WAD generated for the tests: a square room with one wall texture, two flats and a
+//! few things, enough to load, play and render a map without the original game
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::{file::WadFile, map::WadMap};
+
+/// Name of the map of the test WAD
+pub const ROOM_MAP: &str = "E1M1";
+/// Length of the sides of the room
+pub const ROOM_SIZE: i16 = 512;
+/// Player start, in the south of the room looking north
+pub const PLAYER_START: (i16, i16, i16) = (256, 64, 90);
+
+/// Things of the room: player start, a barrel, a clip and an imp, at every skill
+const THINGS: [(i16, i16, i16, i16); 4] = [
+    (PLAYER_START.0, PLAYER_START.1, PLAYER_START.2, 1),
+    (256, 320, 0, 2035),
+    (128, 256, 0, 2007),
+    (384, 448, 270, 3001),
+];
+/// Name of the map of three sectors in a row
+pub const STEPS_MAP: &str = "E1M2";
+/// Floor and ceiling of the steps: the second one is a step up from the first, the third one
+/// is too low for a player to walk in
+pub const STEPS_SECTORS: [(i16, i16); 3] = [(0, 128), (24, 128), (24, 72)];
+
+const ROOM_FLOOR: i16 = 0;
+const ROOM_CEILING: i16 = 128;
+const ROOM_LIGHT: i16 = 160;
+
+fn name(text: &str) -> [u8; 8] {
+    let mut name = [0; 8];
+    name[..text.len()].copy_from_slice(text.as_bytes());
+    name
+}
+
+fn shorts(values: &[i16]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+/// Gray palette repeated for every palette of PLAYPAL
+fn playpal() -> Vec<u8> {
+    let palette: Vec<u8> = (0..=255u8).flat_map(|index| [index; 3]).collect();
+    palette.repeat(14)
+}
+
+/// Light levels going darker toward black, then the invulnerability and black maps
+fn colormap() -> Vec<u8> {
+    let mut content = Vec::new();
+    for level in 0..34usize {
+        let level = level.min(32);
+        content.extend((0..256usize).map(|index| (index * (32 - level) / 32) as u8));
+    }
+    content
+}
+
+/// A 16x16 patch of vertical stripes with a darker band in the middle rows
+fn wall_patch() -> Vec<u8> {
+    const SIZE: usize = 16;
+    let mut content = shorts(&[SIZE as i16, SIZE as i16, 0, 0]);
+    let columns_start = content.len() + 4 * SIZE;
+    let column_size = SIZE + 5;
+    for x in 0..SIZE {
+        content.extend(((columns_start + x * column_size) as i32).to_le_bytes());
+    }
+    for x in 0..SIZE {
+        content.extend([0, SIZE as u8, 0]);
+        content.extend((0..SIZE).map(|y| {
+            let stripe = if x % 4 < 2 { 200 } else { 120 };
+            if (6..10).contains(&y) {
+                stripe / 2
+            } else {
+                stripe
+            }
+        }));
+        content.extend([0, 255]);
+    }
+    content
+}
+
+fn pnames() -> Vec<u8> {
+    let mut content = 1i32.to_le_bytes().to_vec();
+    content.extend(name("WALLP"));
+    content
+}
+
+/// One 64x128 texture made of the wall patch repeated
+fn texture1() -> Vec<u8> {
+    let mut content = 1i32.to_le_bytes().to_vec();
+    content.extend(8i32.to_le_bytes());
+    content.extend(name("WALL"));
+    content.extend(0i32.to_le_bytes());
+    content.extend(shorts(&[64, 128]));
+    content.extend(0i32.to_le_bytes());
+    let patches: Vec<(i16, i16)> = (0..4)
+        .flat_map(|x| (0..8).map(move |y| (x * 16, y * 16)))
+        .collect();
+    content.extend(shorts(&[patches.len() as i16]));
+    for (x, y) in patches {
+        content.extend(shorts(&[x, y, 0, 1, 0]));
+    }
+    content
+}
+
+/// A checkerboard flat of two colors
+fn flat(light: u8, dark: u8) -> Vec<u8> {
+    (0..64 * 64)
+        .map(|i| {
+            if (i % 64 / 8 + i / 64 / 8) % 2 == 0 {
+                light
+            } else {
+                dark
+            }
+        })
+        .collect()
+}
+
+/// A line of a test map: its vertexes, the sector in front and the one at the back if any
+type Line = (usize, usize, i16, Option<i16>);
+
+/// The room: four lines facing inward and one subsector holding the whole room
+fn room_lumps() -> Vec<(&'static str, Vec<u8>)> {
+    let vertexes = [
+        (0, 0),
+        (0, ROOM_SIZE),
+        (ROOM_SIZE, ROOM_SIZE),
+        (ROOM_SIZE, 0),
+    ];
+    let lines: Vec<Line> = (0..4).map(|line| (line, (line + 1) % 4, 0, None)).collect();
+    map_lumps(
+        ROOM_MAP,
+        &THINGS,
+        &vertexes,
+        &lines,
+        &[(ROOM_FLOOR, ROOM_CEILING)],
+        &[],
+    )
+}
+
+/// The steps: three sectors of 128x128 in a row from west to east, each one a subsector, split
+/// by two nodes
+fn steps_lumps() -> Vec<(&'static str, Vec<u8>)> {
+    let vertexes = [
+        (0, 0),
+        (0, 128),
+        (128, 128),
+        (128, 0),
+        (256, 128),
+        (256, 0),
+        (384, 128),
+        (384, 0),
+    ];
+    let lines: [Line; 10] = [
+        (0, 1, 0, None),
+        (1, 2, 0, None),
+        (3, 0, 0, None),
+        (2, 3, 0, Some(1)),
+        (2, 4, 1, None),
+        (5, 3, 1, None),
+        (4, 5, 1, Some(2)),
+        (4, 6, 2, None),
+        (6, 7, 2, None),
+        (7, 5, 2, None),
+    ];
+    let nodes = [
+        // The third sector east of x = 256 and the second one west of it
+        vertical_node(
+            256,
+            [[128, 0, 256, 384], [128, 0, 128, 256]],
+            [0x8002, 0x8001],
+        ),
+        // The node above east of x = 128 and the first sector west of it
+        vertical_node(128, [[128, 0, 128, 384], [128, 0, 0, 128]], [0, 0x8000]),
+    ]
+    .concat();
+    map_lumps(
+        STEPS_MAP,
+        &[(64, 64, 0, 1)],
+        &vertexes,
+        &lines,
+        &STEPS_SECTORS,
+        &nodes,
+    )
+}
+
+/// A node split by a line going north at x, with the boxes (top, bottom, left, right) and the
+/// children east then west of it
+fn vertical_node(x: i16, bboxes: [[i16; 4]; 2], children: [u16; 2]) -> Vec<i16> {
+    let mut node = vec![x, 0, 0, 128];
+    node.extend(bboxes.concat());
+    node.extend(children.map(|child| child as i16));
+    node
+}
+
+/// The lumps of a map whose sectors are convex, each one a subsector
+fn map_lumps(
+    map: &'static str,
+    map_things: &[(i16, i16, i16, i16)],
+    vertexes: &[(i16, i16)],
+    lines: &[Line],
+    map_sectors: &[(i16, i16)],
+    nodes: &[i16],
+) -> Vec<(&'static str, Vec<u8>)> {
+    let mut things = Vec::new();
+    for &(x, y, angle, doomednum) in map_things {
+        things.extend(shorts(&[x, y, angle, doomednum, 7]));
+    }
+
+    let mut linedefs = Vec::new();
+    let mut sidedefs = Vec::new();
+    // Segs of each sector, with the line and the side they are on
+    let mut sector_segs = vec![Vec::new(); map_sectors.len()];
+    let mut side = |sector: i16, texture: &str, middle: &str| {
+        sidedefs.extend(shorts(&[0, 0]));
+        sidedefs.extend(name(texture));
+        sidedefs.extend(name(texture));
+        sidedefs.extend(name(middle));
+        sidedefs.extend(shorts(&[sector]));
+        (sidedefs.len() / 30 - 1) as i16
+    };
+    for (index, &(start, end, front, back)) in lines.iter().enumerate() {
+        let line = index as i16;
+        let (start, end) = (start as i16, end as i16);
+        match back {
+            None => {
+                let front_side = side(front, "-", "WALL");
+                linedefs.extend(shorts(&[start, end, 1, 0, 0, front_side, -1]));
+                sector_segs[front as usize].push((start, end, line, 0));
+            }
+            Some(back) => {
+                let front_side = side(front, "WALL", "-");
+                let back_side = side(back, "WALL", "-");
+                linedefs.extend(shorts(&[start, end, 4, 0, 0, front_side, back_side]));
+                sector_segs[front as usize].push((start, end, line, 0));
+                sector_segs[back as usize].push((end, start, line, 1));
+            }
+        }
+    }
+
+    let mut segs = Vec::new();
+    let mut subsectors = Vec::new();
+    for sector_seg in sector_segs {
+        subsectors.extend(shorts(&[sector_seg.len() as i16, (segs.len() / 12) as i16]));
+        for (start, end, line, side) in sector_seg {
+            let (x1, y1) = vertexes[start as usize];
+            let (x2, y2) = vertexes[end as usize];
+            let angle = ((y2 - y1) as f64).atan2((x2 - x1) as f64);
+            let angle = (angle / std::f64::consts::TAU * 65536.0).round() as i64 as u16 as i16;
+            segs.extend(shorts(&[start, end, angle, line, side, 0]));
+        }
+    }
+
+    let mut sectors = Vec::new();
+    for &(floor, ceiling) in map_sectors {
+        sectors.extend(shorts(&[floor, ceiling]));
+        sectors.extend(name("FLOOR"));
+        sectors.extend(name("CEIL"));
+        sectors.extend(shorts(&[ROOM_LIGHT, 0, 0]));
+    }
+
+    let lines: Vec<(usize, usize)> = lines.iter().map(|&(v1, v2, ..)| (v1, v2)).collect();
+    vec![
+        (map, Vec::new()),
+        ("THINGS", things),
+        ("LINEDEFS", linedefs),
+        ("SIDEDEFS", sidedefs),
+        (
+            "VERTEXES",
+            shorts(
+                &vertexes
+                    .iter()
+                    .flat_map(|&(x, y)| [x, y])
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        ("SEGS", segs),
+        ("SSECTORS", subsectors),
+        ("NODES", shorts(nodes)),
+        ("SECTORS", sectors),
+        ("REJECT", Vec::new()),
+        ("BLOCKMAP", blockmap(vertexes, &lines)),
+    ]
+}
+
+/// Blockmap covering a map, each cell lists the lines whose box touches it
+fn blockmap(vertexes: &[(i16, i16)], lines: &[(usize, usize)]) -> Vec<u8> {
+    const ORIGIN: i16 = -8;
+    const BLOCK: i16 = 128;
+    let columns = (vertexes.iter().map(|v| v.0).max().unwrap() - ORIGIN) / BLOCK + 1;
+    let rows = (vertexes.iter().map(|v| v.1).max().unwrap() - ORIGIN) / BLOCK + 1;
+    let mut header = vec![ORIGIN, ORIGIN, columns, rows];
+    let mut lists = Vec::new();
+    let first_list = 4 + (columns * rows) as usize;
+    for row in 0..rows {
+        for column in 0..columns {
+            header.push((first_list + lists.len()) as i16);
+            let (left, bottom) = (ORIGIN + column * BLOCK, ORIGIN + row * BLOCK);
+            lists.push(0);
+            for (line, &(v1, v2)) in lines.iter().enumerate() {
+                let (x1, y1) = vertexes[v1];
+                let (x2, y2) = vertexes[v2];
+                let touches = x1.max(x2) >= left
+                    && x1.min(x2) < left + BLOCK
+                    && y1.max(y2) >= bottom
+                    && y1.min(y2) < bottom + BLOCK;
+                if touches {
+                    lists.push(line as i16);
+                }
+            }
+            lists.push(-1);
+        }
+    }
+    header.extend(lists);
+    shorts(&header)
+}
+
+/// Content of the whole WAD file, with extra lumps at the end
+fn wad_content(extra: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let mut lumps = vec![
+        ("PLAYPAL", playpal()),
+        ("COLORMAP", colormap()),
+        ("PNAMES", pnames()),
+        ("TEXTURE1", texture1()),
+        ("WALLP", wall_patch()),
+        ("F_START", Vec::new()),
+        ("FLOOR", flat(96, 64)),
+        ("CEIL", flat(160, 144)),
+        ("F_END", Vec::new()),
+    ];
+    lumps.extend(room_lumps());
+    lumps.extend(steps_lumps());
+    lumps.extend(extra.iter().cloned());
+
+    let mut content = b"PWAD".to_vec();
+    let directory_offset = 12 + lumps.iter().map(|(_, lump)| lump.len()).sum::<usize>();
+    content.extend((lumps.len() as i32).to_le_bytes());
+    content.extend((directory_offset as i32).to_le_bytes());
+    let mut directory = Vec::new();
+    for (lump_name, lump) in &lumps {
+        directory.extend((content.len() as i32).to_le_bytes());
+        directory.extend((lump.len() as i32).to_le_bytes());
+        directory.extend(name(lump_name));
+        content.extend(lump);
+    }
+    content.extend(directory);
+    content
+}
+
+/// Write a test WAD in the temporary directory, a new file for each call so the tests can run
+/// in parallel
+fn write_wad(extra: &[(&str, Vec<u8>)]) -> std::path::PathBuf {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "doom-rs-test-{}-{}.wad",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&path, wad_content(extra)).unwrap();
+    path
+}
+
+/// Write the test WAD, the caller removes it
+pub fn room_wad_path() -> std::path::PathBuf {
+    write_wad(&[])
+}
+
+/// Open the test WAD with extra lumps, the file is removed once opened
+pub fn room_wad_with(extra: &[(&str, Vec<u8>)]) -> WadFile {
+    let path = write_wad(extra);
+    let file = WadFile::new(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    file
+}
+
+/// Open the test WAD, the file is removed once opened
+pub fn room_wad() -> WadFile {
+    room_wad_with(&[])
+}
+
+/// The room map, without render resources
+pub fn room_map() -> WadMap {
+    WadMap::load(ROOM_MAP, &room_wad()).unwrap()
+}
+
+/// The steps map, without render resources
+pub fn steps_map() -> WadMap {
+    WadMap::load(STEPS_MAP, &room_wad()).unwrap()
+}
@@ -0,0 +1,30 @@
+use bitflags::bitflags;
+
+bitflags! {
+    pub struct ThingFlags: i16 {
+        const NONE = 0;
+        const SKILL_EASY = 0x0001;
+        const SKILL_MEDIUM = 0x0002;
+        const SKILL_HARD = 0x0004;
+        const AMBUSH = 0x0008;
+        const NOT_SINGLE_PLAYER = 0x0010;
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct Thing {
+    pub x: i16,
+    pub y: i16,
+    /// Facing angle in degrees, 0 is east
+    pub angle: i16,
+    pub doomednum: i16,
+    pub flags: ThingFlags,
+}
+
+impl Thing {
+    /// Facing angle in degrees rounded down to one of the 8 directions, like vanilla does
+    pub fn direction(&self) -> f32 {
+        (45 * (self.angle / 45)) as f32
+    }
+}